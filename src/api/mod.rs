@@ -0,0 +1,535 @@
+//! Local HTTP REST API.
+//!
+//! `ratado serve` exposes the task database over HTTP so scripts and
+//! dashboards can read and change tasks without shelling out. Bodies are the
+//! JSON form of the [`models`](crate::models), and every endpoint is backed
+//! by the same [`Database`] methods the TUI uses.
+//!
+//! ## Endpoints
+//!
+//! | Method | Path | Description |
+//! |--------|------|-------------|
+//! | GET | `/tasks` | List tasks (`filter`, `sort`, `project`, `tag` query parameters) |
+//! | POST | `/tasks` | Create a task |
+//! | GET, PATCH, DELETE | `/tasks/{id}` | Get, update or delete a task |
+//! | POST | `/tasks/{id}/complete` | Mark a task completed |
+//! | GET, POST | `/projects` | List or create projects |
+//! | GET, PATCH, DELETE | `/projects/{id}` | Get, update or delete a project |
+//! | GET | `/tags` | List tags with task counts |
+//! | GET, DELETE | `/tags/{name}` | Get a tag or remove it from all tasks |
+//! | GET | `/openapi.json` | OpenAPI 3 description of this API |
+//!
+//! When a token is configured, every endpoint except `/openapi.json`
+//! requires an `Authorization: Bearer <token>` header.
+//!
+//! Task changes run the [hook scripts](crate::hooks) like any other change;
+//! a change a hook rejects gets `422 Unprocessable Entity` with its message.
+//!
+//! While the TUI holds the database, `ratado serve` can't open it, so it
+//! passes each request to the TUI through its control socket instead
+//! ([`serve_forwarded`]), which answers it with [`respond`]. The token is
+//! still checked by the server.
+
+mod projects;
+mod tags;
+mod tasks;
+
+pub(crate) use tasks::TaskInput;
+
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use log::info;
+use serde::{Deserialize, Deserializer};
+use serde_json::json;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+
+use crate::hooks::HookError;
+use crate::storage::{Database, StorageError};
+
+/// OpenAPI description served at `/openapi.json`.
+pub const OPENAPI_SPEC: &str = include_str!("openapi.json");
+
+/// Control socket method that answers an API request in the TUI.
+pub const FORWARD_METHOD: &str = "api_request";
+
+/// Largest request body passed on to the TUI.
+#[cfg(unix)]
+const MAX_FORWARDED_BODY: usize = 2 * 1024 * 1024;
+
+/// Errors returned by API handlers, rendered as `{"error": "..."}`.
+#[derive(Error, Debug)]
+pub enum ApiError {
+    /// The requested resource doesn't exist
+    #[error("{0}")]
+    NotFound(String),
+
+    /// The request was malformed or invalid
+    #[error("{0}")]
+    BadRequest(String),
+
+    /// Missing or wrong bearer token
+    #[error("Missing or invalid bearer token")]
+    Unauthorized,
+
+    /// Storage/database error
+    #[error("{0}")]
+    Storage(#[from] StorageError),
+
+    /// The TUI the request was passed to couldn't be reached
+    #[error("{0}")]
+    Unavailable(String),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            ApiError::NotFound(_) | ApiError::Storage(StorageError::NotFound(_)) => {
+                StatusCode::NOT_FOUND
+            }
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::Storage(StorageError::Hook(HookError::Rejected { .. })) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            ApiError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        let mut response = (status, Json(json!({ "error": self.to_string() }))).into_response();
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, "Bearer".parse().unwrap());
+        }
+        response
+    }
+}
+
+/// Result type for API handlers.
+pub type ApiResult<T> = std::result::Result<T, ApiError>;
+
+/// State shared by all handlers.
+///
+/// Requests are served one at a time against the single database
+/// connection, so read-modify-write handlers can't interleave.
+#[derive(Clone)]
+pub struct ApiState {
+    db: Arc<Mutex<Database>>,
+    token: Option<Arc<str>>,
+}
+
+/// Builds the API router.
+///
+/// # Arguments
+///
+/// * `db` - Migrated database to serve
+/// * `token` - Bearer token required on every request, if any
+pub fn router(db: Database, token: Option<String>) -> Router {
+    let state = ApiState {
+        db: Arc::new(Mutex::new(db)),
+        token: token.map(Arc::from),
+    };
+
+    let api = Router::new()
+        .route("/tasks", get(tasks::list).post(tasks::create))
+        .route(
+            "/tasks/{id}",
+            get(tasks::get).patch(tasks::update).delete(tasks::delete),
+        )
+        .route("/tasks/{id}/complete", post(tasks::complete))
+        .route("/projects", get(projects::list).post(projects::create))
+        .route(
+            "/projects/{id}",
+            get(projects::get)
+                .patch(projects::update)
+                .delete(projects::delete),
+        )
+        .route("/tags", get(tags::list))
+        .route("/tags/{name}", get(tags::get).delete(tags::delete))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
+    Router::new()
+        .route("/openapi.json", get(openapi))
+        .merge(api)
+        .fallback(|| async { ApiError::NotFound("No such endpoint".to_string()) })
+        .with_state(state)
+}
+
+/// Serves the API on `addr` until the process is stopped.
+///
+/// # Errors
+///
+/// Returns an error if the address can't be bound.
+pub async fn serve(db: Database, addr: SocketAddr, token: Option<String>) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!("Serving API on http://{}", listener.local_addr()?);
+    axum::serve(listener, router(db, token)).await
+}
+
+/// Serves the API on `addr`, passing every request to the TUI listening on
+/// the control socket at `socket`.
+///
+/// # Errors
+///
+/// Returns an error if the address can't be bound.
+#[cfg(unix)]
+pub async fn serve_forwarded(
+    socket: PathBuf,
+    addr: SocketAddr,
+    token: Option<String>,
+) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!("Serving API on http://{} through {:?}", listener.local_addr()?, socket);
+    let state = Forwarding {
+        socket: Arc::from(socket),
+        token: token.map(Arc::from),
+    };
+    axum::serve(listener, Router::new().fallback(forward).with_state(state)).await
+}
+
+/// Answers a request passed on by [`serve_forwarded`], returning the
+/// response status and body.
+pub async fn respond(db: Database, method: &str, uri: &str, body: String) -> (u16, String) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body));
+    let response = match request {
+        // The token was checked by the server that passed the request on
+        Ok(request) => match router(db, None).oneshot(request).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        },
+        Err(e) => ApiError::BadRequest(e.to_string()).into_response(),
+    };
+    let status = response.status().as_u16();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default();
+    (status, body)
+}
+
+/// State of a server that passes requests on to the TUI.
+#[cfg(unix)]
+#[derive(Clone)]
+struct Forwarding {
+    socket: Arc<Path>,
+    token: Option<Arc<str>>,
+}
+
+/// Passes a request on to the TUI and returns its response.
+#[cfg(unix)]
+async fn forward(State(state): State<Forwarding>, request: Request) -> Response {
+    if request.uri().path() != "/openapi.json"
+        && let Err(e) = check_token(request.headers(), state.token.as_deref())
+    {
+        return e.into_response();
+    }
+    let (parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_FORWARDED_BODY).await {
+        Ok(body) => body,
+        Err(e) => return ApiError::BadRequest(e.to_string()).into_response(),
+    };
+    let params = json!({
+        "method": parts.method.as_str(),
+        "uri": parts.uri.to_string(),
+        "body": String::from_utf8_lossy(&body),
+    });
+    match crate::handlers::rpc::call(&state.socket, FORWARD_METHOD, params).await {
+        Ok(response) => {
+            let status = response["status"]
+                .as_u64()
+                .and_then(|s| StatusCode::from_u16(s as u16).ok())
+                .unwrap_or(StatusCode::BAD_GATEWAY);
+            let body = response["body"].as_str().unwrap_or_default().to_string();
+            (status, [(header::CONTENT_TYPE, "application/json")], body).into_response()
+        }
+        Err(e) => ApiError::Unavailable(e.to_string()).into_response(),
+    }
+}
+
+async fn openapi() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], OPENAPI_SPEC)
+}
+
+/// Rejects requests without the configured bearer token.
+async fn require_token(
+    State(state): State<ApiState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match check_token(request.headers(), state.token.as_deref()) {
+        Ok(()) => next.run(request).await,
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Checks the bearer token in `headers` against the `expected` one, if any.
+fn check_token(headers: &HeaderMap, expected: Option<&str>) -> ApiResult<()> {
+    let Some(expected) = expected else {
+        return Ok(());
+    };
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if provided.is_some_and(|p| constant_time_eq(p.as_bytes(), expected.as_bytes())) {
+        Ok(())
+    } else {
+        Err(ApiError::Unauthorized)
+    }
+}
+
+/// Compares secrets without exiting early on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Deserializes a field that distinguishes "absent" from `null`.
+///
+/// Use with `#[serde(default, deserialize_with = "nullable")]` on an
+/// `Option<Option<T>>`: absent is `None`, `null` is `Some(None)`.
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+    use serde_json::Value;
+
+    use crate::storage::run_migrations;
+
+    async fn setup(token: Option<&str>) -> Router {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        router(db, token.map(String::from))
+    }
+
+    async fn send(
+        app: &Router,
+        method: &str,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let builder = Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+        (status, value)
+    }
+
+    #[tokio::test]
+    async fn test_task_lifecycle() {
+        let app = setup(None).await;
+
+        let (status, task) = send(
+            &app,
+            "POST",
+            "/tasks",
+            Some(json!({ "title": "Write API", "priority": "High", "tags": ["dev"] })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(task["title"], "Write API");
+        assert_eq!(task["status"], "Pending");
+        let id = task["id"].as_str().unwrap().to_string();
+
+        let (status, task) = send(
+            &app,
+            "PATCH",
+            &format!("/tasks/{}", id),
+            Some(json!({ "description": "Axum", "project": "Inbox" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(task["description"], "Axum");
+        assert_eq!(task["project_id"], "inbox");
+        // Fields not in the patch are kept
+        assert_eq!(task["priority"], "High");
+        assert_eq!(task["tags"], json!(["dev"]));
+
+        let (status, task) = send(&app, "POST", &format!("/tasks/{}/complete", id), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(task["status"], "Completed");
+        assert!(task["completed_at"].is_string());
+
+        let (_, list) = send(&app, "GET", "/tasks?filter=completed", None).await;
+        assert_eq!(list.as_array().unwrap().len(), 1);
+        let (_, list) = send(&app, "GET", "/tasks?filter=pending", None).await;
+        assert!(list.as_array().unwrap().is_empty());
+
+        let (status, _) = send(&app, "DELETE", &format!("/tasks/{}", id), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, body) = send(&app, "GET", &format!("/tasks/{}", id), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_patch_null_clears_field() {
+        let app = setup(None).await;
+        let (_, task) = send(
+            &app,
+            "POST",
+            "/tasks",
+            Some(json!({ "title": "Due", "due_date": "2026-03-01T12:00:00Z" })),
+        )
+        .await;
+        let id = task["id"].as_str().unwrap();
+
+        let (_, task) = send(&app, "PATCH", &format!("/tasks/{}", id), Some(json!({ "due_date": null }))).await;
+        assert!(task["due_date"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_requests() {
+        let app = setup(None).await;
+
+        let (status, _) = send(&app, "POST", "/tasks", Some(json!({ "title": "  " }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send(&app, "GET", "/tasks?filter=bogus", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send(
+            &app,
+            "POST",
+            "/tasks",
+            Some(json!({ "title": "x", "project": "Nowhere" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send(&app, "GET", "/nope", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // A project by ID and by name would contradict each other
+        let (_, task) = send(&app, "POST", "/tasks", Some(json!({ "title": "x" }))).await;
+        let (status, body) = send(
+            &app,
+            "PATCH",
+            &format!("/tasks/{}", task["id"].as_str().unwrap()),
+            Some(json!({ "project_id": "inbox", "project": "Inbox" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Give either project_id or project, not both");
+    }
+
+    #[tokio::test]
+    async fn test_projects_and_tags() {
+        let app = setup(None).await;
+
+        let (status, project) = send(&app, "POST", "/projects", Some(json!({ "name": "Work" }))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let project_id = project["id"].as_str().unwrap().to_string();
+
+        send(
+            &app,
+            "POST",
+            "/tasks",
+            Some(json!({ "title": "Ship", "project_id": project_id, "tags": ["release"] })),
+        )
+        .await;
+
+        let (_, projects) = send(&app, "GET", "/projects", None).await;
+        let work = projects
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["name"] == "Work")
+            .unwrap();
+        assert_eq!(work["task_count"], 1);
+
+        let (_, tags) = send(&app, "GET", "/tags", None).await;
+        assert_eq!(tags, json!([{ "name": "release", "task_count": 1 }]));
+
+        let (status, _) = send(&app, "DELETE", "/tags/release", None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (_, tasks) = send(&app, "GET", "/tasks", None).await;
+        assert_eq!(tasks[0]["tags"], json!([]));
+
+        // Deleting a project moves its tasks to the Inbox by default
+        let (status, _) = send(&app, "DELETE", &format!("/projects/{}", project_id), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (_, tasks) = send(&app, "GET", "/tasks?project=Inbox", None).await;
+        assert_eq!(tasks.as_array().unwrap().len(), 1);
+
+        let (status, _) = send(&app, "DELETE", "/projects/inbox", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token() {
+        let app = setup(Some("s3cret")).await;
+
+        let (status, _) = send(&app, "GET", "/tasks", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let request = Request::builder()
+            .uri("/tasks")
+            .header(header::AUTHORIZATION, "Bearer s3cret")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // The API description is public
+        let (status, spec) = send(&app, "GET", "/openapi.json", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(spec["openapi"], "3.0.3");
+    }
+
+    #[test]
+    fn test_openapi_covers_routes() {
+        let spec: Value = serde_json::from_str(OPENAPI_SPEC).unwrap();
+        let paths = spec["paths"].as_object().unwrap();
+        for path in [
+            "/tasks",
+            "/tasks/{id}",
+            "/tasks/{id}/complete",
+            "/projects",
+            "/projects/{id}",
+            "/tags",
+            "/tags/{name}",
+        ] {
+            assert!(paths.contains_key(path), "missing {}", path);
+        }
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"tok"));
+    }
+}
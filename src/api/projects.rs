@@ -0,0 +1,167 @@
+//! Project endpoints.
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use super::{ApiError, ApiResult, ApiState};
+use crate::interop::find_project;
+use crate::models::Project;
+use crate::storage::{Database, StorageError};
+
+/// A project with the number of tasks in it.
+#[derive(Debug, Serialize)]
+pub struct ProjectWithCount {
+    #[serde(flatten)]
+    project: Project,
+    task_count: usize,
+}
+
+/// Fields accepted when creating or updating a project.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectInput {
+    name: Option<String>,
+    /// Hex color, like `#3498db`
+    color: Option<String>,
+    /// Emoji or icon character
+    icon: Option<String>,
+}
+
+impl ProjectInput {
+    /// Applies the provided fields to `project`.
+    fn apply(self, project: &mut Project) -> ApiResult<()> {
+        if let Some(name) = self.name {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(ApiError::BadRequest("Name must not be empty".to_string()));
+            }
+            project.name = name.to_string();
+        }
+        if let Some(color) = self.color {
+            let valid = color.len() == 7
+                && color.starts_with('#')
+                && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if !valid {
+                return Err(ApiError::BadRequest(format!("Invalid color: {}", color)));
+            }
+            project.color = color;
+        }
+        if let Some(icon) = self.icon {
+            project.icon = icon;
+        }
+        Ok(())
+    }
+}
+
+/// What to do with a deleted project's tasks.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeleteTasks {
+    /// Move them to the Inbox (default)
+    #[default]
+    Move,
+    /// Delete them with the project
+    Delete,
+}
+
+/// Query parameters for deleting a project.
+#[derive(Debug, Default, Deserialize)]
+pub struct DeleteQuery {
+    #[serde(default)]
+    tasks: DeleteTasks,
+}
+
+/// `GET /projects`
+pub async fn list(State(state): State<ApiState>) -> ApiResult<Json<Vec<ProjectWithCount>>> {
+    let db = state.db.lock().await;
+    let projects = db
+        .get_projects_with_counts()
+        .await?
+        .into_iter()
+        .map(|(project, task_count)| ProjectWithCount {
+            project,
+            task_count,
+        })
+        .collect();
+    Ok(Json(projects))
+}
+
+/// `POST /projects`
+pub async fn create(
+    State(state): State<ApiState>,
+    Json(input): Json<ProjectInput>,
+) -> ApiResult<(StatusCode, Json<Project>)> {
+    let db = state.db.lock().await;
+    let name = input
+        .name
+        .clone()
+        .ok_or_else(|| ApiError::BadRequest("Missing name".to_string()))?;
+    let projects = db.get_all_projects().await?;
+    if find_project(&projects, &name).is_some() {
+        return Err(ApiError::BadRequest(format!("Project already exists: {}", name)));
+    }
+
+    let mut project = Project::new(&name);
+    input.apply(&mut project)?;
+    db.insert_project(&project).await?;
+    Ok((StatusCode::CREATED, Json(project)))
+}
+
+/// `GET /projects/{id}`
+pub async fn get(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ProjectWithCount>> {
+    let db = state.db.lock().await;
+    let project = find(&db, &id).await?;
+    let task_count = db.get_task_count_by_project(&project.id).await?;
+    Ok(Json(ProjectWithCount {
+        project,
+        task_count,
+    }))
+}
+
+/// `PATCH /projects/{id}`
+pub async fn update(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+    Json(input): Json<ProjectInput>,
+) -> ApiResult<Json<Project>> {
+    let db = state.db.lock().await;
+    let mut project = find(&db, &id).await?;
+    input.apply(&mut project)?;
+    db.update_project(&project).await?;
+    Ok(Json(project))
+}
+
+/// `DELETE /projects/{id}?tasks=move|delete`
+pub async fn delete(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+    Query(query): Query<DeleteQuery>,
+) -> ApiResult<StatusCode> {
+    let db = state.db.lock().await;
+    let project = find(&db, &id).await?;
+    if project.id == "inbox" {
+        return Err(ApiError::BadRequest("Cannot delete the Inbox project".to_string()));
+    }
+
+    match query.tasks {
+        DeleteTasks::Move => db.move_tasks_to_inbox(&project.id).await?,
+        DeleteTasks::Delete => db.remove_tasks_by_project(&project.id).await?,
+    };
+    db.delete_project(&project.id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn find(db: &Database, id: &str) -> ApiResult<Project> {
+    match db.get_project(id).await {
+        Ok(Some(project)) => Ok(project),
+        Ok(None) | Err(StorageError::NotFound(_)) => {
+            Err(ApiError::NotFound(format!("Project not found: {}", id)))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,69 @@
+//! Tag endpoints.
+//!
+//! Tags are identified by name and only exist while tasks use them, so they
+//! are created and renamed through the tasks' `tags` field.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Serialize;
+
+use super::{ApiError, ApiResult, ApiState};
+
+/// A tag with the number of tasks using it.
+#[derive(Debug, Serialize)]
+pub struct TagWithCount {
+    name: String,
+    task_count: usize,
+}
+
+/// `GET /tags`
+pub async fn list(State(state): State<ApiState>) -> ApiResult<Json<Vec<TagWithCount>>> {
+    let db = state.db.lock().await;
+    let tags = db
+        .get_tags_with_counts()
+        .await?
+        .into_iter()
+        .map(|(tag, task_count)| TagWithCount {
+            name: tag.name,
+            task_count,
+        })
+        .collect();
+    Ok(Json(tags))
+}
+
+/// `GET /tags/{name}`
+pub async fn get(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+) -> ApiResult<Json<TagWithCount>> {
+    let db = state.db.lock().await;
+    let tag = db
+        .get_tag_by_name(&name)
+        .await?
+        .ok_or_else(|| tag_not_found(&name))?;
+    let task_count = db.get_task_count_by_tag(&tag.id).await?;
+    Ok(Json(TagWithCount {
+        name: tag.name,
+        task_count,
+    }))
+}
+
+/// `DELETE /tags/{name}`: removes the tag from every task, as a change
+/// to each task that runs its hooks and webhooks.
+pub async fn delete(
+    State(state): State<ApiState>,
+    Path(name): Path<String>,
+) -> ApiResult<StatusCode> {
+    let db = state.db.lock().await;
+    let tag = db
+        .get_tag_by_name(&name)
+        .await?
+        .ok_or_else(|| tag_not_found(&name))?;
+    db.delete_tag(&tag.id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn tag_not_found(name: &str) -> ApiError {
+    ApiError::NotFound(format!("Tag not found: {}", name))
+}
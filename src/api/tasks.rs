@@ -0,0 +1,229 @@
+//! Task endpoints.
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::{nullable, ApiError, ApiResult, ApiState};
+use crate::interop::find_project;
+use crate::interop::report::{parse_filter, parse_sort};
+use crate::models::{Priority, Project, SortOrder, Task, TaskStatus};
+use crate::utils::parse_due_date;
+
+/// Query parameters for listing tasks.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListQuery {
+    /// Filter spec, as for `ratado export --filter` (default `all`)
+    filter: Option<String>,
+    /// Sort spec, as for `ratado export --sort` (default `due`)
+    sort: Option<String>,
+    /// Only tasks in this project (name or ID)
+    project: Option<String>,
+    /// Only tasks with this tag
+    tag: Option<String>,
+}
+
+/// Fields accepted when creating or updating a task.
+///
+/// All fields are optional on update; fields that are left out keep their
+/// value, and nullable fields set to `null` are cleared.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskInput {
+    title: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    description: Option<Option<String>>,
+    /// RFC 3339 timestamp or anything the task dialog accepts (`tomorrow`, `+3d`)
+    #[serde(default, deserialize_with = "nullable")]
+    due_date: Option<Option<String>>,
+    priority: Option<Priority>,
+    status: Option<TaskStatus>,
+    #[serde(default, deserialize_with = "nullable")]
+    project_id: Option<Option<String>>,
+    /// Project by name, as an alternative to `project_id`; giving both is
+    /// an error
+    project: Option<String>,
+    tags: Option<Vec<String>>,
+}
+
+impl TaskInput {
+    /// Applies the provided fields to `task`.
+    pub(crate) fn apply(self, task: &mut Task, projects: &[Project]) -> ApiResult<()> {
+        if self.project_id.is_some() && self.project.is_some() {
+            return Err(ApiError::BadRequest(
+                "Give either project_id or project, not both".to_string(),
+            ));
+        }
+        if let Some(title) = self.title {
+            let title = title.trim();
+            if title.is_empty() {
+                return Err(ApiError::BadRequest("Title must not be empty".to_string()));
+            }
+            task.title = title.to_string();
+        }
+        if let Some(description) = self.description {
+            task.description = description.filter(|d| !d.trim().is_empty());
+        }
+        if let Some(due_date) = self.due_date {
+            task.due_date = due_date.map(|d| parse_date(&d)).transpose()?;
+        }
+        if let Some(priority) = self.priority {
+            task.priority = priority;
+        }
+        if let Some(project_id) = self.project_id {
+            if let Some(id) = &project_id
+                && !projects.iter().any(|p| &p.id == id)
+            {
+                return Err(ApiError::BadRequest(format!("Unknown project: {}", id)));
+            }
+            task.project_id = project_id;
+        }
+        if let Some(name) = self.project {
+            task.project_id = Some(resolve_project(projects, &name)?.id.clone());
+        }
+        if let Some(tags) = self.tags {
+            task.tags.clear();
+            for tag in tags {
+                let tag = tag.trim().trim_start_matches('#');
+                if !tag.is_empty() && !task.tags.iter().any(|t| t == tag) {
+                    task.tags.push(tag.to_string());
+                }
+            }
+        }
+        if let Some(status) = self.status
+            && status != task.status
+        {
+            match status {
+                TaskStatus::Completed => task.complete(),
+                _ => {
+                    task.reopen();
+                    task.status = status;
+                }
+            }
+        }
+        task.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+/// `GET /tasks`
+pub async fn list(
+    State(state): State<ApiState>,
+    Query(query): Query<ListQuery>,
+) -> ApiResult<Json<Vec<Task>>> {
+    let db = state.db.lock().await;
+    let all_tasks = db.get_all_tasks().await?;
+    let projects = db.get_all_projects().await?;
+
+    let filter = parse_filter(query.filter.as_deref().unwrap_or("all"), &projects)
+        .map_err(ApiError::BadRequest)?;
+    let sort = match query.sort.as_deref() {
+        Some(spec) => parse_sort(spec).map_err(ApiError::BadRequest)?,
+        None => SortOrder::DueDateAsc,
+    };
+    let project_id = query
+        .project
+        .as_deref()
+        .map(|name| resolve_project(&projects, name).map(|p| p.id.clone()))
+        .transpose()?;
+    let tag = query.tag.map(|t| t.trim_start_matches('#').to_string());
+
+    let mut tasks: Vec<&Task> = filter
+        .apply(&all_tasks)
+        .into_iter()
+        .filter(|t| project_id.is_none() || t.project_id == project_id)
+        .filter(|t| {
+            tag.as_ref()
+                .is_none_or(|tag| t.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+        })
+        .collect();
+    sort.apply(&mut tasks);
+    Ok(Json(tasks.into_iter().cloned().collect()))
+}
+
+/// `POST /tasks`
+pub async fn create(
+    State(state): State<ApiState>,
+    Json(input): Json<TaskInput>,
+) -> ApiResult<(StatusCode, Json<Task>)> {
+    if input.title.is_none() {
+        return Err(ApiError::BadRequest("Missing title".to_string()));
+    }
+    let db = state.db.lock().await;
+    let projects = db.get_all_projects().await?;
+    let mut task = Task::new("");
+    input.apply(&mut task, &projects)?;
+    let task = db.add_task(task).await?;
+    Ok((StatusCode::CREATED, Json(task)))
+}
+
+/// `GET /tasks/{id}`
+pub async fn get(State(state): State<ApiState>, Path(id): Path<String>) -> ApiResult<Json<Task>> {
+    let db = state.db.lock().await;
+    Ok(Json(find_task(&db, &id).await?))
+}
+
+/// `PATCH /tasks/{id}`
+pub async fn update(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+    Json(input): Json<TaskInput>,
+) -> ApiResult<Json<Task>> {
+    let db = state.db.lock().await;
+    let mut task = find_task(&db, &id).await?;
+    let projects = db.get_all_projects().await?;
+    input.apply(&mut task, &projects)?;
+    Ok(Json(db.modify_task(task).await?))
+}
+
+/// `DELETE /tasks/{id}`
+pub async fn delete(State(state): State<ApiState>, Path(id): Path<String>) -> ApiResult<StatusCode> {
+    let db = state.db.lock().await;
+    if db.remove_task(&id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(task_not_found(&id))
+    }
+}
+
+/// `POST /tasks/{id}/complete`
+pub async fn complete(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Task>> {
+    let db = state.db.lock().await;
+    let mut task = find_task(&db, &id).await?;
+    if task.status != TaskStatus::Completed {
+        task.complete();
+        task = db.modify_task(task).await?;
+    }
+    Ok(Json(task))
+}
+
+async fn find_task(db: &crate::storage::Database, id: &str) -> ApiResult<Task> {
+    db.get_task(id).await?.ok_or_else(|| task_not_found(id))
+}
+
+fn task_not_found(id: &str) -> ApiError {
+    ApiError::NotFound(format!("Task not found: {}", id))
+}
+
+/// Finds a project by ID or (case-insensitive) name.
+fn resolve_project<'a>(projects: &'a [Project], name: &str) -> ApiResult<&'a Project> {
+    projects
+        .iter()
+        .find(|p| p.id == name)
+        .or_else(|| find_project(projects, name))
+        .ok_or_else(|| ApiError::BadRequest(format!("Unknown project: {}", name)))
+}
+
+/// Parses an RFC 3339 timestamp or a natural-language due date.
+fn parse_date(input: &str) -> ApiResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(input)
+        .map(|d| d.with_timezone(&Utc))
+        .ok()
+        .or_else(|| parse_due_date(input))
+        .ok_or_else(|| ApiError::BadRequest(format!("Invalid due date: {}", input)))
+}
@@ -26,22 +26,44 @@
 //! ```
 
 use std::collections::HashSet;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use ratatui::layout::Rect;
 use thiserror::Error;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 use tui_logger::TuiWidgetState;
 
-use crate::models::{Filter, Priority, Project, SortOrder, Task, TaskStatus};
-use crate::storage::{Database, StorageError, Tag};
+use crate::config::{Profile, ReviewConfig, StandupConfig};
+use crate::models::{
+    move_positions, review_due, sort_by_keys, BulkAction, BulkEdit, Filter, GroupBy, Priority, Project, ReviewStep,
+    SortKey, SortOrder, Task, TaskGroup, TaskStatus,
+};
+use crate::interop::find_project;
+use crate::interop::standup::{Standup, StandupFormat};
+use crate::storage::{ChangeSet, Database, Delivery, StorageError, Tag, TaskEvent};
+use crate::templates::Templates;
 use crate::ui::calendar::CalendarState;
-use crate::ui::dialogs::Dialog;
+use crate::ui::dialogs::{task_count, Dialog};
 use crate::ui::effects::AnimationState;
+use crate::ui::review::ReviewState;
 use crate::ui::search::SearchResult;
 
 /// How long status messages are displayed before auto-clearing (in seconds).
 const STATUS_MESSAGE_TIMEOUT_SECS: u64 = 3;
 
+/// How often to check a plaintext store for files changed outside the app.
+const EXTERNAL_CHANGE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Webhook delivery attempts shown on the debug screen.
+const WEBHOOK_DELIVERY_LOG_LIMIT: usize = 50;
+
+/// Task changes shown in the activity view.
+const ACTIVITY_LIMIT: usize = 200;
+
+/// Updates the app after a change is saved; see [`App::save`].
+type SaveFinish = Box<dyn FnOnce(&mut App) -> Result<()> + Send>;
+
 /// Errors that can occur in the application.
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -73,6 +95,14 @@ pub enum View {
     Help,
     /// Debug log viewer (F12)
     DebugLogs,
+    /// Statistics and productivity dashboard
+    Stats,
+    /// Guided weekly review
+    Review,
+    /// Recent changes to all tasks
+    Activity,
+    /// Standup report
+    Standup,
 }
 
 /// Input mode determines how keyboard input is interpreted.
@@ -85,6 +115,8 @@ pub enum InputMode {
     Editing,
     /// Search mode - keys are typed into search field
     Search,
+    /// Selection mode - tasks are marked for a bulk action
+    Select,
 }
 
 /// Which panel currently has focus.
@@ -97,6 +129,25 @@ pub enum FocusPanel {
     TaskList,
 }
 
+/// A row of the main task list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListRow<'a> {
+    /// Header of a group, with its number of tasks
+    Header {
+        /// The group's label
+        label: String,
+        /// Number of tasks in the group
+        count: usize,
+        /// Whether the group's tasks are hidden
+        collapsed: bool,
+    },
+    /// A task, with its index in [`App::visible_tasks`]
+    Task(usize, &'a Task),
+}
+
+
+/// Preference key under which the task list grouping is remembered.
+const GROUP_BY_PREFERENCE: &str = "group_by";
 
 /// Central application state.
 ///
@@ -143,6 +194,9 @@ pub struct App {
     /// Current sort order for task list
     pub sort: SortOrder,
 
+    /// Keys that order tasks the main sort considers equal, in turn
+    pub then_by: Vec<SortKey>,
+
     /// Text input buffer for editing/search modes
     pub input_buffer: String,
 
@@ -152,6 +206,9 @@ pub struct App {
     /// State for tui-logger widget
     pub log_state: TuiWidgetState,
 
+    /// Recent webhook delivery attempts, loaded when the debug screen opens
+    pub webhook_deliveries: Vec<Delivery>,
+
     /// Whether the application should exit
     pub should_quit: bool,
 
@@ -176,6 +233,33 @@ pub struct App {
     /// Calendar view state
     pub calendar_state: CalendarState,
 
+    /// Whether the stats view charts weeks instead of days
+    pub stats_weekly: bool,
+
+    /// Progress through the weekly review
+    pub review: ReviewState,
+
+    /// Changes to the task shown in the detail view, newest first
+    pub task_history: Vec<TaskEvent>,
+
+    /// Recent changes to all tasks, newest first, for the activity view
+    pub activity: Vec<TaskEvent>,
+
+    /// Selected entry in the activity view
+    pub selected_activity_index: usize,
+
+    /// Weekly review settings from the configuration file
+    pub review_config: ReviewConfig,
+
+    /// Standup report settings from the configuration file
+    pub standup_config: StandupConfig,
+
+    /// Whether the standup view shows Markdown instead of plain text
+    pub standup_markdown: bool,
+
+    /// When the last weekly review was finished
+    pub last_review: Option<chrono::DateTime<chrono::Utc>>,
+
     /// Animation and visual effects state
     pub animation: AnimationState,
 
@@ -199,6 +283,51 @@ pub struct App {
 
     /// Task IDs that should get a "priority" animation with color on next render
     pub pending_priority_animation: Option<(String, ratatui::style::Color)>,
+
+    /// Text for the main loop to copy to the clipboard
+    pub pending_clipboard: Option<String>,
+
+    /// Name of the open profile (`None` when none was chosen)
+    pub profile_name: Option<String>,
+
+    /// Settings of the open profile
+    pub profile: Profile,
+
+    /// Names of all configured profiles, for the profile switcher
+    pub profile_names: Vec<String>,
+
+    /// Profile chosen in the switcher, for the main loop to open
+    pub requested_profile: Option<String>,
+
+    /// Task templates from the config directory
+    pub templates: Templates,
+
+    /// IDs of the tasks marked in selection mode
+    pub marked: HashSet<String>,
+
+    /// Bulk action waiting for confirmation
+    pub pending_bulk: Option<BulkAction>,
+
+    /// Whether the selected task is being dragged with the mouse
+    pub dragging: bool,
+
+    /// What the task list is grouped by
+    pub group_by: GroupBy,
+
+    /// Labels of the groups whose tasks are hidden
+    pub collapsed_groups: HashSet<String>,
+
+    /// When the store was last checked for external changes
+    last_external_check: Instant,
+
+    /// Changes whose hooks are still running, with the items they wrote
+    pending_saves: Vec<JoinHandle<(ChangeSet, SaveFinish)>>,
+
+    /// Closes when the last save started in the background is written
+    last_save: Option<oneshot::Receiver<()>>,
+
+    /// Whether data changed outside a key press, so the screen needs drawing
+    pub redraw_requested: bool,
 }
 
 impl App {
@@ -230,9 +359,11 @@ impl App {
             selected_project_index: 0,
             filter: Filter::Pending,
             sort: SortOrder::DueDateAsc,
+            then_by: Vec::new(),
             input_buffer: String::new(),
             input_cursor: 0,
             log_state: TuiWidgetState::default(),
+            webhook_deliveries: Vec::new(),
             should_quit: false,
             status_message: None,
             status_message_set_at: None,
@@ -241,6 +372,15 @@ impl App {
             search_results: Vec::new(),
             selected_search_index: 0,
             calendar_state: CalendarState::new(),
+            stats_weekly: false,
+            review: ReviewState::default(),
+            task_history: Vec::new(),
+            activity: Vec::new(),
+            selected_activity_index: 0,
+            review_config: ReviewConfig::default(),
+            standup_config: StandupConfig::default(),
+            standup_markdown: false,
+            last_review: None,
             animation: AnimationState::new(),
             splash_started: false,
             dissolving_tasks: HashSet::new(),
@@ -249,6 +389,21 @@ impl App {
             pending_new_task_animation: None,
             pending_complete_animation: None,
             pending_priority_animation: None,
+            pending_clipboard: None,
+            profile_name: None,
+            profile: Profile::default(),
+            profile_names: Vec::new(),
+            requested_profile: None,
+            templates: Templates::default(),
+            marked: HashSet::new(),
+            pending_bulk: None,
+            dragging: false,
+            group_by: GroupBy::None,
+            collapsed_groups: HashSet::new(),
+            last_external_check: Instant::now(),
+            pending_saves: Vec::new(),
+            last_save: None,
+            redraw_requested: false,
         };
         // Disable animations and splash when RATADO_NO_ANIMATIONS is set (e.g., E2E tests)
         if std::env::var("RATADO_NO_ANIMATIONS").is_ok() {
@@ -257,6 +412,8 @@ impl App {
         }
 
         app.load_data().await?;
+        app.load_group_by().await?;
+        app.last_review = app.db.last_review().await?;
         Ok(app)
     }
 
@@ -270,6 +427,9 @@ impl App {
         self.tags = self.db.get_all_tags().await?;
 
         self.adjust_task_selection();
+        if self.current_view == View::Activity {
+            self.load_activity().await?;
+        }
 
         Ok(())
     }
@@ -281,6 +441,306 @@ impl App {
         self.load_data().await
     }
 
+    /// Loads the most recent webhook delivery attempts for the debug screen.
+    ///
+    /// Does nothing if no webhooks are configured.
+    pub async fn load_webhook_deliveries(&mut self) -> Result<()> {
+        if !self.db.webhooks().is_empty() {
+            self.webhook_deliveries = self
+                .db
+                .recent_webhook_deliveries(WEBHOOK_DELIVERY_LOG_LIMIT)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Loads the history of the selected task for the detail view.
+    pub async fn load_task_history(&mut self) -> Result<()> {
+        let id = self.selected_task().map(|t| t.id.clone());
+        self.task_history = match id {
+            Some(id) => self.db.task_history(&id).await?,
+            None => Vec::new(),
+        };
+        Ok(())
+    }
+
+    /// Loads the latest changes to all tasks for the activity view,
+    /// keeping the selection in range.
+    pub async fn load_activity(&mut self) -> Result<()> {
+        self.activity = self.db.recent_activity(ACTIVITY_LIMIT).await?;
+        self.selected_activity_index = self
+            .selected_activity_index
+            .min(self.activity.len().saturating_sub(1));
+        Ok(())
+    }
+
+    /// Renders the standup report for the current tasks, in the format
+    /// chosen in the standup view.
+    pub fn standup_report(&self) -> String {
+        let format = if self.standup_markdown {
+            StandupFormat::Markdown
+        } else {
+            StandupFormat::Text
+        };
+        Standup::build(&self.tasks, chrono::Utc::now(), &self.standup_config).render(format, &self.projects)
+    }
+
+    /// Returns the selected entry of the activity view.
+    pub fn selected_activity(&self) -> Option<&TaskEvent> {
+        self.activity.get(self.selected_activity_index)
+    }
+
+    /// Reloads the items whose files were changed outside the app.
+    ///
+    /// Called on every tick, but only checks the store once per
+    /// [`EXTERNAL_CHANGE_POLL_INTERVAL`]. Returns whether data was reloaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read.
+    pub async fn check_external_changes(&mut self) -> Result<bool> {
+        if self.last_external_check.elapsed() < EXTERNAL_CHANGE_POLL_INTERVAL {
+            return Ok(false);
+        }
+        self.last_external_check = Instant::now();
+
+        let changes = self.db.external_changes().await?;
+        if changes.is_empty() {
+            return Ok(false);
+        }
+        self.reload_changes(&changes).await?;
+        Ok(true)
+    }
+
+    /// Runs `f` against the database on behalf of another program, then
+    /// reloads the items it changed.
+    ///
+    /// Saves still running in the background are finished first, so `f`
+    /// never writes into one of their transactions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a save failed or the changed items cannot be
+    /// reloaded.
+    pub async fn run_remote<T>(&mut self, f: impl AsyncFnOnce(&Database) -> T) -> Result<T> {
+        self.wait_for_saves().await?;
+        let db = self.db.clone();
+        let (result, changes) = db.track_changes(async || f(&db).await).await;
+        if !changes.is_empty() {
+            self.reload_changes(&changes).await?;
+        }
+        Ok(result)
+    }
+
+    /// Saves a change made in the app, such as adding or editing a task.
+    ///
+    /// `write` makes the change through the database it's given, hooks
+    /// included. Hooks may take a while, so when any are installed `write`
+    /// runs in the background and the app stays responsive; a later tick
+    /// finishes it ([`App::finish_saves`]). Without hooks it's awaited right
+    /// away. Either way, the items it wrote are then reloaded and `done` is
+    /// called with its result, or with `None` if a hook rejected it (the
+    /// status bar says why).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if saving fails without hooks, or if the changed
+    /// items cannot be reloaded.
+    pub async fn save<T, F>(
+        &mut self,
+        write: impl FnOnce(Database) -> F + Send + 'static,
+        done: impl FnOnce(&mut App, Option<T>) + Send + 'static,
+    ) -> Result<()>
+    where
+        T: Send + 'static,
+        F: Future<Output = std::result::Result<T, StorageError>> + Send + 'static,
+    {
+        let db = self.db.clone();
+        let save = async move {
+            let tracker = db.clone();
+            let (result, changes) = tracker.track_changes(async || write(db).await).await;
+            let finish: SaveFinish = Box::new(move |app: &mut App| {
+                let value = app.check_hooks(result)?;
+                done(app, value);
+                Ok(())
+            });
+            (changes, finish)
+        };
+        if self.db.has_hooks() {
+            // Saves share the connection, so each waits for the one before
+            // it rather than writing into its transaction
+            let (written, next) = oneshot::channel::<()>();
+            let previous = self.last_save.replace(next);
+            self.pending_saves.push(tokio::spawn(async move {
+                if let Some(previous) = previous {
+                    let _ = previous.await;
+                }
+                let saved = save.await;
+                drop(written);
+                saved
+            }));
+            self.set_status("Running hooks...");
+            Ok(())
+        } else {
+            let (changes, finish) = save.await;
+            self.finish_save(&changes, finish).await
+        }
+    }
+
+    /// Finishes the saves whose hooks are done. Called on every tick.
+    ///
+    /// Returns whether any save was finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a save failed or its items cannot be reloaded.
+    pub async fn finish_saves(&mut self) -> Result<bool> {
+        let mut finished = false;
+        // In the order they were started, which is the order they were made
+        while let Some(index) = self.pending_saves.iter().position(|s| s.is_finished()) {
+            let save = self.pending_saves.remove(index);
+            let (changes, finish) = save.await.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+            self.finish_save(&changes, finish).await?;
+            finished = true;
+        }
+        Ok(finished)
+    }
+
+    /// Waits for the saves still running and finishes them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a save failed or its items cannot be reloaded.
+    pub async fn wait_for_saves(&mut self) -> Result<()> {
+        for save in std::mem::take(&mut self.pending_saves) {
+            let (changes, finish) = save.await.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+            self.finish_save(&changes, finish).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether any change is still waiting for its hooks.
+    pub fn is_saving(&self) -> bool {
+        !self.pending_saves.is_empty()
+    }
+
+    async fn finish_save(&mut self, changes: &ChangeSet, finish: SaveFinish) -> Result<()> {
+        self.refresh_changes(changes).await?;
+        self.redraw_requested = true;
+        finish(self)
+    }
+
+    /// Reloads the tasks and projects in `changes`, after they were written
+    /// outside the app or on behalf of another program.
+    ///
+    /// Unlike [`App::load_data`], this only reads the changed items, keeps
+    /// the selected task and project (by ID, so they stay put even if rows
+    /// were added above them), and flags an open edit dialog whose task was
+    /// changed or deleted.
+    pub async fn reload_changes(&mut self, changes: &ChangeSet) -> Result<()> {
+        let (added, changed, removed) = self.refresh_changes(changes).await?;
+
+        // Warn an open edit dialog about changes to its task
+        if let Some(Dialog::AddTask(dialog)) = &mut self.dialog
+            && let (Some(id), Some(base)) = (dialog.editing_task_id.clone(), dialog.base_updated_at)
+            && changes.tasks.contains(&id)
+        {
+            match self.tasks.iter().find(|t| t.id == id) {
+                None => dialog.mark_conflict("Task was deleted externally"),
+                Some(task) if task.updated_at != base => {
+                    dialog.mark_conflict("Task was changed externally");
+                    dialog.base_updated_at = Some(task.updated_at);
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut parts = Vec::new();
+        if added > 0 {
+            parts.push(format!("{} added", added));
+        }
+        if changed > 0 {
+            parts.push(format!("{} changed", changed));
+        }
+        if removed > 0 {
+            parts.push(format!("{} removed", removed));
+        }
+        if parts.is_empty() {
+            self.set_status("Updated externally");
+        } else {
+            self.set_status(format!("Updated externally: {}", parts.join(", ")));
+        }
+        self.redraw_requested = true;
+        Ok(())
+    }
+
+    /// Reloads the tasks, projects and tags in `changes`, keeping the
+    /// selection. Returns how many tasks were added, changed and removed.
+    async fn refresh_changes(&mut self, changes: &ChangeSet) -> Result<(usize, usize, usize)> {
+        let selected_task_id = self.selected_task().map(|t| t.id.clone());
+        let selected_project_id = self.selected_project().map(|p| p.id.clone());
+
+        let (mut added, mut changed, mut removed) = (0, 0, 0);
+        for id in &changes.tasks {
+            let index = self.tasks.iter().position(|t| t.id == *id);
+            match (self.db.get_task(id).await?, index) {
+                (Some(task), Some(index)) => {
+                    if task.updated_at != self.tasks[index].updated_at {
+                        changed += 1;
+                    }
+                    self.tasks[index] = task;
+                }
+                (Some(task), None) => {
+                    // Keep the newest-first order tasks are loaded in
+                    let index = self
+                        .tasks
+                        .iter()
+                        .position(|t| t.created_at < task.created_at)
+                        .unwrap_or(self.tasks.len());
+                    self.tasks.insert(index, task);
+                    added += 1;
+                }
+                (None, Some(index)) => {
+                    self.tasks.remove(index);
+                    removed += 1;
+                }
+                (None, None) => {}
+            }
+        }
+        for id in &changes.projects {
+            let index = self.projects.iter().position(|p| p.id == *id);
+            match (self.db.get_project(id).await?, index) {
+                (Some(project), Some(index)) => self.projects[index] = project,
+                (Some(project), None) => self.projects.push(project),
+                (None, Some(index)) => {
+                    self.projects.remove(index);
+                }
+                (None, None) => {}
+            }
+        }
+        if changes.tags {
+            self.tags = self.db.get_all_tags().await?;
+        }
+
+        // Restore the project selection; a deleted project falls back to "All Tasks"
+        if let Some(project_id) = selected_project_id {
+            self.selected_project_index = self
+                .projects
+                .iter()
+                .position(|p| p.id == project_id)
+                .map_or(0, |i| i + 1);
+        }
+
+        // Restore the task selection, or keep the same row if the task is gone
+        if let Some(task_id) = selected_task_id
+            && let Some(index) = self.visible_tasks().iter().position(|t| t.id == task_id)
+        {
+            self.selected_task_index = Some(index);
+        }
+        self.adjust_task_selection();
+        Ok((added, changed, removed))
+    }
+
     /// Adjusts the selected task index to remain valid after tasks change.
     ///
     /// Call this after modifying `self.tasks` in-place to keep the selection
@@ -343,8 +803,55 @@ impl App {
 
     /// Returns the list of tasks after applying current filter and sort.
     ///
-    /// This is the list that should be displayed in the task list UI.
+    /// This is the list that should be displayed in the task list UI. When
+    /// the list is grouped, tasks come in group order and those in
+    /// collapsed groups are left out.
     pub fn visible_tasks(&self) -> Vec<&Task> {
+        if self.group_by == GroupBy::None {
+            return self.filtered_tasks();
+        }
+        self.task_groups()
+            .into_iter()
+            .filter(|g| !self.collapsed_groups.contains(&g.label))
+            .flat_map(|g| g.tasks)
+            .collect()
+    }
+
+    /// Returns the filtered and sorted tasks in groups, including the
+    /// collapsed ones.
+    pub fn task_groups(&self) -> Vec<TaskGroup<'_>> {
+        self.group_by
+            .group(&self.filtered_tasks(), &self.projects, chrono::Local::now())
+    }
+
+    /// Returns the rows of the task list: group headers, when grouped, and
+    /// the visible tasks.
+    pub fn list_rows(&self) -> Vec<ListRow<'_>> {
+        if self.group_by == GroupBy::None {
+            return self.filtered_tasks().into_iter().enumerate().map(|(i, t)| ListRow::Task(i, t)).collect();
+        }
+        let mut rows = Vec::new();
+        let mut index = 0;
+        for group in self.task_groups() {
+            let collapsed = self.collapsed_groups.contains(&group.label);
+            rows.push(ListRow::Header {
+                label: group.label,
+                count: group.tasks.len(),
+                collapsed,
+            });
+            if !collapsed {
+                for task in group.tasks {
+                    rows.push(ListRow::Task(index, task));
+                    index += 1;
+                }
+            }
+        }
+        rows
+    }
+
+    /// Returns the tasks in the selected project that match the filter, in
+    /// sort order.
+    fn filtered_tasks(&self) -> Vec<&Task> {
         // Apply project filter if a specific project is selected
         let project_filter = if self.selected_project_index == 0 {
             None // "All Tasks"
@@ -369,10 +876,16 @@ impl App {
             })
             .collect();
 
-        self.sort.apply(&mut tasks);
+        sort_by_keys(&mut tasks, &self.sort_keys());
         tasks
     }
 
+    /// Returns every key the task list is sorted by: the main sort order,
+    /// then the secondary keys.
+    pub fn sort_keys(&self) -> Vec<SortKey> {
+        std::iter::once(self.sort.key()).chain(self.then_by.iter().copied()).collect()
+    }
+
     /// Returns the currently selected task, if any.
     pub fn selected_task(&self) -> Option<&Task> {
         let tasks = self.visible_tasks();
@@ -420,6 +933,505 @@ impl App {
         }
     }
 
+    /// Returns the profile's default project, if it has one that exists.
+    pub fn default_project(&self) -> Option<&Project> {
+        let name = self.profile.default_project.as_deref()?;
+        self.projects.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the project new tasks go to: the selected project, or the
+    /// profile's default project when "All Tasks" is selected.
+    pub fn project_for_new_tasks(&self) -> Option<&Project> {
+        self.selected_project().or_else(|| self.default_project())
+    }
+
+    /// Sets the open profile, shown in the header.
+    pub fn set_profile(&mut self, name: Option<String>, profile: Profile) {
+        self.profile_name = name;
+        self.profile = profile;
+    }
+
+    /// Replaces the database with another profile's, without restarting.
+    ///
+    /// Selection, filter and view are reset, since they refer to the old
+    /// database's tasks and projects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if data cannot be loaded from the new database.
+    pub async fn switch_database(&mut self, db: Database, name: String, profile: Profile) -> Result<()> {
+        // Changes still waiting for their hooks belong to the old database
+        self.wait_for_saves().await?;
+        self.db = db;
+        self.set_profile(Some(name), profile);
+        self.selected_task_index = None;
+        self.selected_project_index = 0;
+        self.filter = Filter::Pending;
+        self.current_view = View::Main;
+        self.focus = FocusPanel::TaskList;
+        self.editing_task = None;
+        // Marks, a bulk action waiting for confirmation and collapsed groups
+        // all refer to the old database's tasks
+        if self.input_mode == InputMode::Select {
+            self.input_mode = InputMode::Normal;
+        }
+        self.marked.clear();
+        self.pending_bulk = None;
+        self.collapsed_groups.clear();
+        self.search_results.clear();
+        self.webhook_deliveries.clear();
+        self.task_history.clear();
+        self.activity.clear();
+        self.load_data().await?;
+        self.load_group_by().await?;
+        self.last_review = self.db.last_review().await?;
+        Ok(())
+    }
+
+    /// Creates the tasks of the template named `key`, filling `{input}`
+    /// placeholders with `input`.
+    ///
+    /// The tasks go to the template's project, which is created if it
+    /// doesn't exist yet, or else to the project new tasks go to. Tasks
+    /// rejected by hooks are skipped. See [`App::save`] for when the tasks
+    /// show.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tasks or project can't be saved.
+    pub async fn apply_template(&mut self, key: &str, input: &str) -> Result<()> {
+        let Some(template) = self.templates.get(key).cloned() else {
+            self.set_status(format!("Unknown template: {}", key));
+            return Ok(());
+        };
+
+        let project_id = match template.project.as_deref() {
+            Some(name) => match find_project(&self.projects, name) {
+                Some(project) => Some(project.id.clone()),
+                None => {
+                    let project = Project::new(name);
+                    self.db.insert_project(&project).await?;
+                    let id = project.id.clone();
+                    self.projects.push(project);
+                    Some(id)
+                }
+            },
+            None => self.project_for_new_tasks().map(|p| p.id.clone()),
+        };
+
+        let tasks: Vec<Task> = template
+            .instantiate(input, chrono::Utc::now())
+            .into_iter()
+            .map(|task| Task { project_id: project_id.clone(), ..task })
+            .collect();
+        let name = template.display_name().to_string();
+        self.save(
+            async move |db| {
+                let mut created = Vec::new();
+                let mut rejected = 0;
+                for task in tasks {
+                    match db.add_task(task).await {
+                        Ok(task) => created.push(task.id),
+                        Err(StorageError::Hook(_)) => rejected += 1,
+                        Err(e) => return Err(e),
+                    }
+                }
+                Ok((created, rejected))
+            },
+            move |app, result| {
+                let Some((created, rejected)) = result else {
+                    return;
+                };
+                app.pending_new_task_animation = created.first().cloned();
+                let mut message = format!(
+                    "Created {} task{} from {}",
+                    created.len(),
+                    if created.len() == 1 { "" } else { "s" },
+                    name
+                );
+                if rejected > 0 {
+                    message.push_str(&format!(" ({} rejected by hooks)", rejected));
+                }
+                app.set_status(message);
+            },
+        )
+        .await
+    }
+
+    /// Enters selection mode, for marking tasks to change together.
+    pub fn enter_select_mode(&mut self) {
+        self.input_mode = InputMode::Select;
+        self.focus = FocusPanel::TaskList;
+        self.marked.clear();
+    }
+
+    /// Leaves selection mode, clearing the marks.
+    pub fn exit_select_mode(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.marked.clear();
+    }
+
+    /// Marks the selected task, or unmarks it if it's marked.
+    pub fn toggle_mark(&mut self) {
+        if let Some(id) = self.selected_task().map(|t| t.id.clone())
+            && !self.marked.remove(&id)
+        {
+            self.marked.insert(id);
+        }
+    }
+
+    /// Marks every visible task, or clears the marks if all of them are
+    /// marked already.
+    pub fn toggle_mark_all_visible(&mut self) {
+        let visible: Vec<String> = self.visible_tasks().iter().map(|t| t.id.clone()).collect();
+        if visible.iter().all(|id| self.marked.contains(id)) {
+            self.marked.clear();
+        } else {
+            self.marked.extend(visible);
+        }
+    }
+
+    /// Marks the visible tasks that match `filter`, keeping existing marks.
+    ///
+    /// Returns how many tasks matched.
+    pub fn mark_matching(&mut self, filter: &Filter) -> usize {
+        let matching: Vec<String> = self
+            .visible_tasks()
+            .iter()
+            .filter(|t| filter.matches(t))
+            .map(|t| t.id.clone())
+            .collect();
+        let count = matching.len();
+        self.marked.extend(matching);
+        count
+    }
+
+    /// Returns the marked tasks that still exist.
+    pub fn marked_tasks(&self) -> Vec<&Task> {
+        self.tasks.iter().filter(|t| self.marked.contains(&t.id)).collect()
+    }
+
+    /// Applies `edit` to every marked task in one transaction, then leaves
+    /// selection mode.
+    ///
+    /// If a hook rejects the change, nothing is saved and the marks are
+    /// kept. See [`App::save`] for when the change shows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tasks can't be saved.
+    pub async fn apply_bulk_edit(&mut self, edit: &BulkEdit) -> Result<()> {
+        let now = chrono::Utc::now();
+        let changed: Vec<Task> = self
+            .marked_tasks()
+            .into_iter()
+            .filter_map(|t| edit.apply(t, now))
+            .collect();
+        self.save(
+            async move |db| db.modify_tasks(changed).await,
+            |app, saved| {
+                if let Some(saved) = saved {
+                    app.set_status(format!("Updated {}", task_count(saved.len())));
+                    app.exit_select_mode();
+                }
+            },
+        )
+        .await
+    }
+
+    /// Deletes every marked task in one transaction, then leaves selection
+    /// mode, appending `note` to the status message.
+    ///
+    /// If a hook rejects a delete, nothing is deleted and the marks are
+    /// kept. See [`App::save`] for when the change shows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tasks can't be deleted.
+    pub async fn delete_marked(&mut self, note: String) -> Result<()> {
+        let ids: Vec<String> = self.marked_tasks().iter().map(|t| t.id.clone()).collect();
+        self.save(
+            async move |db| db.remove_tasks(&ids).await,
+            move |app, count| {
+                if let Some(count) = count {
+                    app.set_status(format!("Deleted {}{}", task_count(count), note));
+                    app.exit_select_mode();
+                }
+            },
+        )
+        .await
+    }
+
+    /// Moves the selected task to index `to` of the visible list and saves
+    /// the new manual order, keeping the task selected.
+    ///
+    /// Only the manual sort can be rearranged; in any other sort this just
+    /// says so in the status bar.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new positions can't be saved.
+    pub async fn move_selected_task(&mut self, to: usize) -> Result<()> {
+        if self.sort != SortOrder::Manual {
+            self.set_status("Switch to manual sort (f) to reorder tasks");
+            return Ok(());
+        }
+        let Some(from) = self.selected_task_index else {
+            return Ok(());
+        };
+        let visible = self.visible_tasks();
+        let Some(moved_id) = visible.get(from).map(|t| t.id.clone()) else {
+            return Ok(());
+        };
+        // In a grouped list, tasks only move within their group
+        let (start, end) = self.group_range(from);
+        let positions: Vec<f64> = visible[start..end].iter().map(|t| t.position).collect();
+        let to = to.clamp(start, end - 1);
+        let moves: Vec<(String, f64)> = move_positions(&positions, from - start, to - start)
+            .into_iter()
+            .map(|(index, position)| (visible[start + index].id.clone(), position))
+            .collect();
+        if moves.is_empty() {
+            return Ok(());
+        }
+
+        self.db.set_task_positions(&moves).await?;
+        for (id, position) in moves {
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                task.position = position;
+            }
+        }
+        self.selected_task_index = self.visible_tasks().iter().position(|t| t.id == moved_id);
+        Ok(())
+    }
+
+    /// Returns the range of visible task indices in the same group as the
+    /// task at `index`; the whole list when it isn't grouped.
+    fn group_range(&self, index: usize) -> (usize, usize) {
+        let mut start = 0;
+        for group in self.task_groups() {
+            if self.collapsed_groups.contains(&group.label) {
+                continue;
+            }
+            let end = start + group.tasks.len();
+            if index < end {
+                return (start, end);
+            }
+            start = end;
+        }
+        (start, start + 1)
+    }
+
+    /// Returns the index in the visible list of the task drawn at the
+    /// given screen cell, if any.
+    pub fn task_index_at(&self, column: u16, row: u16) -> Option<usize> {
+        match self.row_at(column, row)? {
+            ListRow::Task(index, _) => Some(index),
+            ListRow::Header { .. } => None,
+        }
+    }
+
+    /// Returns the label of the group header drawn at the given screen
+    /// cell, if any.
+    pub fn group_at(&self, column: u16, row: u16) -> Option<String> {
+        match self.row_at(column, row)? {
+            ListRow::Header { label, .. } => Some(label),
+            ListRow::Task(..) => None,
+        }
+    }
+
+    /// Returns the list row drawn at the given screen cell.
+    fn row_at(&self, column: u16, row: u16) -> Option<ListRow<'_>> {
+        let area = self.last_task_list_area?;
+        let inside = column >= area.x
+            && column < area.x + area.width
+            && row >= area.y
+            && row < area.y + area.height;
+        if !inside {
+            return None;
+        }
+        let index = self.last_list_scroll_offset + (row - area.y) as usize;
+        self.list_rows().into_iter().nth(index)
+    }
+
+    /// Loads the grouping remembered for this database.
+    async fn load_group_by(&mut self) -> Result<()> {
+        self.group_by = self
+            .db
+            .get_preference(GROUP_BY_PREFERENCE)
+            .await?
+            .and_then(|name| name.parse().ok())
+            .unwrap_or_default();
+        self.collapsed_groups.clear();
+        Ok(())
+    }
+
+    /// Groups the task list by `group_by` and remembers the choice for
+    /// the next session. All groups start expanded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the choice can't be saved.
+    pub async fn set_group_by(&mut self, group_by: GroupBy) -> Result<()> {
+        if group_by != self.group_by {
+            self.group_by = group_by;
+            self.collapsed_groups.clear();
+            self.db.set_preference(GROUP_BY_PREFERENCE, group_by.name()).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the label of the group holding the selected task.
+    pub fn selected_group(&self) -> Option<String> {
+        let selected = self.selected_task()?;
+        self.task_groups()
+            .into_iter()
+            .find(|g| g.tasks.iter().any(|t| t.id == selected.id))
+            .map(|g| g.label)
+    }
+
+    /// Collapses the group labelled `label`, or expands it if collapsed.
+    ///
+    /// The selection stays on the same task if it's still visible, and
+    /// otherwise moves to the first task after the collapsed group.
+    pub fn toggle_group(&mut self, label: &str) {
+        let selected_id = self.selected_task().map(|t| t.id.clone());
+        if !self.collapsed_groups.remove(label) {
+            self.collapsed_groups.insert(label.to_string());
+        }
+        self.reselect_after_toggle(label, selected_id);
+    }
+
+    /// Expands the group holding the task `task_id`, so it can be selected.
+    pub fn expand_group_of(&mut self, task_id: &str) {
+        let label = self
+            .task_groups()
+            .into_iter()
+            .find(|g| g.tasks.iter().any(|t| t.id == task_id))
+            .map(|g| g.label);
+        if let Some(label) = label {
+            self.collapsed_groups.remove(&label);
+        }
+    }
+
+    /// Collapses every group, or expands them all if any is collapsed.
+    pub fn toggle_all_groups(&mut self) {
+        if self.group_by == GroupBy::None {
+            return;
+        }
+        let selected_group = self.selected_group();
+        let selected_id = self.selected_task().map(|t| t.id.clone());
+        if self.collapsed_groups.is_empty() {
+            self.collapsed_groups = self.task_groups().into_iter().map(|g| g.label).collect();
+        } else {
+            self.collapsed_groups.clear();
+        }
+        match selected_group {
+            Some(label) => self.reselect_after_toggle(&label, selected_id),
+            None => self.adjust_task_selection(),
+        }
+    }
+
+    /// Keeps the task `selected_id` selected if it's visible after
+    /// collapsing or expanding the group `label`, or else selects the task
+    /// after the group.
+    fn reselect_after_toggle(&mut self, label: &str, selected_id: Option<String>) {
+        let visible = self.visible_tasks();
+        if let Some(index) = selected_id.and_then(|id| visible.iter().position(|t| t.id == id)) {
+            self.selected_task_index = Some(index);
+            return;
+        }
+        // Visible tasks in the groups before this one
+        let before: usize = self
+            .task_groups()
+            .into_iter()
+            .take_while(|g| g.label != label)
+            .filter(|g| !self.collapsed_groups.contains(&g.label))
+            .map(|g| g.tasks.len())
+            .sum();
+        self.selected_task_index = Some(before);
+        self.adjust_task_selection();
+    }
+
+    /// Starts the weekly review at its first step with any tasks.
+    pub fn start_review(&mut self) {
+        self.review = ReviewState::default();
+        self.load_review_step(ReviewStep::Overdue);
+        self.current_view = View::Review;
+    }
+
+    /// Returns the task the review is asking about, if the step has one.
+    pub fn review_task(&self) -> Option<&Task> {
+        let id = self.review.queue.get(self.review.position)?;
+        self.tasks.iter().find(|t| &t.id == id)
+    }
+
+    /// Moves the review on to the next task, or to the next step after the
+    /// last one. Tasks deleted in the meantime are skipped.
+    pub fn advance_review(&mut self) {
+        self.review.position += 1;
+        while self.review.position < self.review.queue.len() && self.review_task().is_none() {
+            self.review.position += 1;
+        }
+        if self.review.position >= self.review.queue.len() {
+            self.next_review_step();
+        }
+    }
+
+    /// Skips the rest of the current step.
+    pub fn next_review_step(&mut self) {
+        if let Some(next) = self.review.step.next() {
+            self.load_review_step(next);
+        }
+    }
+
+    /// Shows `step`, or the first step after it with tasks. The last step
+    /// is always shown, even when nothing is coming up.
+    fn load_review_step(&mut self, step: ReviewStep) {
+        let mut step = step;
+        loop {
+            let queue: Vec<String> = step
+                .tasks(&self.tasks, chrono::Utc::now(), self.review_config.stale_days)
+                .into_iter()
+                .map(|t| t.id.clone())
+                .collect();
+            match step.next() {
+                Some(next) if queue.is_empty() => step = next,
+                _ => {
+                    self.review.step = step;
+                    self.review.queue = queue;
+                    self.review.position = 0;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Records the review as done and returns to the task list.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the review time can't be saved.
+    pub async fn finish_review(&mut self) -> Result<()> {
+        let now = chrono::Utc::now();
+        self.db.set_last_review(now).await?;
+        self.last_review = Some(now);
+        self.current_view = View::Main;
+        let handled = self.review.handled;
+        self.set_status(match handled {
+            0 => "Review done, nothing changed".to_string(),
+            1 => "Review done, 1 task updated".to_string(),
+            n => format!("Review done, {} tasks updated", n),
+        });
+        Ok(())
+    }
+
+    /// Returns whether it's time for another weekly review.
+    pub fn review_due(&self) -> bool {
+        let oldest = self.tasks.iter().map(|t| t.created_at).min();
+        review_due(self.last_review, oldest, chrono::Utc::now(), self.review_config.interval_days)
+    }
+
     /// Returns the task count for a specific project.
     pub fn task_count_for_project(&self, project_id: &str) -> usize {
         self.tasks
@@ -543,6 +1555,21 @@ impl App {
         self.status_message_set_at = Some(Instant::now());
     }
 
+    /// Shows a hook rejection in the status bar instead of failing.
+    ///
+    /// Returns `Ok(None)` if a hook rejected the change, so the caller can
+    /// leave its in-memory state alone; other storage errors pass through.
+    pub fn check_hooks<T>(&mut self, result: std::result::Result<T, StorageError>) -> Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(StorageError::Hook(e)) => {
+                self.set_status(e.to_string());
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Clears the status message.
     pub fn clear_status(&mut self) {
         self.status_message = None;
@@ -608,7 +1635,8 @@ impl App {
             SortOrder::DueDateAsc => SortOrder::PriorityDesc,
             SortOrder::PriorityDesc => SortOrder::CreatedDesc,
             SortOrder::CreatedDesc => SortOrder::Alphabetical,
-            SortOrder::Alphabetical => SortOrder::DueDateAsc,
+            SortOrder::Alphabetical => SortOrder::Manual,
+            SortOrder::Manual => SortOrder::DueDateAsc,
             _ => SortOrder::DueDateAsc,
         };
     }
@@ -643,6 +1671,7 @@ impl App {
             SortOrder::CreatedDesc => "Newest",
             SortOrder::CreatedAsc => "Oldest",
             SortOrder::Alphabetical => "A-Z",
+            SortOrder::Manual => "Manual",
         }
     }
 }
@@ -655,6 +1684,7 @@ impl std::fmt::Debug for App {
             .field("current_view", &self.current_view)
             .field("input_mode", &self.input_mode)
             .field("focus", &self.focus)
+            .field("profile_name", &self.profile_name)
             .field("should_quit", &self.should_quit)
             .finish()
     }
@@ -835,4 +1865,366 @@ mod tests {
         assert_eq!(app.total_task_count(), 2);
         assert_eq!(app.task_count_for_project("inbox"), 1);
     }
+
+    #[tokio::test]
+    async fn test_reload_changes_keeps_selection() {
+        let mut app = setup_app().await;
+        app.sort = SortOrder::Alphabetical;
+
+        app.db.insert_task(&Task::new("B first")).await.unwrap();
+        let second = Task::new("C second");
+        app.db.insert_task(&second).await.unwrap();
+        let gone = Task::new("D gone");
+        app.db.insert_task(&gone).await.unwrap();
+        app.load_data().await.unwrap();
+        app.selected_task_index = Some(1);
+        assert_eq!(app.selected_task().unwrap().title, "C second");
+
+        // Another program adds a task that sorts above the selection
+        let db = app.db.clone();
+        let ((), changes) = db
+            .track_changes(async || {
+                db.insert_task(&Task::new("A new")).await.unwrap();
+                let mut renamed = second.clone();
+                renamed.title = "C renamed".to_string();
+                renamed.updated_at += chrono::Duration::seconds(5);
+                db.update_task(&renamed).await.unwrap();
+                db.delete_task(&gone.id).await.unwrap();
+            })
+            .await;
+        app.reload_changes(&changes).await.unwrap();
+
+        assert_eq!(app.tasks.len(), 3);
+        assert_eq!(app.selected_task().unwrap().title, "C renamed");
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Updated externally: 1 added, 1 changed, 1 removed")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_external_file_edits_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open_dir(dir.path()).await.unwrap();
+        run_migrations(&db).await.unwrap();
+        let mut app = App::new(db).await.unwrap();
+
+        // The app's own writes aren't external
+        let task = app.db.add_task(Task::new("Mine")).await.unwrap();
+        app.last_external_check = Instant::now() - EXTERNAL_CHANGE_POLL_INTERVAL;
+        assert!(!app.check_external_changes().await.unwrap());
+
+        let path = dir.path().join("tasks").join(format!("{}.md", task.id));
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, content.replace("Mine", "Edited in git")).unwrap();
+        app.last_external_check = Instant::now() - EXTERNAL_CHANGE_POLL_INTERVAL;
+        assert!(app.check_external_changes().await.unwrap());
+        assert_eq!(app.tasks[0].title, "Edited in git");
+
+        // Nothing new: no reload
+        app.last_external_check = Instant::now() - EXTERNAL_CHANGE_POLL_INTERVAL;
+        assert!(!app.check_external_changes().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_external_change_flags_open_edit_dialog() {
+        use crate::ui::dialogs::AddTaskDialog;
+
+        let mut app = setup_app().await;
+        let task = Task::new("Shared");
+        app.db.insert_task(&task).await.unwrap();
+        app.load_data().await.unwrap();
+        app.dialog = Some(Dialog::AddTask(Box::new(AddTaskDialog::from_task(&app.tasks[0]))));
+
+        let mut theirs = task.clone();
+        theirs.title = "Renamed elsewhere".to_string();
+        theirs.updated_at = task.updated_at + chrono::Duration::seconds(5);
+        let db = app.db.clone();
+        let (_, changes) = db.track_changes(async || db.update_task(&theirs).await).await;
+        app.reload_changes(&changes).await.unwrap();
+
+        let Some(Dialog::AddTask(dialog)) = &app.dialog else {
+            panic!("edit dialog should stay open");
+        };
+        assert_eq!(dialog.conflict.as_deref(), Some("Task was changed externally"));
+    }
+
+    #[tokio::test]
+    async fn test_project_for_new_tasks_uses_profile_default() {
+        let mut app = setup_app().await;
+        let clients = Project::new("Clients");
+        app.db.insert_project(&clients).await.unwrap();
+        app.load_data().await.unwrap();
+        assert!(app.project_for_new_tasks().is_none());
+
+        app.set_profile(
+            Some("work".to_string()),
+            Profile {
+                default_project: Some("clients".to_string()),
+                ..Profile::default()
+            },
+        );
+        assert_eq!(app.project_for_new_tasks().unwrap().id, clients.id);
+
+        // A selected project wins over the default
+        app.selected_project_index = 1;
+        assert_eq!(app.project_for_new_tasks().unwrap().id, app.projects[0].id);
+    }
+
+    #[tokio::test]
+    async fn test_switch_database() {
+        let mut app = setup_app().await;
+        app.db.insert_task(&Task::new("Personal errand")).await.unwrap();
+        app.load_data().await.unwrap();
+        app.selected_task_index = Some(0);
+        app.filter = Filter::All;
+
+        let work = Database::open_in_memory().await.unwrap();
+        run_migrations(&work).await.unwrap();
+        work.insert_task(&Task::new("Quarterly report")).await.unwrap();
+        app.switch_database(work, "work".to_string(), Profile::default())
+            .await
+            .unwrap();
+
+        assert_eq!(app.profile_name.as_deref(), Some("work"));
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(app.tasks[0].title, "Quarterly report");
+        assert_eq!(app.filter, Filter::Pending);
+        assert_eq!(app.selected_task_index, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_switch_database_clears_marks() {
+        let mut app = setup_app().await;
+        app.db.insert_task(&Task::new("Personal errand")).await.unwrap();
+        app.load_data().await.unwrap();
+        app.enter_select_mode();
+        app.toggle_mark_all_visible();
+        assert_eq!(app.marked.len(), 1);
+        app.pending_bulk = Some(BulkAction::Delete);
+        app.collapsed_groups.insert("Personal".to_string());
+
+        let work = Database::open_in_memory().await.unwrap();
+        run_migrations(&work).await.unwrap();
+        work.insert_task(&Task::new("Quarterly report")).await.unwrap();
+        app.switch_database(work, "work".to_string(), Profile::default())
+            .await
+            .unwrap();
+
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.marked.is_empty());
+        assert!(app.pending_bulk.is_none());
+        assert!(app.collapsed_groups.is_empty());
+        assert_eq!(app.db.get_all_tasks().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_apply_template_creates_tasks_and_project() {
+        use crate::templates::{Template, Templates};
+
+        let mut app = setup_app().await;
+        let template = Template::parse(
+            "release",
+            "title = \"Release {input}\"\nproject = \"Ratado\"\n[[tasks]]\ntitle = \"Announce {input}\"",
+        )
+        .unwrap();
+        app.templates = Templates::from_templates(vec![template]);
+
+        app.apply_template("release", "v1.4").await.unwrap();
+        assert_eq!(app.tasks.len(), 2);
+        let project_count = app.projects.len();
+        let ratado = app.projects.iter().find(|p| p.name == "Ratado").unwrap().id.clone();
+        assert!(app.tasks.iter().all(|t| t.project_id.as_deref() == Some(ratado.as_str())));
+        assert!(app.tasks.iter().any(|t| t.title == "Announce v1.4"));
+        assert_eq!(app.db.get_all_tasks().await.unwrap().len(), 2);
+
+        // The project is reused the second time
+        app.apply_template("release", "v1.5").await.unwrap();
+        assert_eq!(app.db.get_all_projects().await.unwrap().len(), project_count);
+
+        app.apply_template("missing", "").await.unwrap();
+        assert_eq!(app.status_message.as_deref(), Some("Unknown template: missing"));
+    }
+
+    #[tokio::test]
+    async fn test_marking_tasks() {
+        let mut app = setup_app().await;
+        let mut urgent = Task::new("Urgent");
+        urgent.priority = Priority::Urgent;
+        app.db.insert_task(&urgent).await.unwrap();
+        app.db.insert_task(&Task::new("Other")).await.unwrap();
+        app.load_data().await.unwrap();
+        app.selected_task_index = Some(0);
+
+        app.enter_select_mode();
+        assert_eq!(app.mark_matching(&Filter::ByPriority(Priority::Urgent)), 1);
+        assert!(app.marked.contains(&urgent.id));
+
+        app.toggle_mark_all_visible();
+        assert_eq!(app.marked_tasks().len(), app.visible_tasks().len());
+        app.toggle_mark_all_visible();
+        assert!(app.marked.is_empty());
+
+        app.toggle_mark();
+        assert_eq!(app.marked.len(), 1);
+        app.toggle_mark();
+        assert!(app.marked.is_empty());
+
+        app.toggle_mark();
+        app.exit_select_mode();
+        assert!(app.marked.is_empty());
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[tokio::test]
+    async fn test_move_selected_task() {
+        let mut app = setup_app().await;
+        for (title, position) in [("One", 1.0), ("Two", 2.0), ("Three", 3.0)] {
+            let mut task = Task::new(title);
+            task.position = position;
+            app.db.insert_task(&task).await.unwrap();
+        }
+        app.load_data().await.unwrap();
+        let titles = |app: &App| app.visible_tasks().iter().map(|t| t.title.clone()).collect::<Vec<_>>();
+
+        // Other sorts can't be rearranged
+        app.selected_task_index = Some(2);
+        app.move_selected_task(0).await.unwrap();
+        assert!(app.status_message.is_some());
+
+        app.sort = SortOrder::Manual;
+        app.selected_task_index = Some(2);
+        app.move_selected_task(0).await.unwrap();
+        assert_eq!(titles(&app), vec!["Three", "One", "Two"]);
+        assert_eq!(app.selected_task().unwrap().title, "Three");
+
+        // The new order is saved
+        app.load_data().await.unwrap();
+        assert_eq!(titles(&app), vec!["Three", "One", "Two"]);
+    }
+
+    #[tokio::test]
+    async fn test_task_index_at() {
+        let mut app = setup_app().await;
+        app.db.insert_task(&Task::new("One")).await.unwrap();
+        app.db.insert_task(&Task::new("Two")).await.unwrap();
+        app.load_data().await.unwrap();
+        app.last_task_list_area = Some(Rect::new(10, 5, 40, 10));
+
+        assert_eq!(app.task_index_at(12, 5), Some(0));
+        assert_eq!(app.task_index_at(12, 6), Some(1));
+        assert_eq!(app.task_index_at(12, 7), None);
+        assert_eq!(app.task_index_at(5, 5), None);
+        app.last_list_scroll_offset = 1;
+        assert_eq!(app.task_index_at(12, 5), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_grouped_list() {
+        let mut app = setup_app().await;
+        for (title, priority) in [("Low", Priority::Low), ("Urgent", Priority::Urgent), ("Urgent 2", Priority::Urgent)] {
+            let mut task = Task::new(title);
+            task.priority = priority;
+            app.db.insert_task(&task).await.unwrap();
+        }
+        app.load_data().await.unwrap();
+        app.sort = SortOrder::Alphabetical;
+        app.set_group_by(GroupBy::Priority).await.unwrap();
+
+        let rows = app.list_rows();
+        assert_eq!(rows.len(), 5);
+        assert!(matches!(&rows[0], ListRow::Header { label, count: 2, collapsed: false } if label == "Urgent"));
+        assert!(matches!(rows[1], ListRow::Task(0, task) if task.title == "Urgent"));
+        assert!(matches!(&rows[3], ListRow::Header { label, .. } if label == "Low"));
+
+        // Navigation moves between tasks across the header
+        app.selected_task_index = Some(1);
+        app.select_next_task();
+        assert_eq!(app.selected_task().unwrap().title, "Low");
+
+        // Collapsing the selected group moves to the next one
+        app.selected_task_index = Some(0);
+        app.toggle_group("Urgent");
+        assert_eq!(app.visible_tasks().len(), 1);
+        assert_eq!(app.selected_task().unwrap().title, "Low");
+        app.toggle_all_groups();
+        assert!(app.collapsed_groups.is_empty());
+        assert_eq!(app.selected_task().unwrap().title, "Low");
+        app.toggle_all_groups();
+        assert!(app.visible_tasks().is_empty());
+        assert_eq!(app.list_rows().len(), 2);
+
+        // The grouping is remembered for the next session
+        let app = App::new(app.db.clone()).await.unwrap();
+        assert_eq!(app.group_by, GroupBy::Priority);
+        assert!(app.collapsed_groups.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_review_skips_empty_steps() {
+        let mut app = setup_app().await;
+        app.start_review();
+        assert_eq!(app.current_view, View::Review);
+        assert_eq!(app.review.step, ReviewStep::NextWeek);
+        assert!(app.review_task().is_none());
+        assert!(!app.review_due());
+
+        let mut old = Task::new("Old");
+        old.created_at = chrono::Utc::now() - chrono::Duration::days(10);
+        app.db.insert_task(&old).await.unwrap();
+        app.load_data().await.unwrap();
+        assert!(app.review_due());
+
+        // The task has no due date, so the review starts at triage
+        app.start_review();
+        assert_eq!(app.review.step, ReviewStep::Triage);
+        assert_eq!(app.review_task().unwrap().title, "Old");
+        app.advance_review();
+        assert_eq!(app.review.step, ReviewStep::NextWeek);
+
+        app.finish_review().await.unwrap();
+        assert!(!app.review_due());
+        assert_eq!(app.current_view, View::Main);
+        let app = App::new(app.db.clone()).await.unwrap();
+        assert!(app.last_review.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_then_by_breaks_ties() {
+        let mut app = setup_app().await;
+        for (title, priority) in [("B", Priority::High), ("C", Priority::Low), ("A", Priority::High)] {
+            let mut task = Task::new(title);
+            task.priority = priority;
+            app.db.insert_task(&task).await.unwrap();
+        }
+        app.load_data().await.unwrap();
+        app.sort = SortOrder::PriorityDesc;
+        app.then_by = vec![SortOrder::Alphabetical.key()];
+
+        let titles: Vec<&str> = app.visible_tasks().iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["A", "B", "C"]);
+        assert_eq!(app.sort_keys().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_move_stays_within_group() {
+        let mut app = setup_app().await;
+        for (title, position, priority) in
+            [("A", 1.0, Priority::High), ("B", 2.0, Priority::High), ("C", 3.0, Priority::Low)]
+        {
+            let mut task = Task::new(title);
+            task.position = position;
+            task.priority = priority;
+            app.db.insert_task(&task).await.unwrap();
+        }
+        app.load_data().await.unwrap();
+        app.sort = SortOrder::Manual;
+        app.set_group_by(GroupBy::Priority).await.unwrap();
+
+        app.selected_task_index = Some(0);
+        app.move_selected_task(2).await.unwrap();
+        let titles: Vec<&str> = app.visible_tasks().iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["B", "A", "C"]);
+        assert_eq!(app.selected_task().unwrap().title, "A");
+    }
 }
@@ -0,0 +1,396 @@
+//! User configuration.
+//!
+//! Settings are read from `config.toml` in the same directory as the default
+//! database (`~/.config/ratado/` on Linux). The file is optional; any setting
+//! that is missing falls back to its default.
+//!
+//! ```toml
+//! [backup]
+//! keep = 10          # backups of each kind to retain per database (0 disables backups)
+//! on_startup = true  # take a backup each time the app starts or switches profile
+//!
+//! [encryption]
+//! key_file = "/home/me/.ratado-key"  # optional, passphrase for an encrypted database
+//!
+//! [storage]
+//! dir = "/home/me/notes/tasks"  # optional, keep tasks as plaintext files here
+//!
+//! [ordering]
+//! new_tasks = "bottom"  # or "top", where new tasks go in manual sort
+//!
+//! [review]
+//! interval_days = 7  # nudge for a weekly review this long after the last
+//! stale_days = 14    # tasks not updated for this long count as stale
+//!
+//! [standup]
+//! workdays = ["mon", "tue", "wed", "thu", "fri"]  # "yesterday" skips other days
+//! blocked_tag = "blocked"                         # open tasks listed as blocked
+//!
+//! [[webhooks]]
+//! url = "https://example.com/ratado"
+//! secret = "s3cret"                   # optional, see crate::webhooks
+//! events = ["task.completed"]         # optional, defaults to all events
+//!
+//! [profiles.work]                     # opened with `ratado --profile work`
+//! db_path = "/home/me/work/ratado.db" # database file or plaintext store directory
+//! default_project = "Clients"         # optional, for new tasks outside a project
+//! theme = { accent = "#f97316" }      # optional, header color for this profile
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::Weekday;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::models::NewTaskPlacement;
+use crate::webhooks::Webhook;
+
+/// Name of the configuration file inside the config directory.
+const CONFIG_FILE: &str = "config.toml";
+
+/// Errors that can occur while loading the configuration.
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    /// The config file exists but couldn't be read
+    #[error("Failed to read config file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The config file isn't valid TOML or has wrong value types
+    #[error("Invalid config file: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    /// Could not determine the user's config directory
+    #[error("Could not determine config directory")]
+    NoConfigDir,
+
+    /// No profile has the requested name
+    #[error("Unknown profile '{name}' (configured: {known})")]
+    UnknownProfile {
+        /// The requested name
+        name: String,
+        /// Names of the configured profiles
+        known: String,
+    },
+}
+
+/// Top-level configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Database backup settings
+    pub backup: BackupConfig,
+    /// Where tasks are stored
+    pub storage: StorageConfig,
+    /// How to unlock an encrypted database
+    pub encryption: EncryptionConfig,
+    /// Manual task ordering settings
+    pub ordering: OrderingConfig,
+    /// Weekly review settings
+    pub review: ReviewConfig,
+    /// Standup report settings
+    pub standup: StandupConfig,
+    /// URLs notified when tasks and projects change
+    pub webhooks: Vec<Webhook>,
+    /// Named databases to switch between, by name
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// A named database with its own settings, like `work` or `personal`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// Database file, or directory of a plaintext store
+    pub db_path: PathBuf,
+    /// Name of the project new tasks go to when no project is selected
+    pub default_project: Option<String>,
+    /// Colors that differ from the default theme
+    pub theme: ThemeOverride,
+}
+
+/// Theme colors a profile replaces, as `#rrggbb`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeOverride {
+    /// Brand color of the header, marking which profile is open
+    pub accent: Option<String>,
+}
+
+/// Settings for automatic database backups.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Number of backups of each kind (startup, pre-reset, ...) to retain
+    /// per database; older ones are deleted.
+    /// Zero disables backups entirely.
+    pub keep: usize,
+    /// Whether to take a backup every time the app starts or switches profile
+    pub on_startup: bool,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            keep: 10,
+            on_startup: true,
+        }
+    }
+}
+
+/// Settings for where tasks are stored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Directory to keep tasks in as plaintext files, used when no
+    /// `--db-path` is given. Unset means the default SQLite database.
+    pub dir: Option<PathBuf>,
+}
+
+/// Settings for the manual sort order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrderingConfig {
+    /// Whether new tasks go before or after the existing ones
+    pub new_tasks: NewTaskPlacement,
+}
+
+/// Settings for the weekly review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewConfig {
+    /// Days after the last review before the header asks for another
+    pub interval_days: u32,
+    /// Days without an update after which an open task is stale
+    pub stale_days: u32,
+}
+
+impl Default for ReviewConfig {
+    fn default() -> Self {
+        Self {
+            interval_days: 7,
+            stale_days: 14,
+        }
+    }
+}
+
+/// Settings for standup reports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StandupConfig {
+    /// Days of the week that are worked. A standup covers what was done
+    /// since the last of them, so Monday's reaches back to Friday.
+    pub workdays: Vec<Weekday>,
+    /// Tag marking open tasks as blocked
+    pub blocked_tag: String,
+}
+
+impl Default for StandupConfig {
+    fn default() -> Self {
+        Self {
+            workdays: vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+            blocked_tag: "blocked".to_string(),
+        }
+    }
+}
+
+/// Settings for opening an encrypted database.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncryptionConfig {
+    /// File whose first line is the passphrase, for running without a
+    /// prompt. Unset means asking, unless `RATADO_PASSPHRASE` is set.
+    pub key_file: Option<PathBuf>,
+}
+
+impl Config {
+    /// Returns the path of the configuration file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined.
+    pub fn default_path() -> Result<PathBuf, ConfigError> {
+        let proj_dirs = ProjectDirs::from("", "", "ratado").ok_or(ConfigError::NoConfigDir)?;
+        Ok(proj_dirs.config_dir().join(CONFIG_FILE))
+    }
+
+    /// Loads the configuration from the default location.
+    ///
+    /// A missing file yields the default configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load_default() -> Result<Self, ConfigError> {
+        Self::load(&Self::default_path()?)
+    }
+
+    /// Loads the configuration from `path`.
+    ///
+    /// A missing file yields the default configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the profile called `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::UnknownProfile`] if there is none.
+    pub fn profile(&self, name: &str) -> Result<&Profile, ConfigError> {
+        self.profiles.get(name).ok_or_else(|| ConfigError::UnknownProfile {
+            name: name.to_string(),
+            known: self.profiles.keys().cloned().collect::<Vec<_>>().join(", "),
+        })
+    }
+
+    /// Parses configuration from TOML text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not valid configuration TOML.
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(content)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhooks::WebhookEvent;
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.backup.keep, 10);
+        assert!(config.backup.on_startup);
+    }
+
+    #[test]
+    fn test_partial_backup_section() {
+        let config = Config::parse("[backup]\nkeep = 3\n").unwrap();
+        assert_eq!(config.backup.keep, 3);
+        assert!(config.backup.on_startup);
+    }
+
+    #[test]
+    fn test_storage_dir() {
+        assert_eq!(Config::default().storage.dir, None);
+        let config = Config::parse("[storage]\ndir = \"/tmp/tasks\"\n").unwrap();
+        assert_eq!(config.storage.dir, Some(PathBuf::from("/tmp/tasks")));
+    }
+
+    #[test]
+    fn test_ordering_new_tasks() {
+        assert_eq!(Config::default().ordering.new_tasks, NewTaskPlacement::Bottom);
+        let config = Config::parse("[ordering]\nnew_tasks = \"top\"\n").unwrap();
+        assert_eq!(config.ordering.new_tasks, NewTaskPlacement::Top);
+    }
+
+    #[test]
+    fn test_review_settings() {
+        assert_eq!(Config::default().review.interval_days, 7);
+        let config = Config::parse("[review]\nstale_days = 30\n").unwrap();
+        assert_eq!(config.review.stale_days, 30);
+        assert_eq!(config.review.interval_days, 7);
+    }
+
+    #[test]
+    fn test_standup_settings() {
+        assert_eq!(Config::default().standup.workdays.len(), 5);
+        let config = Config::parse("[standup]\nworkdays = [\"sun\", \"Monday\"]\n").unwrap();
+        assert_eq!(config.standup.workdays, vec![Weekday::Sun, Weekday::Mon]);
+        assert_eq!(config.standup.blocked_tag, "blocked");
+        assert!(Config::parse("[standup]\nworkdays = [\"someday\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_encryption_key_file() {
+        assert_eq!(Config::default().encryption.key_file, None);
+        let config = Config::parse("[encryption]\nkey_file = \"/tmp/key\"\n").unwrap();
+        assert_eq!(config.encryption.key_file, Some(PathBuf::from("/tmp/key")));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let config = Config::parse(
+            r##"
+            [profiles.work]
+            db_path = "/tmp/work.db"
+            default_project = "Clients"
+            theme = { accent = "#f97316" }
+
+            [profiles.personal]
+            db_path = "/tmp/personal"
+            "##,
+        )
+        .unwrap();
+
+        let work = config.profile("work").unwrap();
+        assert_eq!(work.db_path, PathBuf::from("/tmp/work.db"));
+        assert_eq!(work.default_project.as_deref(), Some("Clients"));
+        assert_eq!(work.theme.accent.as_deref(), Some("#f97316"));
+        let personal = config.profile("personal").unwrap();
+        assert_eq!(personal.default_project, None);
+        assert_eq!(personal.theme, ThemeOverride::default());
+
+        let err = config.profile("home").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown profile 'home' (configured: personal, work)"
+        );
+    }
+
+    #[test]
+    fn test_webhooks() {
+        let config = Config::parse(
+            r#"
+            [[webhooks]]
+            url = "https://example.com/hook"
+            secret = "s3cret"
+            events = ["task.created", "project.deleted"]
+
+            [[webhooks]]
+            url = "http://localhost:8080/"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.webhooks.len(), 2);
+        assert_eq!(config.webhooks[0].secret.as_deref(), Some("s3cret"));
+        assert_eq!(
+            config.webhooks[0].events,
+            [WebhookEvent::TaskCreated, WebhookEvent::ProjectDeleted]
+        );
+        assert!(config.webhooks[1].events.is_empty());
+
+        assert!(Config::parse("[[webhooks]]
+url = \"x\"
+events = [\"task.renamed\"]
+").is_err());
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(matches!(
+            Config::parse("[backup]\nkeep = \"many\"\n"),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_file_is_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::load(&dir.path().join("config.toml")).unwrap();
+        assert_eq!(config, Config::default());
+    }
+}
@@ -29,12 +29,16 @@
 //! # }
 //! ```
 
-use log::debug;
+use std::path::PathBuf;
+
+use log::{debug, warn};
 use tui_logger::TuiWidgetEvent;
 
 use crate::app::{App, AppError, FocusPanel, InputMode, View};
-use crate::models::{Filter, Priority, Task};
-use crate::ui::dialogs::{AddTaskDialog, ConfirmDialog, DeleteProjectDialog, Dialog, FilterSortDialog, MoveToProjectDialog, ProjectDialog, QuickCaptureDialog, SettingsDialog};
+use crate::interop::report::{self, ReportFormat, ReportOptions};
+use crate::models::{BulkAction, BulkEdit, Filter, GroupBy, Priority, Task, TaskStatus};
+use crate::ui::dialogs::{task_count, AddTaskDialog, BulkEditDialog, BulkField, ConfirmDialog, DeleteProjectDialog, Dialog, ExportDialog, FilterSortDialog, MoveToProjectDialog, NoteDialog, ProjectDialog, QuickCaptureDialog, SettingsDialog, SwitchProfileDialog, TemplateDialog};
+use crate::templates::parse_invocation;
 use crate::ui::search::search_tasks;
 
 /// All possible commands that can be executed in the application.
@@ -69,6 +73,10 @@ pub enum Command {
     AddTask,
     /// Open Quick Capture spotlight dialog
     QuickCapture,
+    /// Create a task from Quick Capture syntax without opening the dialog
+    CaptureTask(String),
+    /// Open the template picker
+    ShowTemplates,
     /// Edit the selected task
     EditTask,
     /// Delete the selected task
@@ -77,10 +85,22 @@ pub enum Command {
     ToggleTaskStatus,
     /// Cycle through priority levels for the selected task
     CyclePriority,
+    /// Apply one change to the selected task
+    EditSelectedTask(BulkEdit),
     /// Move selected task to a different project
     MoveToProject,
+    /// Move the selected task up one place in the manual order
+    MoveTaskUp,
+    /// Move the selected task down one place in the manual order
+    MoveTaskDown,
+    /// Collapse or expand the group of the selected task
+    ToggleGroup,
+    /// Collapse every group, or expand them all
+    ToggleAllGroups,
     /// Edit tags on the selected task
     EditTags,
+    /// Open the dialog for adding a note to the selected task
+    AddNote,
     /// Create a new project
     AddProject,
     /// Edit the selected project
@@ -88,6 +108,20 @@ pub enum Command {
     /// Delete the selected project
     DeleteProject,
 
+    // === Selection ===
+    /// Enter selection mode, or leave it and clear the marks
+    ToggleSelectMode,
+    /// Mark or unmark the selected task
+    ToggleMark,
+    /// Mark every visible task, or clear the marks if all are marked
+    MarkAllVisible,
+    /// Mark the visible tasks matching a filter
+    MarkMatching(Filter),
+    /// Ask to apply an action to the marked tasks
+    ConfirmBulk(BulkAction),
+    /// Open the dialog for changing a field of the marked tasks
+    ShowBulkEdit(BulkField),
+
     // === Views ===
     /// Show the main task list view
     ShowMain,
@@ -107,6 +141,10 @@ pub enum Command {
     ShowDebugLogs,
     /// Show detailed view of selected task
     ShowTaskDetail,
+    /// Open the command palette
+    ShowCommandPalette,
+    /// Select a task by ID in the main view, widening the filter if needed
+    FocusTask(String),
 
     // === Calendar Navigation ===
     /// Move to previous day in calendar
@@ -138,9 +176,57 @@ pub enum Command {
     /// Toggle showing completed tasks in calendar
     CalendarToggleCompleted,
 
+    // === Stats ===
+    /// Show the statistics dashboard
+    ShowStats,
+    /// Switch the stats charts between days and weeks
+    ToggleStatsPeriod,
+
+    // === Weekly review ===
+    /// Start the weekly review
+    StartReview,
+    /// Apply a change to the task being reviewed and move on
+    ReviewEdit(BulkEdit),
+    /// Move the task being reviewed to another project
+    ReviewMoveToProject,
+    /// Leave the task being reviewed as it is
+    ReviewSkip,
+    /// Skip the rest of the current review step
+    ReviewNextStep,
+    /// Record the review as done
+    FinishReview,
+
+    // === Activity ===
+    /// Show recent changes to all tasks
+    ShowActivity,
+    /// Select the previous (newer) change in the activity view
+    ActivityUp,
+    /// Select the next (older) change in the activity view
+    ActivityDown,
+
+    // === Standup ===
+    /// Show the standup report
+    ShowStandup,
+    /// Switch the standup report between plain text and Markdown
+    ToggleStandupFormat,
+    /// Copy the standup report to the clipboard
+    CopyStandup,
+
     // === Settings ===
     /// Show the settings dialog
     ShowSettings,
+    /// Show the profile switcher
+    ShowProfiles,
+
+    // === Export ===
+    /// Open the report export dialog
+    ShowExport,
+    /// Write the visible tasks to a report file
+    ExportView {
+        format: ReportFormat,
+        options: ReportOptions,
+        path: PathBuf,
+    },
 
     // === Filters ===
     /// Set a specific filter
@@ -342,8 +428,9 @@ impl Command {
                 // Open the add task dialog with available tags for autocomplete
                 let mut dialog = AddTaskDialog::new().with_available_tags(app.tags.clone());
 
-                // Set the project to the currently selected project (if not "All Tasks")
-                if let Some(project) = app.selected_project() {
+                // Set the project to the currently selected project, or the
+                // profile's default project under "All Tasks"
+                if let Some(project) = app.project_for_new_tasks() {
                     dialog.project_id = Some(project.id.clone());
                 }
 
@@ -360,8 +447,9 @@ impl Command {
                     &app.tasks,
                 );
 
-                // Pre-select the current project (if not "All Tasks")
-                if let Some(project) = app.selected_project() {
+                // Pre-select the current project, or the profile's default
+                // project under "All Tasks"; typing @project overrides it
+                if let Some(project) = app.project_for_new_tasks() {
                     dialog.set_project(project.clone());
                 }
 
@@ -371,6 +459,45 @@ impl Command {
                 Ok(true)
             }
 
+            Command::CaptureTask(text) => {
+                if let Some((key, input)) = parse_invocation(&text) {
+                    app.apply_template(key, input).await?;
+                    return Ok(true);
+                }
+                let mut dialog = QuickCaptureDialog::new(
+                    app.projects.clone(),
+                    app.tags.clone(),
+                    &app.tasks,
+                );
+                if let Some(project) = app.default_project() {
+                    dialog.set_project(project.clone());
+                }
+                dialog.set_input(&text);
+                if let Some(task) = dialog.to_task() {
+                    app.save(async move |db| db.add_task(task).await, |app, task| {
+                        if let Some(task) = task {
+                            app.set_status(format!("Task created: {}", task.title));
+                            app.pending_new_task_animation = Some(task.id);
+                        }
+                    })
+                    .await?;
+                } else {
+                    app.set_status("Task title is required");
+                }
+                Ok(true)
+            }
+
+            Command::ShowTemplates => {
+                if app.templates.is_empty() {
+                    app.set_status("No templates; add .toml files to the templates directory");
+                    return Ok(true);
+                }
+                let dialog = TemplateDialog::new(app.templates.all());
+                app.dialog = Some(Dialog::Template(dialog));
+                app.animation.start_dialog_open();
+                Ok(true)
+            }
+
             Command::EditTask => {
                 if let Some(task) = app.selected_task().cloned() {
                     // Open the add task dialog in edit mode with available tags
@@ -397,14 +524,21 @@ impl Command {
                     let completing = task.status != crate::models::TaskStatus::Completed;
                     if completing {
                         task.complete();
-                        app.set_status("Task completed!");
-                        app.pending_complete_animation = Some(task.id.clone());
                     } else {
                         task.reopen();
-                        app.set_status("Task reopened");
                     }
-                    app.db.update_task(&task).await?;
-                    app.update_task_in_place(task);
+                    app.save(async move |db| db.modify_task(task).await, move |app, task| {
+                        let Some(task) = task else {
+                            return;
+                        };
+                        if completing {
+                            app.set_status("Task completed!");
+                            app.pending_complete_animation = Some(task.id);
+                        } else {
+                            app.set_status("Task reopened");
+                        }
+                    })
+                    .await?;
                 }
                 Ok(true)
             }
@@ -424,14 +558,55 @@ impl Command {
                         Priority::Medium => crate::ui::theme::PRIORITY_NORMAL,
                         Priority::Low => crate::ui::theme::PRIORITY_LOW,
                     };
-                    app.pending_priority_animation = Some((task.id.clone(), priority_color));
-                    app.db.update_task(&task).await?;
-                    app.set_status(format!("Priority: {:?}", task.priority));
-                    app.update_task_in_place(task);
+                    app.save(async move |db| db.modify_task(task).await, move |app, task| {
+                        if let Some(task) = task {
+                            app.set_status(format!("Priority: {:?}", task.priority));
+                            app.pending_priority_animation = Some((task.id, priority_color));
+                        }
+                    })
+                    .await?;
                 }
                 Ok(true)
             }
 
+            Command::EditSelectedTask(edit) => {
+                let Some(task) = app.selected_task() else {
+                    app.set_status("No task selected");
+                    return Ok(true);
+                };
+                let Some(task) = edit.apply(task, chrono::Utc::now()) else {
+                    app.set_status("Nothing to change");
+                    return Ok(true);
+                };
+                let message = match &edit {
+                    BulkEdit::SetStatus(status) => match status {
+                        TaskStatus::Pending => "Task reopened",
+                        TaskStatus::InProgress => "Task started",
+                        TaskStatus::Completed => "Task completed!",
+                        TaskStatus::Archived => "Task archived",
+                    }
+                    .to_string(),
+                    BulkEdit::SetPriority(priority) => format!("Priority: {:?}", priority),
+                    BulkEdit::SetDueDate(Some(due)) => {
+                        format!("Due {}", crate::utils::format_relative_date(*due))
+                    }
+                    BulkEdit::SetDueDate(None) => "Due date cleared".to_string(),
+                    BulkEdit::MoveToProject(id) => {
+                        let name = app.projects.iter().find(|p| &p.id == id).map(|p| p.name.as_str());
+                        format!("Task moved to {}", name.unwrap_or("Unknown"))
+                    }
+                    BulkEdit::AddTag(tag) => format!("Tagged #{}", tag),
+                    BulkEdit::RemoveTag(tag) => format!("Removed #{}", tag),
+                };
+                app.save(async move |db| db.modify_task(task).await, move |app, task| {
+                    if task.is_some() {
+                        app.set_status(message);
+                    }
+                })
+                .await?;
+                Ok(true)
+            }
+
             Command::MoveToProject => {
                 if let Some(task) = app.selected_task() {
                     let dialog = MoveToProjectDialog::new(
@@ -446,6 +621,38 @@ impl Command {
                 Ok(true)
             }
 
+            Command::MoveTaskUp => {
+                if let Some(index) = app.selected_task_index {
+                    app.move_selected_task(index.saturating_sub(1)).await?;
+                }
+                Ok(true)
+            }
+
+            Command::MoveTaskDown => {
+                if let Some(index) = app.selected_task_index {
+                    app.move_selected_task(index + 1).await?;
+                }
+                Ok(true)
+            }
+
+            Command::ToggleGroup => {
+                if app.group_by == GroupBy::None {
+                    app.set_status("Group the list (f) to collapse groups");
+                } else if let Some(label) = app.selected_group() {
+                    app.toggle_group(&label);
+                }
+                Ok(true)
+            }
+
+            Command::ToggleAllGroups => {
+                if app.group_by == GroupBy::None {
+                    app.set_status("Group the list (f) to collapse groups");
+                } else {
+                    app.toggle_all_groups();
+                }
+                Ok(true)
+            }
+
             Command::EditTags => {
                 // Edit tags by opening the task dialog focused on tags field
                 if let Some(task) = app.selected_task().cloned() {
@@ -457,6 +664,14 @@ impl Command {
                 Ok(true)
             }
 
+            Command::AddNote => {
+                if let Some(task) = app.selected_task() {
+                    app.dialog = Some(Dialog::Note(NoteDialog::new(&task.id, &task.title)));
+                    app.animation.start_dialog_open();
+                }
+                Ok(true)
+            }
+
             Command::AddProject => {
                 app.dialog = Some(Dialog::Project(ProjectDialog::new()));
                 app.animation.start_dialog_open();
@@ -578,15 +793,18 @@ impl Command {
                     && let Some(task) = app.tasks.iter().find(|t| &t.id == task_id)
                 {
                     let mut task = task.clone();
-                    if task.status == crate::models::TaskStatus::Completed {
+                    let reopening = task.status == crate::models::TaskStatus::Completed;
+                    if reopening {
                         task.reopen();
-                        app.set_status("Task reopened");
                     } else {
                         task.complete();
-                        app.set_status("Task completed!");
                     }
-                    app.db.update_task(&task).await?;
-                    app.update_task_in_place(task);
+                    app.save(async move |db| db.modify_task(task).await, move |app, task| {
+                        if task.is_some() {
+                            app.set_status(if reopening { "Task reopened" } else { "Task completed!" });
+                        }
+                    })
+                    .await?;
                 }
                 Ok(true)
             }
@@ -603,9 +821,12 @@ impl Command {
                         Priority::High => Priority::Urgent,
                         Priority::Urgent => Priority::Low,
                     };
-                    app.db.update_task(&task).await?;
-                    app.set_status(format!("Priority: {:?}", task.priority));
-                    app.update_task_in_place(task);
+                    app.save(async move |db| db.modify_task(task).await, |app, task| {
+                        if let Some(task) = task {
+                            app.set_status(format!("Priority: {:?}", task.priority));
+                        }
+                    })
+                    .await?;
                 }
                 Ok(true)
             }
@@ -649,6 +870,7 @@ impl Command {
 
                     // Clear filter to ensure task is visible
                     app.filter = Filter::All;
+                    app.expand_group_of(&task_id);
 
                     // Find and select the task
                     if let Some(idx) = app.visible_tasks().iter().position(|t| t.id == task_id) {
@@ -672,6 +894,109 @@ impl Command {
                 Ok(true)
             }
 
+            // === Stats ===
+            Command::ShowStats => {
+                app.current_view = View::Stats;
+                app.animation.start_view_transition();
+                Ok(true)
+            }
+
+            Command::ToggleStatsPeriod => {
+                app.stats_weekly = !app.stats_weekly;
+                Ok(true)
+            }
+
+            // === Activity ===
+            Command::ShowActivity => {
+                app.current_view = View::Activity;
+                app.selected_activity_index = 0;
+                app.load_activity().await?;
+                app.animation.start_view_transition();
+                Ok(true)
+            }
+
+            Command::ActivityUp => {
+                app.selected_activity_index = app.selected_activity_index.saturating_sub(1);
+                Ok(true)
+            }
+
+            Command::ActivityDown => {
+                if app.selected_activity_index + 1 < app.activity.len() {
+                    app.selected_activity_index += 1;
+                }
+                Ok(true)
+            }
+
+            // === Standup ===
+            Command::ShowStandup => {
+                app.current_view = View::Standup;
+                app.animation.start_view_transition();
+                Ok(true)
+            }
+
+            Command::ToggleStandupFormat => {
+                app.standup_markdown = !app.standup_markdown;
+                Ok(true)
+            }
+
+            Command::CopyStandup => {
+                app.pending_clipboard = Some(app.standup_report());
+                app.set_status("Standup copied to clipboard");
+                Ok(true)
+            }
+
+            // === Weekly review ===
+            Command::StartReview => {
+                app.start_review();
+                app.animation.start_view_transition();
+                Ok(true)
+            }
+
+            Command::ReviewEdit(edit) => {
+                let Some(task) = app.review_task() else {
+                    return Ok(true);
+                };
+                if let Some(task) = edit.apply(task, chrono::Utc::now()) {
+                    app.save(async move |db| db.modify_task(task).await, |app, task| {
+                        if task.is_some() {
+                            app.review.handled += 1;
+                        }
+                    })
+                    .await?;
+                }
+                app.advance_review();
+                Ok(true)
+            }
+
+            Command::ReviewMoveToProject => {
+                if let Some(task) = app.review_task() {
+                    let dialog = MoveToProjectDialog::new(
+                        app.projects.clone(),
+                        task.id.clone(),
+                        task.project_id.as_deref(),
+                    );
+                    app.dialog = Some(Dialog::MoveToProject(dialog));
+                    app.animation.start_dialog_open();
+                }
+                Ok(true)
+            }
+
+            Command::ReviewSkip => {
+                app.advance_review();
+                Ok(true)
+            }
+
+            Command::ReviewNextStep => {
+                app.next_review_step();
+                Ok(true)
+            }
+
+            Command::FinishReview => {
+                app.finish_review().await?;
+                app.animation.start_view_transition();
+                Ok(true)
+            }
+
             Command::ShowSearch => {
                 app.current_view = View::Search;
                 app.input_mode = InputMode::Search;
@@ -706,6 +1031,7 @@ impl Command {
                 if let Some(result) = app.search_results.get(app.selected_search_index) {
                     // Find the task index in visible_tasks and select it
                     let task_id = result.task.id.clone();
+                    let title = result.task.title.clone();
                     app.current_view = View::Main;
                     app.animation.start_view_transition();
                     app.input_mode = InputMode::Normal;
@@ -713,6 +1039,7 @@ impl Command {
                     app.input_cursor = 0;
                     // Focus the task list panel
                     app.focus = FocusPanel::TaskList;
+                    app.expand_group_of(&task_id);
 
                     // Try to find and select the task
                     if let Some(idx) = app
@@ -732,7 +1059,7 @@ impl Command {
                             app.selected_task_index = Some(idx);
                         }
                     }
-                    app.set_status(format!("Selected: {}", result.task.title));
+                    app.set_status(format!("Selected: {}", title));
                 }
                 Ok(true)
             }
@@ -741,6 +1068,7 @@ impl Command {
                 app.current_view = if app.current_view == View::DebugLogs {
                     View::Main
                 } else {
+                    app.load_webhook_deliveries().await?;
                     View::DebugLogs
                 };
                 app.animation.start_view_transition();
@@ -750,11 +1078,48 @@ impl Command {
             Command::ShowTaskDetail => {
                 if app.selected_task().is_some() {
                     app.current_view = View::TaskDetail;
+                    app.load_task_history().await?;
                     app.animation.start_view_transition();
                 }
                 Ok(true)
             }
 
+            Command::ShowCommandPalette => {
+                let dialog = super::palette::palette(app);
+                app.dialog = Some(Dialog::CommandPalette(Box::new(dialog)));
+                app.animation.start_quick_capture_open();
+                app.set_status("Enter/run  Tab/complete  Esc/cancel");
+                Ok(true)
+            }
+
+            Command::FocusTask(task_id) => {
+                let Some(title) = app
+                    .tasks
+                    .iter()
+                    .find(|t| t.id == task_id)
+                    .map(|t| t.title.clone())
+                else {
+                    app.set_status(format!("Task not found: {}", task_id));
+                    return Ok(true);
+                };
+                if app.current_view != View::Main {
+                    app.current_view = View::Main;
+                    app.animation.start_view_transition();
+                }
+                app.focus = FocusPanel::TaskList;
+
+                let position = |app: &App| app.visible_tasks().iter().position(|t| t.id == task_id);
+                if position(app).is_none() {
+                    // Hidden by the project or filter; show everything
+                    app.selected_project_index = 0;
+                    app.filter = Filter::All;
+                }
+                app.expand_group_of(&task_id);
+                app.selected_task_index = position(app);
+                app.set_status(format!("Selected: {}", title));
+                Ok(true)
+            }
+
             // === Filters ===
             Command::SetFilter(filter) => {
                 app.filter = filter;
@@ -770,6 +1135,8 @@ impl Command {
                 app.dialog = Some(Dialog::FilterSort(FilterSortDialog::new(
                     &app.filter,
                     &app.sort,
+                    &app.then_by,
+                    app.group_by,
                     &project_tasks,
                 )));
                 app.animation.start_dialog_open();
@@ -782,6 +1149,50 @@ impl Command {
                 Ok(true)
             }
 
+            Command::ShowProfiles => {
+                if app.profile_names.is_empty() {
+                    app.set_status("No profiles configured; add [profiles.<name>] to config.toml");
+                    return Ok(true);
+                }
+                let dialog =
+                    SwitchProfileDialog::new(app.profile_names.clone(), app.profile_name.clone());
+                app.dialog = Some(Dialog::SwitchProfile(dialog));
+                app.animation.start_dialog_open();
+                Ok(true)
+            }
+
+            Command::ShowExport => {
+                let count = app.visible_tasks().len();
+                if count == 0 {
+                    app.set_status("No tasks to export");
+                    return Ok(true);
+                }
+                app.dialog = Some(Dialog::Export(ExportDialog::new(count)));
+                app.animation.start_dialog_open();
+                Ok(true)
+            }
+
+            Command::ExportView { format, options, path } => {
+                // Tasks in collapsed groups are still part of the view
+                let tasks: Vec<&Task> = app.task_groups().into_iter().flat_map(|g| g.tasks).collect();
+                let content = report::render(format, &tasks, &app.projects, &options);
+                let count = tasks.len();
+
+                let path = std::env::current_dir()
+                    .map(|dir| dir.join(&path))
+                    .unwrap_or(path);
+                match std::fs::write(&path, content) {
+                    Ok(()) => {
+                        app.set_status(format!("Exported {} task(s) to {}", count, path.display()));
+                    }
+                    Err(e) => {
+                        warn!("Export to {} failed: {}", path.display(), e);
+                        app.set_status(format!("Export failed: {}", e));
+                    }
+                }
+                Ok(true)
+            }
+
             Command::FilterToday => {
                 app.filter = Filter::DueToday;
                 let count = app.visible_tasks().len();
@@ -828,15 +1239,22 @@ impl Command {
                         if let Some(mut task) = app.editing_task.take() {
                             // Editing existing task
                             task.title = title;
-                            app.db.update_task(&task).await?;
-                            app.set_status("Task updated");
+                            app.save(async move |db| db.modify_task(task).await, |app, task| {
+                                if task.is_some() {
+                                    app.set_status("Task updated");
+                                }
+                            })
+                            .await?;
                         } else {
                             // Creating new task
                             let task = crate::models::Task::new(&title);
-                            app.db.insert_task(&task).await?;
-                            app.set_status("Task added");
+                            app.save(async move |db| db.add_task(task).await, |app, task| {
+                                if task.is_some() {
+                                    app.set_status("Task added");
+                                }
+                            })
+                            .await?;
                         }
-                        app.load_data().await?;
                     }
                 }
                 app.input_mode = InputMode::Normal;
@@ -939,14 +1357,101 @@ impl Command {
                 app.set_status("Data refreshed");
                 Ok(true)
             }
+
+            // === Selection ===
+            Command::ToggleSelectMode => {
+                if app.input_mode == InputMode::Select {
+                    app.exit_select_mode();
+                    app.clear_status();
+                } else {
+                    app.enter_select_mode();
+                    app.set_status("Space: mark  a: mark all  then c/d/m/t/p/D/s");
+                }
+                Ok(true)
+            }
+
+            Command::ToggleMark => {
+                app.toggle_mark();
+                app.select_next_task();
+                Ok(true)
+            }
+
+            Command::MarkAllVisible => {
+                app.toggle_mark_all_visible();
+                app.set_status(format!("{} marked", task_count(app.marked.len())));
+                Ok(true)
+            }
+
+            Command::MarkMatching(filter) => {
+                let matched = app.mark_matching(&filter);
+                app.set_status(format!(
+                    "{} matched, {} marked",
+                    task_count(matched),
+                    app.marked_tasks().len()
+                ));
+                Ok(true)
+            }
+
+            Command::ConfirmBulk(action) => {
+                let count = app.marked_tasks().len();
+                let question = match &action {
+                    BulkAction::Delete => format!("Delete {}?", task_count(count)),
+                    BulkAction::Edit(BulkEdit::SetStatus(TaskStatus::Completed)) => {
+                        format!("Complete {}?", task_count(count))
+                    }
+                    BulkAction::Edit(_) => format!("Change {}?", task_count(count)),
+                };
+                confirm_bulk(app, action, question);
+                Ok(true)
+            }
+
+            Command::ShowBulkEdit(field) => {
+                let count = app.marked_tasks().len();
+                if count == 0 {
+                    app.set_status("No tasks marked; press Space to mark");
+                    return Ok(true);
+                }
+                let dialog = BulkEditDialog::new(field, count, app.projects.clone());
+                app.dialog = Some(Dialog::BulkEdit(dialog));
+                app.animation.start_dialog_open();
+                Ok(true)
+            }
         }
     }
 }
 
+/// Asks for confirmation before applying `action` to the marked tasks.
+///
+/// `question` names the change and how many tasks it affects. The action
+/// is kept in [`App::pending_bulk`] until the dialog is answered.
+pub(crate) fn confirm_bulk(app: &mut App, action: BulkAction, question: String) {
+    let count = app.marked_tasks().len();
+    if count == 0 {
+        app.set_status("No tasks marked; press Space to mark");
+        return;
+    }
+
+    let dialog = match action {
+        BulkAction::Delete => ConfirmDialog::new(
+            "Delete Tasks?",
+            format!("{}\n\nA backup is taken first.", question),
+        )
+        .with_confirm_text("Delete")
+        .with_cancel_text("Cancel")
+        .destructive(),
+        BulkAction::Edit(_) => ConfirmDialog::new("Bulk Edit", question)
+            .with_confirm_text("Apply")
+            .with_cancel_text("Cancel"),
+    };
+    app.pending_bulk = Some(action);
+    app.dialog = Some(Dialog::Confirm(dialog));
+    app.animation.start_dialog_open();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Task;
+    use crate::models::{ReviewStep, Task};
     use crate::storage::{run_migrations, Database};
 
     async fn setup_app() -> App {
@@ -1032,6 +1537,119 @@ mod tests {
         assert_eq!(app.current_view, View::Help);
     }
 
+    #[tokio::test]
+    async fn test_show_stats() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = setup_app().await;
+        let mut task = Task::new("Done");
+        task.tags = vec!["work".to_string()];
+        task.complete();
+        app.db.insert_task(&task).await.unwrap();
+        app.db.insert_task(&Task::new("Open")).await.unwrap();
+        app.load_data().await.unwrap();
+
+        Command::ShowStats.execute(&mut app).await.unwrap();
+        assert_eq!(app.current_view, View::Stats);
+        Command::ToggleStatsPeriod.execute(&mut app).await.unwrap();
+        assert!(app.stats_weekly);
+
+        // Both periods draw without panicking
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        for _ in 0..2 {
+            terminal.draw(|frame| crate::ui::draw(frame, &app)).unwrap();
+            Command::ToggleStatsPeriod.execute(&mut app).await.unwrap();
+        }
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("Completed per day"));
+    }
+
+    #[tokio::test]
+    async fn test_standup_view_and_copy() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = setup_app().await;
+        let mut task = Task::new("Review PR");
+        task.status = TaskStatus::InProgress;
+        app.db.insert_task(&task).await.unwrap();
+        app.load_data().await.unwrap();
+
+        Command::ShowStandup.execute(&mut app).await.unwrap();
+        assert_eq!(app.current_view, View::Standup);
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| crate::ui::draw(frame, &app)).unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("In progress:"));
+
+        Command::ToggleStandupFormat.execute(&mut app).await.unwrap();
+        Command::CopyStandup.execute(&mut app).await.unwrap();
+        let copied = app.pending_clipboard.take().unwrap();
+        assert!(copied.contains("### In progress\n\n**No project**\n- Review PR\n"));
+    }
+
+    #[tokio::test]
+    async fn test_activity_and_task_history() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = setup_app().await;
+        let first = app.db.add_task(Task::new("First")).await.unwrap();
+        app.db.add_task(Task::new("Second")).await.unwrap();
+        app.load_data().await.unwrap();
+
+        Command::ShowActivity.execute(&mut app).await.unwrap();
+        assert_eq!(app.current_view, View::Activity);
+        assert_eq!(app.activity.len(), 2);
+        Command::ActivityDown.execute(&mut app).await.unwrap();
+        Command::ActivityDown.execute(&mut app).await.unwrap();
+        assert_eq!(app.selected_activity().unwrap().title, "First");
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|frame| crate::ui::draw(frame, &app)).unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("Today"));
+
+        // Going to the task shows its history in the detail view
+        Command::FocusTask(first.id.clone()).execute(&mut app).await.unwrap();
+        Command::CyclePriority.execute(&mut app).await.unwrap();
+        Command::ShowTaskDetail.execute(&mut app).await.unwrap();
+        let summaries: Vec<String> = app.task_history.iter().map(|e| e.summary()).collect();
+        assert_eq!(summaries, vec!["Priority medium → high", "Created"]);
+        terminal.draw(|frame| crate::ui::draw(frame, &app)).unwrap();
+        let screen = format!("{:?}", terminal.backend().buffer());
+        assert!(screen.contains("History"));
+    }
+
+    #[tokio::test]
+    async fn test_review_actions() {
+        let mut app = setup_app().await;
+        for days in [3, 1] {
+            let mut task = Task::new(&format!("Late {}", days));
+            task.due_date = Some(chrono::Utc::now() - chrono::Duration::days(days));
+            app.db.insert_task(&task).await.unwrap();
+        }
+        app.load_data().await.unwrap();
+
+        Command::StartReview.execute(&mut app).await.unwrap();
+        assert_eq!(app.review.step, ReviewStep::Overdue);
+        assert_eq!(app.review_task().unwrap().title, "Late 3");
+
+        Command::ReviewEdit(BulkEdit::SetStatus(TaskStatus::Completed))
+            .execute(&mut app)
+            .await
+            .unwrap();
+        let done = app.tasks.iter().find(|t| t.title == "Late 3").unwrap();
+        assert_eq!(done.status, TaskStatus::Completed);
+        assert_eq!(app.review_task().unwrap().title, "Late 1");
+
+        Command::ReviewSkip.execute(&mut app).await.unwrap();
+        // "Late 1" has no project, but was already asked about as overdue
+        assert_eq!(app.review.step, ReviewStep::NextWeek);
+
+        Command::FinishReview.execute(&mut app).await.unwrap();
+        assert_eq!(app.current_view, View::Main);
+        assert_eq!(app.status_message.as_deref(), Some("Review done, 1 task updated"));
+        assert!(app.db.last_review().await.unwrap().is_some());
+    }
+
     #[tokio::test]
     async fn test_show_main() {
         let mut app = setup_app().await;
@@ -1053,6 +1671,36 @@ mod tests {
         assert_eq!(app.current_view, View::Main);
     }
 
+    #[tokio::test]
+    async fn test_debug_logs_load_webhook_deliveries() {
+        use crate::storage::Delivery;
+        use crate::webhooks::Webhook;
+
+        let mut app = setup_app().await;
+        app.current_view = View::Main;
+        app.db.set_webhooks(vec![Webhook {
+            url: "http://localhost:9/hook".to_string(),
+            secret: None,
+            events: Vec::new(),
+        }]);
+        app.db
+            .log_webhook_delivery(&Delivery {
+                delivery_id: "d1".to_string(),
+                url: "http://localhost:9/hook".to_string(),
+                event: "task.created".to_string(),
+                attempt: 1,
+                status: Some(500),
+                error: None,
+                attempted_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        Command::ShowDebugLogs.execute(&mut app).await.unwrap();
+        assert_eq!(app.webhook_deliveries.len(), 1);
+        assert!(!app.webhook_deliveries[0].succeeded());
+    }
+
     #[tokio::test]
     async fn test_toggle_task_status() {
         use crate::models::{Filter, TaskStatus};
@@ -1078,6 +1726,36 @@ mod tests {
         assert_eq!(app.tasks[0].status, TaskStatus::Pending);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hook_rejection_shows_status() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut app = setup_app().await;
+        let dir = tempfile::tempdir().unwrap();
+        let hook = dir.path().join("on-complete");
+        std::fs::write(&hook, "#!/bin/sh\necho 'Log time first'\nexit 1\n").unwrap();
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+        app.db.set_hooks(crate::hooks::Hooks::load(dir.path()).unwrap());
+
+        Command::CaptureTask("Write report".to_string()).execute(&mut app).await.unwrap();
+        app.wait_for_saves().await.unwrap();
+        app.selected_task_index = Some(0);
+        Command::ToggleTaskStatus.execute(&mut app).await.unwrap();
+
+        // Hooks run in the background; the app goes on meanwhile
+        assert!(app.is_saving());
+        assert_eq!(app.status_message.as_deref(), Some("Running hooks..."));
+        while app.is_saving() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            app.finish_saves().await.unwrap();
+        }
+
+        assert_eq!(app.status_message.as_deref(), Some("on-complete: Log time first"));
+        assert_eq!(app.tasks[0].status, crate::models::TaskStatus::Pending);
+        assert!(app.pending_complete_animation.is_none());
+    }
+
     #[tokio::test]
     async fn test_add_task_opens_dialog() {
         use crate::ui::dialogs::Dialog;
@@ -1153,6 +1831,44 @@ mod tests {
         assert_eq!(app.tasks[0].priority, Priority::Low);
     }
 
+    #[tokio::test]
+    async fn test_edit_selected_task() {
+        let mut app = setup_app().await;
+        let task = Task::new("Edit me");
+        app.db.insert_task(&task).await.unwrap();
+        app.load_data().await.unwrap();
+        app.selected_task_index = Some(0);
+
+        Command::EditSelectedTask(BulkEdit::SetPriority(Priority::Urgent))
+            .execute(&mut app)
+            .await
+            .unwrap();
+        assert_eq!(app.tasks[0].priority, Priority::Urgent);
+        assert_eq!(app.status_message.as_deref(), Some("Priority: Urgent"));
+
+        Command::EditSelectedTask(BulkEdit::SetPriority(Priority::Urgent))
+            .execute(&mut app)
+            .await
+            .unwrap();
+        assert_eq!(app.status_message.as_deref(), Some("Nothing to change"));
+
+        let project = app.projects[0].clone();
+        Command::EditSelectedTask(BulkEdit::MoveToProject(project.id.clone()))
+            .execute(&mut app)
+            .await
+            .unwrap();
+        let saved = app.db.get_task(&task.id).await.unwrap().unwrap();
+        assert_eq!(saved.project_id, Some(project.id));
+        assert_eq!(app.status_message, Some(format!("Task moved to {}", project.name)));
+    }
+
+    #[tokio::test]
+    async fn test_show_command_palette() {
+        let mut app = setup_app().await;
+        Command::ShowCommandPalette.execute(&mut app).await.unwrap();
+        assert!(matches!(app.dialog, Some(Dialog::CommandPalette(_))));
+    }
+
     #[tokio::test]
     async fn test_filter_today() {
         let mut app = setup_app().await;
@@ -1169,6 +1885,23 @@ mod tests {
         assert!(matches!(app.dialog, Some(Dialog::FilterSort(_))));
     }
 
+    #[tokio::test]
+    async fn test_show_export() {
+        let mut app = setup_app().await;
+
+        // Nothing to export yet
+        Command::ShowExport.execute(&mut app).await.unwrap();
+        assert!(app.dialog.is_none());
+
+        app.db.insert_task(&Task::new("Report me")).await.unwrap();
+        app.load_data().await.unwrap();
+        Command::ShowExport.execute(&mut app).await.unwrap();
+        match &app.dialog {
+            Some(Dialog::Export(dialog)) => assert_eq!(dialog.task_count, 1),
+            other => panic!("Expected export dialog, got {:?}", other),
+        }
+    }
+
     #[tokio::test]
     async fn test_text_editing_commands() {
         let mut app = setup_app().await;
@@ -1293,4 +2026,53 @@ mod tests {
             panic!("Expected QuickCapture dialog");
         }
     }
+
+    #[tokio::test]
+    async fn test_capture_task_parses_syntax() {
+        let mut app = setup_app().await;
+
+        Command::CaptureTask("Write report #work !1".to_string())
+            .execute(&mut app)
+            .await
+            .unwrap();
+
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(app.tasks[0].title, "Write report");
+        assert_eq!(app.tasks[0].tags, vec!["work".to_string()]);
+        assert_eq!(app.tasks[0].priority, Priority::Urgent);
+        assert_eq!(app.db.get_all_tasks().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_capture_task_requires_title() {
+        let mut app = setup_app().await;
+
+        Command::CaptureTask("#work".to_string())
+            .execute(&mut app)
+            .await
+            .unwrap();
+
+        assert!(app.tasks.is_empty());
+        assert_eq!(app.status_message.as_deref(), Some("Task title is required"));
+    }
+
+    #[tokio::test]
+    async fn test_focus_task_clears_hiding_filter() {
+        let mut app = setup_app_with_tasks().await;
+        let mut done = Task::new("Done");
+        done.complete();
+        app.db.insert_task(&done).await.unwrap();
+        app.load_data().await.unwrap();
+        app.filter = Filter::Pending;
+        app.current_view = View::Help;
+
+        Command::FocusTask(done.id.clone())
+            .execute(&mut app)
+            .await
+            .unwrap();
+
+        assert_eq!(app.current_view, View::Main);
+        assert_eq!(app.filter, Filter::All);
+        assert_eq!(app.selected_task().map(|t| t.id.as_str()), Some(done.id.as_str()));
+    }
 }
@@ -33,6 +33,8 @@ use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
+use super::rpc::RemoteCall;
+
 /// Application events that drive the event loop.
 ///
 /// These events are generated by the [`EventHandler`] and processed
@@ -47,6 +49,8 @@ pub enum AppEvent {
     Tick,
     /// The terminal was resized
     Resize(u16, u16),
+    /// A call received on the control socket
+    Remote(RemoteCall),
 }
 
 /// Handles event polling and distribution.
@@ -62,8 +66,7 @@ pub enum AppEvent {
 pub struct EventHandler {
     /// Receiver for events from the background task
     rx: mpsc::UnboundedReceiver<AppEvent>,
-    /// Sender kept to prevent channel closure (dropped when EventHandler is dropped)
-    #[allow(dead_code)]
+    /// Sender kept to prevent channel closure and handed out to other event sources
     tx: mpsc::UnboundedSender<AppEvent>,
 }
 
@@ -88,8 +91,9 @@ impl EventHandler {
         let (tx, rx) = mpsc::unbounded_channel();
         let event_tx = tx.clone();
 
-        // Spawn background task for event polling
-        tokio::spawn(async move {
+        // Polling blocks, so keep it off the async workers; otherwise it can
+        // starve other tasks such as the control socket on a single core
+        tokio::task::spawn_blocking(move || {
             let mut last_tick = Instant::now();
 
             loop {
@@ -152,6 +156,12 @@ impl EventHandler {
     pub async fn next(&mut self) -> Option<AppEvent> {
         self.rx.recv().await
     }
+
+    /// Returns a sender for feeding events from other sources, such as the
+    /// control socket, into the same stream.
+    pub fn sender(&self) -> mpsc::UnboundedSender<AppEvent> {
+        self.tx.clone()
+    }
 }
 
 #[cfg(test)]
@@ -31,7 +31,9 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::app::{App, FocusPanel, InputMode, View};
 use crate::handlers::commands::{Command, TuiLoggerEvent};
-use crate::models::Priority;
+use crate::models::{BulkAction, BulkEdit, Filter, Priority, ReviewStep, TaskStatus};
+use crate::ui::dialogs::BulkField;
+use crate::utils::parse_due_date;
 
 /// Maps a keyboard event to a command based on the current application state.
 ///
@@ -67,12 +69,14 @@ pub fn map_key_to_command(key: KeyEvent, app: &App) -> Option<Command> {
         View::Help => return map_help_view_key(key),
         View::DebugLogs => return map_debug_view_key(key),
         View::Calendar => return map_calendar_view_key(key, app),
+        View::Stats => return map_stats_view_key(key),
+        View::Review => return map_review_view_key(key, app),
+        View::Activity => return map_activity_view_key(key, app),
+        View::Standup => return map_standup_view_key(key),
         View::TaskDetail => return map_task_detail_view_key(key),
-        View::Search => {
-            // In search view, handle search-specific keys
-            if app.input_mode == InputMode::Search {
-                return map_search_mode_key(key);
-            }
+        // In search view, handle search-specific keys
+        View::Search if app.input_mode == InputMode::Search => {
+            return map_search_mode_key(key);
         }
         _ => {}
     }
@@ -82,6 +86,7 @@ pub fn map_key_to_command(key: KeyEvent, app: &App) -> Option<Command> {
         InputMode::Normal => map_normal_mode_key(key, app),
         InputMode::Editing => map_editing_mode_key(key),
         InputMode::Search => map_search_mode_key(key),
+        InputMode::Select => map_select_mode_key(key),
     }
 }
 
@@ -97,6 +102,80 @@ fn map_help_view_key(key: KeyEvent) -> Option<Command> {
     }
 }
 
+/// Maps keys in the Stats view.
+fn map_stats_view_key(key: KeyEvent) -> Option<Command> {
+    match key.code {
+        // Escape (or s again) returns to main view
+        KeyCode::Esc | KeyCode::Char('s') => Some(Command::ShowMain),
+        KeyCode::Char('w') => Some(Command::ToggleStatsPeriod),
+        KeyCode::Char('r') => Some(Command::Refresh),
+        KeyCode::Char('q') => Some(Command::Quit),
+        _ => None,
+    }
+}
+
+/// Maps keys in the Activity view.
+fn map_activity_view_key(key: KeyEvent, app: &App) -> Option<Command> {
+    match key.code {
+        // Escape (or H again) returns to main view
+        KeyCode::Esc | KeyCode::Char('H') => Some(Command::ShowMain),
+        KeyCode::Char('k') | KeyCode::Up => Some(Command::ActivityUp),
+        KeyCode::Char('j') | KeyCode::Down => Some(Command::ActivityDown),
+        KeyCode::Enter => app
+            .selected_activity()
+            .map(|event| Command::FocusTask(event.task_id.clone())),
+        KeyCode::Char('r') => Some(Command::Refresh),
+        KeyCode::Char('q') => Some(Command::Quit),
+        _ => None,
+    }
+}
+
+/// Maps keys in the Standup view.
+fn map_standup_view_key(key: KeyEvent) -> Option<Command> {
+    match key.code {
+        // Escape (or U again) returns to main view
+        KeyCode::Esc | KeyCode::Char('U') => Some(Command::ShowMain),
+        KeyCode::Char('m') => Some(Command::ToggleStandupFormat),
+        KeyCode::Char('y') | KeyCode::Char('c') => Some(Command::CopyStandup),
+        KeyCode::Char('r') => Some(Command::Refresh),
+        KeyCode::Char('q') => Some(Command::Quit),
+        _ => None,
+    }
+}
+
+/// Maps keys in the weekly review.
+///
+/// Each task gets one-key actions that change it and move on. The last
+/// step, the coming week, only has Enter to finish.
+fn map_review_view_key(key: KeyEvent, app: &App) -> Option<Command> {
+    if app.review.step == ReviewStep::NextWeek {
+        return match key.code {
+            KeyCode::Esc => Some(Command::ShowMain),
+            KeyCode::Enter => Some(Command::FinishReview),
+            _ => None,
+        };
+    }
+    let schedule = |when: &str| Some(Command::ReviewEdit(BulkEdit::SetDueDate(parse_due_date(when))));
+    match key.code {
+        // Leave without recording the review
+        KeyCode::Esc => Some(Command::ShowMain),
+        KeyCode::Tab => Some(Command::ReviewNextStep),
+
+        // Reschedule
+        KeyCode::Char('t') => schedule("today"),
+        KeyCode::Char('T') => schedule("tomorrow"),
+        KeyCode::Char('w') => schedule("+1w"),
+
+        KeyCode::Char('m') => Some(Command::ReviewMoveToProject),
+        KeyCode::Char('c') | KeyCode::Char(' ') => {
+            Some(Command::ReviewEdit(BulkEdit::SetStatus(TaskStatus::Completed)))
+        }
+        KeyCode::Char('d') => Some(Command::ReviewEdit(BulkEdit::SetStatus(TaskStatus::Archived))),
+        KeyCode::Char('s') | KeyCode::Char('l') | KeyCode::Right => Some(Command::ReviewSkip),
+        _ => None,
+    }
+}
+
 /// Maps keys in the Debug Logs view.
 ///
 /// Handles tui-logger widget navigation and log level controls.
@@ -202,6 +281,7 @@ fn map_task_detail_view_key(key: KeyEvent) -> Option<Command> {
         KeyCode::Char('p') => Some(Command::CyclePriority),
         KeyCode::Char('e') | KeyCode::Enter => Some(Command::EditTask),
         KeyCode::Char('d') => Some(Command::DeleteTask),
+        KeyCode::Char('N') => Some(Command::AddNote),
 
         // Quit
         KeyCode::Char('q') => Some(Command::Quit),
@@ -233,6 +313,12 @@ fn map_normal_mode_key(key: KeyEvent, app: &App) -> Option<Command> {
             Some(Command::PageUp)
         }
 
+        // === Command palette ===
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Command::ShowCommandPalette)
+        }
+        KeyCode::Char(':') => Some(Command::ShowCommandPalette),
+
         // === Panel Switching ===
         KeyCode::Tab => Some(Command::SwitchPanel),
         KeyCode::Char('h') | KeyCode::Left => Some(Command::FocusSidebar),
@@ -265,16 +351,31 @@ fn map_normal_mode_key(key: KeyEvent, app: &App) -> Option<Command> {
         KeyCode::Char(' ') => Some(Command::ToggleTaskStatus),
         KeyCode::Char('p') => Some(Command::CyclePriority),
         KeyCode::Char('t') => Some(Command::EditTags),
+        KeyCode::Char('N') => Some(Command::AddNote),
         KeyCode::Char('m') => Some(Command::MoveToProject),
+        KeyCode::Char('K') if app.focus == FocusPanel::TaskList => Some(Command::MoveTaskUp),
+        KeyCode::Char('J') if app.focus == FocusPanel::TaskList => Some(Command::MoveTaskDown),
+        KeyCode::Char('z') => Some(Command::ToggleGroup),
+        KeyCode::Char('Z') => Some(Command::ToggleAllGroups),
 
         // === Full Add Task form ===
         KeyCode::Char('A') => Some(Command::AddTask),
 
+        // === New tasks from a template ===
+        KeyCode::Char('n') => Some(Command::ShowTemplates),
+
         // === Views ===
         KeyCode::Char('?') => Some(Command::ShowHelp),
         KeyCode::Char('/') => Some(Command::ShowSearch),
         KeyCode::Char('c') => Some(Command::ShowCalendar),
-        KeyCode::Char('v') => Some(Command::ShowTaskDetail),
+        KeyCode::Char('s') => Some(Command::ShowStats),
+        KeyCode::Char('R') => Some(Command::StartReview),
+        KeyCode::Char('H') => Some(Command::ShowActivity),
+        KeyCode::Char('U') => Some(Command::ShowStandup),
+        KeyCode::Char('i') => Some(Command::ShowTaskDetail),
+
+        // === Selection ===
+        KeyCode::Char('v') => Some(Command::ToggleSelectMode),
 
         // === Quick Filters ===
         KeyCode::Char('T') => Some(Command::FilterToday),
@@ -291,6 +392,10 @@ fn map_normal_mode_key(key: KeyEvent, app: &App) -> Option<Command> {
 
         // === Settings ===
         KeyCode::Char('S') => Some(Command::ShowSettings),
+        KeyCode::Char('P') => Some(Command::ShowProfiles),
+
+        // === Export ===
+        KeyCode::Char('E') => Some(Command::ShowExport),
 
         // === Other ===
         KeyCode::Char('r') => Some(Command::Refresh),
@@ -299,6 +404,49 @@ fn map_normal_mode_key(key: KeyEvent, app: &App) -> Option<Command> {
     }
 }
 
+/// Maps keys in Selection mode.
+///
+/// Tasks are marked with Space, by filter or all at once, and the action
+/// keys apply to every marked task.
+fn map_select_mode_key(key: KeyEvent) -> Option<Command> {
+    match key.code {
+        // Leave selection mode
+        KeyCode::Esc | KeyCode::Char('v') => Some(Command::ToggleSelectMode),
+
+        // Navigation
+        KeyCode::Char('j') | KeyCode::Down => Some(Command::NavigateDown),
+        KeyCode::Char('k') | KeyCode::Up => Some(Command::NavigateUp),
+        KeyCode::Char('g') | KeyCode::Home => Some(Command::NavigateTop),
+        KeyCode::Char('G') | KeyCode::End => Some(Command::NavigateBottom),
+
+        // Marking
+        KeyCode::Char(' ') => Some(Command::ToggleMark),
+        KeyCode::Char('a') => Some(Command::MarkAllVisible),
+        KeyCode::Char('T') => Some(Command::MarkMatching(Filter::DueToday)),
+        KeyCode::Char('W') => Some(Command::MarkMatching(Filter::DueThisWeek)),
+        KeyCode::Char('o') => Some(Command::MarkMatching(Filter::Overdue)),
+        KeyCode::Char('1') => Some(Command::MarkMatching(Filter::ByPriority(Priority::Low))),
+        KeyCode::Char('2') => Some(Command::MarkMatching(Filter::ByPriority(Priority::Medium))),
+        KeyCode::Char('3') => Some(Command::MarkMatching(Filter::ByPriority(Priority::High))),
+        KeyCode::Char('4') => Some(Command::MarkMatching(Filter::ByPriority(Priority::Urgent))),
+
+        // Bulk actions
+        KeyCode::Char('c') => Some(Command::ConfirmBulk(BulkAction::Edit(BulkEdit::SetStatus(
+            TaskStatus::Completed,
+        )))),
+        KeyCode::Char('d') => Some(Command::ConfirmBulk(BulkAction::Delete)),
+        KeyCode::Char('m') => Some(Command::ShowBulkEdit(BulkField::Project)),
+        KeyCode::Char('t') => Some(Command::ShowBulkEdit(BulkField::Tags)),
+        KeyCode::Char('p') => Some(Command::ShowBulkEdit(BulkField::Priority)),
+        KeyCode::Char('D') => Some(Command::ShowBulkEdit(BulkField::DueDate)),
+        KeyCode::Char('s') => Some(Command::ShowBulkEdit(BulkField::Status)),
+
+        KeyCode::Char('q') => Some(Command::Quit),
+
+        _ => None,
+    }
+}
+
 /// Maps keys in Editing mode (text input for task title, etc.).
 ///
 /// Standard text editing keys plus escape to cancel and enter to submit.
@@ -554,6 +702,80 @@ mod tests {
         assert!(matches!(cmd, Some(Command::ShowCalendar)));
     }
 
+    #[tokio::test]
+    async fn test_stats_view_keys() {
+        let mut app = setup_app().await;
+        let cmd = map_key_to_command(key(KeyCode::Char('s')), &app);
+        assert!(matches!(cmd, Some(Command::ShowStats)));
+
+        app.current_view = View::Stats;
+        let cmd = map_key_to_command(key(KeyCode::Char('w')), &app);
+        assert!(matches!(cmd, Some(Command::ToggleStatsPeriod)));
+        let cmd = map_key_to_command(key(KeyCode::Esc), &app);
+        assert!(matches!(cmd, Some(Command::ShowMain)));
+        let cmd = map_key_to_command(key(KeyCode::Char('j')), &app);
+        assert!(cmd.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_review_view_keys() {
+        let mut app = setup_app().await;
+        let cmd = map_key_to_command(key(KeyCode::Char('R')), &app);
+        assert!(matches!(cmd, Some(Command::StartReview)));
+
+        app.current_view = View::Review;
+        let cmd = map_key_to_command(key(KeyCode::Char('d')), &app);
+        assert!(matches!(
+            cmd,
+            Some(Command::ReviewEdit(BulkEdit::SetStatus(TaskStatus::Archived)))
+        ));
+        let cmd = map_key_to_command(key(KeyCode::Char('T')), &app);
+        assert!(matches!(cmd, Some(Command::ReviewEdit(BulkEdit::SetDueDate(Some(_))))));
+        let cmd = map_key_to_command(key(KeyCode::Char('s')), &app);
+        assert!(matches!(cmd, Some(Command::ReviewSkip)));
+
+        // The last step only finishes or leaves
+        app.review.step = ReviewStep::NextWeek;
+        let cmd = map_key_to_command(key(KeyCode::Enter), &app);
+        assert!(matches!(cmd, Some(Command::FinishReview)));
+        assert!(map_key_to_command(key(KeyCode::Char('d')), &app).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_activity_view_keys() {
+        let mut app = setup_app().await;
+        let cmd = map_key_to_command(key(KeyCode::Char('H')), &app);
+        assert!(matches!(cmd, Some(Command::ShowActivity)));
+
+        app.current_view = View::Activity;
+        let cmd = map_key_to_command(key(KeyCode::Char('j')), &app);
+        assert!(matches!(cmd, Some(Command::ActivityDown)));
+        // Nothing to go to in an empty feed
+        assert!(map_key_to_command(key(KeyCode::Enter), &app).is_none());
+
+        let task = app.db.add_task(crate::models::Task::new("Logged")).await.unwrap();
+        app.load_activity().await.unwrap();
+        let cmd = map_key_to_command(key(KeyCode::Enter), &app);
+        assert!(matches!(cmd, Some(Command::FocusTask(id)) if id == task.id));
+        let cmd = map_key_to_command(key(KeyCode::Esc), &app);
+        assert!(matches!(cmd, Some(Command::ShowMain)));
+    }
+
+    #[tokio::test]
+    async fn test_standup_view_keys() {
+        let mut app = setup_app().await;
+        let cmd = map_key_to_command(key(KeyCode::Char('U')), &app);
+        assert!(matches!(cmd, Some(Command::ShowStandup)));
+
+        app.current_view = View::Standup;
+        let cmd = map_key_to_command(key(KeyCode::Char('m')), &app);
+        assert!(matches!(cmd, Some(Command::ToggleStandupFormat)));
+        let cmd = map_key_to_command(key(KeyCode::Char('y')), &app);
+        assert!(matches!(cmd, Some(Command::CopyStandup)));
+        let cmd = map_key_to_command(key(KeyCode::Esc), &app);
+        assert!(matches!(cmd, Some(Command::ShowMain)));
+    }
+
     #[tokio::test]
     async fn test_filter_today_shift_t() {
         let app = setup_app().await;
@@ -561,6 +783,70 @@ mod tests {
         assert!(matches!(cmd, Some(Command::FilterToday)));
     }
 
+    #[tokio::test]
+    async fn test_show_profiles_shift_p() {
+        let app = setup_app().await;
+        let cmd = map_key_to_command(key(KeyCode::Char('P')), &app);
+        assert!(matches!(cmd, Some(Command::ShowProfiles)));
+    }
+
+    #[tokio::test]
+    async fn test_move_task_keys() {
+        let mut app = setup_app().await;
+        assert!(matches!(map_key_to_command(key(KeyCode::Char('K')), &app), Some(Command::MoveTaskUp)));
+        assert!(matches!(map_key_to_command(key(KeyCode::Char('J')), &app), Some(Command::MoveTaskDown)));
+
+        app.focus = FocusPanel::Sidebar;
+        assert!(map_key_to_command(key(KeyCode::Char('J')), &app).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_group_keys() {
+        let app = setup_app().await;
+        assert!(matches!(map_key_to_command(key(KeyCode::Char('z')), &app), Some(Command::ToggleGroup)));
+        assert!(matches!(map_key_to_command(key(KeyCode::Char('Z')), &app), Some(Command::ToggleAllGroups)));
+    }
+
+    #[tokio::test]
+    async fn test_select_mode_keys() {
+        let mut app = setup_app().await;
+        assert!(matches!(map_key_to_command(key(KeyCode::Char('v')), &app), Some(Command::ToggleSelectMode)));
+        assert!(matches!(map_key_to_command(key(KeyCode::Char('i')), &app), Some(Command::ShowTaskDetail)));
+
+        app.input_mode = InputMode::Select;
+        assert!(matches!(map_key_to_command(key(KeyCode::Char(' ')), &app), Some(Command::ToggleMark)));
+        assert!(matches!(
+            map_key_to_command(key(KeyCode::Char('T')), &app),
+            Some(Command::MarkMatching(Filter::DueToday))
+        ));
+        assert!(matches!(
+            map_key_to_command(key(KeyCode::Char('d')), &app),
+            Some(Command::ConfirmBulk(BulkAction::Delete))
+        ));
+        assert!(matches!(
+            map_key_to_command(key(KeyCode::Char('m')), &app),
+            Some(Command::ShowBulkEdit(BulkField::Project))
+        ));
+        assert!(matches!(map_key_to_command(key(KeyCode::Esc), &app), Some(Command::ToggleSelectMode)));
+    }
+
+    #[tokio::test]
+    async fn test_show_templates_n() {
+        let app = setup_app().await;
+        let cmd = map_key_to_command(key(KeyCode::Char('n')), &app);
+        assert!(matches!(cmd, Some(Command::ShowTemplates)));
+    }
+
+    #[tokio::test]
+    async fn test_command_palette_keys() {
+        let app = setup_app().await;
+        let ctrl_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        assert!(matches!(map_key_to_command(ctrl_p, &app), Some(Command::ShowCommandPalette)));
+        assert!(matches!(map_key_to_command(key(KeyCode::Char(':')), &app), Some(Command::ShowCommandPalette)));
+        // Plain p still cycles priority
+        assert!(matches!(map_key_to_command(key(KeyCode::Char('p')), &app), Some(Command::CyclePriority)));
+    }
+
     #[tokio::test]
     async fn test_filter_week_shift_w() {
         let app = setup_app().await;
@@ -887,4 +1173,13 @@ mod tests {
         let cmd = map_key_to_command(key(KeyCode::Char('d')), &app);
         assert!(matches!(cmd, Some(Command::DeleteTask)));
     }
+
+    #[tokio::test]
+    async fn test_task_detail_view_shift_n_adds_note() {
+        let mut app = setup_app().await;
+        app.current_view = View::TaskDetail;
+
+        let cmd = map_key_to_command(key(KeyCode::Char('N')), &app);
+        assert!(matches!(cmd, Some(Command::AddNote)));
+    }
 }
@@ -17,6 +17,8 @@
 //! - [`events`] - Event polling and distribution
 //! - [`commands`] - Command definitions and execution
 //! - [`input`] - Keyboard-to-command mapping
+//! - [`palette`] - Commands listed in the command palette
+//! - [`rpc`] - JSON-RPC control socket for driving a running instance
 //!
 //! ## Example
 //!
@@ -40,16 +42,21 @@
 pub mod commands;
 pub mod events;
 pub mod input;
+pub mod palette;
+pub mod rpc;
 
 // Re-export commonly used types
 pub use commands::Command;
 pub use events::{AppEvent, EventHandler};
 pub use input::map_key_to_command;
 
-use crossterm::event::KeyEvent;
-use log::debug;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use log::{debug, warn};
 
-use crate::app::{App, AppError};
+use crate::app::{App, AppError, FocusPanel, InputMode, View};
+use crate::templates::parse_invocation;
+use crate::models::BulkAction;
+use crate::storage::Database;
 use crate::ui::dialogs::{DeleteProjectChoice, Dialog, DialogAction, QuickCaptureAction, SettingsOption};
 
 /// Handles an application event and updates state accordingly.
@@ -91,23 +98,29 @@ pub async fn handle_event(app: &mut App, event: AppEvent) -> Result<bool, AppErr
                 }
             }
 
-            // If a dialog is active, route events to it first
-            if app.dialog.is_some() {
-                return handle_dialog_key(app, key).await;
+            // If a dialog is active, route events to it first, otherwise
+            // map the key to a command based on current context
+            let running = if app.dialog.is_some() {
+                handle_dialog_key(app, key).await?
+            } else if let Some(cmd) = map_key_to_command(key, app) {
+                cmd.execute(app).await?
+            } else {
+                // Key has no mapping in current context, ignore
+                true
+            };
+
+            // Changes made from the task detail view show in its history
+            if app.current_view == View::TaskDetail {
+                app.load_task_history().await?;
             }
-
-            // Map the key to a command based on current context
-            if let Some(cmd) = map_key_to_command(key, app) {
-                return cmd.execute(app).await;
-            }
-
-            // Key has no mapping in current context, ignore
-            Ok(true)
+            Ok(running)
         }
 
         AppEvent::Tick => {
             // Timer tick for time-based updates
             app.on_tick();
+            app.finish_saves().await?;
+            app.check_external_changes().await?;
             Ok(true)
         }
 
@@ -117,14 +130,54 @@ pub async fn handle_event(app: &mut App, event: AppEvent) -> Result<bool, AppErr
             Ok(true)
         }
 
-        AppEvent::Mouse(_mouse) => {
-            // Optional: handle mouse events
-            // For now, we ignore mouse input
+        AppEvent::Mouse(mouse) => {
+            handle_mouse(app, mouse).await?;
+            Ok(true)
+        }
+
+        AppEvent::Remote(call) => {
+            debug!("Remote call: {} {}", call.method, call.params);
+            let result = rpc::dispatch(app, &call.method, &call.params).await;
+            if let Err(e) = &result {
+                warn!("Remote call {} failed: {}", call.method, e);
+            }
+            call.respond(result);
             Ok(true)
         }
     }
 }
 
+/// Handles mouse input on the task list.
+///
+/// Clicking a task selects it, and dragging it to another row moves it
+/// there in the manual order as the pointer goes. Clicking a group header
+/// collapses or expands the group.
+async fn handle_mouse(app: &mut App, mouse: MouseEvent) -> Result<(), AppError> {
+    if app.dialog.is_some() || app.current_view != View::Main || app.input_mode != InputMode::Normal {
+        return Ok(());
+    }
+    let index = app.task_index_at(mouse.column, mouse.row);
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(label) = app.group_at(mouse.column, mouse.row) {
+                app.toggle_group(&label);
+            } else if let Some(index) = index {
+                app.focus = FocusPanel::TaskList;
+                app.selected_task_index = Some(index);
+                app.dragging = true;
+            }
+        }
+        MouseEventKind::Drag(MouseButton::Left) if app.dragging => {
+            if let Some(index) = index.filter(|i| app.selected_task_index != Some(*i)) {
+                app.move_selected_task(index).await?;
+            }
+        }
+        MouseEventKind::Up(MouseButton::Left) => app.dragging = false,
+        _ => {}
+    }
+    Ok(())
+}
+
 /// Handles key events when a dialog is active.
 async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppError> {
     // Take the dialog out to work with it
@@ -134,21 +187,45 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
         Some(Dialog::AddTask(mut add_dialog)) => {
             let action = add_dialog.handle_key(key);
             match action {
+                DialogAction::Submit if !add_dialog.confirm_save() => {
+                    // The task changed in another process; ask before overwriting
+                    app.dialog = Some(Dialog::AddTask(add_dialog));
+                }
                 DialogAction::Submit => {
                     // Create or update the task
                     if let Some(task) = add_dialog.to_task() {
-                        if add_dialog.is_editing() {
-                            app.db.update_task(&task).await?;
-                            app.set_status("Task updated");
-                            app.update_task_in_place(task);
-                        } else {
-                            let task_id = task.id.clone();
-                            app.db.insert_task(&task).await?;
-                            app.set_status("Task created");
-                            app.add_task_in_place(task);
-                            app.pending_new_task_animation = Some(task_id);
+                        let editing = add_dialog.is_editing();
+                        let restoring = editing && !app.tasks.iter().any(|t| t.id == task.id);
+                        let rejected = add_dialog.clone();
+                        let write = async move |db: Database| {
+                            if editing && !restoring {
+                                db.modify_task(task).await
+                            } else {
+                                db.add_task(task).await
+                            }
+                        };
+                        app.save(write, move |app, task| {
+                            let Some(task) = task else {
+                                // Rejected by a hook; reopen the dialog to fix the task
+                                if app.dialog.is_none() {
+                                    app.dialog = Some(Dialog::AddTask(rejected));
+                                }
+                                return;
+                            };
+                            if restoring {
+                                // Deleted externally while being edited; saved again
+                                app.set_status("Task restored");
+                            } else if editing {
+                                app.set_status("Task updated");
+                            } else {
+                                app.set_status("Task created");
+                                app.pending_new_task_animation = Some(task.id);
+                            }
+                        })
+                        .await?;
+                        if app.dialog.is_some() {
+                            return Ok(true);
                         }
-                        app.refresh_tags().await?;
                     }
                     app.start_closing_dialog(Dialog::AddTask(add_dialog));
                 }
@@ -165,18 +242,27 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
         Some(Dialog::Confirm(mut confirm_dialog)) => {
             let action = confirm_dialog.handle_key(key);
             match action {
+                DialogAction::Submit if app.pending_bulk.is_some() => {
+                    if let Some(action) = app.pending_bulk.take() {
+                        apply_bulk(app, action).await?;
+                    }
+                    app.start_closing_dialog(Dialog::Confirm(confirm_dialog));
+                }
                 DialogAction::Submit => {
                     // Confirmation accepted - execute the pending delete
-                    if let Some(task) = app.selected_task().cloned() {
+                    if let Some(task) = app.selected_task() {
                         let task_id = task.id.clone();
-                        app.db.delete_task(&task_id).await?;
-                        app.remove_task_in_place(&task_id);
-                        app.refresh_tags().await?;
-                        app.set_status("Task deleted");
+                        app.save(async move |db| db.remove_task(&task_id).await, |app, deleted| {
+                            if deleted.is_some() {
+                                app.set_status("Task deleted");
+                            }
+                        })
+                        .await?;
                     }
                     app.start_closing_dialog(Dialog::Confirm(confirm_dialog));
                 }
                 DialogAction::Cancel => {
+                    app.pending_bulk = None;
                     app.clear_status();
                     app.start_closing_dialog(Dialog::Confirm(confirm_dialog));
                 }
@@ -190,9 +276,11 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
             let action = filter_dialog.handle_key(key);
             match action {
                 DialogAction::Submit => {
-                    // Apply the selected filter and sort
+                    // Apply the selected filter, sort and grouping
                     app.filter = filter_dialog.selected_filter();
                     app.sort = filter_dialog.selected_sort();
+                    app.then_by = filter_dialog.selected_then_by();
+                    app.set_group_by(filter_dialog.selected_group()).await?;
                     // Reset selection for new filter
                     let count = app.visible_tasks().len();
                     app.selected_task_index = if count > 0 { Some(0) } else { None };
@@ -216,24 +304,40 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
                     match delete_dialog.choice() {
                         DeleteProjectChoice::MoveToInbox => {
                             // Move all tasks to inbox, then delete project
-                            app.db.move_tasks_to_inbox(&project_id).await?;
-                            app.db.delete_project(&project_id).await?;
-                            app.set_status("Project deleted, tasks moved to Inbox");
+                            let write = async move |db: Database| {
+                                db.move_tasks_to_inbox(&project_id).await?;
+                                db.delete_project(&project_id).await
+                            };
+                            app.save(write, |app, deleted| {
+                                if deleted.is_some() {
+                                    app.set_status("Project deleted, tasks moved to Inbox");
+                                    // Reset project selection to "All Tasks"
+                                    app.selected_project_index = 0;
+                                }
+                            })
+                            .await?;
                         }
                         DeleteProjectChoice::DeleteTasks => {
-                            // Delete all tasks in project, then delete project
-                            app.db.delete_tasks_by_project(&project_id).await?;
-                            app.db.delete_project(&project_id).await?;
-                            app.set_status("Project and tasks deleted");
+                            if let Some(note) = snapshot(app, "pre-delete-project").await {
+                                // Delete all tasks in project, then delete project
+                                let write = async move |db: Database| {
+                                    db.remove_tasks_by_project(&project_id).await?;
+                                    db.delete_project(&project_id).await
+                                };
+                                app.save(write, move |app, deleted| {
+                                    if deleted.is_some() {
+                                        app.set_status(format!("Project and tasks deleted{}", note));
+                                        app.selected_project_index = 0;
+                                    }
+                                })
+                                .await?;
+                            }
                         }
                         DeleteProjectChoice::Cancel => {
                             // Shouldn't reach here, but handle anyway
                             app.clear_status();
                         }
                     }
-                    app.load_data().await?;
-                    // Reset project selection to "All Tasks"
-                    app.selected_project_index = 0;
                     app.start_closing_dialog(Dialog::DeleteProject(delete_dialog));
                 }
                 DialogAction::Cancel => {
@@ -273,6 +377,34 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
                 }
             }
         }
+        Some(Dialog::Note(mut note_dialog)) => {
+            let action = note_dialog.handle_key(key);
+            match action {
+                DialogAction::Submit => {
+                    let task_id = note_dialog.task_id.clone();
+                    if let Some(task) = app.tasks.iter().find(|t| t.id == task_id) {
+                        let mut task = task.clone();
+                        if task.add_note(&note_dialog.note_text()) {
+                            app.save(async move |db| db.modify_task(task).await, |app, task| {
+                                if task.is_some() {
+                                    app.set_status("Note added");
+                                }
+                            })
+                            .await?;
+                        }
+                    }
+                    app.start_closing_dialog(Dialog::Note(note_dialog));
+                }
+                DialogAction::Cancel => {
+                    app.clear_status();
+                    app.start_closing_dialog(Dialog::Note(note_dialog));
+                }
+                DialogAction::None => {
+                    // Keep the dialog open
+                    app.dialog = Some(Dialog::Note(note_dialog));
+                }
+            }
+        }
         Some(Dialog::MoveToProject(mut move_dialog)) => {
             let action = move_dialog.handle_key(key);
             match action {
@@ -284,13 +416,23 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
                             let mut task = task.clone();
                             task.project_id = Some(project_id.clone());
                             task.updated_at = chrono::Utc::now();
-                            app.db.update_task(&task).await?;
                             let project_name = move_dialog
                                 .selected_project()
-                                .map(|p| p.name.as_str())
-                                .unwrap_or("Unknown");
-                            app.set_status(format!("Task moved to {}", project_name));
-                            app.update_task_in_place(task);
+                                .map_or("Unknown", |p| p.name.as_str())
+                                .to_string();
+                            let reviewing = app.current_view == View::Review;
+                            app.save(async move |db| db.modify_task(task).await, move |app, task| {
+                                if task.is_some() {
+                                    app.set_status(format!("Task moved to {}", project_name));
+                                    if reviewing {
+                                        app.review.handled += 1;
+                                    }
+                                }
+                            })
+                            .await?;
+                            if reviewing {
+                                app.advance_review();
+                            }
                         }
                     }
                     app.start_closing_dialog(Dialog::MoveToProject(move_dialog));
@@ -305,6 +447,48 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
                 }
             }
         }
+        Some(Dialog::CommandPalette(mut palette_dialog)) => {
+            let action = palette_dialog.handle_key(key);
+            match action {
+                DialogAction::Submit => {
+                    let command = palette_dialog.selected_choice().cloned().and_then(palette::command_for);
+                    app.clear_status();
+                    app.start_closing_dialog(Dialog::CommandPalette(palette_dialog));
+                    if let Some(command) = command {
+                        return command.execute(app).await;
+                    }
+                }
+                DialogAction::Cancel => {
+                    app.clear_status();
+                    app.start_closing_dialog(Dialog::CommandPalette(palette_dialog));
+                }
+                DialogAction::None => {
+                    // Keep the palette open
+                    app.dialog = Some(Dialog::CommandPalette(palette_dialog));
+                }
+            }
+        }
+        Some(Dialog::Export(mut export_dialog)) => {
+            let action = export_dialog.handle_key(key);
+            match action {
+                DialogAction::Submit => {
+                    let command = Command::ExportView {
+                        format: export_dialog.selected_format(),
+                        options: export_dialog.options(),
+                        path: export_dialog.output_path(),
+                    };
+                    app.start_closing_dialog(Dialog::Export(export_dialog));
+                    return command.execute(app).await;
+                }
+                DialogAction::Cancel => {
+                    app.start_closing_dialog(Dialog::Export(export_dialog));
+                }
+                DialogAction::None => {
+                    // Keep the dialog open
+                    app.dialog = Some(Dialog::Export(export_dialog));
+                }
+            }
+        }
         Some(Dialog::Settings(mut settings_dialog)) => {
             let action = settings_dialog.handle_key(key);
             match action {
@@ -313,23 +497,36 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
                     if let Some(option) = settings_dialog.confirmed_option() {
                         match option {
                             SettingsOption::DeleteCompletedTasks => {
-                                let count = app.db.delete_completed_tasks().await?;
-                                app.load_data().await?;
-                                app.set_status(format!("Deleted {} completed task(s)", count));
+                                if let Some(note) = snapshot(app, "pre-delete-completed").await {
+                                    let write = async |db: Database| db.remove_completed_tasks().await;
+                                    app.save(write, move |app, count| {
+                                        if let Some(count) = count {
+                                            app.set_status(format!(
+                                                "Deleted {} completed task(s){}",
+                                                count, note
+                                            ));
+                                        }
+                                    })
+                                    .await?;
+                                }
                             }
                             SettingsOption::ResetDatabase => {
-                                // Delete all tasks first
-                                let task_count = app.db.delete_all_tasks().await?;
-                                // Delete all projects except Inbox
-                                let project_count = app.db.delete_all_projects_except_inbox().await?;
-                                app.load_data().await?;
-                                // Reset selection
-                                app.selected_task_index = None;
-                                app.selected_project_index = 0;
-                                app.set_status(format!(
-                                    "Database reset: deleted {} task(s) and {} project(s)",
-                                    task_count, project_count
-                                ));
+                                if let Some(note) = snapshot(app, "pre-reset").await {
+                                    // Delete all tasks first; hooks don't run for a reset
+                                    app.wait_for_saves().await?;
+                                    let task_count = app.db.delete_all_tasks().await?;
+                                    // Delete all projects except Inbox
+                                    let project_count =
+                                        app.db.delete_all_projects_except_inbox().await?;
+                                    app.load_data().await?;
+                                    // Reset selection
+                                    app.selected_task_index = None;
+                                    app.selected_project_index = 0;
+                                    app.set_status(format!(
+                                        "Database reset: deleted {} task(s) and {} project(s){}",
+                                        task_count, project_count, note
+                                    ));
+                                }
                             }
                         }
                     }
@@ -349,13 +546,17 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
             let action = capture_dialog.handle_key(key);
             match action {
                 QuickCaptureAction::Submit => {
-                    if let Some(task) = capture_dialog.to_task() {
-                        let task_id = task.id.clone();
-                        app.db.insert_task(&task).await?;
-                        app.set_status("Task created");
-                        app.add_task_in_place(task);
-                        app.refresh_tags().await?;
-                        app.pending_new_task_animation = Some(task_id);
+                    if let Some((key, input)) = parse_invocation(capture_dialog.input.value()) {
+                        let (key, input) = (key.to_string(), input.to_string());
+                        app.apply_template(&key, &input).await?;
+                    } else if let Some(task) = capture_dialog.to_task() {
+                        app.save(async move |db| db.add_task(task).await, |app, task| {
+                            if let Some(task) = task {
+                                app.set_status("Task created");
+                                app.pending_new_task_animation = Some(task.id);
+                            }
+                        })
+                        .await?;
                     }
                     app.start_closing_dialog(Dialog::QuickCapture(capture_dialog));
                 }
@@ -374,6 +575,72 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
                 }
             }
         }
+        Some(Dialog::SwitchProfile(mut profile_dialog)) => {
+            let action = profile_dialog.handle_key(key);
+            match action {
+                DialogAction::Submit => {
+                    // The main loop opens the database, which needs the config
+                    if let Some(name) = profile_dialog.selected_profile() {
+                        if profile_dialog.current.as_deref() == Some(name) {
+                            app.set_status(format!("Profile {} is already open", name));
+                        } else {
+                            app.set_status(format!("Opening profile {}...", name));
+                            app.requested_profile = Some(name.to_string());
+                        }
+                    }
+                    app.start_closing_dialog(Dialog::SwitchProfile(profile_dialog));
+                }
+                DialogAction::Cancel => {
+                    app.clear_status();
+                    app.start_closing_dialog(Dialog::SwitchProfile(profile_dialog));
+                }
+                DialogAction::None => {
+                    // Keep the dialog open
+                    app.dialog = Some(Dialog::SwitchProfile(profile_dialog));
+                }
+            }
+        }
+        Some(Dialog::Template(mut template_dialog)) => {
+            let action = template_dialog.handle_key(key);
+            match action {
+                DialogAction::Submit => {
+                    if let Some(template_key) = template_dialog.selected_key() {
+                        let template_key = template_key.to_string();
+                        let input = template_dialog.input.value().to_string();
+                        app.apply_template(&template_key, &input).await?;
+                    }
+                    app.start_closing_dialog(Dialog::Template(template_dialog));
+                }
+                DialogAction::Cancel => {
+                    app.clear_status();
+                    app.start_closing_dialog(Dialog::Template(template_dialog));
+                }
+                DialogAction::None => {
+                    // Keep the dialog open
+                    app.dialog = Some(Dialog::Template(template_dialog));
+                }
+            }
+        }
+        Some(Dialog::BulkEdit(mut bulk_dialog)) => {
+            let action = bulk_dialog.handle_key(key);
+            match action {
+                DialogAction::Submit => {
+                    // Ask before changing the marked tasks
+                    if let Some(edit) = bulk_dialog.to_edit() {
+                        let question = bulk_dialog.summary();
+                        commands::confirm_bulk(app, BulkAction::Edit(edit), question);
+                    }
+                }
+                DialogAction::Cancel => {
+                    app.clear_status();
+                    app.start_closing_dialog(Dialog::BulkEdit(bulk_dialog));
+                }
+                DialogAction::None => {
+                    // Keep the dialog open
+                    app.dialog = Some(Dialog::BulkEdit(bulk_dialog));
+                }
+            }
+        }
         None => {
             // No dialog was active (shouldn't happen)
         }
@@ -382,6 +649,35 @@ async fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<bool, AppErro
     Ok(true)
 }
 
+/// Applies a confirmed bulk action to the marked tasks.
+async fn apply_bulk(app: &mut App, action: BulkAction) -> Result<(), AppError> {
+    match action {
+        BulkAction::Delete => {
+            if let Some(note) = snapshot(app, "pre-bulk-delete").await {
+                app.delete_marked(note).await?;
+            }
+        }
+        BulkAction::Edit(edit) => app.apply_bulk_edit(&edit).await?,
+    }
+    Ok(())
+}
+
+/// Backs up the database before a destructive action.
+///
+/// Returns a note naming the backup, to append to the status message, or
+/// `None` if the backup failed, in which case the action must not proceed.
+async fn snapshot(app: &mut App, reason: &str) -> Option<String> {
+    match app.db.backup(reason).await {
+        Ok(Some(backup)) => Some(format!(" (backup {})", backup.id)),
+        Ok(None) => Some(String::new()),
+        Err(e) => {
+            warn!("Backup before {} failed: {}", reason, e);
+            app.set_status(format!("Backup failed, nothing was deleted: {}", e));
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -475,4 +771,137 @@ mod tests {
         assert!(!result);
         assert!(app.should_quit);
     }
+
+    #[tokio::test]
+    async fn test_bulk_edit_marked_tasks() {
+        use crate::models::{Priority, Task};
+
+        let mut app = setup_app().await;
+        app.current_view = View::Main;
+        let tasks: Vec<Task> = ["One", "Two", "Three"].into_iter().map(Task::new).collect();
+        for task in &tasks {
+            app.db.insert_task(task).await.unwrap();
+        }
+        app.load_data().await.unwrap();
+        app.selected_task_index = Some(0);
+
+        let press = |c: char| AppEvent::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        // Select mode, mark the first two tasks, set their priority to Urgent
+        for event in [press('v'), press(' '), press(' '), press('p'), press('j'), press('j'), press('j')] {
+            handle_event(&mut app, event).await.unwrap();
+        }
+        assert_eq!(app.marked.len(), 2);
+        let enter = AppEvent::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        handle_event(&mut app, enter).await.unwrap();
+        assert!(matches!(app.dialog, Some(Dialog::Confirm(ref d)) if d.message == "Set priority of 2 tasks to Urgent?"));
+        handle_event(&mut app, press('y')).await.unwrap();
+
+        let urgent = app.db.get_all_tasks().await.unwrap();
+        assert_eq!(urgent.iter().filter(|t| t.priority == Priority::Urgent).count(), 2);
+        assert!(app.marked.is_empty());
+        assert_eq!(app.input_mode, crate::app::InputMode::Normal);
+        assert_eq!(app.status_message.as_deref(), Some("Updated 2 tasks"));
+
+        // Delete every visible task, after confirming
+        for event in [press('v'), press('a'), press('d')] {
+            handle_event(&mut app, event).await.unwrap();
+        }
+        assert!(matches!(app.dialog, Some(Dialog::Confirm(ref d)) if d.destructive));
+        handle_event(&mut app, press('y')).await.unwrap();
+        assert!(app.visible_tasks().is_empty());
+        assert!(app.db.get_all_tasks().await.unwrap().iter().all(|t| !tasks.iter().any(|x| x.id == t.id)));
+    }
+
+    #[tokio::test]
+    async fn test_command_palette_sets_due_date() {
+        use crate::models::Task;
+
+        let mut app = setup_app().await;
+        app.current_view = View::Main;
+        let task = Task::new("Ship it");
+        app.db.insert_task(&task).await.unwrap();
+        app.load_data().await.unwrap();
+        app.selected_task_index = app.visible_tasks().iter().position(|t| t.id == task.id);
+
+        let press = |code: KeyCode| AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE));
+        handle_event(&mut app, press(KeyCode::Char(':'))).await.unwrap();
+        for c in "set due: tomorrow".chars() {
+            handle_event(&mut app, press(KeyCode::Char(c))).await.unwrap();
+        }
+        handle_event(&mut app, press(KeyCode::Enter)).await.unwrap();
+
+        assert!(app.dialog.is_none());
+        let saved = app.db.get_task(&task.id).await.unwrap().unwrap();
+        assert!(saved.is_due_this_week() && !saved.is_due_today());
+        assert_eq!(app.status_message.as_deref(), Some("Due Tomorrow"));
+    }
+
+    #[tokio::test]
+    async fn test_add_note_from_task_detail() {
+        use crate::models::Task;
+
+        let mut app = setup_app().await;
+        let task = Task::new("Ship it");
+        app.db.insert_task(&task).await.unwrap();
+        app.load_data().await.unwrap();
+        app.selected_task_index = app.visible_tasks().iter().position(|t| t.id == task.id);
+        app.current_view = View::TaskDetail;
+
+        let press = |code: KeyCode, modifiers: KeyModifiers| AppEvent::Key(KeyEvent::new(code, modifiers));
+        handle_event(&mut app, press(KeyCode::Char('N'), KeyModifiers::SHIFT)).await.unwrap();
+        assert!(matches!(app.dialog, Some(Dialog::Note(_))));
+        for c in "Waiting on review".chars() {
+            handle_event(&mut app, press(KeyCode::Char(c), KeyModifiers::NONE)).await.unwrap();
+        }
+        handle_event(&mut app, press(KeyCode::Char('s'), KeyModifiers::CONTROL)).await.unwrap();
+
+        assert!(app.dialog.is_none());
+        assert_eq!(app.status_message.as_deref(), Some("Note added"));
+        assert_eq!(app.selected_task().unwrap().notes[0].text, "Waiting on review");
+        let saved = app.db.get_task(&task.id).await.unwrap().unwrap();
+        assert_eq!(saved.notes.len(), 1);
+        assert_eq!(saved.notes[0].text, "Waiting on review");
+    }
+
+    #[tokio::test]
+    async fn test_mouse_drag_reorders_tasks() {
+        use crate::models::{SortOrder, Task};
+        use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+        use ratatui::layout::Rect;
+
+        let mut app = setup_app().await;
+        app.current_view = View::Main;
+        app.sort = SortOrder::Manual;
+        for (title, position) in [("One", 1.0), ("Two", 2.0), ("Three", 3.0)] {
+            let mut task = Task::new(title);
+            task.position = position;
+            app.db.insert_task(&task).await.unwrap();
+        }
+        app.load_data().await.unwrap();
+        app.last_task_list_area = Some(Rect::new(0, 1, 40, 10));
+
+        let mouse = |kind, row| {
+            AppEvent::Mouse(MouseEvent { kind, column: 5, row, modifiers: KeyModifiers::NONE })
+        };
+        handle_event(&mut app, mouse(MouseEventKind::Down(MouseButton::Left), 1)).await.unwrap();
+        assert_eq!(app.selected_task_index, Some(0));
+        handle_event(&mut app, mouse(MouseEventKind::Drag(MouseButton::Left), 2)).await.unwrap();
+        handle_event(&mut app, mouse(MouseEventKind::Drag(MouseButton::Left), 3)).await.unwrap();
+        handle_event(&mut app, mouse(MouseEventKind::Up(MouseButton::Left), 3)).await.unwrap();
+
+        let titles: Vec<&str> = app.visible_tasks().iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Two", "Three", "One"]);
+        assert_eq!(app.selected_task().unwrap().title, "One");
+        assert!(!app.dragging);
+
+        // Drags that didn't start on a task change nothing
+        handle_event(&mut app, mouse(MouseEventKind::Drag(MouseButton::Left), 1)).await.unwrap();
+        assert_eq!(app.selected_task_index, Some(2));
+
+        // Clicking a group header collapses the group
+        app.set_group_by(crate::models::GroupBy::Priority).await.unwrap();
+        handle_event(&mut app, mouse(MouseEventKind::Down(MouseButton::Left), 1)).await.unwrap();
+        assert_eq!(app.collapsed_groups.len(), 1);
+        assert!(app.visible_tasks().is_empty());
+    }
 }
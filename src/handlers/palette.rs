@@ -0,0 +1,181 @@
+//! Command palette catalogue.
+//!
+//! Names the commands the palette lists and finds their current keybindings
+//! by running keys through [`map_key_to_command`], so the labels always
+//! match the keymap.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::app::App;
+use crate::handlers::commands::Command;
+use crate::handlers::input::map_key_to_command;
+use crate::models::{BulkEdit, Filter, TaskStatus};
+use crate::ui::dialogs::{CommandPaletteDialog, PaletteAction, PaletteChoice};
+
+/// Commands listed in the palette, with their human names.
+///
+/// Includes actions that have no key, like filtering to overdue tasks.
+pub fn catalogue() -> Vec<(&'static str, Command)> {
+    vec![
+        // Tasks
+        ("Quick capture", Command::QuickCapture),
+        ("Add task (full form)", Command::AddTask),
+        ("New tasks from template", Command::ShowTemplates),
+        ("Edit task", Command::EditTask),
+        ("Delete task", Command::DeleteTask),
+        ("Toggle task completion", Command::ToggleTaskStatus),
+        ("Start task", Command::EditSelectedTask(BulkEdit::SetStatus(TaskStatus::InProgress))),
+        ("Archive task", Command::EditSelectedTask(BulkEdit::SetStatus(TaskStatus::Archived))),
+        ("Cycle priority", Command::CyclePriority),
+        ("Edit tags", Command::EditTags),
+        ("Add note", Command::AddNote),
+        ("Move task to project", Command::MoveToProject),
+        ("Clear due date", Command::EditSelectedTask(BulkEdit::SetDueDate(None))),
+        ("Task detail", Command::ShowTaskDetail),
+        ("Selection mode", Command::ToggleSelectMode),
+        // Projects
+        ("Add project", Command::AddProject),
+        ("Edit project", Command::EditProject),
+        ("Delete project", Command::DeleteProject),
+        // Filters
+        ("Filter, sort and group", Command::ShowFilterSort),
+        ("Collapse or expand group", Command::ToggleGroup),
+        ("Collapse or expand all groups", Command::ToggleAllGroups),
+        ("Show tasks due today", Command::FilterToday),
+        ("Show tasks due this week", Command::FilterThisWeek),
+        ("Show overdue tasks", Command::SetFilter(Filter::Overdue)),
+        ("Show tasks in progress", Command::SetFilter(Filter::InProgress)),
+        ("Show completed tasks", Command::SetFilter(Filter::Completed)),
+        ("Show archived tasks", Command::SetFilter(Filter::Archived)),
+        ("Show all tasks", Command::SetFilter(Filter::All)),
+        ("Reset filter", Command::SetFilter(Filter::Pending)),
+        // Views
+        ("Search tasks", Command::ShowSearch),
+        ("Calendar", Command::ShowCalendar),
+        ("Statistics", Command::ShowStats),
+        ("Weekly review", Command::StartReview),
+        ("Activity feed", Command::ShowActivity),
+        ("Standup report", Command::ShowStandup),
+        ("Copy standup to clipboard", Command::CopyStandup),
+        ("Switch stats between days and weeks", Command::ToggleStatsPeriod),
+        ("Help", Command::ShowHelp),
+        ("Debug logs", Command::ShowDebugLogs),
+        // Navigation
+        ("Focus sidebar", Command::FocusSidebar),
+        ("Focus task list", Command::FocusTaskList),
+        ("Switch panel", Command::SwitchPanel),
+        ("Jump to top", Command::NavigateTop),
+        ("Jump to bottom", Command::NavigateBottom),
+        // General
+        ("Settings", Command::ShowSettings),
+        ("Switch profile", Command::ShowProfiles),
+        ("Export view", Command::ShowExport),
+        ("Refresh", Command::Refresh),
+        ("Quit", Command::Quit),
+    ]
+}
+
+/// Builds the palette for the app's current state.
+pub fn palette(app: &App) -> CommandPaletteDialog {
+    let bindings = keybindings(app);
+    let actions = catalogue()
+        .into_iter()
+        .map(|(name, command)| PaletteAction {
+            name: name.to_string(),
+            keys: bindings
+                .iter()
+                .filter(|(bound, _)| *bound == command)
+                .map(|(_, label)| label.as_str())
+                .collect::<Vec<_>>()
+                .join(" / "),
+        })
+        .collect();
+    CommandPaletteDialog::new(actions, &app.projects, &app.tags, &app.tasks)
+}
+
+/// Returns the command to run for what the user picked.
+pub fn command_for(choice: PaletteChoice) -> Option<Command> {
+    match choice {
+        PaletteChoice::Action(index) => catalogue().into_iter().nth(index).map(|(_, command)| command),
+        PaletteChoice::SetFilter(filter) => Some(Command::SetFilter(filter)),
+        PaletteChoice::Edit(edit) => Some(Command::EditSelectedTask(edit)),
+        PaletteChoice::GoToTask(id) => Some(Command::FocusTask(id)),
+    }
+}
+
+/// Returns every key that maps to a command right now, with its label.
+fn keybindings(app: &App) -> Vec<(Command, String)> {
+    let mut keys: Vec<(KeyEvent, String)> = (' '..='~')
+        .map(|c| {
+            let label = if c == ' ' { "Space".to_string() } else { c.to_string() };
+            (KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE), label)
+        })
+        .collect();
+    // Most keys ignore modifiers, so only list Ctrl chords that do
+    // something different from the plain key
+    keys.extend(
+        ('a'..='z')
+            .map(|c| (KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL), format!("Ctrl+{}", c)))
+            .filter(|(key, _)| {
+                let plain = KeyEvent::new(key.code, KeyModifiers::NONE);
+                map_key_to_command(*key, app) != map_key_to_command(plain, app)
+            }),
+    );
+    keys.extend(
+        [
+            (KeyCode::Enter, "Enter"),
+            (KeyCode::Tab, "Tab"),
+            (KeyCode::Esc, "Esc"),
+            (KeyCode::Up, "↑"),
+            (KeyCode::Down, "↓"),
+            (KeyCode::Left, "←"),
+            (KeyCode::Right, "→"),
+            (KeyCode::Home, "Home"),
+            (KeyCode::End, "End"),
+        ]
+        .into_iter()
+        .map(|(code, label)| (KeyEvent::new(code, KeyModifiers::NONE), label.to_string())),
+    );
+    keys.extend((1..=12).map(|n| (KeyEvent::new(KeyCode::F(n), KeyModifiers::NONE), format!("F{}", n))));
+
+    keys.into_iter()
+        .filter_map(|(key, label)| map_key_to_command(key, app).map(|command| (command, label)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{run_migrations, Database};
+
+    async fn setup_app() -> App {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        App::new(db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_palette_shows_current_keybindings() {
+        let app = setup_app().await;
+        let dialog = palette(&app);
+        let keys = |name: &str| {
+            let index = catalogue().iter().position(|(n, _)| *n == name).unwrap();
+            dialog.actions()[index].keys.clone()
+        };
+        assert_eq!(keys("Toggle task completion"), "Space");
+        assert_eq!(keys("Quick capture"), "a");
+        assert_eq!(keys("Jump to top"), "g / Home");
+        assert_eq!(keys("Show overdue tasks"), "");
+        assert_eq!(keys("Quit"), "q");
+    }
+
+    #[test]
+    fn test_command_for_choice() {
+        assert_eq!(command_for(PaletteChoice::Action(0)), Some(Command::QuickCapture));
+        assert_eq!(
+            command_for(PaletteChoice::GoToTask("t1".to_string())),
+            Some(Command::FocusTask("t1".to_string()))
+        );
+        assert_eq!(command_for(PaletteChoice::Action(usize::MAX)), None);
+    }
+}
@@ -0,0 +1,698 @@
+//! JSON-RPC control socket for a running instance.
+//!
+//! While the TUI runs it listens on a Unix domain socket next to its
+//! database (`ratado.db` → `ratado.sock`). Each line received is a
+//! [JSON-RPC 2.0](https://www.jsonrpc.org/specification) request. Requests
+//! are forwarded into the [`EventHandler`](super::EventHandler) channel as
+//! [`AppEvent::Remote`] and translated into [`Command`]s by [`dispatch`], so
+//! editor plugins and tmux bindings drive the visible instance the same way
+//! the keyboard does.
+//!
+//! ## Methods
+//!
+//! | Method       | Params                    | Result                 |
+//! |--------------|---------------------------|------------------------|
+//! | `add_task`   | `text` (Quick Capture)    | the created tasks      |
+//! | `focus_task` | `id`                      | UI state               |
+//! | `switch_view`| `view`                    | UI state               |
+//! | `set_filter` | `filter` (export syntax)  | UI state               |
+//! | `refresh`    |                           | UI state               |
+//! | `state`      |                           | UI state               |
+//!
+//! The `ratado` binary also handles `run_command`, which runs a command
+//! line (`import`, `export`, `standup`, `sync`) started while the TUI holds
+//! the database, and returns its output. `api_request` (`method`, `uri`,
+//! `body`) answers a request `ratado serve` passed on, returning its
+//! `status` and `body`. `mcp_tool_call` (`name`, `arguments`) runs a tool
+//! call for `ratado mcp` and returns its result.
+//!
+//! With the default database on Linux (on macOS the directory is
+//! `~/Library/Application Support/ratado`):
+//!
+//! ```sh
+//! echo '{"jsonrpc":"2.0","id":1,"method":"add_task","params":{"text":"Buy milk #errands"}}' \
+//!     | nc -U ~/.config/ratado/ratado.sock
+//! ```
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+use super::{AppEvent, Command};
+use crate::api;
+use crate::app::{App, View};
+use crate::interop::report::parse_filter;
+use crate::mcp;
+
+#[cfg(unix)]
+pub use socket::{call, socket_path, ClientError, ControlSocket};
+
+/// An error returned to a JSON-RPC client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Error)]
+#[error("{message} (code {code})")]
+pub struct RpcError {
+    /// JSON-RPC error code
+    pub code: i64,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl RpcError {
+    /// The request was not valid JSON.
+    pub const PARSE_ERROR: i64 = -32700;
+    /// The JSON was not a valid request object.
+    pub const INVALID_REQUEST: i64 = -32600;
+    /// The method does not exist.
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    /// The params were missing or invalid.
+    pub const INVALID_PARAMS: i64 = -32602;
+    /// The call was valid but failed inside Ratado.
+    pub const APP_ERROR: i64 = -32000;
+
+    /// Creates an error with the given code.
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an [`INVALID_PARAMS`](Self::INVALID_PARAMS) error.
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_PARAMS, message)
+    }
+}
+
+/// A call received on the control socket, waiting to be handled by the app.
+#[derive(Debug, Clone)]
+pub struct RemoteCall {
+    /// Method name
+    pub method: String,
+    /// Method params (`null` when omitted)
+    pub params: Value,
+    /// Where to send the outcome
+    reply: mpsc::UnboundedSender<Result<Value, RpcError>>,
+}
+
+impl RemoteCall {
+    /// Creates a call and the receiver its outcome will be sent to.
+    pub fn new(
+        method: impl Into<String>,
+        params: Value,
+    ) -> (Self, mpsc::UnboundedReceiver<Result<Value, RpcError>>) {
+        let (reply, rx) = mpsc::unbounded_channel();
+        let call = Self {
+            method: method.into(),
+            params,
+            reply,
+        };
+        (call, rx)
+    }
+
+    /// Sends the outcome back to the client. Does nothing if it hung up.
+    pub fn respond(self, result: Result<Value, RpcError>) {
+        let _ = self.reply.send(result);
+    }
+}
+
+/// Executes a remote call against the app.
+///
+/// # Errors
+///
+/// Returns an [`RpcError`] for unknown methods, invalid params, or if the
+/// resulting command fails.
+pub async fn dispatch(app: &mut App, method: &str, params: &Value) -> Result<Value, RpcError> {
+    // Remote calls dismiss the splash screen like a key press would
+    if app.current_view == View::Splash {
+        app.animation.cancel_splash();
+        app.current_view = View::Main;
+    }
+
+    let command = match method {
+        "state" => return Ok(state(app)),
+        "add_task" => {
+            let text = string_param(params, "text")?;
+            let existing: HashSet<String> = app.tasks.iter().map(|t| t.id.clone()).collect();
+            let db = app.db.clone();
+            let (result, changes) = db
+                .track_changes(async || {
+                    execute(app, Command::CaptureTask(text)).await?;
+                    // The caller waits for the hooks
+                    app.wait_for_saves()
+                        .await
+                        .map_err(|e| RpcError::new(RpcError::APP_ERROR, e.to_string()))
+                })
+                .await;
+            result?;
+            // Templates create several tasks, not necessarily first in the list
+            let created: Vec<_> = app
+                .tasks
+                .iter()
+                .filter(|t| changes.tasks.contains(&t.id) && !existing.contains(&t.id))
+                .collect();
+            return if created.is_empty() {
+                // No title, or rejected by a hook; the status says which
+                Err(RpcError::invalid_params(
+                    app.status_message.clone().unwrap_or_default(),
+                ))
+            } else {
+                Ok(json!(created))
+            };
+        }
+        api::FORWARD_METHOD => {
+            let method = string_param(params, "method")?;
+            let uri = string_param(params, "uri")?;
+            let body = params["body"].as_str().unwrap_or_default().to_string();
+            let (status, body) = app
+                .run_remote(async |db| api::respond(db.clone(), &method, &uri, body).await)
+                .await
+                .map_err(|e| RpcError::new(RpcError::APP_ERROR, e.to_string()))?;
+            return Ok(json!({ "status": status, "body": body }));
+        }
+        mcp::FORWARD_METHOD => {
+            return app
+                .run_remote(async |db| mcp::call_tool(db, params).await)
+                .await
+                .map_err(|e| RpcError::new(RpcError::APP_ERROR, e.to_string()))?;
+        }
+        "focus_task" => {
+            let id = string_param(params, "id")?;
+            if !app.tasks.iter().any(|t| t.id == id) {
+                return Err(RpcError::invalid_params(format!("Task not found: {}", id)));
+            }
+            Command::FocusTask(id)
+        }
+        "switch_view" => {
+            let view = string_param(params, "view")?;
+            match view.to_lowercase().as_str() {
+                "main" => Command::ShowMain,
+                "calendar" => Command::ShowCalendar,
+                "stats" => Command::ShowStats,
+                "review" => Command::StartReview,
+                "activity" => Command::ShowActivity,
+                "standup" => Command::ShowStandup,
+                "search" => Command::ShowSearch,
+                "help" => Command::ShowHelp,
+                "logs" if app.current_view == View::DebugLogs => return Ok(state(app)),
+                "logs" => Command::ShowDebugLogs,
+                "detail" if app.selected_task().is_none() => {
+                    return Err(RpcError::invalid_params("No task selected"));
+                }
+                "detail" => Command::ShowTaskDetail,
+                _ => {
+                    return Err(RpcError::invalid_params(format!(
+                        "Unknown view: {} (expected main, calendar, stats, review, activity, standup, search, help, logs or detail)",
+                        view
+                    )));
+                }
+            }
+        }
+        "set_filter" => {
+            let spec = string_param(params, "filter")?;
+            Command::SetFilter(parse_filter(&spec, &app.projects).map_err(RpcError::invalid_params)?)
+        }
+        "refresh" => Command::Refresh,
+        _ => {
+            return Err(RpcError::new(
+                RpcError::METHOD_NOT_FOUND,
+                format!("Unknown method: {}", method),
+            ));
+        }
+    };
+
+    execute(app, command).await?;
+    Ok(state(app))
+}
+
+async fn execute(app: &mut App, command: Command) -> Result<(), RpcError> {
+    command
+        .execute(app)
+        .await
+        .map(|_| ())
+        .map_err(|e| RpcError::new(RpcError::APP_ERROR, e.to_string()))
+}
+
+/// Reads a required string param.
+fn string_param(params: &Value, name: &str) -> Result<String, RpcError> {
+    params
+        .get(name)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| RpcError::invalid_params(format!("Missing string param: {}", name)))
+}
+
+/// Describes what the app is showing.
+fn state(app: &App) -> Value {
+    let view = match app.current_view {
+        View::Splash | View::Main => "main",
+        View::TaskDetail => "detail",
+        View::Calendar => "calendar",
+        View::Stats => "stats",
+        View::Review => "review",
+        View::Activity => "activity",
+        View::Standup => "standup",
+        View::Search => "search",
+        View::Help => "help",
+        View::DebugLogs => "logs",
+    };
+    json!({
+        "view": view,
+        "project": app.selected_project_name(),
+        "filter": app.filter_name(),
+        "sort": app.sort_name(),
+        "visible_tasks": app.visible_tasks().len(),
+        "selected_task": app.selected_task().map(|t| json!({ "id": t.id, "title": t.title })),
+        "status": app.status_message,
+    })
+}
+
+/// A JSON-RPC request line.
+#[derive(Debug, Deserialize)]
+struct Request {
+    jsonrpc: String,
+    /// Absent for notifications, which get no response
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Handles one request line, returning the response line to send, if any.
+async fn handle_line(line: &str, events: &mpsc::UnboundedSender<AppEvent>) -> Option<String> {
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(e) => {
+            let error = RpcError::new(RpcError::PARSE_ERROR, format!("Parse error: {}", e));
+            return Some(response(Value::Null, Err(error)));
+        }
+    };
+    let request: Request = match serde_json::from_value(value) {
+        Ok(request) => request,
+        Err(e) => {
+            let error = RpcError::new(RpcError::INVALID_REQUEST, format!("Invalid request: {}", e));
+            return Some(response(Value::Null, Err(error)));
+        }
+    };
+    if request.jsonrpc != "2.0" {
+        let error = RpcError::new(RpcError::INVALID_REQUEST, "Only JSON-RPC 2.0 is supported");
+        return Some(response(request.id.unwrap_or_default(), Err(error)));
+    }
+
+    let (call, mut reply) = RemoteCall::new(request.method, request.params);
+    let result = if events.send(AppEvent::Remote(call)).is_ok() {
+        reply.recv().await.unwrap_or_else(|| {
+            Err(RpcError::new(RpcError::APP_ERROR, "Ratado is shutting down"))
+        })
+    } else {
+        Err(RpcError::new(RpcError::APP_ERROR, "Ratado is shutting down"))
+    };
+    request.id.map(|id| response(id, result))
+}
+
+/// Formats a response object.
+fn response(id: Value, result: Result<Value, RpcError>) -> String {
+    let body = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    };
+    body.to_string()
+}
+
+#[cfg(unix)]
+mod socket {
+    use std::io;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::{Path, PathBuf};
+
+    use serde_json::{json, Value};
+    use thiserror::Error;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{UnixListener, UnixStream};
+    use tokio::sync::mpsc;
+    use tokio::task::JoinHandle;
+
+    use super::{handle_line, RpcError};
+    use crate::handlers::AppEvent;
+
+    /// Returns the control socket path for a database (`ratado.db` → `ratado.sock`).
+    ///
+    /// Plaintext stores keep theirs in the state directory, out of git's way.
+    pub fn socket_path(db_path: &Path) -> PathBuf {
+        if db_path.is_dir() {
+            return db_path.join(crate::storage::STATE_DIR).join("ratado.sock");
+        }
+        db_path.with_extension("sock")
+    }
+
+    /// A listening control socket. The socket file is removed on drop.
+    #[derive(Debug)]
+    pub struct ControlSocket {
+        path: PathBuf,
+        task: JoinHandle<()>,
+    }
+
+    impl ControlSocket {
+        /// Starts listening on `path`, forwarding calls to `events`.
+        ///
+        /// A leftover socket file from a crashed instance is replaced. The
+        /// socket is only accessible to the current user.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`io::ErrorKind::AddrInUse`] if another instance is
+        /// listening on `path`, or the error from binding the socket.
+        pub fn bind(path: &Path, events: mpsc::UnboundedSender<AppEvent>) -> io::Result<Self> {
+            if path.exists() {
+                if std::os::unix::net::UnixStream::connect(path).is_ok() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::AddrInUse,
+                        format!("Another Ratado instance is listening on {}", path.display()),
+                    ));
+                }
+                std::fs::remove_file(path)?;
+            }
+
+            let listener = UnixListener::bind(path)?;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+            let task = tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(serve_connection(stream, events.clone()));
+                }
+            });
+            Ok(Self {
+                path: path.to_path_buf(),
+                task,
+            })
+        }
+
+        /// Returns the socket path.
+        pub fn path(&self) -> &Path {
+            &self.path
+        }
+    }
+
+    impl Drop for ControlSocket {
+        fn drop(&mut self) {
+            self.task.abort();
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+
+    /// Answers requests on one connection until the client disconnects.
+    async fn serve_connection(stream: UnixStream, events: mpsc::UnboundedSender<AppEvent>) {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = handle_line(&line, &events).await
+                && writer.write_all(format!("{}\n", response).as_bytes()).await.is_err()
+            {
+                break;
+            }
+        }
+    }
+
+    /// Errors from calling a running instance.
+    #[derive(Debug, Error)]
+    pub enum ClientError {
+        /// No instance is listening on the socket
+        #[error("Ratado is not running (no control socket at {0})")]
+        NotRunning(PathBuf),
+
+        /// Reading from or writing to the socket failed
+        #[error("Control socket error: {0}")]
+        Io(#[from] io::Error),
+
+        /// The instance sent something that isn't a JSON-RPC response
+        #[error("Invalid response: {0}")]
+        Response(String),
+
+        /// The instance rejected the call
+        #[error(transparent)]
+        Rpc(#[from] RpcError),
+    }
+
+    /// Calls `method` on the instance listening on `path` and returns its result.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::NotRunning`] if nothing is listening, or the
+    /// error the instance responded with.
+    pub async fn call(path: &Path, method: &str, params: Value) -> Result<Value, ClientError> {
+        let stream = UnixStream::connect(path).await.map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused => {
+                ClientError::NotRunning(path.to_path_buf())
+            }
+            _ => ClientError::Io(e),
+        })?;
+        let (reader, mut writer) = stream.into_split();
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        writer.write_all(format!("{}\n", request).as_bytes()).await?;
+
+        let line = BufReader::new(reader)
+            .lines()
+            .next_line()
+            .await?
+            .ok_or_else(|| ClientError::Response("connection closed".to_string()))?;
+        let mut response: Value =
+            serde_json::from_str(&line).map_err(|e| ClientError::Response(e.to_string()))?;
+        if let Some(error) = response.get("error") {
+            let error: RpcError = serde_json::from_value(error.clone())
+                .map_err(|e| ClientError::Response(e.to_string()))?;
+            return Err(error.into());
+        }
+        response
+            .get_mut("result")
+            .map(Value::take)
+            .ok_or_else(|| ClientError::Response(line.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Filter, Task};
+    use crate::storage::{run_migrations, Database};
+
+    async fn setup_app() -> App {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        App::new(db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_add_task_returns_task() {
+        let mut app = setup_app().await;
+
+        let tasks = dispatch(&mut app, "add_task", &json!({ "text": "Buy milk #errands" }))
+            .await
+            .unwrap();
+
+        assert_eq!(tasks.as_array().unwrap().len(), 1);
+        assert_eq!(tasks[0]["title"], "Buy milk");
+        assert_eq!(tasks[0]["tags"], json!(["errands"]));
+        assert_eq!(app.tasks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_add_task_returns_template_tasks() {
+        use crate::templates::{Template, Templates};
+
+        let mut app = setup_app().await;
+        let template =
+            Template::parse("release", "title = \"Release {input}\"\n[[tasks]]\ntitle = \"Announce {input}\"")
+                .unwrap();
+        app.templates = Templates::from_templates(vec![template]);
+        app.db.add_task(Task::new("Existing")).await.unwrap();
+        app.load_data().await.unwrap();
+
+        let tasks = dispatch(&mut app, "add_task", &json!({ "text": "tpl:release v1.4" }))
+            .await
+            .unwrap();
+
+        let mut titles: Vec<_> = tasks
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["title"].as_str().unwrap())
+            .collect();
+        titles.sort();
+        assert_eq!(titles, ["Announce v1.4", "Release v1.4"]);
+    }
+
+    #[tokio::test]
+    async fn test_api_request_updates_app() {
+        let mut app = setup_app().await;
+
+        let response = dispatch(
+            &mut app,
+            api::FORWARD_METHOD,
+            &json!({ "method": "POST", "uri": "/tasks", "body": r#"{"title": "From API"}"# }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response["status"], 201);
+        let task: Value = serde_json::from_str(response["body"].as_str().unwrap()).unwrap();
+        assert_eq!(task["title"], "From API");
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(app.tasks[0].id, task["id"]);
+    }
+
+    #[tokio::test]
+    async fn test_mcp_tool_call_updates_app() {
+        let mut app = setup_app().await;
+
+        let result = dispatch(
+            &mut app,
+            mcp::FORWARD_METHOD,
+            &json!({ "name": "create_task", "arguments": { "text": "From MCP #ai" } }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result["isError"], false);
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(app.tasks[0].title, "From MCP");
+        assert!(app.tags.iter().any(|t| t.name == "ai"));
+
+        let err = dispatch(&mut app, mcp::FORWARD_METHOD, &json!({ "name": "bogus" }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, RpcError::INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_params() {
+        let mut app = setup_app().await;
+
+        let err = dispatch(&mut app, "add_task", &json!({})).await.unwrap_err();
+        assert_eq!(err.code, RpcError::INVALID_PARAMS);
+        let err = dispatch(&mut app, "add_task", &json!({ "text": "#only-a-tag" }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, RpcError::INVALID_PARAMS);
+        let err = dispatch(&mut app, "focus_task", &json!({ "id": "missing" }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, RpcError::INVALID_PARAMS);
+        let err = dispatch(&mut app, "set_filter", &json!({ "filter": "bogus" }))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, RpcError::INVALID_PARAMS);
+
+        let err = dispatch(&mut app, "quit", &Value::Null).await.unwrap_err();
+        assert_eq!(err.code, RpcError::METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_switch_view_and_filter() {
+        let mut app = setup_app().await;
+        assert_eq!(app.current_view, View::Splash);
+
+        let state = dispatch(&mut app, "switch_view", &json!({ "view": "calendar" }))
+            .await
+            .unwrap();
+        assert_eq!(state["view"], "calendar");
+        assert_eq!(app.current_view, View::Calendar);
+
+        dispatch(&mut app, "set_filter", &json!({ "filter": "completed" }))
+            .await
+            .unwrap();
+        assert_eq!(app.filter, Filter::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_focus_task() {
+        let mut app = setup_app().await;
+        let task = Task::new("Find me");
+        app.db.insert_task(&task).await.unwrap();
+        app.load_data().await.unwrap();
+
+        let state = dispatch(&mut app, "focus_task", &json!({ "id": task.id }))
+            .await
+            .unwrap();
+
+        assert_eq!(state["view"], "main");
+        assert_eq!(state["selected_task"]["id"], task.id);
+    }
+
+    #[tokio::test]
+    async fn test_handle_line_errors_and_notifications() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let parse = handle_line("{not json", &tx).await.unwrap();
+        assert!(parse.contains("-32700"));
+        let invalid = handle_line(r#"{"jsonrpc":"1.0","id":1,"method":"state"}"#, &tx)
+            .await
+            .unwrap();
+        assert!(invalid.contains("-32600"));
+
+        // Notifications are executed but not answered
+        let app_side = async {
+            let Some(AppEvent::Remote(call)) = rx.recv().await else {
+                panic!("expected a remote call");
+            };
+            assert_eq!(call.method, "refresh");
+            call.respond(Ok(Value::Null));
+        };
+        let (response, ()) = tokio::join!(
+            handle_line(r#"{"jsonrpc":"2.0","method":"refresh"}"#, &tx),
+            app_side
+        );
+        assert!(response.is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_socket_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = socket_path(&dir.path().join("ratado.db"));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let socket = ControlSocket::bind(&path, tx.clone()).unwrap();
+
+        // A second instance can't take over the socket
+        let err = ControlSocket::bind(&path, tx).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AddrInUse);
+
+        let mut app = setup_app().await;
+        let app_side = async {
+            let Some(AppEvent::Remote(call)) = rx.recv().await else {
+                panic!("expected a remote call");
+            };
+            let result = dispatch(&mut app, &call.method, &call.params).await;
+            call.respond(result);
+        };
+        let (result, ()) = tokio::join!(
+            call(&path, "add_task", json!({ "text": "From the socket" })),
+            app_side
+        );
+        assert_eq!(result.unwrap()[0]["title"], "From the socket");
+
+        drop(socket);
+        assert!(!path.exists());
+        assert!(matches!(
+            call(&path, "state", Value::Null).await,
+            Err(ClientError::NotRunning(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_replaces_stale_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ratado.sock");
+        // Left behind by a crashed instance: the file exists but nobody listens
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        assert!(ControlSocket::bind(&path, tx).is_ok());
+    }
+}
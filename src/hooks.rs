@@ -0,0 +1,374 @@
+//! Lifecycle hook scripts.
+//!
+//! Executables in the `hooks` directory next to `config.toml`
+//! (`~/.config/ratado/hooks/` on Linux) run whenever a task is added,
+//! modified, completed or deleted, whether from the TUI, the CLI, the REST
+//! API or the MCP server. As in Taskwarrior, hooks are picked by name prefix,
+//! so `on-add` and `on-add-slack.py` both run on add, in name order.
+//!
+//! | Hook          | stdin (one JSON task per line)       | stdout                       |
+//! |---------------|--------------------------------------|------------------------------|
+//! | `on-add`      | the new task                         | the task, optionally changed |
+//! | `on-modify`   | the task before, then after the edit | the task, optionally changed |
+//! | `on-complete` | the task before, then completed      | the task, optionally changed |
+//! | `on-delete`   | the task                             | ignored                      |
+//!
+//! Tasks use the same JSON shape as the REST API. A hook that prints no
+//! task leaves it unchanged; one that prints a task replaces it (the ID can't
+//! change). Exiting with a non-zero status rejects the change, and the first
+//! other line the hook printed is shown as the reason.
+//!
+//! Hooks run only for changes the user makes, through
+//! [`Database::add_task`](crate::storage::Database::add_task),
+//! [`modify_task`](crate::storage::Database::modify_task),
+//! [`remove_task`](crate::storage::Database::remove_task) and their bulk
+//! versions; synced changes and a database reset don't run them. The TUI
+//! runs them in the background, so it stays responsive while they work.
+//!
+//! Hooks get `RATADO_HOOK` set to the hook name and are killed if they run
+//! longer than [`HOOK_TIMEOUT`].
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use directories::ProjectDirs;
+use log::{debug, info};
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::models::{Task, TaskStatus};
+
+/// How long a hook may run before it is killed and the change rejected.
+pub const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Name of the hooks directory inside the config directory.
+const HOOKS_DIR: &str = "hooks";
+
+/// Errors from running hooks. All of them stop the change.
+#[derive(Error, Debug)]
+pub enum HookError {
+    /// The hook exited with a non-zero status
+    #[error("{hook}: {message}")]
+    Rejected {
+        /// File name of the hook
+        hook: String,
+        /// Reason the hook gave
+        message: String,
+    },
+
+    /// The hook couldn't be started or its output read
+    #[error("Hook {hook} failed: {source}")]
+    Run {
+        /// File name of the hook
+        hook: String,
+        /// Underlying I/O error
+        source: io::Error,
+    },
+
+    /// The hook didn't finish in time
+    #[error("Hook {0} timed out")]
+    Timeout(String),
+
+    /// The hook printed something that isn't a valid task
+    #[error("Hook {hook} printed an invalid task: {message}")]
+    InvalidOutput {
+        /// File name of the hook
+        hook: String,
+        /// What is wrong with the output
+        message: String,
+    },
+}
+
+/// The points in a task's life where hooks run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// A task is created
+    Add,
+    /// A task is changed (other than being completed)
+    Modify,
+    /// A task is marked completed
+    Complete,
+    /// A task is deleted
+    Delete,
+}
+
+impl HookEvent {
+    /// The hook name, which hook file names start with.
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::Add => "on-add",
+            HookEvent::Modify => "on-modify",
+            HookEvent::Complete => "on-complete",
+            HookEvent::Delete => "on-delete",
+        }
+    }
+
+    /// Event for saving `task` over `original`.
+    pub fn for_change(original: &Task, task: &Task) -> Self {
+        if task.status == TaskStatus::Completed && original.status != TaskStatus::Completed {
+            HookEvent::Complete
+        } else {
+            HookEvent::Modify
+        }
+    }
+
+    const ALL: [HookEvent; 4] = [
+        HookEvent::Add,
+        HookEvent::Modify,
+        HookEvent::Complete,
+        HookEvent::Delete,
+    ];
+}
+
+/// The installed hook scripts.
+#[derive(Debug, Clone, Default)]
+pub struct Hooks {
+    /// Scripts with the event they handle, in the order they run
+    scripts: Vec<(HookEvent, PathBuf)>,
+}
+
+impl Hooks {
+    /// Returns the default hooks directory.
+    pub fn default_dir() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "ratado").map(|dirs| dirs.config_dir().join(HOOKS_DIR))
+    }
+
+    /// Finds the hook scripts in `dir`. A missing directory has no hooks.
+    ///
+    /// Files that aren't executable are skipped, so a hook can be disabled
+    /// with `chmod -x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory exists but can't be read.
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| is_executable(path))
+            .collect();
+        paths.sort();
+
+        let mut scripts = Vec::new();
+        for path in paths {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            if let Some(event) = HookEvent::ALL.into_iter().find(|e| name.starts_with(e.name())) {
+                debug!("Found {} hook {:?}", event.name(), path);
+                scripts.push((event, path));
+            }
+        }
+        Ok(Self { scripts })
+    }
+
+    /// Returns whether there are no hooks at all.
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    /// Returns whether any hook handles `event`.
+    pub fn handles(&self, event: HookEvent) -> bool {
+        self.scripts.iter().any(|(e, _)| *e == event)
+    }
+
+    /// Runs the hooks for `event` on `task`, each receiving the previous
+    /// one's output, and returns the task to store.
+    ///
+    /// `original` is the stored task, for modify and complete events.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a hook rejects the change, fails, or prints an
+    /// invalid task.
+    pub async fn run(
+        &self,
+        event: HookEvent,
+        original: Option<&Task>,
+        mut task: Task,
+    ) -> Result<Task, HookError> {
+        for (_, path) in self.scripts.iter().filter(|(e, _)| *e == event) {
+            task = run_script(path, event, original, task).await?;
+        }
+        Ok(task)
+    }
+}
+
+/// Runs one hook script.
+async fn run_script(
+    path: &Path,
+    event: HookEvent,
+    original: Option<&Task>,
+    task: Task,
+) -> Result<Task, HookError> {
+    let hook = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let run_error = |source| HookError::Run {
+        hook: hook.clone(),
+        source,
+    };
+
+    let mut input = String::new();
+    for t in original.into_iter().chain([&task]) {
+        input.push_str(&serde_json::to_string(t).map_err(|e| run_error(e.into()))?);
+        input.push('\n');
+    }
+
+    let mut child = Command::new(path)
+        .env("RATADO_HOOK", event.name())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(run_error)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook may exit without reading its input
+        let _ = stdin.write_all(input.as_bytes()).await;
+    }
+    let output = tokio::time::timeout(HOOK_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| HookError::Timeout(hook.clone()))?
+        .map_err(run_error)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let (json, feedback): (Vec<&str>, Vec<&str>) = stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .partition(|line| line.starts_with('{'));
+    let message = feedback
+        .first()
+        .copied()
+        .or_else(|| stderr.lines().map(str::trim).find(|l| !l.is_empty()))
+        .map(str::to_string);
+
+    if !output.status.success() {
+        return Err(HookError::Rejected {
+            message: message.unwrap_or_else(|| format!("rejected ({})", output.status)),
+            hook,
+        });
+    }
+    if let Some(message) = message {
+        info!("Hook {}: {}", hook, message);
+    }
+
+    match json.last() {
+        Some(line) if event != HookEvent::Delete => {
+            let changed: Task = serde_json::from_str(line).map_err(|e| HookError::InvalidOutput {
+                hook: hook.clone(),
+                message: e.to_string(),
+            })?;
+            if changed.id != task.id {
+                return Err(HookError::InvalidOutput {
+                    hook,
+                    message: "the task ID can't be changed".to_string(),
+                });
+            }
+            Ok(changed)
+        }
+        _ => Ok(task),
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// Writes an executable shell script into `dir`.
+    fn write_hook(dir: &Path, name: &str, body: &str) {
+        let path = dir.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_load_matches_prefixes_and_skips_non_executables() {
+        let dir = tempfile::tempdir().unwrap();
+        write_hook(dir.path(), "on-add", "cat");
+        write_hook(dir.path(), "on-modify-slack", "cat");
+        write_hook(dir.path(), "README", "");
+        std::fs::write(dir.path().join("on-delete"), "disabled").unwrap();
+
+        let hooks = Hooks::load(dir.path()).unwrap();
+        assert!(hooks.handles(HookEvent::Add));
+        assert!(hooks.handles(HookEvent::Modify));
+        assert!(!hooks.handles(HookEvent::Delete));
+        assert!(!hooks.handles(HookEvent::Complete));
+
+        let missing = Hooks::load(&dir.path().join("missing")).unwrap();
+        assert!(!missing.handles(HookEvent::Add));
+    }
+
+    #[tokio::test]
+    async fn test_hooks_chain_and_can_change_tasks() {
+        let dir = tempfile::tempdir().unwrap();
+        write_hook(dir.path(), "on-add-1", r#"sed 's/"tags":\[\]/"tags":["auto"]/'"#);
+        write_hook(dir.path(), "on-add-2", r#"sed 's/"title":"[^"]*"/"title":"Renamed"/'; echo added"#);
+        let hooks = Hooks::load(dir.path()).unwrap();
+
+        let task = hooks.run(HookEvent::Add, None, Task::new("Original")).await.unwrap();
+        assert_eq!(task.title, "Renamed");
+        assert_eq!(task.tags, vec!["auto".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_silent_hook_keeps_task() {
+        let dir = tempfile::tempdir().unwrap();
+        write_hook(dir.path(), "on-modify", "test \"$RATADO_HOOK\" = on-modify");
+        let hooks = Hooks::load(dir.path()).unwrap();
+
+        let original = Task::new("Before");
+        let mut changed = original.clone();
+        changed.title = "After".to_string();
+        let task = hooks
+            .run(HookEvent::Modify, Some(&original), changed.clone())
+            .await
+            .unwrap();
+        assert_eq!(task, changed);
+    }
+
+    #[tokio::test]
+    async fn test_rejection_message() {
+        let dir = tempfile::tempdir().unwrap();
+        write_hook(dir.path(), "on-add", "echo 'Title needs a ticket'; exit 1");
+        write_hook(dir.path(), "on-delete", "echo 'Deleting is off' >&2; exit 3");
+        let hooks = Hooks::load(dir.path()).unwrap();
+
+        let err = hooks.run(HookEvent::Add, None, Task::new("Nope")).await.unwrap_err();
+        assert_eq!(err.to_string(), "on-add: Title needs a ticket");
+        let err = hooks.run(HookEvent::Delete, None, Task::new("Nope")).await.unwrap_err();
+        assert_eq!(err.to_string(), "on-delete: Deleting is off");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_changed_id() {
+        let dir = tempfile::tempdir().unwrap();
+        write_hook(dir.path(), "on-add", r#"sed 's/"id":"[^"]*"/"id":"other"/'"#);
+        let hooks = Hooks::load(dir.path()).unwrap();
+
+        let err = hooks.run(HookEvent::Add, None, Task::new("Task")).await.unwrap_err();
+        assert!(matches!(err, HookError::InvalidOutput { .. }));
+    }
+}
@@ -0,0 +1,265 @@
+//! Import and export of tasks in external formats.
+//!
+//! This module converts between Ratado's [`Task`] model and the file formats
+//! used by other task managers. Each format lives in its own submodule and
+//! exposes a pure `parse` / `format` pair; writing the parsed tasks to the
+//! database is shared and handled by [`import_tasks`].
+//!
+//! ## Submodules
+//!
+//! - [`todotxt`] - The plain-text [todo.txt](https://github.com/todotxt/todo.txt) format
+//! - [`taskwarrior`] - Taskwarrior's `task export` / `task import` JSON
+//! - [`report`] - Export-only Markdown checklists and CSV
+//! - [`standup`] - Export-only standup summaries
+//!
+//! ## Examples
+//!
+//! ```rust,no_run
+//! use ratado::interop::{import_tasks, todotxt};
+//! use ratado::storage::Database;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let db = Database::open_in_memory().await?;
+//! let tasks = todotxt::parse("(A) Call mom +Family @phone due:2026-03-01");
+//! let summary = import_tasks(&db, tasks).await?;
+//! assert_eq!(summary.created, 1);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod report;
+pub mod standup;
+pub mod taskwarrior;
+pub mod todotxt;
+
+use log::warn;
+
+use crate::models::{Note, Project, Task};
+use crate::storage::{Database, Result, StorageError};
+
+/// A task parsed from an external format, before it is stored.
+///
+/// External formats reference projects by name rather than by ID, so the
+/// project is kept separately and resolved (or created) at import time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedTask {
+    /// The parsed task. Its `project_id` is ignored on import.
+    pub task: Task,
+    /// Name of the project the task belongs to, if any
+    pub project: Option<String>,
+}
+
+/// Counts of what an import changed in the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Tasks that did not exist before and were inserted
+    pub created: usize,
+    /// Tasks that already existed (same ID) and were overwritten
+    pub updated: usize,
+    /// Projects that were created because no project matched by name
+    pub projects_created: usize,
+    /// Tasks a hook rejected, which were skipped
+    pub rejected: usize,
+}
+
+/// Stores imported tasks, creating any projects they reference.
+///
+/// Projects are matched by name, case-insensitively. A task whose ID already
+/// exists in the database is updated in place, so re-importing a file that
+/// carries stable IDs does not create duplicates. Its stored notes are kept,
+/// and imported notes it already has aren't added again.
+///
+/// Each task runs the [hooks](crate::hooks) for its change; tasks a hook
+/// rejects are skipped and counted.
+///
+/// # Errors
+///
+/// Returns an error if any database operation fails. Tasks stored before the
+/// failure are kept.
+pub async fn import_tasks(db: &Database, items: Vec<ImportedTask>) -> Result<ImportSummary> {
+    let mut projects = db.get_all_projects().await?;
+    let mut summary = ImportSummary::default();
+
+    for item in items {
+        let mut task = item.task;
+        task.project_id = match item.project {
+            Some(name) => Some(resolve_project(db, &mut projects, &name, &mut summary).await?),
+            None => None,
+        };
+
+        let title = task.title.clone();
+        let (result, count) = if let Some(existing) = db.get_task(&task.id).await? {
+            // Re-importing doesn't undo the task's place in the manual order
+            task.position = existing.position;
+            task.notes = merge_notes(existing.notes, task.notes);
+            (db.modify_task(task).await, &mut summary.updated)
+        } else {
+            (db.add_task(task).await, &mut summary.created)
+        };
+        match result {
+            Ok(_) => *count += 1,
+            Err(StorageError::Hook(e)) => {
+                warn!("Skipped importing {:?}: {}", title, e);
+                summary.rejected += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Adds the `imported` notes that aren't among the `stored` ones.
+///
+/// Imported notes get new IDs, so they are matched by time and text, as
+/// far as formats like Taskwarrior's keep them: to the second, on one line.
+fn merge_notes(stored: Vec<Note>, imported: Vec<Note>) -> Vec<Note> {
+    let mut notes = stored;
+    for note in imported {
+        let known = notes.iter().any(|n| {
+            n.created_at.timestamp() == note.created_at.timestamp() && one_line(&n.text) == one_line(&note.text)
+        });
+        if !known {
+            notes.push(note);
+        }
+    }
+    notes.sort_by_key(|note| note.created_at);
+    notes
+}
+
+/// Joins the non-blank lines of `text` with spaces.
+pub(crate) fn one_line(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Finds a project by name, creating it if it doesn't exist yet.
+///
+/// Returns the project ID. Newly created projects are appended to `projects`
+/// so later tasks in the same import reuse them.
+async fn resolve_project(
+    db: &Database,
+    projects: &mut Vec<Project>,
+    name: &str,
+    summary: &mut ImportSummary,
+) -> Result<String> {
+    if let Some(project) = find_project(projects, name) {
+        return Ok(project.id.clone());
+    }
+
+    let project = Project::new(name);
+    db.insert_project(&project).await?;
+    summary.projects_created += 1;
+    let id = project.id.clone();
+    projects.push(project);
+    Ok(id)
+}
+
+/// Looks up a project by name, ignoring case.
+///
+/// Underscores in `name` also match spaces, since formats like todo.txt
+/// can't represent whitespace inside a project name.
+pub fn find_project<'a>(projects: &'a [Project], name: &str) -> Option<&'a Project> {
+    let lower = name.to_lowercase();
+    let spaced = lower.replace('_', " ");
+    projects.iter().find(|p| {
+        let candidate = p.name.to_lowercase();
+        candidate == lower || candidate == spaced
+    })
+}
+
+/// Returns the name of the task's project, if it has one.
+pub fn project_name<'a>(task: &Task, projects: &'a [Project]) -> Option<&'a str> {
+    let id = task.project_id.as_deref()?;
+    projects.iter().find(|p| p.id == id).map(|p| p.name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::run_migrations;
+
+    async fn setup_db() -> Database {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db
+    }
+
+    #[test]
+    fn test_find_project_matches_case_and_underscores() {
+        let projects = vec![Project::new("Home Renovation"), Project::new("Work")];
+        assert_eq!(find_project(&projects, "work").unwrap().name, "Work");
+        assert_eq!(
+            find_project(&projects, "home_renovation").unwrap().name,
+            "Home Renovation"
+        );
+        assert!(find_project(&projects, "Garden").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_import_creates_missing_projects_once() {
+        let db = setup_db().await;
+        let items = vec![
+            ImportedTask {
+                task: Task::new("First"),
+                project: Some("Garden".to_string()),
+            },
+            ImportedTask {
+                task: Task::new("Second"),
+                project: Some("garden".to_string()),
+            },
+            ImportedTask {
+                task: Task::new("Third"),
+                project: Some("Inbox".to_string()),
+            },
+        ];
+
+        let summary = import_tasks(&db, items).await.unwrap();
+        assert_eq!(summary.created, 3);
+        assert_eq!(summary.projects_created, 1);
+
+        let tasks = db.get_all_tasks().await.unwrap();
+        let inbox_task = tasks.iter().find(|t| t.title == "Third").unwrap();
+        assert_eq!(inbox_task.project_id.as_deref(), Some("inbox"));
+    }
+
+    #[tokio::test]
+    async fn test_import_updates_existing_ids() {
+        let db = setup_db().await;
+        let mut task = Task::new("Original");
+        db.insert_task(&task).await.unwrap();
+
+        task.title = "Renamed".to_string();
+        let summary = import_tasks(&db, vec![ImportedTask { task: task.clone(), project: None }])
+            .await
+            .unwrap();
+
+        assert_eq!(summary.updated, 1);
+        assert_eq!(summary.created, 0);
+        assert_eq!(db.get_task(&task.id).await.unwrap().unwrap().title, "Renamed");
+    }
+
+    #[tokio::test]
+    async fn test_reimport_adds_only_new_notes() {
+        let db = setup_db().await;
+        let mut task = Task::new("Renew passport");
+        task.add_note("Photos at the pharmacy\nthe one on Main St");
+        db.insert_task(&task).await.unwrap();
+
+        // As read back from a Taskwarrior export: new IDs, whole seconds, one line
+        let exported = taskwarrior::format(&[&task], &[]);
+        let mut items = taskwarrior::parse(&exported).unwrap();
+        let mut later = Note::new("Bring old passport");
+        later.created_at = task.notes[0].created_at + chrono::Duration::hours(1);
+        items[0].task.notes.push(later);
+        import_tasks(&db, items.clone()).await.unwrap();
+        import_tasks(&db, items).await.unwrap();
+
+        let notes = db.get_task(&task.id).await.unwrap().unwrap().notes;
+        let texts: Vec<&str> = notes.iter().map(|n| n.text.as_str()).collect();
+        assert_eq!(texts, ["Photos at the pharmacy\nthe one on Main St", "Bring old passport"]);
+    }
+}
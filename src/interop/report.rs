@@ -0,0 +1,860 @@
+//! Markdown and CSV reports.
+//!
+//! Unlike the other formats in this module, reports are one-way: they are
+//! meant for pasting into pull requests and wikis (Markdown checklists) or
+//! opening in a spreadsheet (CSV), not for importing back.
+//!
+//! A report is built from an already filtered and sorted task list, so it
+//! shows exactly what the task list shows. [`ReportOptions`] selects which
+//! columns to include and how to group rows.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, SecondsFormat, Utc};
+
+use super::{find_project, project_name};
+use crate::models::{Filter, Priority, Project, SortOrder, Task, TaskStatus};
+
+/// Output format of a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// Markdown checklist
+    #[default]
+    Markdown,
+    /// Comma-separated values (RFC 4180)
+    Csv,
+}
+
+impl ReportFormat {
+    /// Returns the conventional file extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// A task field that can be included in a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportColumn {
+    Id,
+    Title,
+    Status,
+    Priority,
+    Project,
+    Tags,
+    Due,
+    Created,
+    Completed,
+    Description,
+    Notes,
+}
+
+impl ReportColumn {
+    /// Every column, in the order they are usually listed.
+    pub const ALL: &'static [ReportColumn] = &[
+        ReportColumn::Id,
+        ReportColumn::Title,
+        ReportColumn::Status,
+        ReportColumn::Priority,
+        ReportColumn::Project,
+        ReportColumn::Tags,
+        ReportColumn::Due,
+        ReportColumn::Created,
+        ReportColumn::Completed,
+        ReportColumn::Description,
+        ReportColumn::Notes,
+    ];
+
+    /// Columns used when none are specified.
+    pub const DEFAULT: &'static [ReportColumn] = &[
+        ReportColumn::Title,
+        ReportColumn::Status,
+        ReportColumn::Priority,
+        ReportColumn::Project,
+        ReportColumn::Tags,
+        ReportColumn::Due,
+    ];
+
+    /// Returns the column's name, as used on the command line and in CSV headers.
+    pub fn name(self) -> &'static str {
+        match self {
+            ReportColumn::Id => "id",
+            ReportColumn::Title => "title",
+            ReportColumn::Status => "status",
+            ReportColumn::Priority => "priority",
+            ReportColumn::Project => "project",
+            ReportColumn::Tags => "tags",
+            ReportColumn::Due => "due",
+            ReportColumn::Created => "created",
+            ReportColumn::Completed => "completed",
+            ReportColumn::Description => "description",
+            ReportColumn::Notes => "notes",
+        }
+    }
+}
+
+impl FromStr for ReportColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        ReportColumn::ALL
+            .iter()
+            .copied()
+            .find(|c| c.name() == lower)
+            .ok_or_else(|| {
+                let names: Vec<_> = ReportColumn::ALL.iter().map(|c| c.name()).collect();
+                format!("unknown column '{}' (expected one of: {})", s, names.join(", "))
+            })
+    }
+}
+
+/// How report rows are grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportGroup {
+    /// A single flat list
+    #[default]
+    None,
+    /// One group per project
+    Project,
+    /// One group per tag; tasks with several tags appear in each
+    Tag,
+    /// One group per status
+    Status,
+    /// One group per calendar week of the due date
+    DueWeek,
+}
+
+impl ReportGroup {
+    /// Every grouping, in display order.
+    pub const ALL: &'static [ReportGroup] = &[
+        ReportGroup::None,
+        ReportGroup::Project,
+        ReportGroup::Tag,
+        ReportGroup::Status,
+        ReportGroup::DueWeek,
+    ];
+
+    /// Returns the grouping's name, as used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            ReportGroup::None => "none",
+            ReportGroup::Project => "project",
+            ReportGroup::Tag => "tag",
+            ReportGroup::Status => "status",
+            ReportGroup::DueWeek => "due-week",
+        }
+    }
+}
+
+impl FromStr for ReportGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        ReportGroup::ALL
+            .iter()
+            .copied()
+            .find(|g| g.name() == lower)
+            .ok_or_else(|| {
+                let names: Vec<_> = ReportGroup::ALL.iter().map(|g| g.name()).collect();
+                format!("unknown grouping '{}' (expected one of: {})", s, names.join(", "))
+            })
+    }
+}
+
+impl fmt::Display for ReportGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Column and grouping choices for a report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportOptions {
+    /// Columns to include, in order
+    pub columns: Vec<ReportColumn>,
+    /// How to group rows
+    pub group_by: ReportGroup,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            columns: ReportColumn::DEFAULT.to_vec(),
+            group_by: ReportGroup::None,
+        }
+    }
+}
+
+/// Renders a report in the given format.
+///
+/// `tasks` should already be filtered and sorted; the order is kept within
+/// each group.
+pub fn render(
+    format: ReportFormat,
+    tasks: &[&Task],
+    projects: &[Project],
+    options: &ReportOptions,
+) -> String {
+    match format {
+        ReportFormat::Markdown => to_markdown(tasks, projects, options),
+        ReportFormat::Csv => to_csv(tasks, projects, options),
+    }
+}
+
+/// Renders tasks as a Markdown checklist.
+///
+/// Each task becomes a `- [ ]` item (`- [x]` once completed) followed by the
+/// selected columns, with text escaped so it can't be read as Markdown. The
+/// description and notes, if selected, are indented under the item.
+/// Grouped reports get a `##` heading per group.
+///
+/// # Examples
+///
+/// ```
+/// use ratado::interop::report::{to_markdown, ReportColumn, ReportOptions};
+/// use ratado::models::Task;
+///
+/// let mut done = Task::new("Ship *it*");
+/// done.complete();
+/// let options = ReportOptions {
+///     columns: vec![ReportColumn::Title],
+///     ..Default::default()
+/// };
+/// assert_eq!(to_markdown(&[&done], &[], &options), "- [x] Ship \\*it\\*\n");
+/// ```
+pub fn to_markdown(tasks: &[&Task], projects: &[Project], options: &ReportOptions) -> String {
+    let mut output = String::new();
+
+    for (i, (label, group)) in group_tasks(tasks, projects, options.group_by).iter().enumerate() {
+        if let Some(label) = label {
+            if i > 0 {
+                output.push('\n');
+            }
+            output.push_str(&format!("## {} ({})\n\n", label, group.len()));
+        }
+
+        for task in group {
+            let checkbox = if is_done(task) { "[x]" } else { "[ ]" };
+            let fields: Vec<String> = options
+                .columns
+                .iter()
+                .filter_map(|column| markdown_field(task, *column, projects))
+                .collect();
+            if fields.is_empty() {
+                output.push_str(&format!("- {}\n", checkbox));
+            } else {
+                output.push_str(&format!("- {} {}\n", checkbox, fields.join(" · ")));
+            }
+
+            if options.columns.contains(&ReportColumn::Description)
+                && let Some(description) = task.description.as_deref()
+            {
+                for line in description.lines().filter(|l| !l.trim().is_empty()) {
+                    output.push_str(&format!("  > {}\n", line.trim_end()));
+                }
+            }
+            if options.columns.contains(&ReportColumn::Notes) {
+                for note in &task.notes {
+                    let mut lines = note.text.lines().filter(|l| !l.trim().is_empty());
+                    let first = lines.next().unwrap_or_default();
+                    output.push_str(&format!("  - _{}_ {}\n", local_datetime(note.created_at), first.trim_end()));
+                    for line in lines {
+                        output.push_str(&format!("    {}\n", line.trim_end()));
+                    }
+                }
+            }
+        }
+    }
+
+    output
+}
+
+/// Renders tasks as CSV with a header row.
+///
+/// Dates are ISO 8601 timestamps in UTC and tags are separated by `;`.
+/// Notes are one per line, each after its timestamp.
+/// Grouped reports get a leading `group` column, with rows ordered by group.
+/// Fields are quoted when needed and lines end in CRLF, per RFC 4180.
+/// Fields a spreadsheet would run as a formula get a leading `'`.
+pub fn to_csv(tasks: &[&Task], projects: &[Project], options: &ReportOptions) -> String {
+    let grouped = options.group_by != ReportGroup::None;
+    let mut output = String::new();
+
+    let mut header: Vec<&str> = Vec::new();
+    if grouped {
+        header.push("group");
+    }
+    header.extend(options.columns.iter().map(|c| c.name()));
+    push_csv_row(&mut output, header);
+
+    for (label, group) in group_tasks(tasks, projects, options.group_by) {
+        for task in group {
+            let mut row: Vec<String> = Vec::new();
+            if grouped {
+                row.push(label.clone().unwrap_or_default());
+            }
+            row.extend(
+                options
+                    .columns
+                    .iter()
+                    .map(|column| csv_field(task, *column, projects)),
+            );
+            push_csv_row(&mut output, row);
+        }
+    }
+
+    output
+}
+
+/// Parses a filter specification like `pending`, `project:Work` or `priority:high`.
+///
+/// Project names are resolved against `projects`, since [`Filter::ByProject`]
+/// holds a project ID.
+///
+/// # Errors
+///
+/// Returns a message describing the problem if the specification is not
+/// recognized or names an unknown project.
+pub fn parse_filter(spec: &str, projects: &[Project]) -> Result<Filter, String> {
+    let spec = spec.trim();
+    if let Some((kind, value)) = spec.split_once(':') {
+        return match kind.to_lowercase().as_str() {
+            "project" => find_project(projects, value)
+                .map(|p| Filter::ByProject(p.id.clone()))
+                .ok_or_else(|| format!("no project named '{}'", value)),
+            "tag" => Ok(Filter::ByTag(value.to_string())),
+            "priority" => match value.to_lowercase().as_str() {
+                "low" => Ok(Filter::ByPriority(Priority::Low)),
+                "medium" => Ok(Filter::ByPriority(Priority::Medium)),
+                "high" => Ok(Filter::ByPriority(Priority::High)),
+                "urgent" => Ok(Filter::ByPriority(Priority::Urgent)),
+                _ => Err(format!("unknown priority '{}'", value)),
+            },
+            _ => Err(format!("unknown filter '{}'", spec)),
+        };
+    }
+
+    match spec.to_lowercase().as_str() {
+        "all" => Ok(Filter::All),
+        "pending" => Ok(Filter::Pending),
+        "in-progress" => Ok(Filter::InProgress),
+        "completed" => Ok(Filter::Completed),
+        "archived" => Ok(Filter::Archived),
+        "today" => Ok(Filter::DueToday),
+        "week" => Ok(Filter::DueThisWeek),
+        "overdue" => Ok(Filter::Overdue),
+        _ => Err(format!(
+            "unknown filter '{}' (expected all, pending, in-progress, completed, archived, \
+             today, week, overdue, project:NAME, tag:NAME or priority:LEVEL)",
+            spec
+        )),
+    }
+}
+
+/// Parses a sort specification like `due`, `priority` or `alpha`.
+///
+/// # Errors
+///
+/// Returns a message listing the valid values if `spec` is not recognized.
+pub fn parse_sort(spec: &str) -> Result<SortOrder, String> {
+    match spec.trim().to_lowercase().as_str() {
+        "due" => Ok(SortOrder::DueDateAsc),
+        "due-desc" => Ok(SortOrder::DueDateDesc),
+        "priority" => Ok(SortOrder::PriorityDesc),
+        "priority-asc" => Ok(SortOrder::PriorityAsc),
+        "newest" => Ok(SortOrder::CreatedDesc),
+        "oldest" => Ok(SortOrder::CreatedAsc),
+        "alpha" => Ok(SortOrder::Alphabetical),
+        "manual" => Ok(SortOrder::Manual),
+        _ => Err(format!(
+            "unknown sort '{}' (expected due, due-desc, priority, priority-asc, newest, oldest, alpha or manual)",
+            spec
+        )),
+    }
+}
+
+/// Splits tasks into labelled groups, keeping their order within each group.
+///
+/// Returns a single unlabelled group when `group_by` is [`ReportGroup::None`].
+/// Tasks with no value for the grouping field are collected in a final group.
+fn group_tasks<'a>(
+    tasks: &[&'a Task],
+    projects: &[Project],
+    group_by: ReportGroup,
+) -> Vec<(Option<String>, Vec<&'a Task>)> {
+    let mut groups: Vec<(String, Vec<&'a Task>)> = Vec::new();
+    let mut ungrouped: Vec<&'a Task> = Vec::new();
+
+    fn add<'a>(groups: &mut Vec<(String, Vec<&'a Task>)>, label: String, task: &'a Task) {
+        match groups.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, list)) => list.push(task),
+            None => groups.push((label, vec![task])),
+        }
+    }
+
+    let none_label = match group_by {
+        ReportGroup::None => return vec![(None, tasks.to_vec())],
+        ReportGroup::Project => "No Project",
+        ReportGroup::Tag => "Untagged",
+        ReportGroup::Status => "",
+        ReportGroup::DueWeek => "No Due Date",
+    };
+
+    for &task in tasks {
+        match group_by {
+            ReportGroup::None => unreachable!(),
+            ReportGroup::Project => match project_name(task, projects) {
+                Some(name) => add(&mut groups, name.to_string(), task),
+                None => ungrouped.push(task),
+            },
+            ReportGroup::Tag if task.tags.is_empty() => ungrouped.push(task),
+            ReportGroup::Tag => {
+                for tag in &task.tags {
+                    add(&mut groups, format!("#{}", tag), task);
+                }
+            }
+            ReportGroup::Status => add(&mut groups, status_label(task.status).to_string(), task),
+            ReportGroup::DueWeek => match task.due_date {
+                Some(due) => add(&mut groups, format!("Week of {}", week_start(due)), task),
+                None => ungrouped.push(task),
+            },
+        }
+    }
+
+    // Order groups predictably regardless of the task sort
+    match group_by {
+        ReportGroup::Project => groups.sort_by_key(|(label, _)| {
+            projects
+                .iter()
+                .position(|p| p.name == *label)
+                .unwrap_or(usize::MAX)
+        }),
+        ReportGroup::Status => groups.sort_by_key(|(label, _)| {
+            [TaskStatus::Pending, TaskStatus::InProgress, TaskStatus::Completed, TaskStatus::Archived]
+                .iter()
+                .position(|s| status_label(*s) == label)
+        }),
+        // "#tag" and "Week of YYYY-MM-DD" both sort correctly as text
+        _ => groups.sort_by_key(|(label, _)| label.to_lowercase()),
+    }
+
+    let mut result: Vec<(Option<String>, Vec<&'a Task>)> =
+        groups.into_iter().map(|(label, list)| (Some(label), list)).collect();
+    if !ungrouped.is_empty() {
+        result.push((Some(none_label.to_string()), ungrouped));
+    }
+    result
+}
+
+/// Formats a column for a Markdown checklist item.
+///
+/// Returns `None` for empty values and for columns shown elsewhere (the
+/// description and notes go on their own lines).
+fn markdown_field(task: &Task, column: ReportColumn, projects: &[Project]) -> Option<String> {
+    match column {
+        ReportColumn::Description | ReportColumn::Notes => None,
+        ReportColumn::Title => Some(escape_markdown(&task.title)),
+        ReportColumn::Id => Some(format!("`{}`", task.id)),
+        ReportColumn::Status => match task.status {
+            // Already conveyed by the checkbox
+            TaskStatus::Pending | TaskStatus::Completed => None,
+            status => Some(format!("_{}_", status_label(status))),
+        },
+        ReportColumn::Priority => Some(format!("**{}**", priority_label(task.priority))),
+        ReportColumn::Project => project_name(task, projects).map(escape_markdown),
+        ReportColumn::Tags if task.tags.is_empty() => None,
+        ReportColumn::Tags => Some(
+            task.tags
+                .iter()
+                .map(|t| code_span(&format!("#{}", t)))
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        ReportColumn::Due => task.due_date.map(|d| format!("due {}", local_date(d))),
+        ReportColumn::Created => Some(format!("created {}", local_date(task.created_at))),
+        ReportColumn::Completed => task.completed_at.map(|d| format!("done {}", local_date(d))),
+    }
+}
+
+/// Formats a column as a raw CSV value.
+fn csv_field(task: &Task, column: ReportColumn, projects: &[Project]) -> String {
+    match column {
+        ReportColumn::Id => task.id.clone(),
+        ReportColumn::Title => task.title.clone(),
+        ReportColumn::Status => status_label(task.status).to_string(),
+        ReportColumn::Priority => priority_label(task.priority).to_string(),
+        ReportColumn::Project => project_name(task, projects).unwrap_or_default().to_string(),
+        ReportColumn::Tags => task.tags.join(";"),
+        ReportColumn::Due => task.due_date.map(iso_datetime).unwrap_or_default(),
+        ReportColumn::Created => iso_datetime(task.created_at),
+        ReportColumn::Completed => task.completed_at.map(iso_datetime).unwrap_or_default(),
+        ReportColumn::Description => task.description.clone().unwrap_or_default(),
+        ReportColumn::Notes => task
+            .notes
+            .iter()
+            .map(|note| format!("{} {}", iso_datetime(note.created_at), note.text))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+/// Backslash-escapes characters that Markdown would read as formatting or links.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '~' | '#' | '|') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Wraps text in a Markdown code span, fenced with more backticks than it
+/// contains in a row.
+fn code_span(text: &str) -> String {
+    let longest_run = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run + 1);
+    if longest_run > 0 {
+        // Spaces keep a leading or trailing backtick off the fence
+        format!("{} {} {}", fence, text, fence)
+    } else {
+        format!("{}{}{}", fence, text, fence)
+    }
+}
+
+/// Appends one CSV record, quoting fields that need it.
+fn push_csv_row<S: AsRef<str>>(output: &mut String, fields: impl IntoIterator<Item = S>) {
+    let escaped: Vec<String> = fields
+        .into_iter()
+        .map(|field| escape_csv(field.as_ref()))
+        .collect();
+    output.push_str(&escaped.join(","));
+    output.push_str("\r\n");
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote or line break.
+///
+/// Fields starting with a character spreadsheets treat as the start of a
+/// formula are prefixed with `'` so they're shown as text instead of run.
+fn escape_csv(field: &str) -> String {
+    let field = if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", field)
+    } else {
+        field.to_string()
+    };
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+fn is_done(task: &Task) -> bool {
+    matches!(task.status, TaskStatus::Completed | TaskStatus::Archived)
+}
+
+fn status_label(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Pending => "Pending",
+        TaskStatus::InProgress => "In Progress",
+        TaskStatus::Completed => "Completed",
+        TaskStatus::Archived => "Archived",
+    }
+}
+
+fn priority_label(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "Low",
+        Priority::Medium => "Medium",
+        Priority::High => "High",
+        Priority::Urgent => "Urgent",
+    }
+}
+
+fn local_date(date: DateTime<Utc>) -> String {
+    date.with_timezone(&Local).format("%Y-%m-%d").to_string()
+}
+
+fn local_datetime(date: DateTime<Utc>) -> String {
+    date.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+}
+
+fn iso_datetime(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Returns the Monday of the (local) week containing `date`.
+fn week_start(date: DateTime<Utc>) -> NaiveDate {
+    let local = date.with_timezone(&Local).date_naive();
+    local - Duration::days(local.weekday().num_days_from_monday() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    fn sample() -> (Vec<Task>, Vec<Project>) {
+        let work = Project::new("Work");
+        let home = Project::new("Home");
+
+        let mut notes = Task::new("Write notes");
+        notes.priority = Priority::High;
+        notes.project_id = Some(work.id.clone());
+        notes.tags = vec!["docs".to_string()];
+        notes.due_date = Some(Local.with_ymd_and_hms(2026, 2, 11, 23, 59, 59).unwrap().with_timezone(&Utc));
+
+        let mut passport = Task::new("Renew passport");
+        passport.project_id = Some(home.id.clone());
+        passport.complete();
+
+        let mut refactor = Task::new("Refactor, then \"test\"");
+        refactor.status = TaskStatus::InProgress;
+        refactor.tags = vec!["code".to_string(), "docs".to_string()];
+        refactor.description = Some("Line one\nLine two".to_string());
+
+        (vec![notes, passport, refactor], vec![work, home])
+    }
+
+    fn columns(names: &[ReportColumn]) -> ReportOptions {
+        ReportOptions {
+            columns: names.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_column_and_group_from_str() {
+        assert_eq!("Due".parse::<ReportColumn>(), Ok(ReportColumn::Due));
+        assert!("nope".parse::<ReportColumn>().is_err());
+        assert_eq!("due-week".parse::<ReportGroup>(), Ok(ReportGroup::DueWeek));
+        assert!("weekly".parse::<ReportGroup>().is_err());
+    }
+
+    #[test]
+    fn test_markdown_checklist() {
+        let (tasks, projects) = sample();
+        let refs: Vec<&Task> = tasks.iter().collect();
+        let options = columns(&[
+            ReportColumn::Title,
+            ReportColumn::Status,
+            ReportColumn::Priority,
+            ReportColumn::Project,
+            ReportColumn::Tags,
+            ReportColumn::Due,
+            ReportColumn::Description,
+        ]);
+
+        assert_eq!(
+            to_markdown(&refs, &projects, &options),
+            "- [ ] Write notes · **High** · Work · `#docs` · due 2026-02-11\n\
+             - [x] Renew passport · **Medium** · Home\n\
+             - [ ] Refactor, then \"test\" · _In Progress_ · **Medium** · `#code` `#docs`\n\
+             \x20 > Line one\n\
+             \x20 > Line two\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_escapes_title_and_respects_columns() {
+        let task = Task::new("Fix *bold* [link](x) and `code` in my_var");
+        let refs = vec![&task];
+
+        assert_eq!(
+            to_markdown(&refs, &[], &columns(&[ReportColumn::Title])),
+            "- [ ] Fix \\*bold\\* \\[link\\](x) and \\`code\\` in my\\_var\n"
+        );
+        assert_eq!(
+            to_markdown(&refs, &[], &columns(&[ReportColumn::Priority])),
+            "- [ ] **Medium**\n"
+        );
+        assert_eq!(to_markdown(&refs, &[], &columns(&[ReportColumn::Due])), "- [ ]\n");
+    }
+
+    #[test]
+    fn test_markdown_grouped_by_project() {
+        let (tasks, projects) = sample();
+        let refs: Vec<&Task> = tasks.iter().collect();
+        let options = ReportOptions {
+            columns: vec![ReportColumn::Title],
+            group_by: ReportGroup::Project,
+        };
+
+        assert_eq!(
+            to_markdown(&refs, &projects, &options),
+            "## Work (1)\n\n- [ ] Write notes\n\n\
+             ## Home (1)\n\n- [x] Renew passport\n\n\
+             ## No Project (1)\n\n- [ ] Refactor, then \"test\"\n"
+        );
+    }
+
+    #[test]
+    fn test_group_by_tag_repeats_tasks() {
+        let (tasks, projects) = sample();
+        let refs: Vec<&Task> = tasks.iter().collect();
+        let groups = group_tasks(&refs, &projects, ReportGroup::Tag);
+        let labels: Vec<_> = groups
+            .iter()
+            .map(|(l, g)| (l.clone().unwrap(), g.len()))
+            .collect();
+
+        assert_eq!(
+            labels,
+            vec![
+                ("#code".to_string(), 1),
+                ("#docs".to_string(), 2),
+                ("Untagged".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_by_status_and_due_week() {
+        let (tasks, projects) = sample();
+        let refs: Vec<&Task> = tasks.iter().collect();
+
+        let statuses: Vec<_> = group_tasks(&refs, &projects, ReportGroup::Status)
+            .into_iter()
+            .map(|(l, _)| l.unwrap())
+            .collect();
+        assert_eq!(statuses, vec!["Pending", "In Progress", "Completed"]);
+
+        let weeks: Vec<_> = group_tasks(&refs, &projects, ReportGroup::DueWeek)
+            .into_iter()
+            .map(|(l, g)| (l.unwrap(), g.len()))
+            .collect();
+        assert_eq!(
+            weeks,
+            vec![
+                ("Week of 2026-02-09".to_string(), 1),
+                ("No Due Date".to_string(), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_escaping_and_dates() {
+        let (tasks, projects) = sample();
+        let refs: Vec<&Task> = tasks.iter().collect();
+        let options = columns(&[
+            ReportColumn::Title,
+            ReportColumn::Tags,
+            ReportColumn::Due,
+            ReportColumn::Description,
+        ]);
+        let csv = to_csv(&refs, &projects, &options);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(lines[0], "title,tags,due,description");
+        let due = iso_datetime(tasks[0].due_date.unwrap());
+        assert!(due.ends_with('Z'));
+        assert_eq!(lines[1], format!("Write notes,docs,{},", due));
+        assert_eq!(lines[2], "Renew passport,,,");
+        assert_eq!(lines[3], "\"Refactor, then \"\"test\"\"\",code;docs,,\"Line one\nLine two\"");
+    }
+
+    #[test]
+    fn test_csv_guards_formulas() {
+        let mut tasks: Vec<Task> = ["=HYPERLINK(\"http://x\")", "+1", "-1", "@SUM(A1)", "\tTab", "\rReturn", "Plain - text"]
+            .iter()
+            .map(|title| Task::new(title))
+            .collect();
+        tasks[0].tags = vec!["=cmd".to_string()];
+        let refs: Vec<&Task> = tasks.iter().collect();
+        let csv = to_csv(&refs, &[], &columns(&[ReportColumn::Title, ReportColumn::Tags]));
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(lines[1], "\"'=HYPERLINK(\"\"http://x\"\")\",'=cmd");
+        assert_eq!(lines[2], "'+1,");
+        assert_eq!(lines[3], "'-1,");
+        assert_eq!(lines[4], "'@SUM(A1),");
+        assert_eq!(lines[5], "'\tTab,");
+        assert_eq!(lines[6], "\"'\rReturn\",");
+        assert_eq!(lines[7], "Plain - text,");
+    }
+
+    #[test]
+    fn test_markdown_tags_with_backticks() {
+        let mut task = Task::new("Odd tags");
+        task.tags = vec!["a`b".to_string(), "c``".to_string()];
+        let options = columns(&[ReportColumn::Title, ReportColumn::Tags]);
+
+        assert_eq!(
+            to_markdown(&[&task], &[], &options),
+            "- [ ] Odd tags · `` #a`b `` ``` #c`` ```\n"
+        );
+    }
+
+    #[test]
+    fn test_notes_column() {
+        let (mut tasks, projects) = sample();
+        tasks[0].add_note("Outline done\nNeeds examples");
+        tasks[0].notes[0].created_at = Local.with_ymd_and_hms(2026, 2, 9, 14, 30, 0).unwrap().with_timezone(&Utc);
+        let refs: Vec<&Task> = tasks[..1].iter().collect();
+        let options = columns(&[ReportColumn::Title, ReportColumn::Notes]);
+
+        assert_eq!(
+            to_markdown(&refs, &projects, &options),
+            "- [ ] Write notes\n\
+             \x20 - _2026-02-09 14:30_ Outline done\n\
+             \x20   Needs examples\n"
+        );
+
+        let csv = to_csv(&refs, &projects, &options);
+        let created = iso_datetime(tasks[0].notes[0].created_at);
+        assert_eq!(csv, format!("title,notes\r\nWrite notes,\"{} Outline done\nNeeds examples\"\r\n", created));
+    }
+
+    #[test]
+    fn test_csv_grouped_adds_group_column() {
+        let (tasks, projects) = sample();
+        let refs: Vec<&Task> = tasks.iter().collect();
+        let options = ReportOptions {
+            columns: vec![ReportColumn::Title],
+            group_by: ReportGroup::Status,
+        };
+
+        assert_eq!(
+            to_csv(&refs, &projects, &options),
+            "group,title\r\nPending,Write notes\r\nIn Progress,\"Refactor, then \"\"test\"\"\"\r\nCompleted,Renew passport\r\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        let projects = vec![Project::new("Side Project")];
+        assert_eq!(parse_filter("pending", &projects), Ok(Filter::Pending));
+        assert_eq!(parse_filter("Overdue", &projects), Ok(Filter::Overdue));
+        assert_eq!(
+            parse_filter("project:side_project", &projects),
+            Ok(Filter::ByProject(projects[0].id.clone()))
+        );
+        assert_eq!(parse_filter("tag:docs", &projects), Ok(Filter::ByTag("docs".to_string())));
+        assert_eq!(
+            parse_filter("priority:urgent", &projects),
+            Ok(Filter::ByPriority(Priority::Urgent))
+        );
+        assert!(parse_filter("project:Missing", &projects).is_err());
+        assert!(parse_filter("someday", &projects).is_err());
+    }
+
+    #[test]
+    fn test_parse_sort() {
+        assert_eq!(parse_sort("due"), Ok(SortOrder::DueDateAsc));
+        assert_eq!(parse_sort("priority"), Ok(SortOrder::PriorityDesc));
+        assert_eq!(parse_sort("alpha"), Ok(SortOrder::Alphabetical));
+        assert_eq!(parse_sort("manual"), Ok(SortOrder::Manual));
+        assert!(parse_sort("random").is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! Standup reports.
+//!
+//! A standup lists what was completed since the last workday, what's in
+//! progress, what's due today and what's blocked or overdue, each grouped
+//! by project. Like [`report`](super::report), it's export-only: the text is
+//! meant for pasting into a chat or a meeting doc.
+//!
+//! "The last workday" skips the days not listed in
+//! [`StandupConfig::workdays`], so Monday's standup covers Friday too.
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+use super::project_name;
+use crate::config::StandupConfig;
+use crate::models::{Project, Task, TaskStatus};
+use crate::utils::{is_same_day, previous_workday};
+
+/// Output format of a standup report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StandupFormat {
+    /// Plain text, for chat messages
+    #[default]
+    Text,
+    /// Markdown, with a heading per section
+    Markdown,
+}
+
+/// The tasks in each section of a standup.
+///
+/// An open task appears in at most one section: blocked or overdue first,
+/// then due today, then in progress.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standup<'a> {
+    /// The day the standup is for
+    pub today: NaiveDate,
+    /// The last workday before `today`; completions from then on are listed
+    pub since: NaiveDate,
+    /// Tasks completed since `since`, oldest first
+    pub done: Vec<&'a Task>,
+    /// Tasks being worked on
+    pub in_progress: Vec<&'a Task>,
+    /// Open tasks due today
+    pub due_today: Vec<&'a Task>,
+    /// Open tasks past their due date or tagged as blocked
+    pub blocked: Vec<&'a Task>,
+}
+
+impl<'a> Standup<'a> {
+    /// Sorts tasks into the standup's sections as of `now`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use ratado::config::StandupConfig;
+    /// use ratado::interop::standup::Standup;
+    /// use ratado::models::{Task, TaskStatus};
+    ///
+    /// let mut task = Task::new("Write release notes");
+    /// task.status = TaskStatus::InProgress;
+    /// let tasks = vec![task];
+    /// let standup = Standup::build(&tasks, Utc::now(), &StandupConfig::default());
+    /// assert_eq!(standup.in_progress[0].title, "Write release notes");
+    /// ```
+    pub fn build(tasks: &'a [Task], now: DateTime<Utc>, config: &StandupConfig) -> Self {
+        let today = now.with_timezone(&Local).date_naive();
+        let since = previous_workday(today, &config.workdays);
+        let local_date = |date: DateTime<Utc>| date.with_timezone(&Local).date_naive();
+        let is_open = |t: &Task| matches!(t.status, TaskStatus::Pending | TaskStatus::InProgress);
+        let is_blocked = |t: &Task| {
+            t.due_date.is_some_and(|due| local_date(due) < today)
+                || t.tags.iter().any(|tag| tag.eq_ignore_ascii_case(&config.blocked_tag))
+        };
+        let is_due_today = |t: &Task| t.due_date.is_some_and(|due| is_same_day(due, now));
+
+        let mut done: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Completed)
+            .filter(|t| t.completed_at.is_some_and(|at| local_date(at) >= since))
+            .collect();
+        done.sort_by_key(|t| t.completed_at);
+
+        let open: Vec<&Task> = tasks.iter().filter(|t| is_open(t)).collect();
+        let blocked = open.iter().copied().filter(|t| is_blocked(t)).collect();
+        let due_today = open
+            .iter()
+            .copied()
+            .filter(|t| !is_blocked(t) && is_due_today(t))
+            .collect();
+        let in_progress = open
+            .iter()
+            .copied()
+            .filter(|t| t.status == TaskStatus::InProgress && !is_blocked(t) && !is_due_today(t))
+            .collect();
+
+        Self {
+            today,
+            since,
+            done,
+            in_progress,
+            due_today,
+            blocked,
+        }
+    }
+
+    /// Returns the section headings with their tasks, in report order.
+    fn sections(&self) -> [(String, &[&'a Task]); 4] {
+        [
+            (format!("Done since {}", self.since.format("%A")), &self.done),
+            ("In progress".to_string(), &self.in_progress),
+            ("Due today".to_string(), &self.due_today),
+            ("Blocked or overdue".to_string(), &self.blocked),
+        ]
+    }
+
+    /// Renders the standup in the given format.
+    pub fn render(&self, format: StandupFormat, projects: &[Project]) -> String {
+        match format {
+            StandupFormat::Text => self.to_text(projects),
+            StandupFormat::Markdown => self.to_markdown(projects),
+        }
+    }
+
+    /// Renders the standup as plain text.
+    pub fn to_text(&self, projects: &[Project]) -> String {
+        let mut output = format!("Standup for {}\n", self.today.format("%A %-d %B %Y"));
+        for (heading, tasks) in self.sections() {
+            output.push_str(&format!("\n{}:\n", heading));
+            if tasks.is_empty() {
+                output.push_str("  (nothing)\n");
+            }
+            for (project, tasks) in group_by_project(tasks, projects) {
+                output.push_str(&format!("  {}\n", project));
+                for task in tasks {
+                    output.push_str(&format!("    - {}\n", task.title));
+                }
+            }
+        }
+        output
+    }
+
+    /// Renders the standup as Markdown.
+    pub fn to_markdown(&self, projects: &[Project]) -> String {
+        let mut output = format!("## Standup for {}\n", self.today.format("%A %-d %B %Y"));
+        for (heading, tasks) in self.sections() {
+            output.push_str(&format!("\n### {}\n\n", heading));
+            if tasks.is_empty() {
+                output.push_str("Nothing\n");
+            }
+            for (index, (project, tasks)) in group_by_project(tasks, projects).into_iter().enumerate() {
+                if index > 0 {
+                    output.push('\n');
+                }
+                output.push_str(&format!("**{}**\n", project));
+                for task in tasks {
+                    output.push_str(&format!("- {}\n", task.title));
+                }
+            }
+        }
+        output
+    }
+}
+
+/// Groups tasks by project, in the projects' order, with tasks without a
+/// project last.
+fn group_by_project<'a>(tasks: &[&'a Task], projects: &'a [Project]) -> Vec<(&'a str, Vec<&'a Task>)> {
+    let mut groups: Vec<(&str, Vec<&Task>)> = projects
+        .iter()
+        .map(|project| {
+            let found = tasks
+                .iter()
+                .copied()
+                .filter(|t| t.project_id.as_deref() == Some(project.id.as_str()))
+                .collect();
+            (project.name.as_str(), found)
+        })
+        .collect();
+    groups.push((
+        "No project",
+        tasks.iter().copied().filter(|t| project_name(t, projects).is_none()).collect(),
+    ));
+    groups.retain(|(_, tasks)| !tasks.is_empty());
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Weekday};
+
+    /// Noon on Monday 9 March 2026, local time.
+    fn monday_noon() -> DateTime<Utc> {
+        Local
+            .with_ymd_and_hms(2026, 3, 9, 12, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn sample(now: DateTime<Utc>) -> (Vec<Task>, Vec<Project>) {
+        let work = Project::new("Work");
+        let home = Project::new("Home");
+
+        let mut friday = Task::new("Ship release");
+        friday.project_id = Some(work.id.clone());
+        friday.complete();
+        friday.completed_at = Some(now - Duration::days(3));
+        let mut thursday = Task::new("Old news");
+        thursday.complete();
+        thursday.completed_at = Some(now - Duration::days(4));
+        let mut working = Task::new("Review PR");
+        working.status = TaskStatus::InProgress;
+        working.project_id = Some(work.id.clone());
+        let mut due = Task::new("Pay rent");
+        due.due_date = Some(now + Duration::hours(2));
+        due.project_id = Some(home.id.clone());
+        let mut late = Task::new("Call plumber");
+        late.status = TaskStatus::InProgress;
+        late.due_date = Some(now - Duration::days(2));
+        late.project_id = Some(home.id.clone());
+        let mut stuck = Task::new("Deploy");
+        stuck.tags = vec!["Blocked".to_string()];
+        let idle = Task::new("Someday");
+
+        let tasks = vec![friday, thursday, working, due, late, stuck, idle];
+        (tasks, vec![work, home])
+    }
+
+    fn titles(tasks: &[&Task]) -> Vec<String> {
+        tasks.iter().map(|t| t.title.clone()).collect()
+    }
+
+    #[test]
+    fn test_sections_skip_weekend() {
+        let now = monday_noon();
+        let (tasks, _) = sample(now);
+        let standup = Standup::build(&tasks, now, &StandupConfig::default());
+
+        assert_eq!(standup.since, NaiveDate::from_ymd_opt(2026, 3, 6).unwrap());
+        assert_eq!(titles(&standup.done), vec!["Ship release"]);
+        assert_eq!(titles(&standup.in_progress), vec!["Review PR"]);
+        assert_eq!(titles(&standup.due_today), vec!["Pay rent"]);
+        assert_eq!(titles(&standup.blocked), vec!["Call plumber", "Deploy"]);
+
+        // Working every day, yesterday is Sunday and Friday's work is old
+        let every_day = StandupConfig {
+            workdays: (0..7).map(|d| Weekday::try_from(d).unwrap()).collect(),
+            ..StandupConfig::default()
+        };
+        let standup = Standup::build(&tasks, now, &every_day);
+        assert_eq!(standup.since, NaiveDate::from_ymd_opt(2026, 3, 8).unwrap());
+        assert!(standup.done.is_empty());
+    }
+
+    #[test]
+    fn test_markdown_groups_by_project() {
+        let now = monday_noon();
+        let (tasks, projects) = sample(now);
+        let markdown = Standup::build(&tasks, now, &StandupConfig::default()).to_markdown(&projects);
+
+        assert!(markdown.starts_with("## Standup for Monday 9 March 2026\n"));
+        assert!(markdown.contains("### Done since Friday\n\n**Work**\n- Ship release\n"));
+        assert!(markdown.contains("### Blocked or overdue\n\n**Home**\n- Call plumber\n\n**No project**\n- Deploy\n"));
+    }
+
+    #[test]
+    fn test_text_lists_empty_sections() {
+        let now = monday_noon();
+        let text = Standup::build(&[], now, &StandupConfig::default()).render(StandupFormat::Text, &[]);
+        assert!(text.contains("In progress:\n  (nothing)\n"));
+        assert_eq!(text.matches("(nothing)").count(), 4);
+    }
+}
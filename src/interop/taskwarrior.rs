@@ -0,0 +1,430 @@
+//! Taskwarrior's JSON export format.
+//!
+//! Reads the output of `task export` and writes JSON that `task import`
+//! accepts. Both the JSON array produced by Taskwarrior 2.6+ and the older
+//! one-object-per-line form are understood on import.
+//!
+//! ## Mapping
+//!
+//! | Taskwarrior | Ratado |
+//! |-------------|--------|
+//! | `uuid` | Task ID, so re-importing updates instead of duplicating |
+//! | `description` | Title |
+//! | `annotations` | Notes |
+//! | `ratadodescription` | Description |
+//! | `project` | Project, created on import if missing |
+//! | `tags` | Tags |
+//! | `priority` `H` / `M` / `L` | High, Medium, Low; no priority imports as Medium |
+//! | `priority` `H` with `ratadopriority` `urgent` | Urgent |
+//! | `status` | `pending`, or in progress when `start` is set; `completed`; `deleted` is archived |
+//! | `entry` / `modified` / `end` / `due` | Created, updated, completed and due times |
+//!
+//! Taskwarrior has no field for a description or an urgent priority, so
+//! they're written as the `ratadodescription` and `ratadopriority`
+//! attributes. Taskwarrior keeps attributes it doesn't know about, so they
+//! survive a trip through `task import` and `task export`.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{one_line, project_name, ImportedTask};
+use crate::models::{position_at, Note, Priority, Project, Task, TaskStatus};
+
+/// Timestamp format used by Taskwarrior, always in UTC.
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// `ratadopriority` value marking a `H` task as urgent.
+const URGENT: &str = "urgent";
+
+/// A task as it appears in Taskwarrior JSON.
+///
+/// Only the attributes Ratado maps are listed; others are ignored on import.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TwTask {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uuid: Option<String>,
+    description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modified: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<TwAnnotation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ratadodescription: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ratadopriority: Option<String>,
+}
+
+/// A timestamped note attached to a Taskwarrior task.
+#[derive(Debug, Serialize, Deserialize)]
+struct TwAnnotation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    description: String,
+}
+
+/// Parses Taskwarrior JSON export output.
+///
+/// # Errors
+///
+/// Returns an error if the input is not valid Taskwarrior JSON.
+///
+/// # Examples
+///
+/// ```
+/// use ratado::interop::taskwarrior;
+/// use ratado::models::Priority;
+///
+/// let items = taskwarrior::parse(r#"[{"description":"Fix bug","priority":"H","project":"Work"}]"#)?;
+/// assert_eq!(items[0].task.title, "Fix bug");
+/// assert_eq!(items[0].task.priority, Priority::High);
+/// assert_eq!(items[0].project.as_deref(), Some("Work"));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub fn parse(input: &str) -> serde_json::Result<Vec<ImportedTask>> {
+    let trimmed = input.trim_start();
+    let tasks: Vec<TwTask> = if trimmed.starts_with('[') {
+        serde_json::from_str(trimmed)?
+    } else {
+        // Taskwarrior before 2.6 printed one JSON object per line
+        trimmed
+            .lines()
+            .map(|line| line.trim().trim_end_matches(','))
+            .filter(|line| !line.is_empty())
+            .map(serde_json::from_str)
+            .collect::<serde_json::Result<_>>()?
+    };
+
+    Ok(tasks.into_iter().map(to_imported).collect())
+}
+
+/// Formats tasks as a Taskwarrior JSON array, suitable for `task import`.
+///
+/// `projects` is used to look up project names.
+pub fn format(tasks: &[&Task], projects: &[Project]) -> String {
+    let tw_tasks: Vec<TwTask> = tasks.iter().map(|t| to_taskwarrior(t, projects)).collect();
+    let mut output =
+        serde_json::to_string_pretty(&tw_tasks).expect("Taskwarrior tasks always serialize");
+    output.push('\n');
+    output
+}
+
+fn to_imported(tw: TwTask) -> ImportedTask {
+    let mut task = Task::new(tw.description.trim());
+
+    if let Some(uuid) = tw.uuid.filter(|u| !u.is_empty()) {
+        task.id = uuid;
+    }
+    if let Some(entry) = tw.entry.as_deref().and_then(parse_date) {
+        task.created_at = entry;
+        task.position = position_at(entry);
+    }
+    task.updated_at = tw
+        .modified
+        .as_deref()
+        .and_then(parse_date)
+        .unwrap_or(task.created_at);
+    task.due_date = tw.due.as_deref().and_then(parse_date);
+    task.priority = match tw.priority.as_deref() {
+        Some("H") if tw.ratadopriority.as_deref() == Some(URGENT) => Priority::Urgent,
+        Some("H") => Priority::High,
+        Some("L") => Priority::Low,
+        _ => Priority::Medium,
+    };
+    task.description = tw.ratadodescription.filter(|d| !d.trim().is_empty());
+    task.status = match tw.status.as_deref() {
+        Some("completed") => TaskStatus::Completed,
+        Some("deleted") => TaskStatus::Archived,
+        _ if tw.start.is_some() => TaskStatus::InProgress,
+        _ => TaskStatus::Pending,
+    };
+    if matches!(task.status, TaskStatus::Completed | TaskStatus::Archived) {
+        task.completed_at = tw
+            .end
+            .as_deref()
+            .and_then(parse_date)
+            .or(Some(task.updated_at));
+    }
+    for tag in tw.tags {
+        if !tag.is_empty() && !task.tags.contains(&tag) {
+            task.tags.push(tag);
+        }
+    }
+    for annotation in tw.annotations {
+        let text = annotation.description.trim();
+        if !text.is_empty() {
+            task.notes.push(Note {
+                created_at: annotation
+                    .entry
+                    .as_deref()
+                    .and_then(parse_date)
+                    .unwrap_or(task.created_at),
+                ..Note::new(text)
+            });
+        }
+    }
+    task.notes.sort_by_key(|note| note.created_at);
+
+    ImportedTask {
+        task,
+        project: tw.project.filter(|p| !p.is_empty()),
+    }
+}
+
+fn to_taskwarrior(task: &Task, projects: &[Project]) -> TwTask {
+    let status = match task.status {
+        TaskStatus::Pending | TaskStatus::InProgress => "pending",
+        TaskStatus::Completed => "completed",
+        TaskStatus::Archived => "deleted",
+    };
+    let priority = match task.priority {
+        Priority::Urgent | Priority::High => "H",
+        Priority::Medium => "M",
+        Priority::Low => "L",
+    };
+    let annotations = task
+        .notes
+        .iter()
+        .map(|note| TwAnnotation {
+            entry: Some(format_date(note.created_at)),
+            // Annotations are single lines
+            description: one_line(&note.text),
+        })
+        .collect();
+
+    TwTask {
+        uuid: Some(task.id.clone()),
+        description: task.title.clone(),
+        status: Some(status.to_string()),
+        entry: Some(format_date(task.created_at)),
+        modified: Some(format_date(task.updated_at)),
+        start: (task.status == TaskStatus::InProgress).then(|| format_date(task.updated_at)),
+        end: task.completed_at.map(format_date),
+        due: task.due_date.map(format_date),
+        project: project_name(task, projects).map(str::to_string),
+        priority: Some(priority.to_string()),
+        tags: task.tags.clone(),
+        annotations,
+        ratadodescription: task.description.clone().filter(|d| !d.trim().is_empty()),
+        ratadopriority: (task.priority == Priority::Urgent).then(|| URGENT.to_string()),
+    }
+}
+
+/// Parses a Taskwarrior timestamp, also accepting RFC 3339.
+fn parse_date(s: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(s, DATE_FORMAT)
+        .map(|dt| dt.and_utc())
+        .ok()
+        .or_else(|| DateTime::parse_from_rfc3339(s).ok().map(|dt| dt.with_timezone(&Utc)))
+}
+
+fn format_date(date: DateTime<Utc>) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    const EXPORT: &str = r#"[
+{"id":1,"description":"Write release notes","entry":"20260130T120000Z","modified":"20260131T090000Z","project":"Ratado","status":"pending","tags":["docs","work"],"uuid":"9f3a2c1e-4b5d-4e6f-8a7b-1c2d3e4f5a6b","due":"20260210T225959Z","priority":"H","urgency":9.2},
+{"id":0,"description":"Renew passport","end":"20260203T101500Z","entry":"20260128T080000Z","modified":"20260203T101500Z","status":"completed","uuid":"0b1c2d3e-4f5a-4b6c-8d7e-9f0a1b2c3d4e","annotations":[{"entry":"20260129T080000Z","description":"Photos at the pharmacy"},{"entry":"20260130T080000Z","description":"Bring old passport"}]},
+{"id":2,"description":"Refactor parser","entry":"20260101T000000Z","status":"pending","start":"20260102T000000Z","uuid":"1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c5d","priority":"L"},
+{"id":0,"description":"Old idea","entry":"20250101T000000Z","end":"20250201T000000Z","status":"deleted","uuid":"2b3c4d5e-6f7a-4b8c-9d0e-1f2a3b4c5d6e"}
+]"#;
+
+    #[test]
+    fn test_parse_export_array() {
+        let items = parse(EXPORT).unwrap();
+        assert_eq!(items.len(), 4);
+
+        let notes = &items[0];
+        assert_eq!(notes.task.id, "9f3a2c1e-4b5d-4e6f-8a7b-1c2d3e4f5a6b");
+        assert_eq!(notes.task.title, "Write release notes");
+        assert_eq!(notes.project.as_deref(), Some("Ratado"));
+        assert_eq!(notes.task.tags, vec!["docs", "work"]);
+        assert_eq!(notes.task.priority, Priority::High);
+        assert_eq!(notes.task.status, TaskStatus::Pending);
+        assert_eq!(
+            notes.task.created_at,
+            Utc.with_ymd_and_hms(2026, 1, 30, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            notes.task.updated_at,
+            Utc.with_ymd_and_hms(2026, 1, 31, 9, 0, 0).unwrap()
+        );
+        assert_eq!(
+            notes.task.due_date,
+            Some(Utc.with_ymd_and_hms(2026, 2, 10, 22, 59, 59).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_completed_with_annotations() {
+        let items = parse(EXPORT).unwrap();
+        let passport = &items[1].task;
+
+        assert_eq!(passport.status, TaskStatus::Completed);
+        assert_eq!(passport.priority, Priority::Medium);
+        assert_eq!(
+            passport.completed_at,
+            Some(Utc.with_ymd_and_hms(2026, 2, 3, 10, 15, 0).unwrap())
+        );
+        assert_eq!(passport.description, None);
+        let notes: Vec<(&str, DateTime<Utc>)> =
+            passport.notes.iter().map(|n| (n.text.as_str(), n.created_at)).collect();
+        assert_eq!(
+            notes,
+            [
+                ("Photos at the pharmacy", Utc.with_ymd_and_hms(2026, 1, 29, 8, 0, 0).unwrap()),
+                ("Bring old passport", Utc.with_ymd_and_hms(2026, 1, 30, 8, 0, 0).unwrap()),
+            ]
+        );
+        assert!(items[1].project.is_none());
+    }
+
+    #[test]
+    fn test_description_and_notes_round_trip() {
+        let mut task = Task::new("Ship it");
+        task.created_at = Utc.with_ymd_and_hms(2026, 2, 1, 9, 0, 0).unwrap();
+        task.description = Some("Tag the release\nPublish the crate".to_string());
+        task.add_note("Tested on staging");
+
+        let items = parse(&format(&[&task], &[])).unwrap();
+        let parsed = &items[0].task;
+        assert_eq!(parsed.description, task.description);
+        assert_eq!(parsed.notes.len(), 1);
+        assert_eq!(parsed.notes[0].text, "Tested on staging");
+        assert_eq!(parsed.notes[0].created_at.timestamp(), task.notes[0].created_at.timestamp());
+    }
+
+    #[test]
+    fn test_parse_started_and_deleted() {
+        let items = parse(EXPORT).unwrap();
+        assert_eq!(items[2].task.status, TaskStatus::InProgress);
+        assert_eq!(items[2].task.priority, Priority::Low);
+        assert_eq!(items[3].task.status, TaskStatus::Archived);
+        assert!(items[3].task.completed_at.is_some());
+    }
+
+    #[test]
+    fn test_parse_line_per_object() {
+        let input = "{\"description\":\"One\",\"status\":\"pending\"},\n{\"description\":\"Two\",\"status\":\"pending\"}\n";
+        let items = parse(input).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].task.title, "Two");
+    }
+
+    #[test]
+    fn test_parse_invalid_json() {
+        assert!(parse("[{\"nope\": 1}]").is_err());
+        assert!(parse("not json").is_err());
+    }
+
+    #[test]
+    fn test_format_maps_fields() {
+        let project = Project::new("Work");
+        let mut task = Task::new("Ship it");
+        task.priority = Priority::Urgent;
+        task.status = TaskStatus::InProgress;
+        task.project_id = Some(project.id.clone());
+        task.tags = vec!["release".to_string()];
+        task.description = Some("First note\n\nSecond note".to_string());
+        task.add_note("Tested on staging\nLooks good");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&format(&[&task], &[project])).unwrap();
+        let tw = &json[0];
+
+        assert_eq!(tw["uuid"], task.id.as_str());
+        assert_eq!(tw["description"], "Ship it");
+        assert_eq!(tw["status"], "pending");
+        assert_eq!(tw["priority"], "H");
+        assert_eq!(tw["project"], "Work");
+        assert_eq!(tw["tags"][0], "release");
+        assert!(tw["start"].is_string());
+        assert!(tw.get("due").is_none());
+        assert_eq!(tw["ratadopriority"], "urgent");
+        assert_eq!(tw["ratadodescription"], "First note\n\nSecond note");
+        assert_eq!(tw["annotations"].as_array().unwrap().len(), 1);
+        assert_eq!(tw["annotations"][0]["description"], "Tested on staging Looks good");
+        assert_eq!(tw["annotations"][0]["entry"], format_date(task.notes[0].created_at).as_str());
+    }
+
+    #[test]
+    fn test_priorities_round_trip() {
+        for (priority, letter) in [
+            (Priority::Urgent, "H"),
+            (Priority::High, "H"),
+            (Priority::Medium, "M"),
+            (Priority::Low, "L"),
+        ] {
+            let mut task = Task::new("Prioritized");
+            task.priority = priority;
+            let output = format(&[&task], &[]);
+            let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+            assert_eq!(json[0]["priority"], letter);
+            assert_eq!(json[0].get("ratadopriority").is_some(), priority == Priority::Urgent);
+            assert_eq!(parse(&output).unwrap()[0].task.priority, priority);
+        }
+        let unprioritized = parse(r#"[{"description":"No rush"}]"#).unwrap();
+        assert_eq!(unprioritized[0].task.priority, Priority::Medium);
+    }
+
+    #[test]
+    fn test_parse_annotations_are_notes() {
+        // Made in the same second as the task, which used to read as description
+        let items = parse(
+            r#"[{"description":"Quick","entry":"20260130T120000Z","annotations":[{"entry":"20260130T120000Z","description":"Right away"},{"entry":"20260130T120001Z","description":"Then this"}]}]"#,
+        )
+        .unwrap();
+        let task = &items[0].task;
+        assert_eq!(task.description, None);
+        let notes: Vec<&str> = task.notes.iter().map(|n| n.text.as_str()).collect();
+        assert_eq!(notes, ["Right away", "Then this"]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let items = parse(EXPORT).unwrap();
+        let projects = vec![Project::new("Ratado")];
+        let tasks: Vec<Task> = items
+            .iter()
+            .map(|item| {
+                let mut task = item.task.clone();
+                task.project_id = item.project.as_ref().map(|_| projects[0].id.clone());
+                task
+            })
+            .collect();
+
+        let refs: Vec<&Task> = tasks.iter().collect();
+        let reparsed = parse(&format(&refs, &projects)).unwrap();
+        // Annotations carry no IDs, so notes get new ones
+        let without_note_ids = |task: &Task| {
+            let mut task = task.clone();
+            task.notes.iter_mut().for_each(|note| note.id.clear());
+            task
+        };
+        for (original, item) in items.iter().zip(&reparsed) {
+            assert_eq!(without_note_ids(&item.task), without_note_ids(&original.task));
+            assert_eq!(item.project, original.project);
+        }
+    }
+}
@@ -0,0 +1,508 @@
+//! The todo.txt plain-text format.
+//!
+//! Each line of a todo.txt file is one task:
+//!
+//! ```text
+//! x 2026-02-03 2026-01-28 Renew passport +Admin @errands due:2026-02-10 pri:A
+//! (B) 2026-01-30 Write release notes +Ratado @docs
+//! ```
+//!
+//! ## Mapping
+//!
+//! | todo.txt | Ratado |
+//! |----------|--------|
+//! | `(A)` `(B)` `(C)` `(D)` | Urgent, High, Medium, Low (later letters are Low) |
+//! | no priority | Medium, the default, exported without a marker |
+//! | `+project` | Project, created on import if missing |
+//! | `@context` | Tag |
+//! | `due:YYYY-MM-DD` | Due date |
+//! | `x` + completion date | Completed status and completion time |
+//! | creation date | Creation time |
+//! | `pri:X` on completed lines | Priority (todo.txt drops `(X)` on completion) |
+//! | `status:in_progress` / `status:archived` | Statuses todo.txt can't express |
+//!
+//! Anything else Ratado can't represent — extra `+project` tokens and unknown
+//! `key:value` extensions — is kept on a `todo.txt:` line in the task
+//! description and written back out on export. Other description text has no
+//! place in a one-line format and is not exported.
+//!
+//! ## Limitations
+//!
+//! todo.txt has no way to escape its markers, so some titles don't survive a
+//! round trip unchanged:
+//!
+//! - A title starting with `x`, `(A)` or a date is exported with a `- `
+//!   prefix so it isn't read back as a marker, and keeps the prefix on import.
+//! - `+word`, `@word` and `key:value` words inside a title are read back as a
+//!   project, tag or extension.
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+
+use super::{project_name, ImportedTask};
+use crate::models::{position_at, Priority, Project, Task, TaskStatus};
+
+/// Prefix of the description line holding tokens Ratado can't represent.
+const PRESERVED_PREFIX: &str = "todo.txt:";
+
+/// Parses a whole todo.txt file.
+///
+/// Blank lines are skipped. Parsing is lenient: every non-blank line yields
+/// a task, with unrecognized tokens kept in the title or description.
+pub fn parse(input: &str) -> Vec<ImportedTask> {
+    input.lines().filter_map(parse_line).collect()
+}
+
+/// Parses a single todo.txt line.
+///
+/// Returns `None` for blank lines.
+///
+/// # Examples
+///
+/// ```
+/// use ratado::interop::todotxt::parse_line;
+/// use ratado::models::Priority;
+///
+/// let item = parse_line("(A) Call mom +Family @phone").unwrap();
+/// assert_eq!(item.task.title, "Call mom");
+/// assert_eq!(item.task.priority, Priority::Urgent);
+/// assert_eq!(item.project.as_deref(), Some("Family"));
+/// assert_eq!(item.task.tags, vec!["phone"]);
+/// ```
+pub fn parse_line(line: &str) -> Option<ImportedTask> {
+    let mut tokens = line.split_whitespace().peekable();
+    tokens.peek()?;
+
+    let mut priority = None;
+    let mut status = TaskStatus::Pending;
+    let mut completed_on = None;
+    let mut created_on = None;
+
+    if tokens.peek() == Some(&"x") {
+        tokens.next();
+        status = TaskStatus::Completed;
+        if let Some(date) = tokens.peek().and_then(|t| parse_date(t)) {
+            tokens.next();
+            completed_on = Some(date);
+            if let Some(date) = tokens.peek().and_then(|t| parse_date(t)) {
+                tokens.next();
+                created_on = Some(date);
+            }
+        }
+    } else {
+        if let Some(p) = tokens.peek().and_then(|t| parse_priority_marker(t)) {
+            tokens.next();
+            priority = Some(p);
+        }
+        if let Some(date) = tokens.peek().and_then(|t| parse_date(t)) {
+            tokens.next();
+            created_on = Some(date);
+        }
+    }
+
+    let mut words = Vec::new();
+    let mut project = None;
+    let mut tags: Vec<String> = Vec::new();
+    let mut due_date = None;
+    let mut preserved = Vec::new();
+
+    for token in tokens {
+        if let Some(name) = token.strip_prefix('+').filter(|n| !n.is_empty()) {
+            if project.is_none() {
+                project = Some(name.to_string());
+            } else {
+                preserved.push(token);
+            }
+        } else if let Some(name) = token.strip_prefix('@').filter(|n| !n.is_empty()) {
+            if !tags.iter().any(|t| t == name) {
+                tags.push(name.to_string());
+            }
+        } else if let Some((key, value)) = split_extension(token) {
+            match key {
+                "due" if parse_date(value).is_some() => {
+                    due_date = parse_date(value).map(end_of_day);
+                }
+                "pri" if priority.is_none() && parse_priority_letter(value).is_some() => {
+                    priority = parse_priority_letter(value);
+                }
+                "status" if value == "in_progress" && status == TaskStatus::Pending => {
+                    status = TaskStatus::InProgress;
+                }
+                "status" if value == "archived" => {
+                    status = TaskStatus::Archived;
+                }
+                _ => preserved.push(token),
+            }
+        } else {
+            words.push(token);
+        }
+    }
+
+    let title = if words.is_empty() {
+        line.trim().to_string()
+    } else {
+        words.join(" ")
+    };
+
+    let mut task = Task::new(&title);
+    task.priority = priority.unwrap_or_default();
+    task.status = status;
+    task.tags = tags;
+    task.due_date = due_date;
+    if let Some(date) = created_on {
+        task.created_at = start_of_day(date);
+        task.updated_at = task.created_at;
+    }
+    if matches!(status, TaskStatus::Completed | TaskStatus::Archived) {
+        let completed_at = completed_on.map(start_of_day).unwrap_or_else(Utc::now);
+        task.completed_at = Some(completed_at);
+        if created_on.is_none() {
+            // A task can't have been created after it was completed
+            task.created_at = task.created_at.min(completed_at);
+        }
+        task.updated_at = completed_at.max(task.created_at);
+    }
+    task.position = position_at(task.created_at);
+    if !preserved.is_empty() {
+        task.description = Some(format!("{} {}", PRESERVED_PREFIX, preserved.join(" ")));
+    }
+
+    Some(ImportedTask { task, project })
+}
+
+/// Formats tasks as a todo.txt file, one line per task.
+///
+/// `projects` is used to look up project names; tasks whose project isn't
+/// in the list are written without a `+project` token.
+pub fn format(tasks: &[&Task], projects: &[Project]) -> String {
+    tasks
+        .iter()
+        .map(|task| format_line(task, projects) + "\n")
+        .collect()
+}
+
+/// Formats a single task as a todo.txt line.
+///
+/// # Examples
+///
+/// ```
+/// use ratado::interop::todotxt::format_line;
+/// use ratado::models::{Priority, Task};
+///
+/// let mut task = Task::new("Call mom");
+/// task.priority = Priority::High;
+/// task.tags = vec!["phone".to_string()];
+/// let line = format_line(&task, &[]);
+/// assert!(line.starts_with("(B) "));
+/// assert!(line.ends_with("Call mom @phone"));
+/// ```
+pub fn format_line(task: &Task, projects: &[Project]) -> String {
+    let mut parts = Vec::new();
+    let done = matches!(task.status, TaskStatus::Completed | TaskStatus::Archived);
+
+    if done {
+        parts.push("x".to_string());
+        let completed_at = task.completed_at.unwrap_or(task.updated_at);
+        parts.push(format_date(completed_at));
+    } else if task.priority != Priority::default() {
+        parts.push(format!("({})", priority_letter(task.priority)));
+    }
+    parts.push(format_date(task.created_at));
+
+    // Titles can't start with something that reads as a completion marker,
+    // priority or date, or it would be parsed back as one.
+    let title = task.title.trim();
+    if title.split_whitespace().next().is_some_and(is_reserved_leading_token) {
+        parts.push(format!("- {}", title));
+    } else {
+        parts.push(title.to_string());
+    }
+
+    if let Some(name) = project_name(task, projects) {
+        parts.push(format!("+{}", name.split_whitespace().collect::<Vec<_>>().join("_")));
+    }
+    for tag in &task.tags {
+        parts.push(format!("@{}", tag.split_whitespace().collect::<Vec<_>>().join("_")));
+    }
+    if let Some(due) = task.due_date {
+        parts.push(format!("due:{}", format_date(due)));
+    }
+    if done && task.priority != Priority::default() {
+        parts.push(format!("pri:{}", priority_letter(task.priority)));
+    }
+    match task.status {
+        TaskStatus::InProgress => parts.push("status:in_progress".to_string()),
+        TaskStatus::Archived => parts.push("status:archived".to_string()),
+        TaskStatus::Pending | TaskStatus::Completed => {}
+    }
+    if let Some(preserved) = task
+        .description
+        .as_deref()
+        .and_then(|d| d.lines().find_map(|l| l.strip_prefix(PRESERVED_PREFIX)))
+    {
+        parts.extend(preserved.split_whitespace().map(str::to_string));
+    }
+
+    parts.join(" ")
+}
+
+/// Maps a priority to its todo.txt letter.
+fn priority_letter(priority: Priority) -> char {
+    match priority {
+        Priority::Urgent => 'A',
+        Priority::High => 'B',
+        Priority::Medium => 'C',
+        Priority::Low => 'D',
+    }
+}
+
+/// Maps a todo.txt priority letter to a priority.
+///
+/// Letters after `D` are all treated as low priority.
+fn parse_priority_letter(letter: &str) -> Option<Priority> {
+    let mut chars = letter.chars();
+    let c = chars.next()?;
+    if chars.next().is_some() || !c.is_ascii_uppercase() {
+        return None;
+    }
+    Some(match c {
+        'A' => Priority::Urgent,
+        'B' => Priority::High,
+        'C' => Priority::Medium,
+        _ => Priority::Low,
+    })
+}
+
+/// Parses a `(A)` style priority marker.
+fn parse_priority_marker(token: &str) -> Option<Priority> {
+    token
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .and_then(parse_priority_letter)
+}
+
+/// Splits a `key:value` extension token.
+///
+/// URLs such as `https://example.com` are not treated as extensions.
+fn split_extension(token: &str) -> Option<(&str, &str)> {
+    let (key, value) = token.split_once(':')?;
+    if key.is_empty() || value.is_empty() || value.starts_with("//") {
+        return None;
+    }
+    Some((key, value))
+}
+
+/// Returns `true` if a title starting with `token` would be misparsed.
+fn is_reserved_leading_token(token: &str) -> bool {
+    token == "x" || parse_priority_marker(token).is_some() || parse_date(token).is_some()
+}
+
+fn parse_date(token: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(token, "%Y-%m-%d").ok()
+}
+
+fn format_date(date: DateTime<Utc>) -> String {
+    date.with_timezone(&Local).format("%Y-%m-%d").to_string()
+}
+
+/// Converts a local calendar date to midnight local time, in UTC.
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    local_to_utc(date, NaiveTime::MIN)
+}
+
+/// Converts a local calendar date to 23:59:59 local time, in UTC.
+///
+/// Matches how due dates entered in the app are stored.
+fn end_of_day(date: NaiveDate) -> DateTime<Utc> {
+    local_to_utc(date, NaiveTime::from_hms_opt(23, 59, 59).unwrap())
+}
+
+fn local_to_utc(date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+    let naive = date.and_time(time);
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&naive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn date(s: &str) -> NaiveDate {
+        parse_date(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_blank_lines_skipped() {
+        assert!(parse_line("").is_none());
+        assert!(parse_line("   ").is_none());
+        assert_eq!(parse("Buy milk\n\nWalk dog\n").len(), 2);
+    }
+
+    #[test]
+    fn test_parse_pending_line() {
+        let item = parse_line("(B) 2026-01-30 Write notes +Ratado @docs @work due:2026-02-10").unwrap();
+        let task = item.task;
+
+        assert_eq!(task.title, "Write notes");
+        assert_eq!(task.priority, Priority::High);
+        assert_eq!(task.status, TaskStatus::Pending);
+        assert_eq!(item.project.as_deref(), Some("Ratado"));
+        assert_eq!(task.tags, vec!["docs", "work"]);
+        assert_eq!(task.created_at, start_of_day(date("2026-01-30")));
+        assert_eq!(task.due_date, Some(end_of_day(date("2026-02-10"))));
+        assert!(task.description.is_none());
+    }
+
+    #[test]
+    fn test_parse_priority_letters() {
+        assert_eq!(parse_line("(A) t").unwrap().task.priority, Priority::Urgent);
+        assert_eq!(parse_line("(C) t").unwrap().task.priority, Priority::Medium);
+        assert_eq!(parse_line("(D) t").unwrap().task.priority, Priority::Low);
+        assert_eq!(parse_line("(Z) t").unwrap().task.priority, Priority::Low);
+        assert_eq!(parse_line("t").unwrap().task.priority, Priority::Medium);
+        // Lowercase isn't a priority marker
+        assert_eq!(parse_line("(a) t").unwrap().task.title, "(a) t");
+    }
+
+    #[test]
+    fn test_parse_completed_line() {
+        let item = parse_line("x 2026-02-03 2026-01-28 Renew passport pri:A").unwrap();
+        let task = item.task;
+
+        assert_eq!(task.title, "Renew passport");
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert_eq!(task.priority, Priority::Urgent);
+        assert_eq!(task.completed_at, Some(start_of_day(date("2026-02-03"))));
+        assert_eq!(task.created_at, start_of_day(date("2026-01-28")));
+    }
+
+    #[test]
+    fn test_parse_completed_without_dates() {
+        let task = parse_line("x Done thing").unwrap().task;
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert!(task.completed_at.is_some());
+    }
+
+    #[test]
+    fn test_parse_single_date_after_x_is_completion() {
+        let task = parse_line("x 2026-02-03 Done").unwrap().task;
+        assert_eq!(task.completed_at, Some(start_of_day(date("2026-02-03"))));
+        assert_eq!(task.created_at, start_of_day(date("2026-02-03")));
+    }
+
+    #[test]
+    fn test_parse_preserves_unknown_tokens() {
+        let item = parse_line("Water plants +Home +Garden rec:1w t:2026-01-01 due:someday").unwrap();
+
+        assert_eq!(item.task.title, "Water plants");
+        assert_eq!(item.project.as_deref(), Some("Home"));
+        assert_eq!(
+            item.task.description.as_deref(),
+            Some("todo.txt: +Garden rec:1w t:2026-01-01 due:someday")
+        );
+        assert!(item.task.due_date.is_none());
+    }
+
+    #[test]
+    fn test_parse_urls_stay_in_title() {
+        let task = parse_line("Read https://example.com/post").unwrap().task;
+        assert_eq!(task.title, "Read https://example.com/post");
+        assert!(task.description.is_none());
+    }
+
+    #[test]
+    fn test_parse_status_extensions() {
+        assert_eq!(
+            parse_line("Draft status:in_progress").unwrap().task.status,
+            TaskStatus::InProgress
+        );
+        let archived = parse_line("x 2026-01-02 Old status:archived").unwrap().task;
+        assert_eq!(archived.status, TaskStatus::Archived);
+        assert!(archived.completed_at.is_some());
+    }
+
+    #[test]
+    fn test_format_pending_line() {
+        let project = Project::new("Side Project");
+        let mut task = Task::new("Ship it");
+        task.priority = Priority::Urgent;
+        task.project_id = Some(project.id.clone());
+        task.tags = vec!["release".to_string()];
+        task.created_at = start_of_day(date("2026-01-30"));
+        task.due_date = Some(end_of_day(date("2026-02-10")));
+
+        assert_eq!(
+            format_line(&task, &[project]),
+            "(A) 2026-01-30 Ship it +Side_Project @release due:2026-02-10"
+        );
+    }
+
+    #[test]
+    fn test_format_completed_line() {
+        let mut task = Task::new("Renew passport");
+        task.priority = Priority::Low;
+        task.created_at = start_of_day(date("2026-01-28"));
+        task.status = TaskStatus::Completed;
+        task.completed_at = Some(start_of_day(date("2026-02-03")));
+
+        assert_eq!(
+            format_line(&task, &[]),
+            "x 2026-02-03 2026-01-28 Renew passport pri:D"
+        );
+    }
+
+    #[test]
+    fn test_format_omits_default_priority() {
+        let mut task = Task::new("Water plants");
+        task.created_at = start_of_day(date("2026-01-28"));
+        let line = format_line(&task, &[]);
+        assert_eq!(line, "2026-01-28 Water plants");
+        assert_eq!(parse_line(&line).unwrap().task.priority, Priority::Medium);
+
+        task.status = TaskStatus::Completed;
+        task.completed_at = Some(start_of_day(date("2026-02-03")));
+        assert_eq!(format_line(&task, &[]), "x 2026-02-03 2026-01-28 Water plants");
+    }
+
+    #[test]
+    fn test_format_prefixes_reserved_title() {
+        let mut task = Task::new("x marks the spot");
+        task.created_at = start_of_day(date("2026-01-28"));
+        let line = format_line(&task, &[]);
+        assert_eq!(line, "2026-01-28 - x marks the spot");
+        assert_eq!(parse_line(&line).unwrap().task.title, "- x marks the spot");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = "(A) 2026-01-30 Write notes +Ratado @docs due:2026-02-10\n\
+                     x 2026-02-03 2026-01-28 Renew passport +Admin pri:B\n\
+                     2026-01-01 Draft plan +Ratado status:in_progress rec:1w +Extra\n\
+                     x 2026-02-04 2026-02-01 Plain chore\n";
+        let items = parse(input);
+        let projects: Vec<Project> = ["Ratado", "Admin"].iter().map(|n| Project::new(n)).collect();
+        let tasks: Vec<Task> = items
+            .into_iter()
+            .map(|item| {
+                let mut task = item.task;
+                task.project_id = item
+                    .project
+                    .and_then(|name| projects.iter().find(|p| p.name == name))
+                    .map(|p| p.id.clone());
+                task
+            })
+            .collect();
+
+        let refs: Vec<&Task> = tasks.iter().collect();
+        assert_eq!(
+            format(&refs, &projects),
+            "(A) 2026-01-30 Write notes +Ratado @docs due:2026-02-10\n\
+             x 2026-02-03 2026-01-28 Renew passport +Admin pri:B\n\
+             2026-01-01 Draft plan +Ratado status:in_progress rec:1w +Extra\n\
+             x 2026-02-04 2026-02-01 Plain chore\n"
+        );
+    }
+}
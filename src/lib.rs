@@ -13,19 +13,35 @@
 //!
 //! ## Modules
 //!
+//! - [`api`] - Local HTTP REST API (`ratado serve`)
 //! - [`app`] - Central application state management
+//! - [`config`] - User configuration file
 //! - [`models`] - Data structures (Task, Project, Filter, etc.)
 //! - [`handlers`] - Keyboard input and command handling
+//! - [`hooks`] - User scripts run when tasks change
+//! - [`interop`] - Import and export in external task formats
+//! - [`mcp`] - Model Context Protocol server for assistants (`ratado mcp`)
 //! - [`storage`] - Database operations
+//! - [`sync`] - Merging with other Ratado databases (`ratado sync`)
+//! - [`templates`] - Reusable recipes for one or more tasks
 //! - [`ui`] - Terminal UI widgets and views
 //! - [`utils`] - Helper functions for dates, IDs, etc.
+//! - [`webhooks`] - HTTP callbacks when tasks and projects change
 
+pub mod api;
 pub mod app;
+pub mod config;
 pub mod handlers;
+pub mod hooks;
+pub mod interop;
+pub mod mcp;
 pub mod models;
 pub mod storage;
+pub mod sync;
+pub mod templates;
 pub mod ui;
 pub mod utils;
+pub mod webhooks;
 
 pub use app::{App, AppError, FocusPanel, InputMode, View};
 pub use handlers::{handle_event, AppEvent, Command, EventHandler};
@@ -2,34 +2,221 @@
 //!
 //! A terminal-based task manager built with Rust and Ratatui.
 
-use std::io;
-use std::path::PathBuf;
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use clap::Parser;
+use chrono::Utc;
+use clap::{Parser, Subcommand, ValueEnum};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use log::info;
+use log::{info, warn};
 use ratatui::{backend::CrosstermBackend, Terminal};
 
+use ratado::api;
 use ratado::app::App;
-use ratado::handlers::{handle_event, EventHandler};
-use ratado::storage::{check_and_update_app_version, run_migrations, Database};
+use ratado::config::Config;
+use ratado::hooks::Hooks;
+use ratado::handlers::{handle_event, rpc, EventHandler};
+use ratado::models::SortOrder;
+use ratado::interop::report::{self, ReportColumn, ReportFormat, ReportGroup, ReportOptions};
+use ratado::interop::standup::{Standup, StandupFormat};
+use ratado::interop::{import_tasks, taskwarrior, todotxt};
+use ratado::mcp;
+use ratado::storage::{
+    change_key, check_and_update_app_version, is_encrypted, list_backups, restore_backup,
+    run_migrations, Database, EncryptionKey, StorageError,
+};
+use ratado::sync;
 use ratado::ui;
+use ratado::utils::copy_to_clipboard;
+use ratado::templates::Templates;
+use ratado::webhooks;
 
 /// A fast, keyboard-driven terminal task manager
 #[derive(Parser)]
 #[command(name = "ratado")]
 #[command(version, about, long_about = None)]
 struct Cli {
-    /// Path to the database file (defaults to platform-specific location)
-    #[arg(short = 'd', long)]
+    /// Path to the database file, or a directory for plaintext storage
+    /// (defaults to platform-specific location)
+    #[arg(short = 'd', long, conflicts_with = "profile")]
     db_path: Option<PathBuf>,
+
+    /// Name of a profile from config.toml to open
+    #[arg(short = 'p', long)]
+    profile: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<CliCommand>,
 }
 
+/// One-shot commands that run without starting the TUI.
+#[derive(Subcommand)]
+enum CliCommand {
+    /// Import tasks from a file
+    Import {
+        /// File to import
+        file: PathBuf,
+
+        /// Format of the file
+        #[arg(short, long, value_enum)]
+        format: TaskFormat,
+    },
+    /// Export tasks, optionally filtered and sorted like the task list
+    Export {
+        /// Output format
+        #[arg(short, long, value_enum)]
+        format: ExportFormat,
+
+        /// File to write (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Which tasks to export: all, pending, in-progress, completed, archived,
+        /// today, week, overdue, project:NAME, tag:NAME or priority:LEVEL
+        #[arg(long, default_value = "all")]
+        filter: String,
+
+        /// Task order: due, due-desc, priority, priority-asc, newest, oldest, alpha or manual
+        #[arg(long, default_value = "due", value_parser = report::parse_sort)]
+        sort: SortOrder,
+
+        /// Comma-separated report columns (md/csv): id, title, status, priority,
+        /// project, tags, due, created, completed, description, notes
+        #[arg(long, value_delimiter = ',')]
+        columns: Vec<ReportColumn>,
+
+        /// Group report rows (md/csv): none, project, tag, status or due-week
+        #[arg(long, default_value = "none")]
+        group_by: ReportGroup,
+    },
+    /// Summarize what was done since the last workday and what's next
+    ///
+    /// Lists tasks completed since the last workday, in progress, due today
+    /// and blocked or overdue, grouped by project.
+    Standup {
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: StandupOutput,
+
+        /// Also copy the report to the clipboard (OSC 52)
+        #[arg(long)]
+        copy: bool,
+    },
+    /// Serve a local HTTP REST API for tasks, projects and tags
+    ///
+    /// While the TUI is running, requests are passed to it through its
+    /// control socket, and its window shows their changes.
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8765")]
+        bind: SocketAddr,
+
+        /// Require `Authorization: Bearer <TOKEN>` on every request
+        /// (defaults to the RATADO_API_TOKEN environment variable)
+        #[arg(long)]
+        token: Option<String>,
+    },
+    /// Serve the Model Context Protocol on stdio for AI assistants
+    ///
+    /// While the TUI is running, tool calls are passed to it through its
+    /// control socket, and its window shows their changes.
+    Mcp,
+    /// Merge with another Ratado database, or a folder shared between machines
+    ///
+    /// A database file is merged both ways. In a folder, every other
+    /// machine's copy is merged in, then this database is published there.
+    Sync {
+        /// Database file or sync folder
+        target: PathBuf,
+    },
+    /// Call a method on the running TUI through its control socket
+    ///
+    /// Methods: add_task text=..., focus_task id=..., switch_view view=...,
+    /// set_filter filter=..., refresh, state
+    Rpc {
+        /// Method name
+        method: String,
+
+        /// Params as key=value pairs, or a single JSON object
+        params: Vec<String>,
+    },
+    /// List or restore database backups
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+    /// Encrypt the database with a passphrase
+    ///
+    /// The passphrase is read from RATADO_PASSPHRASE, the `[encryption]
+    /// key_file` setting, or asked for.
+    Encrypt,
+    /// Remove the database's encryption
+    Decrypt,
+    /// Change the passphrase of an encrypted database
+    ///
+    /// The new passphrase is read from RATADO_NEW_PASSPHRASE or asked for.
+    Rekey,
+}
+
+/// Actions of the `backup` command.
+#[derive(Subcommand)]
+enum BackupAction {
+    /// List backups of the database, newest first
+    List,
+    /// Replace the database with a backup (the current one is backed up first)
+    Restore {
+        /// Backup ID, as shown by `ratado backup list`
+        id: String,
+    },
+}
+
+/// External task formats supported by `import`.
+#[derive(Clone, Copy, ValueEnum)]
+enum TaskFormat {
+    /// todo.txt plain text (http://todotxt.org)
+    Todotxt,
+    /// Taskwarrior JSON (`task export` / `task import`)
+    Taskwarrior,
+}
+
+/// Formats supported by `export`.
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    /// todo.txt plain text (http://todotxt.org)
+    Todotxt,
+    /// Taskwarrior JSON (`task export` / `task import`)
+    Taskwarrior,
+    /// Markdown checklist report
+    Md,
+    /// CSV report
+    Csv,
+}
+
+/// Formats supported by `standup`.
+#[derive(Clone, Copy, ValueEnum)]
+enum StandupOutput {
+    /// Plain text
+    Text,
+    /// Markdown
+    Md,
+}
+
+/// Environment variable holding the database passphrase.
+const PASSPHRASE_ENV: &str = "RATADO_PASSPHRASE";
+
+/// Control socket method that runs a command line in the TUI, for commands
+/// started while the TUI holds the database.
+const RUN_COMMAND_METHOD: &str = "run_command";
+
+/// Environment variable holding the new passphrase for `rekey`.
+const NEW_PASSPHRASE_ENV: &str = "RATADO_NEW_PASSPHRASE";
+
 /// Tick rate for the event loop (60fps).
 ///
 /// Polling at 16ms is nearly free (just an OS syscall). The `needs_redraw` flag
@@ -37,7 +224,16 @@ struct Cli {
 const TICK_RATE: Duration = Duration::from_millis(16);
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() {
+    // Errors are shown with their message, not their debug form
+    if let Err(e) = run().await {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Runs the command line, or the TUI when no command is given.
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // Parse CLI arguments (handles --version and --help automatically)
     let cli = Cli::parse();
 
@@ -47,6 +243,84 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     info!("Starting Ratado v{}", env!("CARGO_PKG_VERSION"));
 
+    let config = Config::load_default()?;
+    let profile = match &cli.profile {
+        Some(name) => Some(config.profile(name)?.clone()),
+        None => None,
+    };
+    let db_path = match cli
+        .db_path
+        .or_else(|| profile.as_ref().map(|p| p.db_path.clone()))
+        .or_else(|| config.storage.dir.clone())
+    {
+        Some(path) => path,
+        None => Database::default_path()?,
+    };
+
+    // One-shot commands don't touch the terminal
+    match cli.command {
+        // Restoring replaces the database file, so it must not be open
+        Some(CliCommand::Backup { action }) => {
+            return run_backup_command(&db_path, &config, action).await;
+        }
+        // The running TUI holds the database; talk to it instead
+        Some(CliCommand::Rpc { method, params }) => {
+            return run_rpc_command(&db_path, &method, &params).await;
+        }
+        // These replace the database file, so it must not be open
+        Some(command @ (CliCommand::Encrypt | CliCommand::Decrypt | CliCommand::Rekey)) => {
+            return run_encryption_command(&db_path, &config, command).await;
+        }
+        // A running TUI holds the database; let it run the tool calls
+        Some(CliCommand::Mcp) if tui_is_running(&db_path).await => {
+            return mcp_through_tui(&db_path).await;
+        }
+        Some(command) => {
+            let db = match open_database(&db_path, &config, true).await {
+                Ok(db) => db,
+                // The running TUI holds the database; let it run the command
+                Err(e) if is_locked(&*e) && is_forwardable(&command) => {
+                    return forward_command(&db_path, e).await;
+                }
+                Err(e) if is_locked(&*e) && matches!(command, CliCommand::Serve { .. }) => {
+                    return serve_through_tui(&db_path, command, e).await;
+                }
+                Err(e) => return Err(e),
+            };
+            // Servers deliver webhooks as they go; other commands send what
+            // they queued before exiting
+            let delivery = webhooks::spawn(db.clone());
+            let result = run_command(&db, &config, command, &mut io::stdout(), &mut io::stderr()).await;
+            if let Some(delivery) = delivery {
+                delivery.abort();
+                webhooks::deliver_due(&db, &webhooks::client(), Utc::now()).await?;
+            }
+            return result;
+        }
+        None => {}
+    }
+
+    // Initialize database before the terminal, so a passphrase can be asked for
+    let db = match open_database(&db_path, &config, true).await {
+        Ok(db) => db,
+        // Only one TUI can hold the database; point at the one that does
+        Err(e) if is_locked(&*e) && tui_is_running(&db_path).await => {
+            return Err(format!(
+                "Ratado is already open on {}; use that window, or run commands such as `ratado import` which it runs for you",
+                db_path.display()
+            )
+            .into());
+        }
+        Err(e) => return Err(e),
+    };
+    if config.backup.on_startup {
+        db.backup("startup").await?;
+    }
+    let templates = match Templates::default_dir() {
+        Some(dir) => Templates::load(&dir)?,
+        None => Templates::default(),
+    };
+
     // Setup panic hook to restore terminal on crash
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
@@ -62,22 +336,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Initialize database
-    let db_path = match cli.db_path {
-        Some(path) => path,
-        None => Database::default_path()?,
-    };
-    info!("Opening database at {:?}", db_path);
-    let db = Database::open(&db_path).await?;
-    run_migrations(&db).await?;
-    check_and_update_app_version(&db).await?;
-
     // Initialize app
     let mut app = App::new(db).await?;
+    app.set_profile(cli.profile, profile.unwrap_or_default());
+    app.profile_names = config.profiles.keys().cloned().collect();
+    app.templates = templates;
+    app.review_config = config.review;
+    app.standup_config = config.standup.clone();
     info!("App initialized with {} tasks", app.tasks.len());
 
     // Run the main loop
-    let result = run_app(&mut terminal, &mut app).await;
+    let result = run_app(&mut terminal, &mut app, &db_path, &config).await;
 
     // Restore terminal
     restore_terminal()?;
@@ -92,6 +361,532 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Opens the database and brings its schema up to date.
+///
+/// The passphrase of an encrypted database is asked for only if `prompt`
+/// is set; otherwise it must be configured.
+async fn open_database(
+    db_path: &Path,
+    config: &Config,
+    prompt: bool,
+) -> Result<Database, Box<dyn std::error::Error>> {
+    info!("Opening database at {:?}", db_path);
+    let mut db = if db_path.is_dir() {
+        Database::open_dir(db_path).await?
+    } else {
+        let key = database_key(db_path, config, prompt)?;
+        Database::open_with_key(db_path, key.as_ref()).await?
+    };
+    db.set_backup_retention(config.backup.keep);
+    db.set_webhooks(config.webhooks.clone());
+    db.set_new_task_placement(config.ordering.new_tasks);
+    if let Some(dir) = Hooks::default_dir() {
+        let hooks = Hooks::load(&dir)?;
+        if !hooks.is_empty() {
+            info!("Loaded hooks from {:?}", dir);
+        }
+        db.set_hooks(hooks);
+    }
+    run_migrations(&db).await?;
+    check_and_update_app_version(&db).await?;
+    Ok(db)
+}
+
+/// Returns whether opening the database failed because another process
+/// holds it.
+fn is_locked(error: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(error.downcast_ref(), Some(StorageError::Locked(_)))
+}
+
+/// Returns whether a command can be run by the TUI holding the database.
+fn is_forwardable(command: &CliCommand) -> bool {
+    matches!(
+        command,
+        CliCommand::Import { .. } | CliCommand::Export { .. } | CliCommand::Standup { .. } | CliCommand::Sync { .. }
+    )
+}
+
+/// Runs a one-shot CLI command against the database, writing its output
+/// to `out` and messages to `err`.
+async fn run_command(
+    db: &Database,
+    config: &Config,
+    command: CliCommand,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        CliCommand::Import { file, format } => {
+            let input = std::fs::read_to_string(&file)?;
+            let items = match format {
+                TaskFormat::Todotxt => todotxt::parse(&input),
+                TaskFormat::Taskwarrior => taskwarrior::parse(&input)?,
+            };
+            db.backup("pre-import").await?;
+            let summary = import_tasks(db, items).await?;
+            writeln!(
+                out,
+                "Imported {} tasks from {} ({} updated, {} new projects)",
+                summary.created + summary.updated,
+                file.display(),
+                summary.updated,
+                summary.projects_created
+            )?;
+            if summary.rejected > 0 {
+                writeln!(out, "Skipped {} tasks rejected by hooks", summary.rejected)?;
+            }
+        }
+        CliCommand::Export {
+            format,
+            output,
+            filter,
+            sort,
+            columns,
+            group_by,
+        } => {
+            let all_tasks = db.get_all_tasks().await?;
+            let projects = db.get_all_projects().await?;
+            let filter = report::parse_filter(&filter, &projects)?;
+            let mut tasks = filter.apply(&all_tasks);
+            sort.apply(&mut tasks);
+
+            let options = ReportOptions {
+                columns: if columns.is_empty() {
+                    ReportColumn::DEFAULT.to_vec()
+                } else {
+                    columns
+                },
+                group_by,
+            };
+            let content = match format {
+                ExportFormat::Todotxt => todotxt::format(&tasks, &projects),
+                ExportFormat::Taskwarrior => taskwarrior::format(&tasks, &projects),
+                ExportFormat::Md => report::render(ReportFormat::Markdown, &tasks, &projects, &options),
+                ExportFormat::Csv => report::render(ReportFormat::Csv, &tasks, &projects, &options),
+            };
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, content)?;
+                    writeln!(err, "Exported {} tasks to {}", tasks.len(), path.display())?;
+                }
+                None => write!(out, "{}", content)?,
+            }
+        }
+        CliCommand::Standup { format, copy } => {
+            let tasks = db.get_all_tasks().await?;
+            let projects = db.get_all_projects().await?;
+            let format = match format {
+                StandupOutput::Text => StandupFormat::Text,
+                StandupOutput::Md => StandupFormat::Markdown,
+            };
+            let content = Standup::build(&tasks, Utc::now(), &config.standup).render(format, &projects);
+            write!(out, "{}", content)?;
+            if copy {
+                // stdout may be piped, so the terminal is reached through stderr
+                copy_to_clipboard(&content, err)?;
+                writeln!(err, "Copied to clipboard")?;
+            }
+        }
+        CliCommand::Serve { bind, token } => {
+            let token = api_token(token, bind, err)?;
+            writeln!(err, "Serving Ratado API on http://{} (Ctrl+C to stop)", bind)?;
+            api::serve(db.clone(), bind, token).await?;
+        }
+        CliCommand::Mcp => mcp::serve_stdio(mcp::Tools::Database(db)).await?,
+        CliCommand::Sync { target } => {
+            db.backup("pre-sync").await?;
+            let report = sync::sync_path(db, &target).await?;
+            writeln!(
+                out,
+                "Synced with {} database(s): {} changes received, {} sent",
+                report.peers, report.received, report.sent
+            )?;
+            for conflict in &report.conflicts {
+                writeln!(out, "Conflict: {}", conflict)?;
+            }
+            if let Some(path) = report.published {
+                writeln!(out, "Published to {}", path.display())?;
+            }
+        }
+        CliCommand::Backup { .. }
+        | CliCommand::Rpc { .. }
+        | CliCommand::Encrypt
+        | CliCommand::Decrypt
+        | CliCommand::Rekey => {
+            unreachable!("these commands run without opening the database")
+        }
+    }
+    Ok(())
+}
+
+/// Runs a `backup` subcommand.
+async fn run_backup_command(
+    db_path: &Path,
+    config: &Config,
+    action: BackupAction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if db_path.is_dir() {
+        return Err(format!(
+            "{} is a plaintext store; use git to see and restore earlier versions",
+            db_path.display()
+        )
+        .into());
+    }
+    match action {
+        BackupAction::List => {
+            let backups = list_backups(db_path)?;
+            if backups.is_empty() {
+                eprintln!("No backups of {}", db_path.display());
+            }
+            for backup in backups {
+                println!(
+                    "{}  {}  {:>8} KB  {}",
+                    backup.id,
+                    backup.created_at.format("%Y-%m-%d %H:%M:%S"),
+                    backup.size.div_ceil(1024),
+                    backup.reason
+                );
+            }
+        }
+        BackupAction::Restore { id } => {
+            let key = database_key(db_path, config, true)?;
+            let backup = restore_backup(db_path, &id, config.backup.keep, key.as_ref()).await?;
+            println!(
+                "Restored {} from backup {} (taken {})",
+                db_path.display(),
+                backup.id,
+                backup.created_at.format("%Y-%m-%d %H:%M:%S")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Runs `encrypt`, `decrypt` or `rekey`, rewriting the database file.
+async fn run_encryption_command(
+    db_path: &Path,
+    config: &Config,
+    command: CliCommand,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if db_path.is_dir() {
+        return Err(format!(
+            "{} is a plaintext store, which can't be encrypted",
+            db_path.display()
+        )
+        .into());
+    }
+    match command {
+        CliCommand::Encrypt => {
+            if is_encrypted(db_path)? {
+                return Err(format!("{} is already encrypted", db_path.display()).into());
+            }
+            let passphrase = match configured_passphrase(config)? {
+                Some(passphrase) => passphrase,
+                None => prompt_new_passphrase()?,
+            };
+            let key = EncryptionKey::from_passphrase(&passphrase)?;
+            // Plaintext backups are encrypted along with the database
+            change_key(db_path, None, Some(&key)).await?;
+            match list_backups(db_path)?.len() {
+                0 => println!("Encrypted {}", db_path.display()),
+                n => println!("Encrypted {} and its {} backup(s)", db_path.display(), n),
+            }
+        }
+        CliCommand::Decrypt => {
+            let key = database_key(db_path, config, true)?
+                .ok_or_else(|| format!("{} is not encrypted", db_path.display()))?;
+            change_key(db_path, Some(&key), None).await?;
+            println!("Decrypted {}", db_path.display());
+        }
+        CliCommand::Rekey => {
+            let key = database_key(db_path, config, true)?
+                .ok_or_else(|| format!("{} is not encrypted", db_path.display()))?;
+            let passphrase = match std::env::var(NEW_PASSPHRASE_ENV) {
+                Ok(passphrase) => passphrase,
+                Err(_) => prompt_new_passphrase()?,
+            };
+            let new_key = EncryptionKey::from_passphrase(&passphrase)?;
+            change_key(db_path, Some(&key), Some(&new_key)).await?;
+            println!("Changed the passphrase of {}", db_path.display());
+        }
+        _ => unreachable!("not an encryption command"),
+    }
+    Ok(())
+}
+
+/// Returns the key for the database at `db_path` if it's encrypted, from
+/// the configured passphrase or, if `prompt` is set, by asking for it.
+fn database_key(
+    db_path: &Path,
+    config: &Config,
+    prompt: bool,
+) -> Result<Option<EncryptionKey>, Box<dyn std::error::Error>> {
+    if !is_encrypted(db_path)? {
+        return Ok(None);
+    }
+    let passphrase = match configured_passphrase(config)? {
+        Some(passphrase) => passphrase,
+        None if !prompt => return Err(StorageError::KeyRequired(db_path.to_path_buf()).into()),
+        None => rpassword::prompt_password(format!("Passphrase for {}: ", db_path.display()))
+            .map_err(|_| {
+                format!(
+                    "{}; set {} or [encryption] key_file when there is no terminal to ask on",
+                    StorageError::KeyRequired(db_path.to_path_buf()),
+                    PASSPHRASE_ENV
+                )
+            })?,
+    };
+    Ok(Some(EncryptionKey::from_passphrase(&passphrase)?))
+}
+
+/// Returns the passphrase from RATADO_PASSPHRASE or the configured key
+/// file, if either is set.
+fn configured_passphrase(config: &Config) -> io::Result<Option<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(Some(passphrase));
+    }
+    let Some(path) = &config.encryption.key_file else {
+        return Ok(None);
+    };
+    let content = std::fs::read_to_string(path)?;
+    Ok(Some(content.lines().next().unwrap_or_default().to_string()))
+}
+
+/// Asks for a new passphrase twice.
+fn prompt_new_passphrase() -> Result<String, Box<dyn std::error::Error>> {
+    let passphrase = rpassword::prompt_password("New passphrase: ")?;
+    if rpassword::prompt_password("Repeat passphrase: ")? != passphrase {
+        return Err("Passphrases don't match".into());
+    }
+    Ok(passphrase)
+}
+
+/// Runs an `rpc` command against the running TUI and prints the result.
+#[cfg(unix)]
+async fn run_rpc_command(
+    db_path: &Path,
+    method: &str,
+    params: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let params = parse_rpc_params(params)?;
+    let result = rpc::call(&rpc::socket_path(db_path), method, params).await?;
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    Ok(())
+}
+
+/// Runs an `rpc` command against the running TUI and prints the result.
+#[cfg(not(unix))]
+async fn run_rpc_command(
+    _db_path: &Path,
+    _method: &str,
+    _params: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err("The control socket is only available on Unix".into())
+}
+
+/// Runs the command line in the TUI that holds the database at `db_path`,
+/// printing its output.
+///
+/// Returns `locked` if no TUI is listening, since then the database is
+/// held by some other process.
+#[cfg(unix)]
+async fn forward_command(
+    db_path: &Path,
+    locked: Box<dyn std::error::Error>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args_os()
+        .skip(1)
+        .map(|arg| arg.to_string_lossy().into_owned())
+        .collect();
+    let params = serde_json::json!({ "args": args, "cwd": std::env::current_dir()? });
+    let output = match rpc::call(&rpc::socket_path(db_path), RUN_COMMAND_METHOD, params).await {
+        Ok(output) => output,
+        Err(rpc::ClientError::NotRunning(_)) => return Err(locked),
+        Err(e) => return Err(e.into()),
+    };
+    print!("{}", output["stdout"].as_str().unwrap_or_default());
+    eprint!("{}", output["stderr"].as_str().unwrap_or_default());
+    match output["error"].as_str() {
+        Some(error) => Err(error.into()),
+        None => Ok(()),
+    }
+}
+
+/// Runs the command line in the TUI that holds the database at `db_path`.
+#[cfg(not(unix))]
+async fn forward_command(
+    _db_path: &Path,
+    locked: Box<dyn std::error::Error>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err(locked)
+}
+
+/// Serves the API by passing requests to the TUI that holds the database
+/// at `db_path`.
+///
+/// Returns `locked` if no TUI is listening.
+#[cfg(unix)]
+async fn serve_through_tui(
+    db_path: &Path,
+    command: CliCommand,
+    locked: Box<dyn std::error::Error>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let CliCommand::Serve { bind, token } = command else {
+        return Err(locked);
+    };
+    if !tui_is_running(db_path).await {
+        return Err(locked);
+    }
+    let socket = rpc::socket_path(db_path);
+    let token = api_token(token, bind, &mut io::stderr())?;
+    eprintln!("Serving Ratado API on http://{} through the running TUI (Ctrl+C to stop)", bind);
+    api::serve_forwarded(socket, bind, token).await?;
+    Ok(())
+}
+
+/// Serves the API by passing requests to the TUI that holds the database
+/// at `db_path`.
+#[cfg(not(unix))]
+async fn serve_through_tui(
+    _db_path: &Path,
+    _command: CliCommand,
+    locked: Box<dyn std::error::Error>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err(locked)
+}
+
+/// Serves MCP on stdio, running the tool calls in the TUI that holds the
+/// database at `db_path`.
+#[cfg(unix)]
+async fn mcp_through_tui(db_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    mcp::serve_stdio(mcp::Tools::Forwarded(rpc::socket_path(db_path))).await?;
+    Ok(())
+}
+
+/// Serves MCP on stdio, running the tool calls in the TUI that holds the
+/// database at `db_path`.
+#[cfg(not(unix))]
+async fn mcp_through_tui(_db_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    unreachable!("no control socket to reach the TUI through")
+}
+
+/// Returns whether a TUI is answering on the control socket of the
+/// database at `db_path`.
+#[cfg(unix)]
+async fn tui_is_running(db_path: &Path) -> bool {
+    rpc::call(&rpc::socket_path(db_path), "state", serde_json::Value::Null)
+        .await
+        .is_ok()
+}
+
+/// Returns whether a TUI is answering on the control socket of the
+/// database at `db_path`.
+#[cfg(not(unix))]
+async fn tui_is_running(_db_path: &Path) -> bool {
+    false
+}
+
+/// Returns the API token to require, warning on `err` if the server is
+/// reachable from other machines without one.
+fn api_token(
+    token: Option<String>,
+    bind: SocketAddr,
+    err: &mut dyn Write,
+) -> io::Result<Option<String>> {
+    let token = token.or_else(|| std::env::var("RATADO_API_TOKEN").ok());
+    if token.is_none() && !bind.ip().is_loopback() {
+        writeln!(
+            err,
+            "Warning: serving on {} without a token; anyone who can reach it can change your tasks",
+            bind
+        )?;
+    }
+    Ok(token)
+}
+
+/// Runs a command line forwarded by [`forward_command`] against the app's
+/// database, then reloads the items it changed.
+///
+/// Paths are resolved against the caller's working directory. The result
+/// holds the command's output and error, if any.
+async fn run_forwarded_command(
+    app: &mut App,
+    config: &Config,
+    params: &serde_json::Value,
+) -> Result<serde_json::Value, rpc::RpcError> {
+    let args: Vec<String> = serde_json::from_value(params["args"].clone())
+        .map_err(|_| rpc::RpcError::invalid_params("Missing args"))?;
+    let cwd = PathBuf::from(params["cwd"].as_str().unwrap_or_default());
+    let cli = Cli::try_parse_from(std::iter::once("ratado".to_string()).chain(args))
+        .map_err(|e| rpc::RpcError::invalid_params(e.to_string()))?;
+    let command = match cli.command {
+        Some(command) if is_forwardable(&command) => with_absolute_paths(command, &cwd),
+        _ => {
+            return Err(rpc::RpcError::invalid_params(
+                "Only import, export, standup and sync can be run by the TUI",
+            ));
+        }
+    };
+
+    let (mut out, mut err) = (Vec::new(), Vec::new());
+    let result = app
+        .run_remote(async |db| run_command(db, config, command, &mut out, &mut err).await)
+        .await
+        .map_err(|e| rpc::RpcError::new(rpc::RpcError::APP_ERROR, e.to_string()))?;
+    Ok(serde_json::json!({
+        "stdout": String::from_utf8_lossy(&out),
+        "stderr": String::from_utf8_lossy(&err),
+        "error": result.err().map(|e| e.to_string()),
+    }))
+}
+
+/// Makes the file paths of a command absolute, relative to `cwd`.
+fn with_absolute_paths(command: CliCommand, cwd: &Path) -> CliCommand {
+    match command {
+        CliCommand::Import { file, format } => CliCommand::Import {
+            file: cwd.join(file),
+            format,
+        },
+        CliCommand::Export {
+            format,
+            output,
+            filter,
+            sort,
+            columns,
+            group_by,
+        } => CliCommand::Export {
+            format,
+            output: output.map(|path| cwd.join(path)),
+            filter,
+            sort,
+            columns,
+            group_by,
+        },
+        CliCommand::Sync { target } => CliCommand::Sync {
+            target: cwd.join(target),
+        },
+        command => command,
+    }
+}
+
+/// Parses `rpc` params given as `key=value` pairs or a single JSON object.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn parse_rpc_params(params: &[String]) -> Result<serde_json::Value, String> {
+    if let [json] = params
+        && json.trim_start().starts_with('{')
+    {
+        return serde_json::from_str(json).map_err(|e| format!("Invalid JSON params: {}", e));
+    }
+    let mut object = serde_json::Map::new();
+    for param in params {
+        let (key, value) = param
+            .split_once('=')
+            .ok_or_else(|| format!("Expected key=value, got: {}", param))?;
+        object.insert(key.to_string(), serde_json::Value::String(value.to_string()));
+    }
+    Ok(serde_json::Value::Object(object))
+}
+
 /// Restores the terminal to its original state.
 fn restore_terminal() -> io::Result<()> {
     disable_raw_mode()?;
@@ -105,18 +900,29 @@ fn restore_terminal() -> io::Result<()> {
 
 /// Main application loop.
 ///
-/// Handles rendering, input events, and ticks until the app signals to quit.
+/// Handles rendering, input events, remote calls, and ticks until the app signals to quit.
 /// Runs at a fixed 16ms tick (60fps). The `needs_redraw` flag skips rendering
 /// when nothing has changed, keeping idle CPU usage low.
 async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    db_path: &Path,
+    config: &Config,
 ) -> Result<(), Box<dyn std::error::Error>>
 where
     <B as ratatui::backend::Backend>::Error: 'static,
 {
     let mut events = EventHandler::new(TICK_RATE);
 
+    // Let other programs drive this instance; the socket is removed on exit
+    #[cfg(unix)]
+    let mut control_socket = bind_control_socket(db_path, &events);
+    #[cfg(not(unix))]
+    let _ = db_path;
+
+    // Delivers queued webhook events until the app exits
+    let mut webhook_delivery = webhooks::spawn(app.db.clone());
+
     let mut needs_redraw = true;
 
     loop {
@@ -134,6 +940,9 @@ where
 
                 // Process pending task animations using render info
                 if let Some(render_info) = ui::task_list::take_last_render_info() {
+                    // Remember where rows are for mouse clicks and drags
+                    app.last_task_list_area = Some(render_info.content_area);
+                    app.last_list_scroll_offset = render_info.scroll_offset;
                     // New task coalesce animation
                     if let Some(task_id) = app.pending_new_task_animation.take() {
                         if let Some(rect) = render_info.find_task_rect(&task_id) {
@@ -158,6 +967,11 @@ where
                 app.animation.process(frame.buffer_mut(), area);
             })?;
             needs_redraw = false;
+
+            // The clipboard is set by the terminal, through an escape sequence
+            if let Some(text) = app.pending_clipboard.take() {
+                copy_to_clipboard(&text, &mut io::stdout())?;
+            }
         }
 
         // Wait for and handle the next event
@@ -165,7 +979,8 @@ where
             match &event {
                 ratado::handlers::AppEvent::Key(_)
                 | ratado::handlers::AppEvent::Resize(_, _)
-                | ratado::handlers::AppEvent::Mouse(_) => {
+                | ratado::handlers::AppEvent::Mouse(_)
+                | ratado::handlers::AppEvent::Remote(_) => {
                     needs_redraw = true;
                 }
                 ratado::handlers::AppEvent::Tick => {
@@ -183,16 +998,86 @@ where
             }
 
             // handle_event returns false when the app should quit
-            if !handle_event(app, event).await? {
+            let running = match event {
+                // Commands started on the command line while this instance
+                // holds the database
+                ratado::handlers::AppEvent::Remote(call) if call.method == RUN_COMMAND_METHOD => {
+                    let result = run_forwarded_command(app, config, &call.params).await;
+                    call.respond(result);
+                    true
+                }
+                event => handle_event(app, event).await?,
+            };
+            if !running {
                 break;
             }
+            // Saves finished, or changes made elsewhere were reloaded
+            if std::mem::take(&mut app.redraw_requested) {
+                needs_redraw = true;
+            }
         }
 
         // Double-check quit flag (in case command set it without returning false)
         if app.should_quit {
             break;
         }
+
+        // Open the profile chosen in the switcher in place of the current one
+        if let Some(name) = app.requested_profile.take() {
+            needs_redraw = true;
+            let opened = match config.profile(&name) {
+                Ok(profile) => open_database(&profile.db_path, config, false)
+                    .await
+                    .map(|db| (db, profile.clone())),
+                Err(e) => Err(e.into()),
+            };
+            let (db, profile) = match opened {
+                Ok(opened) => opened,
+                Err(e) => {
+                    app.set_status(format!("Couldn't open profile {}: {}", name, e));
+                    continue;
+                }
+            };
+            if config.backup.on_startup
+                && let Err(e) = db.backup("profile-switch").await
+            {
+                app.set_status(format!("Couldn't back up profile {}: {}", name, e));
+                continue;
+            }
+
+            // Let go of the old database before using the new one
+            if let Some(delivery) = webhook_delivery.take() {
+                delivery.abort();
+            }
+            #[cfg(unix)]
+            {
+                drop(control_socket.take());
+                control_socket = bind_control_socket(&profile.db_path, &events);
+            }
+            let db_path = profile.db_path.clone();
+            app.switch_database(db, name.clone(), profile).await?;
+            webhook_delivery = webhooks::spawn(app.db.clone());
+            info!("Switched to profile {} at {:?}", name, db_path);
+            app.set_status(format!("Switched to profile {}", name));
+        }
     }
 
+    // Changes still waiting for their hooks are saved before exiting
+    app.wait_for_saves().await?;
     Ok(())
 }
+
+/// Listens for remote calls on the control socket of the database at `db_path`.
+#[cfg(unix)]
+fn bind_control_socket(db_path: &Path, events: &EventHandler) -> Option<rpc::ControlSocket> {
+    match rpc::ControlSocket::bind(&rpc::socket_path(db_path), events.sender()) {
+        Ok(socket) => {
+            info!("Listening for remote calls on {:?}", socket.path());
+            Some(socket)
+        }
+        Err(e) => {
+            warn!("Control socket unavailable: {}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,350 @@
+//! Model Context Protocol server (`ratado mcp`).
+//!
+//! Speaks [MCP](https://modelcontextprotocol.io) over stdio, one JSON-RPC 2.0
+//! message per line, so assistants in editors can read and manage tasks
+//! without any network service. The server exposes these tools:
+//!
+//! | Tool            | Does                                                    |
+//! |-----------------|---------------------------------------------------------|
+//! | `list_tasks`    | Lists tasks by filter, sort, project and tag            |
+//! | `search`        | Searches titles, descriptions and tags                  |
+//! | `create_task`   | Creates a task from a Quick Capture string              |
+//! | `update_task`   | Changes a task's fields                                 |
+//! | `complete_task` | Marks a task completed                                  |
+//! | `list_projects` | Lists projects with their task counts                   |
+//!
+//! Tool results are JSON text. A failing tool call (unknown task, invalid
+//! arguments) is reported as a result with `isError` set, so the assistant
+//! can read the message and correct itself.
+//!
+//! While the TUI holds the database, the server sends tool calls to it
+//! through its control socket ([`Tools::Forwarded`]), so its window shows
+//! their changes; it opens the database itself only when no TUI is running.
+
+mod tools;
+
+#[cfg(unix)]
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::{self, AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::handlers::rpc::{self, RpcError};
+use crate::storage::Database;
+
+/// The MCP revision the server implements.
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Earlier revisions the server also speaks; tools are unchanged across them.
+const SUPPORTED_VERSIONS: &[&str] = &[PROTOCOL_VERSION, "2025-03-26", "2024-11-05"];
+
+/// Control socket method that runs a tool call in the TUI.
+pub const FORWARD_METHOD: &str = "mcp_tool_call";
+
+/// Where the server runs tool calls.
+pub enum Tools<'a> {
+    /// Against a database the server opened
+    Database(&'a Database),
+    /// In the TUI listening on this control socket
+    #[cfg(unix)]
+    Forwarded(PathBuf),
+}
+
+impl Tools<'_> {
+    /// Runs a `tools/call` request, returning its result.
+    async fn call(&self, params: Value) -> Result<Value, RpcError> {
+        match self {
+            Tools::Database(db) => call_tool(db, &params).await,
+            #[cfg(unix)]
+            Tools::Forwarded(socket) => match rpc::call(socket, FORWARD_METHOD, params).await {
+                Ok(result) => Ok(result),
+                Err(rpc::ClientError::Rpc(error)) => Err(error),
+                Err(e) => Err(RpcError::new(RpcError::APP_ERROR, e.to_string())),
+            },
+        }
+    }
+}
+
+/// Serves MCP on stdin/stdout until stdin is closed.
+///
+/// # Errors
+///
+/// Returns an error if reading stdin or writing stdout fails.
+pub async fn serve_stdio(tools: Tools<'_>) -> io::Result<()> {
+    serve(&tools, BufReader::new(io::stdin()), io::stdout()).await
+}
+
+/// Serves MCP messages read from `input`, writing responses to `output`.
+///
+/// # Errors
+///
+/// Returns an error if reading or writing fails.
+pub async fn serve<R, W>(tools: &Tools<'_>, input: R, mut output: W) -> io::Result<()>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut lines = input.lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_message(tools, &line).await {
+            output.write_all(format!("{}\n", response).as_bytes()).await?;
+            output.flush().await?;
+        }
+    }
+    Ok(())
+}
+
+/// A JSON-RPC request or notification.
+#[derive(Debug, Deserialize)]
+struct Message {
+    jsonrpc: String,
+    /// Absent for notifications, which get no response
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Handles one message line, returning the response line, if any.
+async fn handle_message(tools: &Tools<'_>, line: &str) -> Option<String> {
+    let message: Message = match serde_json::from_str::<Value>(line) {
+        Err(e) => {
+            let error = RpcError::new(RpcError::PARSE_ERROR, format!("Parse error: {}", e));
+            return Some(response(Value::Null, Err(error)));
+        }
+        Ok(value) => match serde_json::from_value(value) {
+            Ok(message) => message,
+            // Responses from the client (we send no requests) and garbage
+            Err(e) => {
+                let error = RpcError::new(RpcError::INVALID_REQUEST, format!("Invalid request: {}", e));
+                return Some(response(Value::Null, Err(error)));
+            }
+        },
+    };
+    if message.jsonrpc != "2.0" {
+        let error = RpcError::new(RpcError::INVALID_REQUEST, "Only JSON-RPC 2.0 is supported");
+        return Some(response(message.id.unwrap_or_default(), Err(error)));
+    }
+
+    // Notifications (`notifications/initialized`, `notifications/cancelled`)
+    // need no action: every request is answered before the next is read.
+    let id = message.id?;
+    let result = handle_request(tools, &message.method, message.params).await;
+    Some(response(id, result))
+}
+
+/// Answers a request.
+async fn handle_request(tools: &Tools<'_>, method: &str, params: Value) -> Result<Value, RpcError> {
+    match method {
+        "initialize" => {
+            let requested = params.get("protocolVersion").and_then(Value::as_str);
+            let version = requested
+                .filter(|v| SUPPORTED_VERSIONS.contains(v))
+                .unwrap_or(PROTOCOL_VERSION);
+            Ok(json!({
+                "protocolVersion": version,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "ratado", "version": env!("CARGO_PKG_VERSION") },
+                "instructions": "Tools for the user's Ratado task list. Task IDs come from \
+                    list_tasks or search; create_task takes Quick Capture syntax \
+                    (`title @project #tag !1-4 due:date`).",
+            }))
+        }
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools::definitions() })),
+        "tools/call" => tools.call(params).await,
+        _ => Err(RpcError::new(
+            RpcError::METHOD_NOT_FOUND,
+            format!("Unknown method: {}", method),
+        )),
+    }
+}
+
+/// Runs the tool call in `params` (`name`, `arguments`) against `db`,
+/// returning the `tools/call` result.
+///
+/// # Errors
+///
+/// Returns an error if the tool name is missing or unknown; a failing tool
+/// is reported in the result.
+pub async fn call_tool(db: &Database, params: &Value) -> Result<Value, RpcError> {
+    let name = params
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::invalid_params("Missing tool name"))?;
+    let arguments = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+    let outcome = tools::call(db, name, arguments)
+        .await
+        .ok_or_else(|| RpcError::invalid_params(format!("Unknown tool: {}", name)))?;
+    Ok(match outcome {
+        Ok(value) => json!({
+            "content": [{ "type": "text", "text": pretty(&value) }],
+            "isError": false,
+        }),
+        Err(e) => json!({
+            "content": [{ "type": "text", "text": e.to_string() }],
+            "isError": true,
+        }),
+    })
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/// Formats a response object.
+fn response(id: Value, result: Result<Value, RpcError>) -> String {
+    let body = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    };
+    body.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Project;
+    use crate::storage::run_migrations;
+
+    async fn setup_db() -> Database {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db
+    }
+
+    /// Sends each message and returns the parsed responses.
+    async fn exchange(db: &Database, messages: &[Value]) -> Vec<Value> {
+        let input: String = messages.iter().map(|m| format!("{}\n", m)).collect();
+        let mut output = Vec::new();
+        serve(&Tools::Database(db), input.as_bytes(), &mut output).await.unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    fn call_tool(id: u64, name: &str, arguments: Value) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "tools/call",
+            "params": { "name": name, "arguments": arguments },
+        })
+    }
+
+    /// Parses the JSON text of a successful tool result.
+    fn tool_output(response: &Value) -> Value {
+        assert_eq!(response["result"]["isError"], false, "{}", response);
+        serde_json::from_str(response["result"]["content"][0]["text"].as_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_initialize_and_list_tools() {
+        let db = setup_db().await;
+        let responses = exchange(
+            &db,
+            &[
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "initialize",
+                    "params": { "protocolVersion": "2024-11-05", "capabilities": {} },
+                }),
+                json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+                json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+            ],
+        )
+        .await;
+
+        // The notification gets no response
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(responses[0]["result"]["serverInfo"]["name"], "ratado");
+        let names: Vec<&str> = responses[1]["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["list_tasks", "search", "create_task", "update_task", "complete_task", "list_projects"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_task_lifecycle() {
+        let db = setup_db().await;
+        db.insert_project(&Project::new("Work")).await.unwrap();
+        let created = exchange(
+            &db,
+            &[call_tool(1, "create_task", json!({ "text": "Ship release @Work #ops !2" }))],
+        )
+        .await;
+        let task = tool_output(&created[0]);
+        assert_eq!(task["title"], "Ship release");
+        assert_eq!(task["project"], "Work");
+        assert_eq!(task["tags"], json!(["ops"]));
+        assert_eq!(task["priority"], "High");
+        let id = task["id"].as_str().unwrap();
+
+        let responses = exchange(
+            &db,
+            &[
+                call_tool(2, "update_task", json!({ "id": id, "description": "v1.2" })),
+                call_tool(3, "complete_task", json!({ "id": id })),
+                call_tool(4, "list_tasks", json!({})),
+                call_tool(5, "list_tasks", json!({ "filter": "completed", "project": "work" })),
+                call_tool(6, "search", json!({ "query": "#ops" })),
+            ],
+        )
+        .await;
+        assert_eq!(tool_output(&responses[0])["description"], "v1.2");
+        assert_eq!(tool_output(&responses[1])["status"], "Completed");
+        // Pending tasks by default
+        assert_eq!(tool_output(&responses[2]), json!([]));
+        assert_eq!(tool_output(&responses[3])[0]["id"], id);
+        assert_eq!(tool_output(&responses[4])[0]["id"], id);
+    }
+
+    #[tokio::test]
+    async fn test_list_projects() {
+        let db = setup_db().await;
+        let responses = exchange(&db, &[call_tool(1, "list_projects", json!({}))]).await;
+
+        let projects = tool_output(&responses[0]);
+        assert_eq!(projects[0]["name"], "Inbox");
+        assert_eq!(projects[0]["task_count"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_tool_errors_are_results() {
+        let db = setup_db().await;
+        let responses = exchange(
+            &db,
+            &[
+                call_tool(1, "complete_task", json!({ "id": "missing" })),
+                call_tool(2, "create_task", json!({ "text": "Task @Nowhere" })),
+                call_tool(3, "update_task", json!({ "id": "missing", "colour": "red" })),
+                call_tool(4, "delete_everything", json!({})),
+                json!({ "jsonrpc": "2.0", "id": 5, "method": "resources/list" }),
+            ],
+        )
+        .await;
+
+        assert_eq!(responses[0]["result"]["isError"], true);
+        assert_eq!(responses[0]["result"]["content"][0]["text"], "Task not found: missing");
+        assert_eq!(responses[1]["result"]["isError"], true);
+        assert_eq!(responses[1]["result"]["content"][0]["text"], "Unknown project: Nowhere");
+        assert_eq!(responses[2]["result"]["isError"], true);
+        assert_eq!(responses[3]["error"]["code"], RpcError::INVALID_PARAMS);
+        assert_eq!(responses[4]["error"]["code"], RpcError::METHOD_NOT_FOUND);
+    }
+}
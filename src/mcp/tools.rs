@@ -0,0 +1,319 @@
+//! MCP tool definitions and implementations.
+
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::api::TaskInput;
+use crate::interop::report::{parse_filter, parse_sort};
+use crate::interop::{find_project, project_name};
+use crate::models::{Priority, Project, SortOrder, Task, TaskStatus};
+use crate::storage::{Database, StorageError};
+use crate::ui::dialogs::{match_project, parse_capture_input};
+use crate::ui::search::search_tasks;
+
+/// Default number of results for `list_tasks` and `search`.
+const DEFAULT_LIMIT: usize = 50;
+
+/// Why a tool call failed. Reported to the assistant as an error result.
+#[derive(Debug, Error)]
+pub(super) enum ToolError {
+    /// Missing or malformed arguments
+    #[error("{0}")]
+    InvalidArguments(String),
+
+    /// The task doesn't exist
+    #[error("Task not found: {0}")]
+    TaskNotFound(String),
+
+    /// Storage/database error
+    #[error("{0}")]
+    Storage(#[from] StorageError),
+}
+
+type ToolResult = Result<Value, ToolError>;
+
+/// Describes the tools for `tools/list`.
+pub(super) fn definitions() -> Value {
+    json!([
+        {
+            "name": "list_tasks",
+            "description": "List tasks. Returns pending tasks sorted by due date unless \
+                a filter or sort is given.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "filter": {
+                        "type": "string",
+                        "description": "all, pending, in-progress, completed, archived, today, \
+                            week, overdue, project:NAME, tag:NAME or priority:LEVEL (default pending)",
+                    },
+                    "sort": {
+                        "type": "string",
+                        "description": "due, due-desc, priority, priority-asc, newest, oldest \
+                            or alpha (default due)",
+                    },
+                    "project": { "type": "string", "description": "Only tasks in this project" },
+                    "tag": { "type": "string", "description": "Only tasks with this tag" },
+                    "limit": { "type": "integer", "minimum": 1, "description": "Maximum tasks to return (default 50)" },
+                },
+            },
+        },
+        {
+            "name": "search",
+            "description": "Search task titles, descriptions and tags (case-insensitive). \
+                Use #name to search tags only.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 1, "description": "Maximum tasks to return (default 50)" },
+                },
+                "required": ["query"],
+            },
+        },
+        {
+            "name": "create_task",
+            "description": "Create a task from a Quick Capture line: the title followed by \
+                optional @project, #tag, !1-!4 priority (1 = urgent) and due:DATE tokens, \
+                e.g. \"Review PR @Work #review !2 due:tomorrow\".",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "text": { "type": "string", "description": "Quick Capture line" },
+                    "description": { "type": "string", "description": "Longer notes for the task" },
+                },
+                "required": ["text"],
+            },
+        },
+        {
+            "name": "update_task",
+            "description": "Change fields of a task. Fields left out keep their value; \
+                null clears description, due_date or project_id.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "title": { "type": "string" },
+                    "description": { "type": ["string", "null"] },
+                    "due_date": {
+                        "type": ["string", "null"],
+                        "description": "RFC 3339, or today, tomorrow, +3d, 2026-03-01",
+                    },
+                    "priority": { "type": "string", "enum": ["Low", "Medium", "High", "Urgent"] },
+                    "status": {
+                        "type": "string",
+                        "enum": ["Pending", "InProgress", "Completed", "Archived"],
+                    },
+                    "project": { "type": "string", "description": "Project name" },
+                    "project_id": { "type": ["string", "null"] },
+                    "tags": { "type": "array", "items": { "type": "string" }, "description": "Replaces all tags" },
+                },
+                "required": ["id"],
+            },
+        },
+        {
+            "name": "complete_task",
+            "description": "Mark a task completed.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "id": { "type": "string" } },
+                "required": ["id"],
+            },
+        },
+        {
+            "name": "list_projects",
+            "description": "List projects with the number of tasks in each.",
+            "inputSchema": { "type": "object", "properties": {} },
+        },
+    ])
+}
+
+/// Runs a tool. Returns `None` if there is no tool called `name`.
+pub(super) async fn call(db: &Database, name: &str, arguments: Value) -> Option<ToolResult> {
+    let result = match name {
+        "list_tasks" => list_tasks(db, &arguments).await,
+        "search" => search(db, &arguments).await,
+        "create_task" => create_task(db, &arguments).await,
+        "update_task" => update_task(db, arguments).await,
+        "complete_task" => complete_task(db, &arguments).await,
+        "list_projects" => list_projects(db).await,
+        _ => return None,
+    };
+    Some(result)
+}
+
+async fn list_tasks(db: &Database, arguments: &Value) -> ToolResult {
+    let all_tasks = db.get_all_tasks().await?;
+    let projects = db.get_all_projects().await?;
+
+    let filter = parse_filter(optional_string(arguments, "filter")?.unwrap_or("pending"), &projects)
+        .map_err(ToolError::InvalidArguments)?;
+    let sort = match optional_string(arguments, "sort")? {
+        Some(spec) => parse_sort(spec).map_err(ToolError::InvalidArguments)?,
+        None => SortOrder::DueDateAsc,
+    };
+    let project_id = match optional_string(arguments, "project")? {
+        Some(name) => Some(
+            projects
+                .iter()
+                .find(|p| p.id == name)
+                .or_else(|| find_project(&projects, name))
+                .ok_or_else(|| unknown_project(name))?
+                .id
+                .clone(),
+        ),
+        None => None,
+    };
+    let tag = optional_string(arguments, "tag")?.map(|t| t.trim_start_matches('#'));
+
+    let mut tasks: Vec<&Task> = filter
+        .apply(&all_tasks)
+        .into_iter()
+        .filter(|t| project_id.is_none() || t.project_id == project_id)
+        .filter(|t| tag.is_none_or(|tag| t.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))))
+        .collect();
+    sort.apply(&mut tasks);
+    Ok(Value::Array(
+        tasks
+            .into_iter()
+            .take(limit(arguments)?)
+            .map(|t| task_json(t, &projects))
+            .collect(),
+    ))
+}
+
+async fn search(db: &Database, arguments: &Value) -> ToolResult {
+    let query = required_string(arguments, "query")?;
+    let tasks = db.get_all_tasks().await?;
+    let projects = db.get_all_projects().await?;
+    Ok(Value::Array(
+        search_tasks(query, &tasks)
+            .iter()
+            .take(limit(arguments)?)
+            .map(|result| task_json(&result.task, &projects))
+            .collect(),
+    ))
+}
+
+async fn create_task(db: &Database, arguments: &Value) -> ToolResult {
+    let parsed = parse_capture_input(required_string(arguments, "text")?);
+    let title = parsed.title.trim();
+    if title.is_empty() {
+        return Err(ToolError::InvalidArguments("Task title is required".to_string()));
+    }
+    let projects = db.get_all_projects().await?;
+
+    let mut task = Task::new(title);
+    task.priority = parsed.priority.unwrap_or(Priority::Medium);
+    task.tags = parsed.tags;
+    task.description = optional_string(arguments, "description")?
+        .filter(|d| !d.trim().is_empty())
+        .map(str::to_string);
+    if let Some(text) = parsed.due_date_text {
+        task.due_date = Some(
+            parsed
+                .due_date
+                .ok_or_else(|| ToolError::InvalidArguments(format!("Invalid due date: {}", text)))?,
+        );
+    }
+    if let Some(name) = parsed.project_name {
+        let project = match_project(&projects, &name).ok_or_else(|| unknown_project(&name))?;
+        task.project_id = Some(project.id.clone());
+    }
+
+    let task = db.add_task(task).await?;
+    Ok(task_json(&task, &projects))
+}
+
+async fn update_task(db: &Database, arguments: Value) -> ToolResult {
+    let Value::Object(mut fields) = arguments else {
+        return Err(ToolError::InvalidArguments("Arguments must be an object".to_string()));
+    };
+    let id = match fields.remove("id") {
+        Some(Value::String(id)) => id,
+        _ => return Err(ToolError::InvalidArguments("Missing string argument: id".to_string())),
+    };
+    let input: TaskInput = serde_json::from_value(Value::Object(fields))
+        .map_err(|e| ToolError::InvalidArguments(e.to_string()))?;
+
+    let mut task = find_task(db, &id).await?;
+    let projects = db.get_all_projects().await?;
+    input
+        .apply(&mut task, &projects)
+        .map_err(|e| ToolError::InvalidArguments(e.to_string()))?;
+    let task = db.modify_task(task).await?;
+    Ok(task_json(&task, &projects))
+}
+
+async fn complete_task(db: &Database, arguments: &Value) -> ToolResult {
+    let mut task = find_task(db, required_string(arguments, "id")?).await?;
+    if task.status != TaskStatus::Completed {
+        task.complete();
+        task = db.modify_task(task).await?;
+    }
+    let projects = db.get_all_projects().await?;
+    Ok(task_json(&task, &projects))
+}
+
+async fn list_projects(db: &Database) -> ToolResult {
+    Ok(Value::Array(
+        db.get_projects_with_counts()
+            .await?
+            .into_iter()
+            .map(|(project, task_count)| {
+                json!({
+                    "id": project.id,
+                    "name": project.name,
+                    "icon": project.icon,
+                    "task_count": task_count,
+                })
+            })
+            .collect(),
+    ))
+}
+
+async fn find_task(db: &Database, id: &str) -> Result<Task, ToolError> {
+    db.get_task(id)
+        .await?
+        .ok_or_else(|| ToolError::TaskNotFound(id.to_string()))
+}
+
+/// Serializes a task with its project name, so the assistant needn't look it up.
+fn task_json(task: &Task, projects: &[Project]) -> Value {
+    let mut value = json!(task);
+    if let Value::Object(fields) = &mut value {
+        fields.insert(
+            "project".to_string(),
+            project_name(task, projects).map_or(Value::Null, |name| json!(name)),
+        );
+    }
+    value
+}
+
+fn unknown_project(name: &str) -> ToolError {
+    ToolError::InvalidArguments(format!("Unknown project: {}", name))
+}
+
+fn required_string<'a>(arguments: &'a Value, name: &str) -> Result<&'a str, ToolError> {
+    optional_string(arguments, name)?
+        .ok_or_else(|| ToolError::InvalidArguments(format!("Missing string argument: {}", name)))
+}
+
+fn optional_string<'a>(arguments: &'a Value, name: &str) -> Result<Option<&'a str>, ToolError> {
+    match arguments.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(_) => Err(ToolError::InvalidArguments(format!("{} must be a string", name))),
+    }
+}
+
+fn limit(arguments: &Value) -> Result<usize, ToolError> {
+    match arguments.get("limit") {
+        None | Some(Value::Null) => Ok(DEFAULT_LIMIT),
+        Some(value) => value
+            .as_u64()
+            .filter(|&n| n > 0)
+            .map(|n| n as usize)
+            .ok_or_else(|| ToolError::InvalidArguments("limit must be a positive integer".to_string())),
+    }
+}
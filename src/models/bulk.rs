@@ -0,0 +1,142 @@
+//! Changes applied to many tasks at once.
+//!
+//! In selection mode the user marks tasks and then applies one
+//! [`BulkAction`] to all of them, which is saved in a single transaction.
+
+use chrono::{DateTime, Utc};
+
+use super::{Priority, Task, TaskStatus};
+
+/// A change made to every marked task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkEdit {
+    /// Set the status, completing or reopening tasks as needed
+    SetStatus(TaskStatus),
+    /// Set the priority
+    SetPriority(Priority),
+    /// Set or clear the due date
+    SetDueDate(Option<DateTime<Utc>>),
+    /// Move tasks to the project with this ID
+    MoveToProject(String),
+    /// Add a tag
+    AddTag(String),
+    /// Remove a tag
+    RemoveTag(String),
+}
+
+impl BulkEdit {
+    /// Returns `task` with the change applied, or `None` if it already has
+    /// it and doesn't need saving.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use ratado::models::{BulkEdit, Priority, Task};
+    ///
+    /// let task = Task::new("Write report");
+    /// let edit = BulkEdit::SetPriority(Priority::High);
+    /// let changed = edit.apply(&task, Utc::now()).unwrap();
+    /// assert_eq!(changed.priority, Priority::High);
+    /// assert!(edit.apply(&changed, Utc::now()).is_none());
+    /// ```
+    pub fn apply(&self, task: &Task, now: DateTime<Utc>) -> Option<Task> {
+        let mut changed = task.clone();
+        match self {
+            BulkEdit::SetStatus(status) => {
+                if task.status == *status {
+                    return None;
+                }
+                changed.status = *status;
+                changed.completed_at = match status {
+                    TaskStatus::Completed => Some(now),
+                    // Archiving keeps the completion time of completed tasks
+                    TaskStatus::Archived => task.completed_at,
+                    TaskStatus::Pending | TaskStatus::InProgress => None,
+                };
+            }
+            BulkEdit::SetPriority(priority) => {
+                if task.priority == *priority {
+                    return None;
+                }
+                changed.priority = *priority;
+            }
+            BulkEdit::SetDueDate(due) => {
+                if task.due_date == *due {
+                    return None;
+                }
+                changed.due_date = *due;
+            }
+            BulkEdit::MoveToProject(project_id) => {
+                if task.project_id.as_deref() == Some(project_id.as_str()) {
+                    return None;
+                }
+                changed.project_id = Some(project_id.clone());
+            }
+            BulkEdit::AddTag(tag) => {
+                if task.tags.contains(tag) {
+                    return None;
+                }
+                changed.tags.push(tag.clone());
+            }
+            BulkEdit::RemoveTag(tag) => {
+                if !task.tags.contains(tag) {
+                    return None;
+                }
+                changed.tags.retain(|t| t != tag);
+            }
+        }
+        changed.updated_at = now;
+        Some(changed)
+    }
+}
+
+/// What to do with the marked tasks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkAction {
+    /// Change every marked task
+    Edit(BulkEdit),
+    /// Delete every marked task
+    Delete,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_status_completes_and_reopens() {
+        let now = Utc::now();
+        let task = Task::new("Task");
+
+        let done = BulkEdit::SetStatus(TaskStatus::Completed).apply(&task, now).unwrap();
+        assert_eq!(done.status, TaskStatus::Completed);
+        assert_eq!(done.completed_at, Some(now));
+
+        let archived = BulkEdit::SetStatus(TaskStatus::Archived).apply(&done, now).unwrap();
+        assert_eq!(archived.completed_at, Some(now));
+
+        let reopened = BulkEdit::SetStatus(TaskStatus::Pending).apply(&done, now).unwrap();
+        assert!(reopened.completed_at.is_none());
+        assert!(BulkEdit::SetStatus(TaskStatus::Pending).apply(&task, now).is_none());
+    }
+
+    #[test]
+    fn test_tags_and_project() {
+        let now = Utc::now();
+        let mut task = Task::new("Task");
+        task.tags = vec!["work".to_string()];
+
+        let tagged = BulkEdit::AddTag("urgent".to_string()).apply(&task, now).unwrap();
+        assert_eq!(tagged.tags, vec!["work", "urgent"]);
+        assert!(BulkEdit::AddTag("work".to_string()).apply(&task, now).is_none());
+
+        let untagged = BulkEdit::RemoveTag("work".to_string()).apply(&task, now).unwrap();
+        assert!(untagged.tags.is_empty());
+        assert!(BulkEdit::RemoveTag("home".to_string()).apply(&task, now).is_none());
+
+        let moved = BulkEdit::MoveToProject("inbox".to_string()).apply(&task, now).unwrap();
+        assert_eq!(moved.project_id.as_deref(), Some("inbox"));
+        assert_eq!(moved.updated_at, now);
+    }
+}
@@ -1,7 +1,11 @@
 //! Filtering and sorting for task lists.
 //!
 //! This module provides [`Filter`] and [`SortOrder`] types for querying
-//! and organizing task collections.
+//! and organizing task collections. A [`SortOrder`] is one [`SortKey`];
+//! [`sort_by_keys`] sorts by several, breaking remaining ties by manual
+//! position and then ID so the order is always the same.
+
+use std::cmp::Ordering;
 
 use super::task::{Priority, Task, TaskStatus};
 
@@ -151,6 +155,8 @@ pub enum SortOrder {
     CreatedAsc,
     /// Sort alphabetically by title
     Alphabetical,
+    /// Sort in the order the user arranged, by task position
+    Manual,
 }
 
 impl SortOrder {
@@ -174,48 +180,122 @@ impl SortOrder {
     /// assert_eq!(refs[0].title, "Apple");
     /// ```
     pub fn apply(&self, tasks: &mut [&Task]) {
+        sort_by_keys(tasks, &[self.key()]);
+    }
+
+    /// Returns the sort key this order sorts by.
+    pub fn key(self) -> SortKey {
+        let (field, descending) = match self {
+            SortOrder::DueDateAsc => (SortField::DueDate, false),
+            SortOrder::DueDateDesc => (SortField::DueDate, true),
+            SortOrder::PriorityDesc => (SortField::Priority, true),
+            SortOrder::PriorityAsc => (SortField::Priority, false),
+            SortOrder::CreatedDesc => (SortField::Created, true),
+            SortOrder::CreatedAsc => (SortField::Created, false),
+            SortOrder::Alphabetical => (SortField::Title, false),
+            SortOrder::Manual => (SortField::Position, false),
+        };
+        SortKey { field, descending }
+    }
+}
+
+/// A task field to sort by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    /// Due date; tasks without one come last in either direction
+    DueDate,
+    /// Priority
+    Priority,
+    /// Creation time
+    Created,
+    /// Title
+    Title,
+    /// Place in the manual order
+    Position,
+}
+
+impl SortField {
+    /// Returns the field's display name.
+    pub fn name(self) -> &'static str {
         match self {
-            SortOrder::DueDateAsc => {
-                // Tasks with due dates first (earliest first), then tasks without (by created date)
-                tasks.sort_by(|a, b| {
-                    match (&a.due_date, &b.due_date) {
-                        (Some(a_due), Some(b_due)) => a_due.cmp(b_due),
-                        (Some(_), None) => std::cmp::Ordering::Less,
-                        (None, Some(_)) => std::cmp::Ordering::Greater,
-                        (None, None) => a.created_at.cmp(&b.created_at),
-                    }
-                });
-            }
-            SortOrder::DueDateDesc => {
-                // Tasks with due dates first (latest first), then tasks without (by created date desc)
-                tasks.sort_by(|a, b| {
-                    match (&a.due_date, &b.due_date) {
-                        (Some(a_due), Some(b_due)) => b_due.cmp(a_due),
-                        (Some(_), None) => std::cmp::Ordering::Less,
-                        (None, Some(_)) => std::cmp::Ordering::Greater,
-                        (None, None) => b.created_at.cmp(&a.created_at),
-                    }
-                });
-            }
-            SortOrder::PriorityDesc => {
-                tasks.sort_by(|a, b| b.priority.cmp(&a.priority));
-            }
-            SortOrder::PriorityAsc => {
-                tasks.sort_by(|a, b| a.priority.cmp(&b.priority));
-            }
-            SortOrder::CreatedDesc => {
-                tasks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-            }
-            SortOrder::CreatedAsc => {
-                tasks.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-            }
-            SortOrder::Alphabetical => {
-                tasks.sort_by(|a, b| a.title.cmp(&b.title));
-            }
+            SortField::DueDate => "Due Date",
+            SortField::Priority => "Priority",
+            SortField::Created => "Created",
+            SortField::Title => "Title",
+            SortField::Position => "Manual",
         }
     }
 }
 
+/// One level of a multi-level sort: a field and a direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    /// Field to compare
+    pub field: SortField,
+    /// Whether larger values come first
+    pub descending: bool,
+}
+
+impl SortKey {
+    /// Compares two tasks by this key.
+    pub fn compare(&self, a: &Task, b: &Task) -> Ordering {
+        let ordering = match self.field {
+            // Missing due dates go last whichever way the dates run
+            SortField::DueDate => match (a.due_date, b.due_date) {
+                (Some(a_due), Some(b_due)) => a_due.cmp(&b_due),
+                (Some(_), None) => return Ordering::Less,
+                (None, Some(_)) => return Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            SortField::Priority => a.priority.cmp(&b.priority),
+            SortField::Created => a.created_at.cmp(&b.created_at),
+            SortField::Title => a.title.cmp(&b.title),
+            SortField::Position => a.position.total_cmp(&b.position),
+        };
+        if self.descending { ordering.reverse() } else { ordering }
+    }
+
+    /// Returns the key's display name, with an arrow for the direction.
+    pub fn label(&self) -> String {
+        format!("{} {}", self.field.name(), if self.descending { "↓" } else { "↑" })
+    }
+}
+
+/// Sorts tasks by each key in turn, so later keys only order tasks that
+/// earlier keys consider equal.
+///
+/// Tasks still tied after every key are ordered by manual position, then
+/// creation time, then ID, so the result never depends on the input order.
+///
+/// # Examples
+///
+/// ```
+/// use ratado::models::{sort_by_keys, Priority, SortOrder, Task};
+///
+/// let mut urgent = Task::new("Call vendor");
+/// urgent.priority = Priority::Urgent;
+/// let a = Task::new("Answer email");
+/// let tasks = [a, urgent];
+/// let mut refs: Vec<&Task> = tasks.iter().collect();
+///
+/// // Due date first; neither is due, so priority decides
+/// sort_by_keys(&mut refs, &[SortOrder::DueDateAsc.key(), SortOrder::PriorityDesc.key()]);
+/// assert_eq!(refs[0].title, "Call vendor");
+/// ```
+pub fn sort_by_keys(tasks: &mut [&Task], keys: &[SortKey]) {
+    tasks.sort_by(|a, b| {
+        keys.iter()
+            .map(|key| key.compare(a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| {
+                a.position
+                    .total_cmp(&b.position)
+                    .then_with(|| a.created_at.cmp(&b.created_at))
+                    .then_with(|| a.id.cmp(&b.id))
+            })
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -280,7 +360,7 @@ mod tests {
         let mut task3 = Task::new("Medium");
         task3.priority = Priority::Medium;
 
-        let tasks = vec![task1, task2, task3];
+        let tasks = [task1, task2, task3];
         let mut refs: Vec<&Task> = tasks.iter().collect();
 
         SortOrder::PriorityDesc.apply(&mut refs);
@@ -295,7 +375,7 @@ mod tests {
         let task2 = Task::new("Apple");
         let task3 = Task::new("Mango");
 
-        let tasks = vec![task1, task2, task3];
+        let tasks = [task1, task2, task3];
         let mut refs: Vec<&Task> = tasks.iter().collect();
 
         SortOrder::Alphabetical.apply(&mut refs);
@@ -304,6 +384,23 @@ mod tests {
         assert_eq!(refs[2].title, "Zebra");
     }
 
+    #[test]
+    fn test_sort_manual() {
+        let mut task1 = Task::new("Second");
+        task1.position = 2.0;
+        let mut task2 = Task::new("First");
+        task2.position = -1.5;
+        let mut task3 = Task::new("Third");
+        task3.position = 2.5;
+
+        let tasks = [task1, task2, task3];
+        let mut refs: Vec<&Task> = tasks.iter().collect();
+
+        SortOrder::Manual.apply(&mut refs);
+        let titles: Vec<&str> = refs.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["First", "Second", "Third"]);
+    }
+
     #[test]
     fn test_sort_due_date_with_none() {
         // Tasks with due dates should come first, then tasks without
@@ -315,7 +412,7 @@ mod tests {
         let mut task_no_due_2 = Task::new("No due date 2");
         task_no_due_2.created_at = Utc::now() + Duration::seconds(1);
 
-        let tasks = vec![task_no_due_1, task_with_due, task_no_due_2];
+        let tasks = [task_no_due_1, task_with_due, task_no_due_2];
         let mut refs: Vec<&Task> = tasks.iter().collect();
 
         SortOrder::DueDateAsc.apply(&mut refs);
@@ -325,4 +422,46 @@ mod tests {
         assert_eq!(refs[1].title, "No due date 1");
         assert_eq!(refs[2].title, "No due date 2");
     }
+
+    #[test]
+    fn test_sort_by_several_keys() {
+        let due = Utc::now() + Duration::days(1);
+        let make = |title: &str, due_date, priority| {
+            let mut task = Task::new(title);
+            task.due_date = due_date;
+            task.priority = priority;
+            task
+        };
+        let tasks = [
+            make("Low due", Some(due), Priority::Low),
+            make("No date", None, Priority::Urgent),
+            make("B high due", Some(due), Priority::High),
+            make("A high due", Some(due), Priority::High),
+        ];
+        let mut refs: Vec<&Task> = tasks.iter().collect();
+
+        let keys = [
+            SortOrder::DueDateAsc.key(),
+            SortOrder::PriorityDesc.key(),
+            SortOrder::Alphabetical.key(),
+        ];
+        sort_by_keys(&mut refs, &keys);
+        let titles: Vec<&str> = refs.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["A high due", "B high due", "Low due", "No date"]);
+    }
+
+    #[test]
+    fn test_ties_are_stable() {
+        let mut first = Task::new("Same");
+        first.position = 1.0;
+        let mut second = Task::new("Same");
+        second.position = 2.0;
+        let tasks = [second.clone(), first.clone()];
+
+        for order in [SortOrder::Alphabetical, SortOrder::PriorityDesc, SortOrder::DueDateDesc] {
+            let mut refs: Vec<&Task> = tasks.iter().collect();
+            order.apply(&mut refs);
+            assert_eq!(refs[0].id, first.id);
+        }
+    }
 }
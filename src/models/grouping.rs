@@ -0,0 +1,301 @@
+//! Grouping the task list into sections.
+//!
+//! With a [`GroupBy`] other than `None`, the main list shows its tasks in
+//! [`TaskGroup`]s under headers, keeping the sort order within each group.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Local};
+
+use super::{Priority, Project, Task, TaskStatus};
+
+/// What the task list is grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    /// A single flat list
+    #[default]
+    None,
+    /// One group per project, in sidebar order
+    Project,
+    /// One group per priority, most urgent first
+    Priority,
+    /// One group per status
+    Status,
+    /// Overdue, Today, Tomorrow, This week, Later and No date
+    Due,
+    /// One group per first tag, alphabetically
+    Tag,
+}
+
+impl GroupBy {
+    /// Every grouping, in the order the filter/sort dialog lists them.
+    pub const ALL: &'static [GroupBy] = &[
+        GroupBy::None,
+        GroupBy::Project,
+        GroupBy::Priority,
+        GroupBy::Status,
+        GroupBy::Due,
+        GroupBy::Tag,
+    ];
+
+    /// Returns the grouping's name, as stored in preferences.
+    pub fn name(self) -> &'static str {
+        match self {
+            GroupBy::None => "none",
+            GroupBy::Project => "project",
+            GroupBy::Priority => "priority",
+            GroupBy::Status => "status",
+            GroupBy::Due => "due",
+            GroupBy::Tag => "tag",
+        }
+    }
+
+    /// Splits `tasks` into groups, keeping their order within each group.
+    ///
+    /// Groups come in a fixed order that doesn't depend on the task sort,
+    /// and empty groups are left out. With [`GroupBy::None`] every task is
+    /// in one unlabelled group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Local;
+    /// use ratado::models::{GroupBy, Priority, Task};
+    ///
+    /// let mut urgent = Task::new("Fix outage");
+    /// urgent.priority = Priority::Urgent;
+    /// let normal = Task::new("Write notes");
+    /// let groups = GroupBy::Priority.group(&[&normal, &urgent], &[], Local::now());
+    /// assert_eq!(groups[0].label, "Urgent");
+    /// assert_eq!(groups[1].tasks[0].title, "Write notes");
+    /// ```
+    pub fn group<'a>(self, tasks: &[&'a Task], projects: &[Project], now: DateTime<Local>) -> Vec<TaskGroup<'a>> {
+        if self == GroupBy::None {
+            return vec![TaskGroup {
+                label: String::new(),
+                tasks: tasks.to_vec(),
+            }];
+        }
+
+        // (rank, label) of each task's group; lower ranks come first
+        let key = |task: &Task| -> (usize, String) {
+            match self {
+                GroupBy::None => unreachable!(),
+                GroupBy::Project => {
+                    let project = task
+                        .project_id
+                        .as_ref()
+                        .and_then(|id| projects.iter().position(|p| &p.id == id));
+                    match project {
+                        Some(index) => (index, projects[index].name.clone()),
+                        None => (usize::MAX, "No project".to_string()),
+                    }
+                }
+                GroupBy::Priority => {
+                    let rank = [Priority::Urgent, Priority::High, Priority::Medium, Priority::Low]
+                        .iter()
+                        .position(|p| *p == task.priority)
+                        .unwrap_or(0);
+                    (rank, format!("{:?}", task.priority))
+                }
+                GroupBy::Status => {
+                    let (rank, label) = match task.status {
+                        TaskStatus::InProgress => (0, "In progress"),
+                        TaskStatus::Pending => (1, "Pending"),
+                        TaskStatus::Completed => (2, "Completed"),
+                        TaskStatus::Archived => (3, "Archived"),
+                    };
+                    (rank, label.to_string())
+                }
+                GroupBy::Due => {
+                    let bucket = DueBucket::of(task, now);
+                    (bucket as usize, bucket.label().to_string())
+                }
+                // Ranks are equal, so tags end up alphabetical below
+                GroupBy::Tag => match task.tags.first() {
+                    Some(tag) => (0, format!("#{}", tag)),
+                    None => (1, "No tag".to_string()),
+                },
+            }
+        };
+
+        let mut groups: Vec<((usize, String), TaskGroup<'a>)> = Vec::new();
+        for &task in tasks {
+            let key = key(task);
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, group)) => group.tasks.push(task),
+                None => groups.push((
+                    key.clone(),
+                    TaskGroup {
+                        label: key.1,
+                        tasks: vec![task],
+                    },
+                )),
+            }
+        }
+        groups.sort_by(|((rank_a, label_a), _), ((rank_b, label_b), _)| {
+            rank_a.cmp(rank_b).then_with(|| label_a.to_lowercase().cmp(&label_b.to_lowercase()))
+        });
+        groups.into_iter().map(|(_, group)| group).collect()
+    }
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        GroupBy::ALL
+            .iter()
+            .copied()
+            .find(|g| g.name() == lower)
+            .ok_or_else(|| {
+                let names: Vec<_> = GroupBy::ALL.iter().map(|g| g.name()).collect();
+                format!("unknown grouping '{}' (expected one of: {})", s, names.join(", "))
+            })
+    }
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Tasks that share a group, under a header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskGroup<'a> {
+    /// Header text, which also identifies the group
+    pub label: String,
+    /// The group's tasks, in sort order
+    pub tasks: Vec<&'a Task>,
+}
+
+/// When a task is due, for grouping by due date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueBucket {
+    /// Due before now and not done yet, or due on an earlier day
+    Overdue,
+    /// Due later today
+    Today,
+    /// Due tomorrow
+    Tomorrow,
+    /// Due within the next 7 days
+    ThisWeek,
+    /// Due after that
+    Later,
+    /// No due date
+    NoDate,
+}
+
+impl DueBucket {
+    /// Returns the bucket of `task` as of `now`.
+    pub fn of(task: &Task, now: DateTime<Local>) -> Self {
+        let Some(due) = task.due_date else {
+            return DueBucket::NoDate;
+        };
+        let days = (due.with_timezone(&Local).date_naive() - now.date_naive()).num_days();
+        let done = matches!(task.status, TaskStatus::Completed | TaskStatus::Archived);
+        if days < 0 || (!done && due < now) {
+            DueBucket::Overdue
+        } else if days == 0 {
+            DueBucket::Today
+        } else if days == 1 {
+            DueBucket::Tomorrow
+        } else if days <= 7 {
+            DueBucket::ThisWeek
+        } else {
+            DueBucket::Later
+        }
+    }
+
+    /// Returns the group header for the bucket.
+    pub fn label(self) -> &'static str {
+        match self {
+            DueBucket::Overdue => "Overdue",
+            DueBucket::Today => "Today",
+            DueBucket::Tomorrow => "Tomorrow",
+            DueBucket::ThisWeek => "This week",
+            DueBucket::Later => "Later",
+            DueBucket::NoDate => "No date",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn labels(groups: &[TaskGroup]) -> Vec<String> {
+        groups.iter().map(|g| g.label.clone()).collect()
+    }
+
+    #[test]
+    fn test_due_buckets() {
+        let now = Local.with_ymd_and_hms(2026, 3, 4, 12, 0, 0).unwrap();
+        let due = |offset: Duration| {
+            let mut task = Task::new("Task");
+            task.due_date = Some((now + offset).to_utc());
+            DueBucket::of(&task, now)
+        };
+        assert_eq!(due(Duration::days(-2)), DueBucket::Overdue);
+        assert_eq!(due(Duration::hours(-1)), DueBucket::Overdue);
+        assert_eq!(due(Duration::hours(3)), DueBucket::Today);
+        assert_eq!(due(Duration::days(1)), DueBucket::Tomorrow);
+        assert_eq!(due(Duration::days(5)), DueBucket::ThisWeek);
+        assert_eq!(due(Duration::days(30)), DueBucket::Later);
+        assert_eq!(DueBucket::of(&Task::new("Task"), now), DueBucket::NoDate);
+
+        // Done earlier today isn't overdue
+        let mut done = Task::new("Done");
+        done.due_date = Some((now - Duration::hours(1)).to_utc());
+        done.complete();
+        assert_eq!(DueBucket::of(&done, now), DueBucket::Today);
+    }
+
+    #[test]
+    fn test_group_by_project_keeps_sidebar_order() {
+        let work = Project::new("Work");
+        let home = Project::new("Home");
+        let projects = vec![work.clone(), home.clone()];
+        let mut a = Task::new("A");
+        a.project_id = Some(home.id.clone());
+        let b = Task::new("B");
+        let mut c = Task::new("C");
+        c.project_id = Some(work.id.clone());
+        let mut d = Task::new("D");
+        d.project_id = Some(home.id.clone());
+
+        let groups = GroupBy::Project.group(&[&a, &b, &c, &d], &projects, Local::now());
+        assert_eq!(labels(&groups), vec!["Work", "Home", "No project"]);
+        let home_titles: Vec<&str> = groups[1].tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(home_titles, vec!["A", "D"]);
+    }
+
+    #[test]
+    fn test_group_by_first_tag_and_status() {
+        let mut a = Task::new("A");
+        a.tags = vec!["work".to_string(), "admin".to_string()];
+        let mut b = Task::new("B");
+        b.tags = vec!["Errands".to_string()];
+        b.status = TaskStatus::InProgress;
+        let c = Task::new("C");
+
+        let groups = GroupBy::Tag.group(&[&a, &b, &c], &[], Local::now());
+        assert_eq!(labels(&groups), vec!["#Errands", "#work", "No tag"]);
+
+        let groups = GroupBy::Status.group(&[&a, &b, &c], &[], Local::now());
+        assert_eq!(labels(&groups), vec!["In progress", "Pending"]);
+    }
+
+    #[test]
+    fn test_group_by_names() {
+        for group in GroupBy::ALL {
+            assert_eq!(group.name().parse::<GroupBy>(), Ok(*group));
+        }
+        assert!("colour".parse::<GroupBy>().is_err());
+        assert_eq!(GroupBy::None.group(&[], &[], Local::now()).len(), 1);
+    }
+}
@@ -7,11 +7,17 @@
 //! ## Key Types
 //!
 //! - [`Task`] - A task item with title, due date, priority, etc.
+//! - [`Note`] - A timestamped note on a task
 //! - [`Project`] - A project for organizing tasks
 //! - [`Priority`] - Task priority levels (Low, Medium, High, Urgent)
 //! - [`TaskStatus`] - Task states (Pending, InProgress, Completed, Archived)
 //! - [`Filter`] - Criteria for filtering task lists
 //! - [`SortOrder`] - Options for sorting task lists
+//! - [`GroupBy`] - Sections the task list can be grouped into
+//! - [`Stats`] - Productivity statistics for the stats view
+//! - [`ReviewStep`] - The steps of the weekly review
+//! - [`BulkAction`] - A change applied to many marked tasks at once
+//! - [`NewTaskPlacement`] - Where new tasks go in the manual order
 //!
 //! ## Examples
 //!
@@ -27,10 +33,22 @@
 //! let high_priority = Filter::ByPriority(Priority::High).apply(&tasks);
 //! ```
 
+mod bulk;
 mod filter;
+mod grouping;
+mod note;
+mod ordering;
 mod project;
+mod review;
+mod stats;
 mod task;
 
-pub use filter::{Filter, SortOrder};
+pub use bulk::{BulkAction, BulkEdit};
+pub use filter::{sort_by_keys, Filter, SortField, SortKey, SortOrder};
+pub use grouping::{DueBucket, GroupBy, TaskGroup};
+pub use note::Note;
+pub use ordering::{move_positions, position_at, position_between, NewTaskPlacement};
 pub use project::Project;
+pub use review::{review_due, ReviewStep};
+pub use stats::{Completion, DayStats, Stats};
 pub use task::{Priority, Task, TaskStatus};
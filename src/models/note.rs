@@ -0,0 +1,41 @@
+//! Note model for a task's running log.
+//!
+//! A task's description is one block of text that gets rewritten as plans
+//! change. Notes are kept alongside it: timestamped entries that are only
+//! ever added, so what was learned along the way isn't lost.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A timestamped note on a task.
+///
+/// # Examples
+///
+/// ```
+/// use ratado::models::Task;
+///
+/// let mut task = Task::new("Fix login bug");
+/// task.add_note("Only happens with SSO accounts");
+/// assert_eq!(task.notes[0].text, "Only happens with SSO accounts");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    /// Unique identifier (UUID v7 string - time-ordered)
+    pub id: String,
+    /// The note's text, possibly several lines
+    pub text: String,
+    /// When the note was added (UTC)
+    pub created_at: DateTime<Utc>,
+}
+
+impl Note {
+    /// Creates a note with the given text, added now.
+    pub fn new(text: &str) -> Self {
+        Self {
+            id: Uuid::now_v7().to_string(),
+            text: text.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+}
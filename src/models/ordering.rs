@@ -0,0 +1,140 @@
+//! Manual ordering of tasks.
+//!
+//! A task's `position` is a fractional index: moving a task gives it a
+//! position halfway between its new neighbours, so only the moved task is
+//! saved. New tasks start at their creation time in milliseconds, which
+//! puts them after every existing task (or, negated, before) without
+//! looking at the others.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Gap left when a task moves past the first or last task, and between
+/// tasks when a list runs out of room and is renumbered.
+const STEP: f64 = 1024.0;
+
+/// Where new tasks go in the manual order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NewTaskPlacement {
+    /// Before every existing task
+    Top,
+    /// After every existing task
+    #[default]
+    Bottom,
+}
+
+impl NewTaskPlacement {
+    /// Returns the position of a task created at `time`.
+    pub fn position(self, time: DateTime<Utc>) -> f64 {
+        match self {
+            NewTaskPlacement::Top => -position_at(time),
+            NewTaskPlacement::Bottom => position_at(time),
+        }
+    }
+}
+
+/// Returns the position of a task created at `time` and placed at the
+/// bottom: its creation time in milliseconds.
+pub fn position_at(time: DateTime<Utc>) -> f64 {
+    time.timestamp_millis() as f64
+}
+
+/// Returns a position between `before` and `after`, either of which may be
+/// missing at the ends of a list, or `None` if they are too close together
+/// to fit one.
+///
+/// # Examples
+///
+/// ```
+/// use ratado::models::position_between;
+///
+/// assert_eq!(position_between(Some(1.0), Some(2.0)), Some(1.5));
+/// assert!(position_between(None, Some(1.0)).unwrap() < 1.0);
+/// assert_eq!(position_between(Some(1.0), Some(1.0)), None);
+/// ```
+pub fn position_between(before: Option<f64>, after: Option<f64>) -> Option<f64> {
+    let position = match (before, after) {
+        (Some(before), Some(after)) => before + (after - before) / 2.0,
+        (Some(before), None) => before + STEP,
+        (None, Some(after)) => after - STEP,
+        (None, None) => 0.0,
+    };
+    let fits = before.is_none_or(|b| position > b) && after.is_none_or(|a| position < a);
+    fits.then_some(position)
+}
+
+/// Works out the new positions when the item at `from` in a list ordered
+/// by `positions` moves to index `to`.
+///
+/// Returns `(index, position)` for each item that changes. That is just
+/// the moved item, unless its neighbours have no room left between them;
+/// then the whole list is renumbered in its new order.
+pub fn move_positions(positions: &[f64], from: usize, to: usize) -> Vec<(usize, f64)> {
+    if from >= positions.len() || to >= positions.len() || from == to {
+        return Vec::new();
+    }
+
+    // Neighbours of the new place, in the list without the moved item
+    let (before, after) = if to < from {
+        (to.checked_sub(1).map(|i| positions[i]), Some(positions[to]))
+    } else {
+        (Some(positions[to]), positions.get(to + 1).copied())
+    };
+    if let Some(position) = position_between(before, after) {
+        return vec![(from, position)];
+    }
+
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    let moved = order.remove(from);
+    order.insert(to, moved);
+    let start = positions[0].min(positions[positions.len() - 1]);
+    order
+        .into_iter()
+        .enumerate()
+        .map(|(place, index)| (index, start + place as f64 * STEP))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies the moves and returns the item indices in their new order.
+    fn reordered(positions: &[f64], from: usize, to: usize) -> Vec<usize> {
+        let mut positions = positions.to_vec();
+        for (index, position) in move_positions(&positions, from, to) {
+            positions[index] = position;
+        }
+        let mut order: Vec<usize> = (0..positions.len()).collect();
+        order.sort_by(|a, b| positions[*a].total_cmp(&positions[*b]));
+        order
+    }
+
+    #[test]
+    fn test_move_changes_only_the_moved_item() {
+        let positions = [10.0, 20.0, 30.0, 40.0];
+        assert_eq!(move_positions(&positions, 3, 1), vec![(3, 15.0)]);
+        assert_eq!(move_positions(&positions, 0, 2), vec![(0, 35.0)]);
+        assert_eq!(reordered(&positions, 0, 3), vec![1, 2, 3, 0]);
+        assert_eq!(reordered(&positions, 3, 0), vec![3, 0, 1, 2]);
+        assert!(move_positions(&positions, 1, 1).is_empty());
+    }
+
+    #[test]
+    fn test_renumbers_when_out_of_room() {
+        let positions = [1.0, 1.0, 1.0];
+        let moves = move_positions(&positions, 2, 1);
+        assert_eq!(moves.len(), 3);
+        assert_eq!(reordered(&positions, 2, 1), vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_new_task_placement() {
+        let now = Utc::now();
+        let earlier = now - chrono::Duration::days(1);
+        assert!(NewTaskPlacement::Bottom.position(now) > NewTaskPlacement::Bottom.position(earlier));
+        assert!(NewTaskPlacement::Top.position(now) < NewTaskPlacement::Bottom.position(earlier));
+        assert!(NewTaskPlacement::Top.position(now) < NewTaskPlacement::Top.position(earlier));
+    }
+}
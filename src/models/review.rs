@@ -0,0 +1,171 @@
+//! The steps of the weekly review.
+//!
+//! A review walks through the open tasks that need a decision: overdue
+//! ones, ones missing a due date or project, and ones nobody has touched in
+//! a while. It ends with a look at what's due in the coming week.
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::{Task, TaskStatus};
+
+/// A step of the weekly review, in the order they're taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReviewStep {
+    /// Open tasks past their due date
+    #[default]
+    Overdue,
+    /// Open tasks without a due date or a project
+    Triage,
+    /// Open tasks not updated for a while
+    Stale,
+    /// Tasks due in the next 7 days
+    NextWeek,
+}
+
+impl ReviewStep {
+    /// Every step, in review order.
+    pub const ALL: &'static [ReviewStep] = &[
+        ReviewStep::Overdue,
+        ReviewStep::Triage,
+        ReviewStep::Stale,
+        ReviewStep::NextWeek,
+    ];
+
+    /// Returns the step's heading.
+    pub fn title(self) -> &'static str {
+        match self {
+            ReviewStep::Overdue => "Overdue",
+            ReviewStep::Triage => "Triage",
+            ReviewStep::Stale => "Stale",
+            ReviewStep::NextWeek => "Next week",
+        }
+    }
+
+    /// Returns what the step asks of the user.
+    pub fn description(self) -> &'static str {
+        match self {
+            ReviewStep::Overdue => "Reschedule, complete or drop each overdue task",
+            ReviewStep::Triage => "Give these tasks a due date or a project",
+            ReviewStep::Stale => "These haven't changed in a while. Still relevant?",
+            ReviewStep::NextWeek => "Here's what's coming up",
+        }
+    }
+
+    /// Returns the step after this one, if any.
+    pub fn next(self) -> Option<Self> {
+        let index = Self::ALL.iter().position(|s| *s == self)?;
+        Self::ALL.get(index + 1).copied()
+    }
+
+    /// Returns the tasks this step goes through, in the order shown.
+    ///
+    /// Each open task is in at most one of the first three steps, so a task
+    /// that's overdue isn't asked about again as stale. Tasks count as
+    /// stale when not updated for `stale_days` days.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{Duration, Utc};
+    /// use ratado::models::{ReviewStep, Task};
+    ///
+    /// let mut late = Task::new("File taxes");
+    /// late.due_date = Some(Utc::now() - Duration::days(2));
+    /// let tasks = vec![late, Task::new("Someday")];
+    /// assert_eq!(ReviewStep::Overdue.tasks(&tasks, Utc::now(), 14)[0].title, "File taxes");
+    /// assert_eq!(ReviewStep::Triage.tasks(&tasks, Utc::now(), 14)[0].title, "Someday");
+    /// ```
+    pub fn tasks(self, tasks: &[Task], now: DateTime<Utc>, stale_days: u32) -> Vec<&Task> {
+        let stale_before = now - Duration::days(stale_days as i64);
+        let is_open = |t: &Task| matches!(t.status, TaskStatus::Pending | TaskStatus::InProgress);
+        let is_overdue = |t: &Task| t.due_date.is_some_and(|due| due < now);
+        let needs_triage = |t: &Task| t.due_date.is_none() || t.project_id.is_none();
+
+        let mut found: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| is_open(t))
+            .filter(|t| match self {
+                ReviewStep::Overdue => is_overdue(t),
+                ReviewStep::Triage => !is_overdue(t) && needs_triage(t),
+                ReviewStep::Stale => !is_overdue(t) && !needs_triage(t) && t.updated_at < stale_before,
+                ReviewStep::NextWeek => t.due_date.is_some_and(|due| due >= now && due < now + Duration::days(7)),
+            })
+            .collect();
+        match self {
+            ReviewStep::Overdue | ReviewStep::NextWeek => found.sort_by_key(|t| t.due_date),
+            ReviewStep::Triage => found.sort_by_key(|t| t.created_at),
+            ReviewStep::Stale => found.sort_by_key(|t| t.updated_at),
+        }
+        found
+    }
+}
+
+/// Returns whether a review is due, `interval_days` after the last one.
+///
+/// Before the first review, the interval counts from `since`, usually when
+/// the oldest task was created; with no tasks at all nothing is due.
+pub fn review_due(
+    last_review: Option<DateTime<Utc>>,
+    since: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    interval_days: u32,
+) -> bool {
+    match last_review.or(since) {
+        Some(from) => now - from >= Duration::days(interval_days as i64),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(tasks: Vec<&Task>) -> Vec<&str> {
+        tasks.into_iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_steps_split_open_tasks() {
+        let now = Utc::now();
+        let project = Some("p1".to_string());
+
+        let mut overdue = Task::new("Overdue");
+        overdue.due_date = Some(now - Duration::days(1));
+        overdue.project_id = project.clone();
+        let inbox = Task::new("Inbox");
+        let mut stale = Task::new("Stale");
+        stale.due_date = Some(now + Duration::days(30));
+        stale.project_id = project.clone();
+        stale.updated_at = now - Duration::days(20);
+        let mut soon = Task::new("Soon");
+        soon.due_date = Some(now + Duration::days(2));
+        soon.project_id = project.clone();
+        let mut done = Task::new("Done");
+        done.due_date = Some(now - Duration::days(3));
+        done.complete();
+
+        let tasks = vec![overdue, inbox, stale, soon, done];
+        assert_eq!(titles(ReviewStep::Overdue.tasks(&tasks, now, 14)), vec!["Overdue"]);
+        assert_eq!(titles(ReviewStep::Triage.tasks(&tasks, now, 14)), vec!["Inbox"]);
+        assert_eq!(titles(ReviewStep::Stale.tasks(&tasks, now, 14)), vec!["Stale"]);
+        assert!(ReviewStep::Stale.tasks(&tasks, now, 30).is_empty());
+        assert_eq!(titles(ReviewStep::NextWeek.tasks(&tasks, now, 14)), vec!["Soon"]);
+    }
+
+    #[test]
+    fn test_step_order() {
+        assert_eq!(ReviewStep::default().next(), Some(ReviewStep::Triage));
+        assert_eq!(ReviewStep::Stale.next(), Some(ReviewStep::NextWeek));
+        assert_eq!(ReviewStep::NextWeek.next(), None);
+    }
+
+    #[test]
+    fn test_review_due() {
+        let now = Utc::now();
+        assert!(!review_due(None, None, now, 7));
+        assert!(!review_due(None, Some(now - Duration::days(3)), now, 7));
+        assert!(review_due(None, Some(now - Duration::days(8)), now, 7));
+        assert!(!review_due(Some(now - Duration::days(2)), Some(now - Duration::days(30)), now, 7));
+        assert!(review_due(Some(now - Duration::days(7)), None, now, 7));
+    }
+}
@@ -0,0 +1,278 @@
+//! Productivity statistics for the stats view.
+//!
+//! [`Stats::compute`] works from the timestamps every task already has:
+//! `created_at`, `completed_at` and `due_date`. Nothing extra is stored.
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime};
+
+use super::{Project, Task, TaskStatus};
+
+/// Task counts for one day, or one week in [`Stats::weekly`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayStats {
+    /// The day, or the Monday of the week
+    pub date: NaiveDate,
+    /// Tasks created that day
+    pub created: usize,
+    /// Tasks completed that day
+    pub completed: usize,
+    /// Open tasks past their due date at the end of the day
+    pub overdue: usize,
+}
+
+/// How many of a project's or tag's tasks are done.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// Project name or `#tag`
+    pub label: String,
+    /// Completed or archived tasks
+    pub done: usize,
+    /// All tasks
+    pub total: usize,
+}
+
+/// Statistics over a range of days ending today.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    /// One entry per day, oldest first, the last being today
+    pub days: Vec<DayStats>,
+    /// Average time from creation to completion of completed tasks
+    pub lead_time: Option<Duration>,
+    /// Completion per project in sidebar order, then tasks without one
+    pub by_project: Vec<Completion>,
+    /// Completion per tag, alphabetically
+    pub by_tag: Vec<Completion>,
+}
+
+impl Stats {
+    /// Days covered by the stats view: twelve weeks, enough for the heatmap.
+    pub const DAYS: usize = 84;
+
+    /// Computes statistics for the `days` days up to and including `now`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Local;
+    /// use ratado::models::{Stats, Task};
+    ///
+    /// let mut task = Task::new("Ship it");
+    /// task.complete();
+    /// let stats = Stats::compute(&[task], &[], Local::now(), 7);
+    /// assert_eq!(stats.days.len(), 7);
+    /// assert_eq!(stats.days[6].completed, 1);
+    /// ```
+    pub fn compute(tasks: &[Task], projects: &[Project], now: DateTime<Local>, days: usize) -> Self {
+        let local = |time: DateTime<chrono::Utc>| time.with_timezone(&Local).naive_local();
+        let now_local = now.naive_local();
+        let today = now_local.date();
+
+        let days = (0..days)
+            .rev()
+            .map(|ago| {
+                let date = today - Duration::days(ago as i64);
+                // Overdue is counted at the end of the day, or now for today
+                let cutoff: NaiveDateTime = if ago == 0 {
+                    now_local
+                } else {
+                    (date + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap_or(now_local)
+                };
+                let overdue = tasks
+                    .iter()
+                    .filter(|t| {
+                        // When an archived task was closed isn't known
+                        let unknown = t.status == TaskStatus::Archived && t.completed_at.is_none();
+                        !unknown
+                            && local(t.created_at) <= cutoff
+                            && t.due_date.is_some_and(|due| local(due) < cutoff)
+                            && t.completed_at.is_none_or(|done| local(done) > cutoff)
+                    })
+                    .count();
+                DayStats {
+                    date,
+                    created: tasks.iter().filter(|t| local(t.created_at).date() == date).count(),
+                    completed: tasks
+                        .iter()
+                        .filter(|t| t.completed_at.is_some_and(|done| local(done).date() == date))
+                        .count(),
+                    overdue,
+                }
+            })
+            .collect();
+
+        let lead_times: Vec<Duration> = tasks
+            .iter()
+            .filter_map(|t| t.completed_at.map(|done| (done - t.created_at).max(Duration::zero())))
+            .collect();
+        let lead_time = (!lead_times.is_empty())
+            .then(|| lead_times.iter().fold(Duration::zero(), |sum, d| sum + *d) / lead_times.len() as i32);
+
+        let is_done = |t: &Task| matches!(t.status, TaskStatus::Completed | TaskStatus::Archived);
+        let completion = |label: String, tasks: &[&Task]| Completion {
+            label,
+            done: tasks.iter().filter(|t| is_done(t)).count(),
+            total: tasks.len(),
+        };
+
+        let mut by_project: Vec<Completion> = projects
+            .iter()
+            .map(|p| {
+                let in_project: Vec<&Task> = tasks.iter().filter(|t| t.project_id.as_ref() == Some(&p.id)).collect();
+                completion(p.name.clone(), &in_project)
+            })
+            .collect();
+        let no_project: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| t.project_id.as_ref().is_none_or(|id| !projects.iter().any(|p| &p.id == id)))
+            .collect();
+        by_project.push(completion("No project".to_string(), &no_project));
+        by_project.retain(|c| c.total > 0);
+
+        let mut tags: Vec<&String> = tasks.iter().flat_map(|t| &t.tags).collect();
+        tags.sort_by_key(|tag| tag.to_lowercase());
+        tags.dedup();
+        let by_tag = tags
+            .into_iter()
+            .map(|tag| {
+                let tagged: Vec<&Task> = tasks.iter().filter(|t| t.tags.contains(tag)).collect();
+                completion(format!("#{}", tag), &tagged)
+            })
+            .collect();
+
+        Self {
+            days,
+            lead_time,
+            by_project,
+            by_tag,
+        }
+    }
+
+    /// Sums the days into weeks starting on Monday, oldest first.
+    ///
+    /// The first week may be partial. A week's overdue count is the one at
+    /// the end of its last day.
+    pub fn weekly(&self) -> Vec<DayStats> {
+        let mut weeks: Vec<DayStats> = Vec::new();
+        for day in &self.days {
+            let monday = day.date - Duration::days(day.date.weekday().num_days_from_monday() as i64);
+            match weeks.last_mut() {
+                Some(week) if week.date == monday => {
+                    week.created += day.created;
+                    week.completed += day.completed;
+                    week.overdue = day.overdue;
+                }
+                _ => weeks.push(DayStats { date: monday, ..*day }),
+            }
+        }
+        weeks
+    }
+
+    /// Returns the total number of tasks completed over the range.
+    pub fn completed(&self) -> usize {
+        self.days.iter().map(|d| d.completed).sum()
+    }
+
+    /// Returns the total number of tasks created over the range.
+    pub fn created(&self) -> usize {
+        self.days.iter().map(|d| d.created).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn task(title: &str, created: DateTime<Local>) -> Task {
+        let mut task = Task::new(title);
+        task.created_at = created.to_utc();
+        task
+    }
+
+    #[test]
+    fn test_daily_counts_and_lead_time() {
+        // Wednesday 4 March 2026
+        let now = at(4, 12);
+        let mut a = task("A", at(2, 9));
+        a.status = TaskStatus::Completed;
+        a.completed_at = Some(at(3, 9).to_utc());
+        let mut b = task("B", at(3, 9));
+        b.status = TaskStatus::Completed;
+        b.completed_at = Some(at(4, 9).to_utc());
+        let c = task("C", at(4, 9));
+
+        let stats = Stats::compute(&[a, b, c], &[], now, 3);
+        let dates: Vec<u32> = stats.days.iter().map(|d| d.date.day()).collect();
+        assert_eq!(dates, vec![2, 3, 4]);
+        let created: Vec<usize> = stats.days.iter().map(|d| d.created).collect();
+        let completed: Vec<usize> = stats.days.iter().map(|d| d.completed).collect();
+        assert_eq!(created, vec![1, 1, 1]);
+        assert_eq!(completed, vec![0, 1, 1]);
+        assert_eq!(stats.lead_time, Some(Duration::days(1)));
+        assert_eq!((stats.created(), stats.completed()), (3, 2));
+    }
+
+    #[test]
+    fn test_overdue_over_time() {
+        let now = at(4, 12);
+        // Due on the 2nd, done on the 3rd
+        let mut late = task("Late", at(1, 9));
+        late.due_date = Some(at(2, 17).to_utc());
+        late.status = TaskStatus::Completed;
+        late.completed_at = Some(at(3, 10).to_utc());
+        // Due this morning, still open
+        let mut open = task("Open", at(1, 9));
+        open.due_date = Some(at(4, 9).to_utc());
+        // Archived without a completion time is left out
+        let mut archived = task("Archived", at(1, 9));
+        archived.due_date = Some(at(1, 10).to_utc());
+        archived.status = TaskStatus::Archived;
+
+        let stats = Stats::compute(&[late, open, archived], &[], now, 4);
+        let overdue: Vec<usize> = stats.days.iter().map(|d| d.overdue).collect();
+        assert_eq!(overdue, vec![0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_weekly_totals() {
+        // Sunday 8 March to Monday 9 March 2026
+        let now = at(9, 12);
+        let mut a = task("A", at(8, 9));
+        a.status = TaskStatus::Completed;
+        a.completed_at = Some(at(8, 10).to_utc());
+        let b = task("B", at(9, 9));
+        let c = task("C", at(9, 10));
+
+        let weeks = Stats::compute(&[a, b, c], &[], now, 8).weekly();
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[0].date, NaiveDate::from_ymd_opt(2026, 3, 2).unwrap());
+        assert_eq!((weeks[0].created, weeks[0].completed), (1, 1));
+        assert_eq!((weeks[1].created, weeks[1].completed), (2, 0));
+    }
+
+    #[test]
+    fn test_completion_by_project_and_tag() {
+        let work = Project::new("Work");
+        let home = Project::new("Home");
+        let mut a = Task::new("A");
+        a.project_id = Some(work.id.clone());
+        a.tags = vec!["urgent".to_string()];
+        a.complete();
+        let mut b = Task::new("B");
+        b.project_id = Some(work.id.clone());
+        b.tags = vec!["urgent".to_string(), "admin".to_string()];
+        let c = Task::new("C");
+
+        let stats = Stats::compute(&[a, b, c], &[work, home], Local::now(), 1);
+        let projects: Vec<(&str, usize, usize)> =
+            stats.by_project.iter().map(|c| (c.label.as_str(), c.done, c.total)).collect();
+        assert_eq!(projects, vec![("Work", 1, 2), ("No project", 0, 1)]);
+        let tags: Vec<(&str, usize, usize)> = stats.by_tag.iter().map(|c| (c.label.as_str(), c.done, c.total)).collect();
+        assert_eq!(tags, vec![("#admin", 0, 1), ("#urgent", 1, 2)]);
+        assert_eq!(Stats::compute(&[], &[], Local::now(), 1).lead_time, None);
+    }
+}
@@ -7,6 +7,8 @@ use chrono::{DateTime, Duration, Local, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{position_at, Note};
+
 /// Task priority levels.
 ///
 /// Priority determines the importance of a task and affects how it's displayed
@@ -78,7 +80,7 @@ impl Ord for Priority {
 /// task.complete();
 /// assert_eq!(task.status, TaskStatus::Completed);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum TaskStatus {
     /// Task is waiting to be started (default state)
     #[default]
@@ -136,6 +138,12 @@ pub struct Task {
     pub updated_at: DateTime<Utc>,
     /// When the task was completed (UTC), if applicable
     pub completed_at: Option<DateTime<Utc>>,
+    /// Place in the manual sort order; lower comes first
+    #[serde(default)]
+    pub position: f64,
+    /// Notes added over time, oldest first
+    #[serde(default)]
+    pub notes: Vec<Note>,
 }
 
 impl Task {
@@ -146,7 +154,8 @@ impl Task {
     /// - Medium priority
     /// - Pending status
     /// - Current timestamp for created_at and updated_at
-    /// - No description, due date, project, or tags
+    /// - No description, due date, project, tags or notes
+    /// - A manual order position after every existing task
     ///
     /// # Arguments
     ///
@@ -178,6 +187,8 @@ impl Task {
             created_at: now,
             updated_at: now,
             completed_at: None,
+            position: position_at(now),
+            notes: Vec::new(),
         }
     }
 
@@ -314,6 +325,32 @@ impl Task {
         self.completed_at = None;
         self.updated_at = Utc::now();
     }
+
+    /// Adds a note to the end of the task's notes.
+    ///
+    /// Surrounding blank lines are trimmed; a note with no text isn't added.
+    /// Returns whether the note was added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ratado::models::Task;
+    ///
+    /// let mut task = Task::new("Task");
+    /// assert!(task.add_note("Waiting on legal\n"));
+    /// assert!(!task.add_note("   "));
+    /// assert_eq!(task.notes.len(), 1);
+    /// assert_eq!(task.notes[0].text, "Waiting on legal");
+    /// ```
+    pub fn add_note(&mut self, text: &str) -> bool {
+        let text = text.trim_matches(|c: char| c == '\n' || c == '\r').trim_end();
+        if text.trim().is_empty() {
+            return false;
+        }
+        self.notes.push(Note::new(text));
+        self.updated_at = Utc::now();
+        true
+    }
 }
 
 impl Default for Task {
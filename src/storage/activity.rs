@@ -0,0 +1,444 @@
+//! Task change history.
+//!
+//! Changes made through [`Database`] to a task's title, status, priority,
+//! project, tags or due date are recorded as [`TaskEvent`]s, one per field,
+//! as are added notes and creating and deleting the task. The task detail view shows a
+//! task's history and the activity view the latest events of all tasks.
+//!
+//! Like the sync bookkeeping, the history is kept in SQLite, also for
+//! plaintext stores, and writing to it doesn't count as an external change.
+
+use chrono::{DateTime, Local, Utc};
+use turso::Value;
+
+use crate::models::{Task, TaskStatus};
+use crate::storage::sqlite::{priority_to_str, status_to_str, str_to_status};
+use crate::storage::{Database, Result, StorageError};
+
+/// What a [`TaskEvent`] changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskField {
+    /// The task was created
+    Created,
+    /// The title changed
+    Title,
+    /// The status changed
+    Status,
+    /// The priority changed
+    Priority,
+    /// The task moved to another project, or out of one
+    Project,
+    /// The due date was set, changed or removed
+    DueDate,
+    /// A tag was added or removed
+    Tag,
+    /// A note was added
+    Note,
+    /// The task was deleted
+    Deleted,
+}
+
+impl TaskField {
+    /// Name stored in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskField::Created => "created",
+            TaskField::Title => "title",
+            TaskField::Status => "status",
+            TaskField::Priority => "priority",
+            TaskField::Project => "project",
+            TaskField::DueDate => "due_date",
+            TaskField::Tag => "tag",
+            TaskField::Note => "note",
+            TaskField::Deleted => "deleted",
+        }
+    }
+
+    /// Parses a stored name, or `None` for names from a newer version.
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "created" => TaskField::Created,
+            "title" => TaskField::Title,
+            "status" => TaskField::Status,
+            "priority" => TaskField::Priority,
+            "project" => TaskField::Project,
+            "due_date" => TaskField::DueDate,
+            "tag" => TaskField::Tag,
+            "note" => TaskField::Note,
+            "deleted" => TaskField::Deleted,
+            _ => return None,
+        })
+    }
+}
+
+/// One recorded change to a task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskEvent {
+    /// ID of the task, which may since have been deleted
+    pub task_id: String,
+    /// Title of the task after the change
+    pub title: String,
+    /// What changed
+    pub field: TaskField,
+    /// Value before the change: a status or priority name, project name,
+    /// RFC 3339 due date, title or removed tag
+    pub old_value: Option<String>,
+    /// Value after the change, in the same form; an added tag for tags,
+    /// and the text for notes
+    pub new_value: Option<String>,
+    /// When the change was made
+    pub changed_at: DateTime<Utc>,
+}
+
+impl TaskEvent {
+    /// Describes the change, e.g. "Completed" or "Priority medium → high".
+    pub fn summary(&self) -> String {
+        let old = self.old_value.as_deref();
+        let new = self.new_value.as_deref();
+        match self.field {
+            TaskField::Created => "Created".to_string(),
+            TaskField::Deleted => "Deleted".to_string(),
+            TaskField::Title => format!("Renamed from \"{}\"", old.unwrap_or_default()),
+            TaskField::Status => match (old.map(str_to_status), new.map(str_to_status)) {
+                (_, Some(TaskStatus::Completed)) => "Completed".to_string(),
+                (_, Some(TaskStatus::Archived)) => "Archived".to_string(),
+                (_, Some(TaskStatus::InProgress)) => "Started".to_string(),
+                (Some(TaskStatus::Completed | TaskStatus::Archived), _) => "Reopened".to_string(),
+                _ => "Marked pending".to_string(),
+            },
+            TaskField::Priority => format!(
+                "Priority {} → {}",
+                old.unwrap_or("medium"),
+                new.unwrap_or("medium")
+            ),
+            TaskField::Project => match (old, new) {
+                (_, Some(project)) => format!("Moved to {}", project),
+                (Some(project), None) => format!("Removed from {}", project),
+                (None, None) => "Moved".to_string(),
+            },
+            TaskField::DueDate => match (old.and_then(format_date), new.and_then(format_date)) {
+                (None, Some(due)) => format!("Due date set to {}", due),
+                (Some(from), Some(to)) => format!("Due date moved from {} to {}", from, to),
+                _ => "Due date removed".to_string(),
+            },
+            TaskField::Tag => match (old, new) {
+                (_, Some(tag)) => format!("Tagged #{}", tag),
+                (Some(tag), None) => format!("Untagged #{}", tag),
+                (None, None) => "Tags changed".to_string(),
+            },
+            TaskField::Note => "Added a note".to_string(),
+        }
+    }
+
+    /// Returns whether this event completed the task.
+    pub fn is_completion(&self) -> bool {
+        self.field == TaskField::Status
+            && self.new_value.as_deref().map(str_to_status) == Some(TaskStatus::Completed)
+    }
+}
+
+/// Formats a stored due date for a summary, in local time.
+fn format_date(value: &str) -> Option<String> {
+    let date = DateTime::parse_from_rfc3339(value).ok()?.with_timezone(&Local);
+    Some(date.format("%a %-d %b").to_string())
+}
+
+/// Returns the events for a change from `before` to `after`.
+///
+/// A task that didn't exist `before` was created, and one that doesn't
+/// exist `after` was deleted. Projects are given by ID; the caller
+/// replaces them with names.
+fn task_changes(before: Option<&Task>, after: Option<&Task>, now: DateTime<Utc>) -> Vec<TaskEvent> {
+    let event = |task: &Task, field, old_value: Option<String>, new_value: Option<String>| TaskEvent {
+        task_id: task.id.clone(),
+        title: task.title.clone(),
+        field,
+        old_value,
+        new_value,
+        changed_at: now,
+    };
+    let (before, after) = match (before, after) {
+        (None, Some(task)) => return vec![event(task, TaskField::Created, None, None)],
+        (Some(task), None) => return vec![event(task, TaskField::Deleted, None, None)],
+        (Some(before), Some(after)) => (before, after),
+        (None, None) => return Vec::new(),
+    };
+
+    let mut events = Vec::new();
+    if before.title != after.title {
+        events.push(event(after, TaskField::Title, Some(before.title.clone()), Some(after.title.clone())));
+    }
+    if before.status != after.status {
+        events.push(event(
+            after,
+            TaskField::Status,
+            Some(status_to_str(before.status).to_string()),
+            Some(status_to_str(after.status).to_string()),
+        ));
+    }
+    if before.priority != after.priority {
+        events.push(event(
+            after,
+            TaskField::Priority,
+            Some(priority_to_str(before.priority).to_string()),
+            Some(priority_to_str(after.priority).to_string()),
+        ));
+    }
+    if before.project_id != after.project_id {
+        events.push(event(after, TaskField::Project, before.project_id.clone(), after.project_id.clone()));
+    }
+    if before.due_date != after.due_date {
+        events.push(event(
+            after,
+            TaskField::DueDate,
+            before.due_date.map(|d| d.to_rfc3339()),
+            after.due_date.map(|d| d.to_rfc3339()),
+        ));
+    }
+    for tag in before.tags.iter().filter(|t| !after.tags.contains(t)) {
+        events.push(event(after, TaskField::Tag, Some(tag.clone()), None));
+    }
+    for tag in after.tags.iter().filter(|t| !before.tags.contains(t)) {
+        events.push(event(after, TaskField::Tag, None, Some(tag.clone())));
+    }
+    for note in after.notes.iter().filter(|n| !before.notes.iter().any(|b| b.id == n.id)) {
+        events.push(event(after, TaskField::Note, None, Some(note.text.clone())));
+    }
+    events
+}
+
+impl Database {
+    /// Records the history events for a change from `before` to `after`;
+    /// see [`Database::task_history`].
+    pub(crate) async fn record_task_events(&self, before: Option<&Task>, after: Option<&Task>) -> Result<()> {
+        let mut events = task_changes(before, after, Utc::now());
+        if events.iter().any(|e| e.field == TaskField::Project) {
+            let projects = self.get_all_projects().await?;
+            let name = |id: Option<String>| {
+                id.map(|id| projects.iter().find(|p| p.id == id).map_or(id, |p| p.name.clone()))
+            };
+            for event in events.iter_mut().filter(|e| e.field == TaskField::Project) {
+                event.old_value = name(event.old_value.take());
+                event.new_value = name(event.new_value.take());
+            }
+        }
+
+        for event in events {
+            self.execute(
+                "INSERT INTO _task_events (task_id, title, field, old_value, new_value, changed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                [
+                    Value::Text(event.task_id),
+                    Value::Text(event.title),
+                    Value::Text(event.field.as_str().to_string()),
+                    event.old_value.map_or(Value::Null, Value::Text),
+                    event.new_value.map_or(Value::Null, Value::Text),
+                    Value::Text(event.changed_at.to_rfc3339()),
+                ],
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the recorded changes to a task, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use ratado::models::Task;
+    /// use ratado::storage::Database;
+    ///
+    /// # async fn example(db: &Database) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut task = db.add_task(Task::new("Write report")).await?;
+    /// task.complete();
+    /// db.update_task(&task).await?;
+    ///
+    /// let history = db.task_history(&task.id).await?;
+    /// assert_eq!(history[0].summary(), "Completed");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn task_history(&self, task_id: &str) -> Result<Vec<TaskEvent>> {
+        self.query_events(
+            "SELECT task_id, title, field, old_value, new_value, changed_at FROM _task_events
+             WHERE task_id = ?1 ORDER BY changed_at DESC, id DESC",
+            [Value::Text(task_id.to_string())],
+        )
+        .await
+    }
+
+    /// Returns up to `limit` changes to any task, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn recent_activity(&self, limit: usize) -> Result<Vec<TaskEvent>> {
+        self.query_events(
+            "SELECT task_id, title, field, old_value, new_value, changed_at FROM _task_events
+             ORDER BY changed_at DESC, id DESC LIMIT ?1",
+            [Value::Integer(limit as i64)],
+        )
+        .await
+    }
+
+    /// Runs a query selecting event columns, skipping events of unknown
+    /// fields.
+    async fn query_events(&self, sql: &str, params: impl turso::IntoParams) -> Result<Vec<TaskEvent>> {
+        let mut rows = self.query(sql, params).await?;
+        let mut events = Vec::new();
+        while let Some(row) = rows.next().await? {
+            let Some(field) = TaskField::parse(&value_to_string(row.get_value(2)?)?) else {
+                continue;
+            };
+            events.push(TaskEvent {
+                task_id: value_to_string(row.get_value(0)?)?,
+                title: value_to_string(row.get_value(1)?)?,
+                field,
+                old_value: value_to_option_string(row.get_value(3)?)?,
+                new_value: value_to_option_string(row.get_value(4)?)?,
+                changed_at: value_to_datetime(row.get_value(5)?)?,
+            });
+        }
+        Ok(events)
+    }
+}
+
+fn value_to_string(value: Value) -> Result<String> {
+    match value {
+        Value::Text(s) => Ok(s),
+        _ => Err(StorageError::Conversion(format!(
+            "Expected text, got {:?}",
+            value
+        ))),
+    }
+}
+
+fn value_to_option_string(value: Value) -> Result<Option<String>> {
+    match value {
+        Value::Text(s) => Ok(Some(s)),
+        Value::Null => Ok(None),
+        _ => Err(StorageError::Conversion(format!(
+            "Expected text or null, got {:?}",
+            value
+        ))),
+    }
+}
+
+fn value_to_datetime(value: Value) -> Result<DateTime<Utc>> {
+    match value {
+        Value::Text(s) => DateTime::parse_from_rfc3339(&s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|e| StorageError::Conversion(format!("Invalid datetime '{}': {}", s, e))),
+        _ => Err(StorageError::Conversion(format!(
+            "Expected datetime text, got {:?}",
+            value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, Project};
+    use crate::storage::run_migrations;
+
+    async fn setup_db() -> Database {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db
+    }
+
+    fn summaries(events: &[TaskEvent]) -> Vec<String> {
+        events.iter().map(TaskEvent::summary).collect()
+    }
+
+    #[test]
+    fn test_task_changes() {
+        let now = Utc::now();
+        let before = Task::new("Draft");
+        let mut after = before.clone();
+        after.title = "Final".to_string();
+        after.priority = Priority::High;
+        after.tags = vec!["work".to_string()];
+        after.add_note("Ready for review");
+
+        let events = task_changes(Some(&before), Some(&after), now);
+        let fields: Vec<TaskField> = events.iter().map(|e| e.field).collect();
+        assert_eq!(
+            fields,
+            vec![TaskField::Title, TaskField::Priority, TaskField::Tag, TaskField::Note]
+        );
+        assert_eq!(
+            summaries(&events),
+            vec!["Renamed from \"Draft\"", "Priority medium → high", "Tagged #work", "Added a note"]
+        );
+        assert_eq!(events[3].new_value.as_deref(), Some("Ready for review"));
+        assert!(events.iter().all(|e| e.title == "Final"));
+
+        assert_eq!(task_changes(None, Some(&before), now)[0].field, TaskField::Created);
+        assert_eq!(task_changes(Some(&before), None, now)[0].field, TaskField::Deleted);
+        assert!(task_changes(Some(&before), Some(&before), now).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_task_history() {
+        let db = setup_db().await;
+        let project = Project::new("Work");
+        db.insert_project(&project).await.unwrap();
+
+        let mut task = db.add_task(Task::new("Report")).await.unwrap();
+        task.project_id = Some(project.id.clone());
+        db.update_task(&task).await.unwrap();
+        task.complete();
+        db.modify_task(task.clone()).await.unwrap();
+        // Unchanged fields aren't recorded
+        db.update_task(&task).await.unwrap();
+
+        let history = db.task_history(&task.id).await.unwrap();
+        assert_eq!(summaries(&history), vec!["Completed", "Moved to Work", "Created"]);
+        assert!(history[0].is_completion());
+    }
+
+    #[tokio::test]
+    async fn test_recent_activity_outlives_tasks() {
+        let db = setup_db().await;
+        let first = db.add_task(Task::new("First")).await.unwrap();
+        let second = db.add_task(Task::new("Second")).await.unwrap();
+        db.delete_task(&first.id).await.unwrap();
+
+        let activity = db.recent_activity(10).await.unwrap();
+        let titles: Vec<(&str, TaskField)> = activity.iter().map(|e| (e.title.as_str(), e.field)).collect();
+        assert_eq!(
+            titles,
+            vec![
+                ("First", TaskField::Deleted),
+                ("Second", TaskField::Created),
+                ("First", TaskField::Created),
+            ]
+        );
+        assert_eq!(db.recent_activity(1).await.unwrap().len(), 1);
+        assert_eq!(db.task_history(&second.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_history_of_bulk_changes_and_deleted_tags() {
+        let db = setup_db().await;
+        let mut task = Task::new("Tagged");
+        task.tags = vec!["old".to_string()];
+        db.insert_task(&task).await.unwrap();
+
+        let mut changed = task.clone();
+        changed.priority = Priority::Urgent;
+        db.modify_tasks(vec![changed]).await.unwrap();
+        let tag = db.get_tag_by_name("old").await.unwrap().unwrap();
+        db.delete_tag(&tag.id).await.unwrap();
+
+        let history = db.task_history(&task.id).await.unwrap();
+        assert_eq!(summaries(&history), vec!["Untagged #old", "Priority medium → urgent"]);
+    }
+}
@@ -0,0 +1,518 @@
+//! Database backups and snapshots.
+//!
+//! Backups are full copies of the database file, kept in a `backups`
+//! directory next to it and named after the database, the time they were
+//! taken and the reason:
+//!
+//! ```text
+//! ~/.config/ratado/backups/ratado-20260203-091500-startup.db
+//! ~/.config/ratado/backups/ratado-20260203-091500-pre-migration-v4.db
+//! ```
+//!
+//! The part after the database name (`20260203-091500-startup`) is the
+//! backup ID used by `ratado backup restore`. Only the most recent backups
+//! of each kind are kept, so frequent startup backups never push out the
+//! snapshot taken before a reset or migration; see
+//! [`Database::set_backup_retention`].
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use log::{info, warn};
+
+use crate::storage::encryption::{copy_salt, remove_salt};
+use crate::storage::{Database, EncryptionKey, Result, StorageError};
+
+/// Name of the backup directory, created next to the database file.
+const BACKUP_DIR: &str = "backups";
+
+/// Timestamp prefix of every backup ID.
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Length of a formatted [`TIMESTAMP_FORMAT`] timestamp.
+const TIMESTAMP_LEN: usize = 15;
+
+/// A backup copy of a database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    /// Identifier used to restore the backup (timestamp and reason)
+    pub id: String,
+    /// Location of the backup file
+    pub path: PathBuf,
+    /// When the backup was taken
+    pub created_at: DateTime<Local>,
+    /// Why the backup was taken (e.g. `startup`, `pre-reset`)
+    pub reason: String,
+    /// Size of the backup file in bytes
+    pub size: u64,
+}
+
+impl Database {
+    /// Takes a backup of the database, then deletes backups of the same kind
+    /// beyond the retention limit.
+    ///
+    /// Returns `None` without doing anything for in-memory databases,
+    /// plaintext stores (which are versioned with git instead), or when
+    /// backups are disabled (retention of zero).
+    ///
+    /// # Arguments
+    ///
+    /// * `reason` - Short label stored in the backup name, like `startup`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database can't be checkpointed or the file
+    /// can't be copied.
+    pub async fn backup(&self, reason: &str) -> Result<Option<Backup>> {
+        let Some(path) = self.path() else {
+            return Ok(None);
+        };
+        if self.plaintext_dir().is_some() || self.backup_retention() == 0 {
+            return Ok(None);
+        }
+
+        // Move everything from the write-ahead log into the main file, so
+        // copying that one file captures the whole database.
+        self.query_one("PRAGMA wal_checkpoint(TRUNCATE)", ()).await?;
+
+        let backup = copy_to_backup(path, reason)?;
+        info!("Backed up database to {:?}", backup.path);
+        prune_backups(path, &backup.reason, self.backup_retention())?;
+        Ok(Some(backup))
+    }
+
+    /// Writes a copy of the database to `dest`, replacing it atomically so
+    /// readers never see a partly written file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Backup`] for in-memory databases and
+    /// plaintext stores, or if the database can't be checkpointed or the
+    /// file can't be copied.
+    pub async fn snapshot_to(&self, dest: &Path) -> Result<()> {
+        let Some(path) = self.path() else {
+            return Err(StorageError::Backup(
+                "In-memory databases can't be copied".to_string(),
+            ));
+        };
+        if self.plaintext_dir().is_some() {
+            return Err(StorageError::Backup(
+                "Plaintext stores can't be copied to a single file".to_string(),
+            ));
+        }
+        self.query_one("PRAGMA wal_checkpoint(TRUNCATE)", ()).await?;
+
+        let staged = dest.with_extension("db.tmp");
+        std::fs::copy(path, &staged).map_err(|e| backup_error("copy", path, e))?;
+        std::fs::rename(&staged, dest).map_err(|e| backup_error("write", dest, e))?;
+        copy_salt(path, dest)
+    }
+}
+
+/// Returns the directory holding backups of the database at `db_path`.
+pub fn backup_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(BACKUP_DIR)
+}
+
+/// Lists backups of the database at `db_path`, newest first.
+///
+/// # Errors
+///
+/// Returns an error if the backup directory exists but can't be read.
+pub fn list_backups(db_path: &Path) -> Result<Vec<Backup>> {
+    let dir = backup_dir(db_path);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(backup_error("read", &dir, e)),
+    };
+
+    let prefix = file_prefix(db_path);
+    let mut backups: Vec<(Backup, Option<SystemTime>)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let id = name.strip_prefix(&prefix)?.strip_suffix(".db")?;
+            let (created_at, reason) = parse_id(id)?;
+            let metadata = entry.metadata().ok();
+            let backup = Backup {
+                id: id.to_string(),
+                path: entry.path(),
+                created_at,
+                reason,
+                size: metadata.as_ref().map_or(0, |m| m.len()),
+            };
+            Some((backup, metadata.and_then(|m| m.modified().ok())))
+        })
+        .collect();
+
+    // IDs only have second precision; the file time orders backups taken
+    // within the same second.
+    backups.sort_by(|(a, a_time), (b, b_time)| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| b_time.cmp(a_time))
+    });
+    Ok(backups.into_iter().map(|(backup, _)| backup).collect())
+}
+
+/// Deletes all but the `keep` newest backups of the database at `db_path`
+/// that are of the same kind as `reason`.
+///
+/// The kind is the reason without a migration version or same-second
+/// counter, so `pre-migration-v4` and `pre-migration-v5` are pruned
+/// together while `startup` backups never displace them.
+///
+/// Returns the number of backups deleted.
+///
+/// # Errors
+///
+/// Returns an error if the backups can't be listed or deleted.
+pub fn prune_backups(db_path: &Path, reason: &str, keep: usize) -> Result<usize> {
+    let kind = backup_kind(reason);
+    let stale: Vec<Backup> = list_backups(db_path)?
+        .into_iter()
+        .filter(|b| backup_kind(&b.reason) == kind)
+        .skip(keep)
+        .collect();
+    for backup in &stale {
+        std::fs::remove_file(&backup.path)
+            .map_err(|e| backup_error("delete", &backup.path, e))?;
+        remove_salt(&backup.path)?;
+    }
+    Ok(stale.len())
+}
+
+/// Replaces the database at `db_path` with the backup `id`.
+///
+/// The current database is backed up first (as `pre-restore`), so a restore
+/// can itself be undone. The database must not be open elsewhere.
+///
+/// # Arguments
+///
+/// * `db_path` - Path of the database to restore
+/// * `id` - Backup ID, as shown by [`list_backups`]
+/// * `keep` - Retention limit applied to the `pre-restore` backup
+/// * `key` - Key of the current database, if it's encrypted
+///
+/// # Errors
+///
+/// Returns [`StorageError::NotFound`] if there is no backup with that ID,
+/// or an error if any file operation fails.
+pub async fn restore_backup(
+    db_path: &Path,
+    id: &str,
+    keep: usize,
+    key: Option<&EncryptionKey>,
+) -> Result<Backup> {
+    let backup = list_backups(db_path)?
+        .into_iter()
+        .find(|b| b.id == id)
+        .ok_or_else(|| StorageError::NotFound(format!("Backup not found: {}", id)))?;
+
+    // Stage the backup first: pruning after the pre-restore backup below
+    // may delete the very file being restored.
+    let staged = db_path.with_extension("db.restore");
+    std::fs::copy(&backup.path, &staged).map_err(|e| backup_error("copy", &backup.path, e))?;
+
+    if db_path.exists() {
+        let mut db = Database::open_with_key(db_path, key).await?;
+        db.set_backup_retention(keep.max(1));
+        db.backup("pre-restore").await?;
+    }
+
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = PathBuf::from(format!("{}{}", db_path.display(), suffix));
+        if sidecar.exists() {
+            std::fs::remove_file(&sidecar).map_err(|e| backup_error("delete", &sidecar, e))?;
+        }
+    }
+    std::fs::rename(&staged, db_path).map_err(|e| backup_error("restore", db_path, e))?;
+    copy_salt(&backup.path, db_path)?;
+
+    info!("Restored database {:?} from backup {}", db_path, backup.id);
+    Ok(backup)
+}
+
+/// Copies the database file into the backup directory.
+fn copy_to_backup(db_path: &Path, reason: &str) -> Result<Backup> {
+    let dir = backup_dir(db_path);
+    std::fs::create_dir_all(&dir).map_err(|e| backup_error("create", &dir, e))?;
+
+    let now = Local::now();
+    let reason = sanitize_reason(reason);
+    let base_id = format!("{}-{}", now.format(TIMESTAMP_FORMAT), reason);
+    let prefix = file_prefix(db_path);
+
+    // Two backups within the same second get a numeric suffix
+    let mut id = base_id.clone();
+    let mut counter = 2;
+    while dir.join(format!("{}{}.db", prefix, id)).exists() {
+        id = format!("{}-{}", base_id, counter);
+        counter += 1;
+    }
+
+    let path = dir.join(format!("{}{}.db", prefix, id));
+    let size = std::fs::copy(db_path, &path).map_err(|e| backup_error("copy", db_path, e))?;
+    copy_salt(db_path, &path)?;
+
+    let (created_at, reason) = parse_id(&id).unwrap_or((now, reason));
+    Ok(Backup {
+        id,
+        path,
+        created_at,
+        reason,
+        size,
+    })
+}
+
+/// Returns the file name prefix shared by all backups of a database.
+fn file_prefix(db_path: &Path) -> String {
+    let stem = db_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "ratado".to_string());
+    format!("{}-", stem)
+}
+
+/// Splits a backup ID into its timestamp and reason.
+fn parse_id(id: &str) -> Option<(DateTime<Local>, String)> {
+    let timestamp = id.get(..TIMESTAMP_LEN)?;
+    let reason = id.get(TIMESTAMP_LEN + 1..).unwrap_or_default();
+    let naive = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+    let created_at = Local.from_local_datetime(&naive).earliest()?;
+    Some((created_at, reason.to_string()))
+}
+
+/// Returns the kind of a backup reason, used to prune each kind separately.
+///
+/// Strips a same-second counter (`-2`) and then a version (`-v4`).
+fn backup_kind(reason: &str) -> &str {
+    fn strip_number<'a>(reason: &'a str, marker: &str) -> &'a str {
+        reason
+            .rsplit_once(marker)
+            .filter(|(_, n)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+            .map_or(reason, |(kind, _)| kind)
+    }
+    strip_number(strip_number(reason, "-"), "-v")
+}
+
+/// Restricts a reason to characters that are safe in file names.
+fn sanitize_reason(reason: &str) -> String {
+    let cleaned: String = reason
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let cleaned = cleaned.trim_matches('-');
+    if cleaned.is_empty() {
+        "manual".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+fn backup_error(action: &str, path: &Path, e: std::io::Error) -> StorageError {
+    warn!("Failed to {} {:?}: {}", action, path, e);
+    StorageError::Backup(format!("Failed to {} {}: {}", action, path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Task;
+    use crate::storage::run_migrations;
+
+    async fn setup_file_db(dir: &Path) -> Database {
+        let db = Database::open(&dir.join("ratado.db")).await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_database_is_not_backed_up() {
+        let db = Database::open_in_memory().await.unwrap();
+        assert!(db.backup("startup").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_backup_disabled_by_zero_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = setup_file_db(dir.path()).await;
+        db.set_backup_retention(0);
+        assert!(db.backup("startup").await.unwrap().is_none());
+        assert!(list_backups(db.path().unwrap()).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_backup_contains_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = setup_file_db(dir.path()).await;
+        db.insert_task(&Task::new("Keep me safe")).await.unwrap();
+
+        let backup = db.backup("Pre Reset!").await.unwrap().unwrap();
+        assert_eq!(backup.reason, "pre-reset");
+        assert!(backup.size > 0);
+
+        let copy = Database::open(&backup.path).await.unwrap();
+        let tasks = copy.get_all_tasks().await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Keep me safe");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_to() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = setup_file_db(dir.path()).await;
+        db.insert_task(&Task::new("Copied")).await.unwrap();
+
+        let dest = dir.path().join("copy.db");
+        db.snapshot_to(&dest).await.unwrap();
+        assert!(!dir.path().join("copy.db.tmp").exists());
+        let copy = Database::open(&dest).await.unwrap();
+        assert_eq!(copy.get_all_tasks().await.unwrap()[0].title, "Copied");
+
+        let memory = Database::open_in_memory().await.unwrap();
+        assert!(memory.snapshot_to(&dest).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_and_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = setup_file_db(dir.path()).await;
+        db.set_backup_retention(2);
+
+        let first = db.backup("startup").await.unwrap().unwrap();
+        let second = db.backup("startup").await.unwrap().unwrap();
+        let third = db.backup("startup").await.unwrap().unwrap();
+
+        let ids: Vec<String> = list_backups(db.path().unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|b| b.id)
+            .collect();
+        assert_eq!(ids, vec![third.id, second.id]);
+        assert!(!first.path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_prune_keeps_each_kind() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = setup_file_db(dir.path()).await;
+        db.set_backup_retention(2);
+
+        let reset = db.backup("pre-reset").await.unwrap().unwrap();
+        let migration = db.backup("pre-migration-v4").await.unwrap().unwrap();
+        for _ in 0..5 {
+            db.backup("startup").await.unwrap();
+        }
+
+        let reasons: Vec<String> = list_backups(db.path().unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|b| b.reason)
+            .collect();
+        assert_eq!(reasons.len(), 4);
+        assert!(reset.path.exists());
+        assert!(migration.path.exists());
+    }
+
+    #[test]
+    fn test_backup_kind() {
+        assert_eq!(backup_kind("startup"), "startup");
+        assert_eq!(backup_kind("startup-3"), "startup");
+        assert_eq!(backup_kind("pre-migration-v4"), "pre-migration");
+        assert_eq!(backup_kind("pre-migration-v4-2"), "pre-migration");
+        assert_eq!(backup_kind("pre-reset"), "pre-reset");
+    }
+
+    #[test]
+    fn test_list_ignores_other_databases() {
+        let dir = tempfile::tempdir().unwrap();
+        let backups = dir.path().join(BACKUP_DIR);
+        std::fs::create_dir_all(&backups).unwrap();
+        std::fs::write(backups.join("ratado-20260203-091500-startup.db"), b"x").unwrap();
+        std::fs::write(backups.join("ratado-work-20260203-091500-startup.db"), b"x").unwrap();
+        std::fs::write(backups.join("notes.txt"), b"x").unwrap();
+
+        let list = list_backups(&dir.path().join("ratado.db")).unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].id, "20260203-091500-startup");
+        assert_eq!(list[0].reason, "startup");
+
+        let work = list_backups(&dir.path().join("ratado-work.db")).unwrap();
+        assert_eq!(work.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_restore_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("ratado.db");
+        let backup_id = {
+            let db = setup_file_db(dir.path()).await;
+            db.insert_task(&Task::new("Original")).await.unwrap();
+            let backup = db.backup("before").await.unwrap().unwrap();
+            db.delete_all_tasks().await.unwrap();
+            backup.id
+        };
+
+        let restored = restore_backup(&db_path, &backup_id, 10, None).await.unwrap();
+        assert_eq!(restored.id, backup_id);
+
+        let db = Database::open(&db_path).await.unwrap();
+        let tasks = db.get_all_tasks().await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Original");
+
+        // The emptied database was kept as a pre-restore backup
+        let backups = list_backups(&db_path).unwrap();
+        assert!(backups.iter().any(|b| b.reason == "pre-restore"));
+    }
+
+    #[tokio::test]
+    async fn test_restore_encrypted_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("ratado.db");
+        setup_file_db(dir.path()).await;
+        let old = EncryptionKey::from_passphrase("old").unwrap();
+        let new = EncryptionKey::from_passphrase("new").unwrap();
+        crate::storage::change_key(&db_path, None, Some(&old)).await.unwrap();
+        let backup_id = {
+            let db = Database::open_with_key(&db_path, Some(&old)).await.unwrap();
+            db.insert_task(&Task::new("Original")).await.unwrap();
+            db.backup("before").await.unwrap().unwrap().id
+        };
+        crate::storage::change_key(&db_path, Some(&old), Some(&new)).await.unwrap();
+
+        // The backup keeps the salt it was encrypted with
+        restore_backup(&db_path, &backup_id, 10, Some(&new)).await.unwrap();
+        let db = Database::open_with_key(&db_path, Some(&old)).await.unwrap();
+        assert_eq!(db.get_all_tasks().await.unwrap()[0].title, "Original");
+        drop(db);
+
+        // Pruning takes the salt along
+        prune_backups(&db_path, "pre-restore", 0).unwrap();
+        let salts = std::fs::read_dir(backup_dir(&db_path))
+            .unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().ends_with("-salt"))
+            .count();
+        assert_eq!(salts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_restore_unknown_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = restore_backup(&dir.path().join("ratado.db"), "nope", 10, None).await;
+        assert!(matches!(result, Err(StorageError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_sanitize_reason() {
+        assert_eq!(sanitize_reason("pre-migration-v4"), "pre-migration-v4");
+        assert_eq!(sanitize_reason("Bulk delete"), "bulk-delete");
+        assert_eq!(sanitize_reason("../"), "manual");
+    }
+}
@@ -0,0 +1,390 @@
+//! Tracking which tasks and projects a set of writes changed.
+//!
+//! A running Ratado holds its database file exclusively, so other programs
+//! (the CLI, `ratado serve`, `ratado mcp`) don't open it while the TUI is
+//! up; they send their work through its control socket instead (see
+//! [`crate::handlers::rpc`]). The TUI runs that work with
+//! [`Database::track_changes`], which records the IDs of the items it wrote,
+//! and reloads just those.
+//!
+//! Plaintext stores can also be edited outside Ratado, by hand or by a
+//! `git pull`. [`Database::external_changes`] compares their files with what
+//! the store last saw and reports the items whose files changed.
+//!
+//! ```rust,no_run
+//! use ratado::models::Task;
+//! use ratado::storage::Database;
+//!
+//! # async fn example(db: &Database) -> Result<(), Box<dyn std::error::Error>> {
+//! let (added, changes) = db.track_changes(async || db.add_task(Task::new("Call Bob")).await).await;
+//! let task = added?;
+//! assert!(changes.tasks.contains(&task.id));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::models::{Filter, Note, Project, SortOrder, Task, TaskStatus};
+use crate::storage::{Database, Result, Store, Tag};
+
+/// IDs of the items changed by a set of writes.
+///
+/// Items that were created, changed and deleted are all listed; reading
+/// them back tells which. Writes that were rolled back may still be listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeSet {
+    /// Tasks that were written or deleted
+    pub tasks: BTreeSet<String>,
+    /// Projects that were written or deleted
+    pub projects: BTreeSet<String>,
+    /// Whether the tags or their task counts may have changed
+    pub tags: bool,
+}
+
+impl ChangeSet {
+    /// Returns whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty() && self.projects.is_empty() && !self.tags
+    }
+
+    /// Adds the changes in `other`.
+    pub fn merge(&mut self, other: ChangeSet) {
+        self.tasks.extend(other.tasks);
+        self.projects.extend(other.projects);
+        self.tags |= other.tags;
+    }
+}
+
+/// Collects the changes of the writes made during [`Database::track_changes`].
+///
+/// Shared between clones of a [`Database`], which all use the same store.
+#[derive(Debug, Default)]
+pub(crate) struct ChangeTracker {
+    /// Changes recorded so far by each running `track_changes` call
+    recording: Mutex<Vec<(u64, ChangeSet)>>,
+    /// Identifies the next `track_changes` call
+    next_id: AtomicU64,
+}
+
+impl ChangeTracker {
+    fn is_recording(&self) -> bool {
+        !self.recording.lock().unwrap().is_empty()
+    }
+
+    fn record(&self, f: impl Fn(&mut ChangeSet)) {
+        for (_, changes) in self.recording.lock().unwrap().iter_mut() {
+            f(changes);
+        }
+    }
+}
+
+/// A [`Store`] that records the items written through it.
+///
+/// [`Database`] wraps its store in one, so every write is seen whichever
+/// store keeps the data.
+pub(crate) struct TrackedStore {
+    inner: Arc<dyn Store>,
+    changes: Arc<ChangeTracker>,
+}
+
+impl TrackedStore {
+    pub(crate) fn new(inner: Arc<dyn Store>, changes: Arc<ChangeTracker>) -> Self {
+        Self { inner, changes }
+    }
+}
+
+#[async_trait]
+impl Store for TrackedStore {
+    async fn get_task(&self, id: &str) -> Result<Option<Task>> {
+        self.inner.get_task(id).await
+    }
+
+    async fn get_all_tasks(&self) -> Result<Vec<Task>> {
+        self.inner.get_all_tasks().await
+    }
+
+    async fn query_tasks(&self, filter: &Filter, sort: &SortOrder) -> Result<Vec<Task>> {
+        self.inner.query_tasks(filter, sort).await
+    }
+
+    async fn count_tasks_by_status(&self) -> Result<HashMap<TaskStatus, usize>> {
+        self.inner.count_tasks_by_status().await
+    }
+
+    async fn count_tasks_by_project(&self) -> Result<HashMap<String, usize>> {
+        self.inner.count_tasks_by_project().await
+    }
+
+    async fn insert_task(&self, task: &Task) -> Result<()> {
+        self.inner.insert_task(task).await?;
+        self.changes.record(|c| {
+            c.tasks.insert(task.id.clone());
+            c.tags = true;
+        });
+        Ok(())
+    }
+
+    async fn update_task(&self, task: &Task) -> Result<bool> {
+        let updated = self.inner.update_task(task).await?;
+        self.changes.record(|c| {
+            c.tasks.insert(task.id.clone());
+            c.tags = true;
+        });
+        Ok(updated)
+    }
+
+    async fn add_note(&self, task_id: &str, note: &Note) -> Result<()> {
+        self.inner.add_note(task_id, note).await?;
+        self.changes.record(|c| {
+            c.tasks.insert(task_id.to_string());
+        });
+        Ok(())
+    }
+
+    async fn delete_tasks(&self, ids: &[String]) -> Result<usize> {
+        let deleted = self.inner.delete_tasks(ids).await?;
+        self.changes.record(|c| {
+            c.tasks.extend(ids.iter().cloned());
+            c.tags = true;
+        });
+        Ok(deleted)
+    }
+
+    async fn get_project(&self, id: &str) -> Result<Option<Project>> {
+        self.inner.get_project(id).await
+    }
+
+    async fn get_all_projects(&self) -> Result<Vec<Project>> {
+        self.inner.get_all_projects().await
+    }
+
+    async fn insert_project(&self, project: &Project) -> Result<()> {
+        self.inner.insert_project(project).await?;
+        self.changes.record(|c| {
+            c.projects.insert(project.id.clone());
+        });
+        Ok(())
+    }
+
+    async fn update_project(&self, project: &Project) -> Result<bool> {
+        let updated = self.inner.update_project(project).await?;
+        self.changes.record(|c| {
+            c.projects.insert(project.id.clone());
+        });
+        Ok(updated)
+    }
+
+    async fn delete_projects(&self, ids: &[String]) -> Result<usize> {
+        // Their tasks are left without a project
+        let mut orphaned = Vec::new();
+        if self.changes.is_recording() {
+            for id in ids {
+                orphaned.extend(task_ids(&*self.inner, &Filter::ByProject(id.clone())).await?);
+            }
+        }
+        let deleted = self.inner.delete_projects(ids).await?;
+        self.changes.record(|c| {
+            c.projects.extend(ids.iter().cloned());
+            c.tasks.extend(orphaned.iter().cloned());
+        });
+        Ok(deleted)
+    }
+
+    async fn get_tags_with_counts(&self) -> Result<Vec<(Tag, usize)>> {
+        self.inner.get_tags_with_counts().await
+    }
+
+    async fn insert_tag(&self, name: &str) -> Result<String> {
+        let id = self.inner.insert_tag(name).await?;
+        self.changes.record(|c| c.tags = true);
+        Ok(id)
+    }
+
+    async fn delete_tag(&self, id: &str) -> Result<bool> {
+        // The tag is removed from its tasks
+        let mut untagged = Vec::new();
+        if self.changes.is_recording() {
+            let tags = self.inner.get_tags_with_counts().await?;
+            if let Some((tag, _)) = tags.into_iter().find(|(tag, _)| tag.id == id) {
+                untagged = task_ids(&*self.inner, &Filter::ByTag(tag.name)).await?;
+            }
+        }
+        let deleted = self.inner.delete_tag(id).await?;
+        self.changes.record(|c| {
+            c.tasks.extend(untagged.iter().cloned());
+            c.tags = true;
+        });
+        Ok(deleted)
+    }
+
+    async fn add_tag_to_task(&self, task_id: &str, name: &str) -> Result<()> {
+        self.inner.add_tag_to_task(task_id, name).await?;
+        self.changes.record(|c| {
+            c.tasks.insert(task_id.to_string());
+            c.tags = true;
+        });
+        Ok(())
+    }
+
+    async fn remove_tag_from_task(&self, task_id: &str, tag_id: &str) -> Result<bool> {
+        let removed = self.inner.remove_tag_from_task(task_id, tag_id).await?;
+        self.changes.record(|c| {
+            c.tasks.insert(task_id.to_string());
+            c.tags = true;
+        });
+        Ok(removed)
+    }
+
+    async fn external_changes(&self) -> Result<ChangeSet> {
+        self.inner.external_changes().await
+    }
+
+    async fn begin(&self) {
+        self.inner.begin().await
+    }
+
+    async fn commit(&self) -> Result<()> {
+        self.inner.commit().await
+    }
+
+    async fn rollback(&self) {
+        self.inner.rollback().await
+    }
+}
+
+/// Returns the IDs of the tasks in `store` matching `filter`.
+async fn task_ids(store: &dyn Store, filter: &Filter) -> Result<Vec<String>> {
+    let tasks = store.query_tasks(filter, &SortOrder::default()).await?;
+    Ok(tasks.into_iter().map(|t| t.id).collect())
+}
+
+impl Database {
+    /// Runs `f`, returning its result and the items it wrote.
+    ///
+    /// Writes made meanwhile through clones of this database are included
+    /// too. Calls may be nested or run at the same time; each sees every
+    /// write made while it runs.
+    pub async fn track_changes<T>(&self, f: impl AsyncFnOnce() -> T) -> (T, ChangeSet) {
+        let id = self.changes.next_id.fetch_add(1, Ordering::Relaxed);
+        self.changes
+            .recording
+            .lock()
+            .unwrap()
+            .push((id, ChangeSet::default()));
+        let result = f().await;
+        let mut recording = self.changes.recording.lock().unwrap();
+        let index = recording.iter().position(|(i, _)| *i == id);
+        let changes = index.map(|i| recording.remove(i).1).unwrap_or_default();
+        (result, changes)
+    }
+
+    /// Returns the items changed outside Ratado since the last call.
+    ///
+    /// Only plaintext stores can change this way, e.g. by editing their
+    /// files or pulling them with git; while Ratado has a database file
+    /// open, no other process can write to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store can't be checked.
+    pub async fn external_changes(&self) -> Result<ChangeSet> {
+        self.store.external_changes().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::run_migrations;
+
+    async fn setup_db() -> Database {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_tracks_written_items() {
+        let db = setup_db().await;
+        let project = crate::models::Project::new("Work");
+        db.insert_project(&project).await.unwrap();
+        let mut task = Task::new("Plan");
+        task.project_id = Some(project.id.clone());
+        db.insert_task(&task).await.unwrap();
+        let untouched = Task::new("Untouched");
+        db.insert_task(&untouched).await.unwrap();
+
+        let (result, changes) = db
+            .track_changes(async || {
+                db.add_task(Task::new("New")).await.unwrap();
+                db.delete_project(&project.id).await
+            })
+            .await;
+        result.unwrap();
+
+        // The project's task moved out of it
+        assert!(changes.tasks.contains(&task.id));
+        assert!(!changes.tasks.contains(&untouched.id));
+        assert_eq!(changes.tasks.len(), 2);
+        assert_eq!(changes.projects, BTreeSet::from([project.id.clone()]));
+
+        // Writes outside track_changes aren't recorded
+        db.insert_task(&Task::new("Later")).await.unwrap();
+        let ((), changes) = db.track_changes(async || {}).await;
+        assert!(changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_nested_tracking() {
+        let db = setup_db().await;
+        let first = Task::new("First");
+        let second = Task::new("Second");
+
+        let (inner, outer) = db
+            .track_changes(async || {
+                db.insert_task(&first).await.unwrap();
+                db.track_changes(async || db.insert_task(&second).await.unwrap())
+                    .await
+                    .1
+            })
+            .await;
+
+        assert_eq!(inner.tasks, BTreeSet::from([second.id.clone()]));
+        assert_eq!(outer.tasks, BTreeSet::from([first.id.clone(), second.id.clone()]));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_tracking() {
+        let db = setup_db().await;
+        let first = Task::new("First");
+        let second = Task::new("Second");
+
+        // The first call ends while the second is still running
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let (((), one), ((), two)) = tokio::join!(
+            db.track_changes(async || {
+                db.insert_task(&first).await.unwrap();
+                tx.send(()).unwrap();
+            }),
+            db.track_changes(async || {
+                rx.await.unwrap();
+                db.insert_task(&second).await.unwrap();
+            }),
+        );
+
+        assert_eq!(one.tasks, BTreeSet::from([first.id.clone()]));
+        assert!(two.tasks.contains(&second.id));
+    }
+
+    #[tokio::test]
+    async fn test_database_files_have_no_external_changes() {
+        let db = setup_db().await;
+        db.insert_task(&Task::new("Mine")).await.unwrap();
+        assert!(db.external_changes().await.unwrap().is_empty());
+    }
+}
@@ -20,11 +20,20 @@
 //! ```
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use directories::ProjectDirs;
 use thiserror::Error;
 use turso::{Builder, Connection, Row, Rows, Value};
 
+use crate::hooks::{HookError, Hooks};
+use crate::models::NewTaskPlacement;
+use crate::storage::changes::{ChangeTracker, TrackedStore};
+use crate::storage::encryption::{is_encrypted, open_encrypted};
+use crate::storage::files::STATE_DIR;
+use crate::storage::{EncryptionKey, FileStore, SqliteStore, Store};
+use crate::webhooks::Webhook;
+
 /// Errors that can occur during storage operations.
 ///
 /// This enum covers all error conditions that can arise when working with
@@ -55,6 +64,59 @@ pub enum StorageError {
     /// Migration error
     #[error("Migration error: {0}")]
     Migration(String),
+
+    /// The database file is held open by another process
+    #[error("Database {} is in use by another Ratado process", .0.display())]
+    Locked(PathBuf),
+
+    /// Backup could not be taken, listed or restored
+    #[error("Backup error: {0}")]
+    Backup(String),
+
+    /// A hook script rejected the change or failed
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
+    /// A file of a plaintext store couldn't be read or written
+    #[error("Failed to access {}: {source}", path.display())]
+    File {
+        /// The file or directory involved
+        path: PathBuf,
+        /// Underlying I/O error
+        source: std::io::Error,
+    },
+
+    /// An item with the same ID already exists
+    #[error("Already exists: {0}")]
+    Exists(String),
+
+    /// The database is encrypted and no key was given
+    #[error("Database {} is encrypted; a passphrase is needed to open it", .0.display())]
+    KeyRequired(PathBuf),
+
+    /// The key doesn't decrypt the database
+    #[error("Wrong passphrase for encrypted database {}", .0.display())]
+    WrongKey(PathBuf),
+
+    /// The file is neither a plain nor an encrypted database
+    #[error("Database {} is damaged or not a Ratado database", .0.display())]
+    Corrupt(PathBuf),
+
+    /// Encrypting, decrypting or re-keying failed
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+}
+
+impl StorageError {
+    /// Wraps an error from opening the database file at `path`.
+    pub(crate) fn from_open(path: &Path, e: turso::Error) -> Self {
+        // Turso holds an exclusive lock on the file while it's open
+        if e.to_string().contains("locked by another process") {
+            StorageError::Locked(path.to_path_buf())
+        } else {
+            e.into()
+        }
+    }
 }
 
 /// Result type for storage operations.
@@ -62,8 +124,10 @@ pub type Result<T> = std::result::Result<T, StorageError>;
 
 /// Database connection wrapper.
 ///
-/// Provides a high-level interface for database operations. The database
-/// uses Turso (SQLite-compatible) for local storage.
+/// Provides a high-level interface for database operations. Tasks,
+/// projects and tags are kept by a [`Store`]: Turso (SQLite-compatible)
+/// tables by default, or plaintext files (see [`Database::open_dir`]).
+/// Bookkeeping such as migrations and the webhook outbox is always in SQLite.
 ///
 /// # Thread Safety
 ///
@@ -85,9 +149,34 @@ pub type Result<T> = std::result::Result<T, StorageError>;
 /// ```
 #[derive(Clone)]
 pub struct Database {
-    conn: Connection,
+    /// Connection for bookkeeping tables, and for data when it's the store
+    sql: SqliteStore,
+    /// Where tasks, projects and tags are kept
+    pub(crate) store: Arc<dyn Store>,
+    /// Location of the database file (`None` for in-memory databases)
+    path: Option<PathBuf>,
+    /// Directory of the plaintext store, if the data is kept in files
+    dir: Option<PathBuf>,
+    /// Key of the database file, if it's encrypted
+    key: Option<EncryptionKey>,
+    /// Number of backups to retain (zero disables backups)
+    backup_keep: usize,
+    /// Items written during [`Database::track_changes`]
+    pub(crate) changes: Arc<ChangeTracker>,
+    /// User scripts run when tasks are added, changed or deleted
+    pub(crate) hooks: Arc<Hooks>,
+    /// URLs that task and project changes are queued for
+    pub(crate) webhooks: Arc<[Webhook]>,
+    /// Where [`Database::add_task`] puts new tasks in the manual order
+    pub(crate) new_tasks: NewTaskPlacement,
+    /// Held while a transaction is open, and by writes that must stay out
+    /// of one, since clones share the connection
+    transaction: Arc<tokio::sync::Mutex<()>>,
 }
 
+/// Backups retained per database unless configured otherwise.
+const DEFAULT_BACKUP_KEEP: usize = 10;
+
 impl Database {
     /// Opens a database at the specified path.
     ///
@@ -119,19 +208,46 @@ impl Database {
     /// # }
     /// ```
     pub async fn open(path: &Path) -> Result<Self> {
+        Self::open_with_key(path, None).await
+    }
+
+    /// Opens a database file that may be encrypted.
+    ///
+    /// `key` is used only if the file is encrypted; new files are created
+    /// unencrypted (see [`change_key`](crate::storage::change_key)).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::KeyRequired`] if the file is encrypted and
+    /// `key` is `None`, [`StorageError::WrongKey`] if `key` doesn't decrypt
+    /// it, [`StorageError::Corrupt`] if it isn't a database, or an error if
+    /// it can't be opened.
+    pub async fn open_with_key(path: &Path, key: Option<&EncryptionKey>) -> Result<Self> {
         // Create parent directory if it doesn't exist
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        let path_str = path.to_string_lossy();
-        let db = Builder::new_local(&path_str).build().await?;
+        let key = if is_encrypted(path)? {
+            Some(key.ok_or_else(|| StorageError::KeyRequired(path.to_path_buf()))?)
+        } else {
+            None
+        };
+        let db = match key {
+            Some(key) => open_encrypted(path, key).await?,
+            None => Builder::new_local(&path.to_string_lossy())
+                .build()
+                .await
+                .map_err(|e| StorageError::from_open(path, e))?,
+        };
         let conn = db.connect()?;
 
         // Enable foreign key constraints
         conn.execute("PRAGMA foreign_keys = ON", ()).await?;
 
-        Ok(Self { conn })
+        let mut db = Self::with_connection(conn, Some(path.to_path_buf()));
+        db.key = key.cloned();
+        Ok(db)
     }
 
     /// Opens an in-memory database.
@@ -164,7 +280,60 @@ impl Database {
         // Enable foreign key constraints
         conn.execute("PRAGMA foreign_keys = ON", ()).await?;
 
-        Ok(Self { conn })
+        Ok(Self::with_connection(conn, None))
+    }
+
+    /// Opens a plaintext store in a directory, for keeping tasks in git.
+    ///
+    /// Each task and project is a file (see [`FileStore`]); bookkeeping is
+    /// kept in `.ratado/state.db` inside the directory, which is ignored by
+    /// git. Creates the directory and its layout if they don't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or its files can't be created, or
+    /// the bookkeeping database can't be opened.
+    pub async fn open_dir(dir: &Path) -> Result<Self> {
+        let mut db = Self::open(&dir.join(STATE_DIR).join("state.db")).await?;
+        db.store = Arc::new(TrackedStore::new(
+            Arc::new(FileStore::open(dir)?),
+            db.changes.clone(),
+        ));
+        db.dir = Some(dir.to_path_buf());
+        Ok(db)
+    }
+
+    /// Opens `path` as a plaintext store if it is a directory, or as a
+    /// database file otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store can't be opened.
+    pub async fn open_path(path: &Path) -> Result<Self> {
+        if path.is_dir() {
+            Self::open_dir(path).await
+        } else {
+            Self::open(path).await
+        }
+    }
+
+    /// Wraps a connection, using its tables as the store.
+    fn with_connection(conn: Connection, path: Option<PathBuf>) -> Self {
+        let changes: Arc<ChangeTracker> = Arc::default();
+        let sql = SqliteStore::new(conn);
+        Self {
+            store: Arc::new(TrackedStore::new(Arc::new(sql.clone()), changes.clone())),
+            sql,
+            path,
+            dir: None,
+            key: None,
+            backup_keep: DEFAULT_BACKUP_KEEP,
+            changes,
+            hooks: Arc::default(),
+            webhooks: Arc::new([]),
+            new_tasks: NewTaskPlacement::default(),
+            transaction: Arc::default(),
+        }
     }
 
     /// Opens the database at the default location.
@@ -222,6 +391,118 @@ impl Database {
         Ok(config_dir.join("ratado.db"))
     }
 
+    /// Returns the path of the database file, or `None` for in-memory databases.
+    ///
+    /// For a plaintext store, this is its bookkeeping database.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns the key of the database file, if it's encrypted.
+    pub fn encryption_key(&self) -> Option<&EncryptionKey> {
+        self.key.as_ref()
+    }
+
+    /// Returns the directory of the plaintext store, or `None` if the data
+    /// is kept in SQLite.
+    pub fn plaintext_dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Returns how many backups are retained for this database.
+    pub fn backup_retention(&self) -> usize {
+        self.backup_keep
+    }
+
+    /// Sets how many backups to retain for this database.
+    ///
+    /// Zero disables backups. See [`Database::backup`].
+    pub fn set_backup_retention(&mut self, keep: usize) {
+        self.backup_keep = keep;
+    }
+
+    /// Sets the hook scripts run by [`Database::add_task`],
+    /// [`Database::modify_task`], [`Database::remove_task`] and their bulk
+    /// versions.
+    pub fn set_hooks(&mut self, hooks: Hooks) {
+        self.hooks = Arc::new(hooks);
+    }
+
+    /// Returns whether any hook scripts are installed.
+    pub fn has_hooks(&self) -> bool {
+        !self.hooks.is_empty()
+    }
+
+    /// Sets the webhooks that task and project changes are queued for.
+    ///
+    /// See [`crate::webhooks`] for how queued events are delivered.
+    pub fn set_webhooks(&mut self, webhooks: Vec<Webhook>) {
+        self.webhooks = webhooks.into();
+    }
+
+    /// Sets where [`Database::add_task`] puts new tasks in the manual
+    /// order.
+    pub fn set_new_task_placement(&mut self, placement: NewTaskPlacement) {
+        self.new_tasks = placement;
+    }
+
+    /// Returns the configured webhooks.
+    pub fn webhooks(&self) -> &[Webhook] {
+        &self.webhooks
+    }
+
+    /// Runs `f` in a transaction, so its writes are saved together or, if
+    /// it fails, not at all.
+    ///
+    /// Clones share the connection, so other writes made through them
+    /// while `f` runs become part of the transaction, unless they go
+    /// through [`Database::outside_transaction`].
+    ///
+    /// Plaintext stores hold their file writes back until the commit, so
+    /// they are all or none too.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `f`, after rolling back, or an error if the
+    /// transaction can't be started or committed.
+    pub(crate) async fn in_transaction<T>(&self, f: impl AsyncFnOnce() -> Result<T>) -> Result<T> {
+        let _guard = self.transaction.lock().await;
+        self.sql.connection().execute("BEGIN", ()).await?;
+        self.store.begin().await;
+        let result = match f().await {
+            Ok(value) => self.store.commit().await.map(|()| value),
+            Err(e) => {
+                self.store.rollback().await;
+                Err(e)
+            }
+        };
+        match result {
+            Ok(value) => {
+                self.sql.connection().execute("COMMIT", ()).await?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.sql.connection().execute("ROLLBACK", ()).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Runs `f` once no transaction is open, and keeps new ones from
+    /// starting until it's done. Its reads then only see committed data,
+    /// and a rollback can't undo its writes.
+    ///
+    /// For work running alongside the app, like delivering webhooks. `f`
+    /// must not call [`Database::in_transaction`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `f`.
+    pub(crate) async fn outside_transaction<T>(&self, f: impl AsyncFnOnce() -> Result<T>) -> Result<T> {
+        let _guard = self.transaction.lock().await;
+        f().await
+    }
+
     /// Executes a SQL statement that doesn't return rows.
     ///
     /// Use this for INSERT, UPDATE, DELETE, and DDL statements.
@@ -258,7 +539,7 @@ impl Database {
         sql: impl AsRef<str>,
         params: impl turso::IntoParams,
     ) -> Result<u64> {
-        Ok(self.conn.execute(sql, params).await?)
+        self.sql.execute(sql, params).await
     }
 
     /// Executes a batch of SQL statements.
@@ -289,7 +570,7 @@ impl Database {
     /// # }
     /// ```
     pub async fn execute_batch(&self, sql: impl AsRef<str>) -> Result<()> {
-        Ok(self.conn.execute_batch(sql).await?)
+        Ok(self.sql.connection().execute_batch(sql).await?)
     }
 
     /// Executes a query and returns the result rows.
@@ -328,7 +609,7 @@ impl Database {
         sql: impl AsRef<str>,
         params: impl turso::IntoParams,
     ) -> Result<Rows> {
-        Ok(self.conn.query(sql, params).await?)
+        self.sql.query(sql, params).await
     }
 
     /// Executes a query and returns the first row.
@@ -350,8 +631,7 @@ impl Database {
         sql: impl AsRef<str>,
         params: impl turso::IntoParams,
     ) -> Result<Option<Row>> {
-        let mut rows = self.query(sql, params).await?;
-        Ok(rows.next().await?)
+        self.sql.query_one(sql, params).await
     }
 
     /// Executes a query and returns a single scalar value.
@@ -386,7 +666,7 @@ impl Database {
     ///
     /// Use this for advanced operations not covered by the wrapper methods.
     pub fn connection(&self) -> &Connection {
-        &self.conn
+        self.sql.connection()
     }
 }
 
@@ -466,4 +746,27 @@ mod tests {
         assert!(path.ends_with("ratado.db"));
         assert!(path.to_string_lossy().contains("ratado"));
     }
+
+    #[tokio::test]
+    async fn test_in_transaction_rolls_back_on_error() {
+        use crate::models::Task;
+        use crate::storage::run_migrations;
+
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+
+        let result: Result<()> = db
+            .in_transaction(async || {
+                db.insert_task(&Task::new("Kept?")).await?;
+                Err(StorageError::NotFound("missing".to_string()))
+            })
+            .await;
+        assert!(result.is_err());
+        assert!(db.get_all_tasks().await.unwrap().is_empty());
+
+        db.in_transaction(async || db.insert_task(&Task::new("Kept")).await)
+            .await
+            .unwrap();
+        assert_eq!(db.get_all_tasks().await.unwrap().len(), 1);
+    }
 }
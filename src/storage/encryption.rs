@@ -0,0 +1,704 @@
+//! Encryption of database files at rest.
+//!
+//! Encrypted databases use Turso's AEGIS-256 page encryption, with a key
+//! derived from a passphrase by Argon2id. Every encrypted file gets its own
+//! random salt, kept next to it in a `-salt` file (`ratado.db-salt`) that
+//! backups and sync copies carry along. Files encrypted before salts were
+//! stored have no such file and use the old fixed salt.
+//!
+//! An encrypted file starts with `Turso` where a plain one starts with
+//! `SQLite format 3`, which is how [`is_encrypted`] tells them apart
+//! without a key. Page 1 of an encrypted file keeps the first 100 bytes of
+//! the SQLite header in the clear and authenticates them with the rest of
+//! the page, so decrypting it checks a key without opening the database.
+//! [`change_key`] encrypts, decrypts or re-keys a database by copying it
+//! into a new file and swapping that in.
+
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use aegis::aegis256::Aegis256;
+use argon2::Argon2;
+use turso::{Builder, Connection, EncryptionOpts, Value};
+
+use crate::storage::{Database, Result, StorageError, list_backups};
+
+/// Turso cipher used for encrypted databases.
+const CIPHER: &str = "aegis256";
+
+/// Turso's identifier for [`CIPHER`], stored in the file header.
+const CIPHER_ID: u8 = 3;
+
+/// Version of the Turso header format.
+const TURSO_VERSION: u8 = 0;
+
+/// Size of the SQLite header, which page 1 leaves unencrypted.
+const HEADER_SIZE: usize = 100;
+
+/// Bytes of authentication tag at the end of an encrypted page's data.
+const TAG_SIZE: usize = 16;
+
+/// Bytes of nonce at the end of every encrypted page.
+const NONCE_SIZE: usize = 32;
+
+/// Bytes of random salt generated for each encrypted file.
+const SALT_SIZE: usize = 16;
+
+/// Argon2 salt of files encrypted before each file had its own.
+const LEGACY_SALT: &[u8] = b"ratado database key v1";
+
+/// Suffix of the file holding an encrypted file's salt.
+const SALT_SUFFIX: &str = "-salt";
+
+/// First bytes of a plain database file.
+const SQLITE_MAGIC: &[u8] = b"SQLite format 3\0";
+
+/// First bytes of an encrypted database file.
+const TURSO_MAGIC: &[u8] = b"Turso";
+
+/// The passphrase of an encrypted database, from which the key of each
+/// file is derived with that file's salt.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey {
+    /// The passphrase
+    passphrase: String,
+}
+
+impl EncryptionKey {
+    /// Creates the key for `passphrase`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Encryption`] if the passphrase is empty.
+    pub fn from_passphrase(passphrase: &str) -> Result<Self> {
+        if passphrase.is_empty() {
+            return Err(StorageError::Encryption(
+                "The passphrase is empty".to_string(),
+            ));
+        }
+        Ok(Self {
+            passphrase: passphrase.to_string(),
+        })
+    }
+
+    /// Derives the 256-bit key for a file with the given salt.
+    fn derive(&self, salt: &[u8]) -> Result<[u8; 32]> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| StorageError::Encryption(e.to_string()))?;
+        Ok(key)
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptionKey(..)")
+    }
+}
+
+/// Returns whether the database file at `path` is encrypted.
+///
+/// A missing or empty file isn't.
+///
+/// # Errors
+///
+/// Returns [`StorageError::Corrupt`] if the file isn't a database at all,
+/// or an error if it can't be read.
+pub fn is_encrypted(path: &Path) -> Result<bool> {
+    let file_error = |source| StorageError::File {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(file_error(e)),
+    };
+    let mut header = Vec::with_capacity(SQLITE_MAGIC.len());
+    file.by_ref()
+        .take(SQLITE_MAGIC.len() as u64)
+        .read_to_end(&mut header)
+        .map_err(file_error)?;
+
+    if header.is_empty() || header == SQLITE_MAGIC {
+        Ok(false)
+    } else if header.starts_with(TURSO_MAGIC) {
+        Ok(true)
+    } else {
+        Err(StorageError::Corrupt(path.to_path_buf()))
+    }
+}
+
+/// Opens (or creates) the encrypted database file at `path`.
+///
+/// # Errors
+///
+/// Returns [`StorageError::WrongKey`] if `key` doesn't decrypt the file,
+/// or [`StorageError::Corrupt`] if it isn't a readable encrypted database.
+pub(crate) async fn open_encrypted(path: &Path, key: &EncryptionKey) -> Result<turso::Database> {
+    let key = check_key(path, key)?;
+
+    let path_str = path.to_string_lossy().into_owned();
+    let hexkey: String = key.iter().map(|b| format!("{:02x}", b)).collect();
+    // Turso panics instead of failing when a page doesn't decrypt. The key
+    // is checked above, so that only happens for a damaged file, but the
+    // open runs on its own task so it can't take the app down with it.
+    let opened = tokio::spawn(async move {
+        Builder::new_local(&path_str)
+            .experimental_encryption(true)
+            .with_encryption(EncryptionOpts {
+                cipher: CIPHER.to_string(),
+                hexkey,
+            })
+            .build()
+            .await
+    })
+    .await;
+
+    match opened {
+        Ok(db) => db.map_err(|e| StorageError::from_open(path, e)),
+        Err(e) if e.is_panic() => Err(StorageError::Corrupt(path.to_path_buf())),
+        Err(e) => Err(StorageError::Encryption(e.to_string())),
+    }
+}
+
+/// Derives the key of the encrypted file at `path` from its salt and
+/// checks that it decrypts page 1, returning the key.
+///
+/// A missing or empty file passes, since it will be created with the key;
+/// it gets a new random salt.
+///
+/// # Errors
+///
+/// Returns [`StorageError::WrongKey`] if the page doesn't decrypt,
+/// [`StorageError::Corrupt`] if the file isn't an encrypted database, or
+/// an error if it can't be read.
+fn check_key(path: &Path, key: &EncryptionKey) -> Result<[u8; 32]> {
+    let file_error = |source| StorageError::File {
+        path: path.to_path_buf(),
+        source,
+    };
+    let corrupt = || StorageError::Corrupt(path.to_path_buf());
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return key.derive(&new_salt(path)?),
+        Err(e) => return Err(file_error(e)),
+    };
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    file.by_ref()
+        .take(HEADER_SIZE as u64)
+        .read_to_end(&mut header)
+        .map_err(file_error)?;
+    if header.is_empty() {
+        return key.derive(&new_salt(path)?);
+    }
+    if header.len() < HEADER_SIZE
+        || !header.starts_with(TURSO_MAGIC)
+        || header[TURSO_MAGIC.len()] != TURSO_VERSION
+        || header[TURSO_MAGIC.len() + 1] != CIPHER_ID
+    {
+        return Err(corrupt());
+    }
+
+    // The page size is stored big-endian at offset 16, with 1 for 65536
+    let page_size = match u16::from_be_bytes([header[16], header[17]]) {
+        1 => 65536,
+        n => usize::from(n),
+    };
+    if !page_size.is_power_of_two() || page_size < 512 {
+        return Err(corrupt());
+    }
+    let mut page = header;
+    file.take((page_size - HEADER_SIZE) as u64)
+        .read_to_end(&mut page)
+        .map_err(file_error)?;
+    if page.len() < page_size {
+        return Err(corrupt());
+    }
+
+    let key = match read_salt(path)? {
+        Some(salt) => key.derive(&salt)?,
+        None => key.derive(LEGACY_SALT)?,
+    };
+    let (data, nonce) = page.split_at(page_size - NONCE_SIZE);
+    let (sealed, tag) = data.split_at(data.len() - TAG_SIZE);
+    let nonce: &[u8; NONCE_SIZE] = nonce.try_into().map_err(|_| corrupt())?;
+    let tag: &[u8; TAG_SIZE] = tag.try_into().map_err(|_| corrupt())?;
+    Aegis256::<TAG_SIZE>::new(&key, nonce)
+        .decrypt(&sealed[HEADER_SIZE..], tag, &sealed[..HEADER_SIZE])
+        .map(|_| key)
+        .map_err(|_| StorageError::WrongKey(path.to_path_buf()))
+}
+
+/// Returns the salt stored for the file at `path`, if it has one.
+///
+/// # Errors
+///
+/// Returns [`StorageError::Corrupt`] if the salt file is empty, or an
+/// error if it can't be read.
+fn read_salt(path: &Path) -> Result<Option<Vec<u8>>> {
+    let salt_path = sidecar(path, SALT_SUFFIX);
+    match std::fs::read(&salt_path) {
+        Ok(salt) if salt.is_empty() => Err(StorageError::Corrupt(salt_path)),
+        Ok(salt) => Ok(Some(salt)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(StorageError::File {
+            path: salt_path,
+            source: e,
+        }),
+    }
+}
+
+/// Generates a random salt for the file about to be created at `path` and
+/// stores it.
+fn new_salt(path: &Path) -> Result<Vec<u8>> {
+    let mut salt = vec![0u8; SALT_SIZE];
+    getrandom::fill(&mut salt).map_err(|e| StorageError::Encryption(e.to_string()))?;
+    let salt_path = sidecar(path, SALT_SUFFIX);
+    std::fs::write(&salt_path, &salt).map_err(|source| StorageError::File {
+        path: salt_path,
+        source,
+    })?;
+    Ok(salt)
+}
+
+/// Makes the salt of the file at `to` that of the file at `from`, after
+/// copying or moving `from` there: copies it, or deletes the salt of `to`
+/// if `from` has none.
+///
+/// # Errors
+///
+/// Returns an error if the salt file can't be copied or deleted.
+pub(crate) fn copy_salt(from: &Path, to: &Path) -> Result<()> {
+    let (from, to) = (sidecar(from, SALT_SUFFIX), sidecar(to, SALT_SUFFIX));
+    match std::fs::copy(&from, &to) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => remove_file(&to),
+        Err(e) => Err(StorageError::File {
+            path: from,
+            source: e,
+        }),
+    }
+}
+
+/// Deletes the salt of the file at `path`, if it has one.
+pub(crate) fn remove_salt(path: &Path) -> Result<()> {
+    remove_file(&sidecar(path, SALT_SUFFIX))
+}
+
+/// Rewrites the database file at `path` under a new key: encrypting it
+/// when `from` is `None`, decrypting it when `to` is `None`, and re-keying
+/// it otherwise.
+///
+/// The data is copied into a new file next to it, which then replaces the
+/// original, so a failure leaves the original as it was. An encrypted
+/// result gets a new random salt. The database must not be open.
+///
+/// Encrypting also encrypts the plaintext backups of the database with the
+/// same passphrase, before the database itself, so no readable copy of it
+/// is left behind.
+///
+/// # Errors
+///
+/// Returns [`StorageError::Encryption`] if the file isn't encrypted as
+/// `from` says, [`StorageError::WrongKey`] if `from` doesn't decrypt it, or
+/// an error if it can't be copied or replaced.
+pub async fn change_key(
+    path: &Path,
+    from: Option<&EncryptionKey>,
+    to: Option<&EncryptionKey>,
+) -> Result<()> {
+    match (is_encrypted(path)?, from.is_some()) {
+        (false, true) => {
+            return Err(StorageError::Encryption(format!(
+                "{} is not encrypted",
+                path.display()
+            )));
+        }
+        (true, false) => {
+            return Err(StorageError::Encryption(format!(
+                "{} is already encrypted",
+                path.display()
+            )));
+        }
+        _ => {}
+    }
+    if !path.exists() {
+        return Err(StorageError::NotFound(path.display().to_string()));
+    }
+
+    if let (None, Some(key)) = (from, to) {
+        for backup in list_backups(path)? {
+            if !is_encrypted(&backup.path)? {
+                rewrite(&backup.path, None, Some(key)).await?;
+            }
+        }
+    }
+    rewrite(path, from, to).await
+}
+
+/// Copies the database file at `path` into a new file under the key `to`,
+/// which then replaces it.
+async fn rewrite(
+    path: &Path,
+    from: Option<&EncryptionKey>,
+    to: Option<&EncryptionKey>,
+) -> Result<()> {
+    let source = Database::open_with_key(path, from).await?;
+    let staged = path.with_extension("db.rekey");
+    remove_database_files(&staged)?;
+
+    let copied = async {
+        let dest = match to {
+            Some(key) => open_encrypted(&staged, key).await?,
+            None => Builder::new_local(&staged.to_string_lossy()).build().await?,
+        };
+        let conn = dest.connect()?;
+        copy_database(&source, &conn).await?;
+        checkpoint(&conn).await
+    }
+    .await;
+    if let Err(e) = copied {
+        let _ = remove_database_files(&staged);
+        return Err(e);
+    }
+
+    checkpoint(source.connection()).await?;
+    drop(source);
+    std::fs::rename(&staged, path).map_err(|source| StorageError::File {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    copy_salt(&staged, path)?;
+    // Both write-ahead logs were checkpointed empty
+    remove_database_files(&staged)?;
+    for suffix in ["-wal", "-shm"] {
+        remove_file(&sidecar(path, suffix))?;
+    }
+    Ok(())
+}
+
+/// Copies every table and index of `source` into the empty database `dest`.
+async fn copy_database(source: &Database, dest: &Connection) -> Result<()> {
+    let mut rows = source
+        .query(
+            "SELECT type, name, sql FROM sqlite_schema WHERE sql IS NOT NULL",
+            (),
+        )
+        .await?;
+    let mut tables = Vec::new();
+    let mut indexes = Vec::new();
+    while let Some(row) = rows.next().await? {
+        let (Value::Text(kind), Value::Text(name), Value::Text(sql)) =
+            (row.get_value(0)?, row.get_value(1)?, row.get_value(2)?)
+        else {
+            continue;
+        };
+        if name.starts_with("sqlite_") {
+            continue;
+        }
+        match kind.as_str() {
+            "table" => tables.push((name, sql)),
+            _ => indexes.push(sql),
+        }
+    }
+
+    for (name, sql) in &tables {
+        dest.execute(sql, ()).await?;
+        let mut rows = source
+            .query(format!("SELECT * FROM \"{}\"", name), ())
+            .await?;
+        while let Some(row) = rows.next().await? {
+            let values = (0..row.column_count())
+                .map(|i| row.get_value(i))
+                .collect::<std::result::Result<Vec<Value>, _>>()?;
+            let placeholders = (1..=values.len())
+                .map(|i| format!("?{}", i))
+                .collect::<Vec<_>>()
+                .join(", ");
+            dest.execute(
+                &format!("INSERT INTO \"{}\" VALUES ({})", name, placeholders),
+                values,
+            )
+            .await?;
+        }
+    }
+    // Indexes last, so rows aren't indexed one by one
+    for sql in &indexes {
+        dest.execute(sql, ()).await?;
+    }
+    Ok(())
+}
+
+/// Moves everything in the write-ahead log into the database file.
+async fn checkpoint(conn: &Connection) -> Result<()> {
+    let mut rows = conn.query("PRAGMA wal_checkpoint(TRUNCATE)", ()).await?;
+    while rows.next().await?.is_some() {}
+    Ok(())
+}
+
+/// Returns the path of a file SQLite keeps next to the database.
+fn sidecar(path: &Path, suffix: &str) -> PathBuf {
+    PathBuf::from(format!("{}{}", path.display(), suffix))
+}
+
+/// Deletes a database file with its write-ahead log and salt, if they
+/// exist.
+fn remove_database_files(path: &Path) -> Result<()> {
+    remove_file(path)?;
+    remove_file(&sidecar(path, "-wal"))?;
+    remove_file(&sidecar(path, "-shm"))?;
+    remove_salt(path)
+}
+
+/// Deletes a file, if it exists.
+fn remove_file(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(StorageError::File {
+            path: path.to_path_buf(),
+            source: e,
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Task;
+    use crate::storage::run_migrations;
+
+    async fn create_plain(path: &Path) {
+        let db = Database::open(path).await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db.insert_task(&Task::new("Client secret")).await.unwrap();
+    }
+
+    fn contains(path: &Path, text: &str) -> bool {
+        let bytes = std::fs::read(path).unwrap();
+        bytes.windows(text.len()).any(|w| w == text.as_bytes())
+    }
+
+    #[test]
+    fn test_key_from_passphrase() {
+        let key = EncryptionKey::from_passphrase("correct horse").unwrap();
+        assert_eq!(key, EncryptionKey::from_passphrase("correct horse").unwrap());
+        assert_ne!(key, EncryptionKey::from_passphrase("battery staple").unwrap());
+        assert_eq!(key.derive(b"some salt").unwrap(), key.derive(b"some salt").unwrap());
+        assert_ne!(key.derive(b"some salt").unwrap(), key.derive(b"other salt").unwrap());
+        assert_eq!(format!("{:?}", key), "EncryptionKey(..)");
+        assert!(matches!(
+            EncryptionKey::from_passphrase(""),
+            Err(StorageError::Encryption(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_is_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ratado.db");
+        assert!(!is_encrypted(&path).unwrap());
+
+        create_plain(&path).await;
+        assert!(!is_encrypted(&path).unwrap());
+
+        let garbage = dir.path().join("garbage.db");
+        std::fs::write(&garbage, "not a database at all").unwrap();
+        assert!(matches!(is_encrypted(&garbage), Err(StorageError::Corrupt(_))));
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_decrypt_and_rekey() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ratado.db");
+        create_plain(&path).await;
+        let key = EncryptionKey::from_passphrase("first").unwrap();
+        let new_key = EncryptionKey::from_passphrase("second").unwrap();
+
+        change_key(&path, None, Some(&key)).await.unwrap();
+        assert!(is_encrypted(&path).unwrap());
+        assert!(!contains(&path, "Client secret"));
+        {
+            let db = Database::open_with_key(&path, Some(&key)).await.unwrap();
+            assert_eq!(db.get_all_tasks().await.unwrap()[0].title, "Client secret");
+            assert_eq!(db.encryption_key(), Some(&key));
+        }
+
+        change_key(&path, Some(&key), Some(&new_key)).await.unwrap();
+        assert!(matches!(
+            Database::open_with_key(&path, Some(&key)).await,
+            Err(StorageError::WrongKey(_))
+        ));
+
+        change_key(&path, Some(&new_key), None).await.unwrap();
+        assert!(!is_encrypted(&path).unwrap());
+        let db = Database::open(&path).await.unwrap();
+        run_migrations(&db).await.unwrap();
+        assert_eq!(db.get_all_tasks().await.unwrap()[0].title, "Client secret");
+        assert_eq!(db.encryption_key(), None);
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_leaves_no_plaintext_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ratado.db");
+        create_plain(&path).await;
+        {
+            let db = Database::open(&path).await.unwrap();
+            db.backup("startup").await.unwrap();
+            db.backup("pre-reset").await.unwrap();
+        }
+        let key = EncryptionKey::from_passphrase("first").unwrap();
+        change_key(&path, None, Some(&key)).await.unwrap();
+
+        let mut files = Vec::new();
+        let mut dirs = vec![dir.path().to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else {
+                    files.push(path);
+                }
+            }
+        }
+        for file in &files {
+            assert!(!contains(file, "Client secret"), "{} is readable", file.display());
+        }
+        let backups = list_backups(&path).unwrap();
+        assert_eq!(backups.len(), 2);
+        for backup in backups {
+            let db = Database::open_with_key(&backup.path, Some(&key)).await.unwrap();
+            assert_eq!(db.get_all_tasks().await.unwrap()[0].title, "Client secret");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_salt_per_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.db");
+        let second = dir.path().join("second.db");
+        let key = EncryptionKey::from_passphrase("shared").unwrap();
+        for path in [&first, &second] {
+            create_plain(path).await;
+            change_key(path, None, Some(&key)).await.unwrap();
+        }
+
+        // The same passphrase gives each database its own salt, and key
+        let salt = read_salt(&first).unwrap().unwrap();
+        assert_eq!(salt.len(), SALT_SIZE);
+        assert_ne!(read_salt(&second).unwrap().unwrap(), salt);
+        copy_salt(&second, &first).unwrap();
+        assert!(matches!(
+            Database::open_with_key(&first, Some(&key)).await,
+            Err(StorageError::WrongKey(_))
+        ));
+        std::fs::write(sidecar(&first, SALT_SUFFIX), &salt).unwrap();
+        assert!(Database::open_with_key(&first, Some(&key)).await.is_ok());
+
+        // Re-keying salts afresh; decrypting drops the salt
+        change_key(&first, Some(&key), Some(&key)).await.unwrap();
+        assert_ne!(read_salt(&first).unwrap().unwrap(), salt);
+        change_key(&first, Some(&key), None).await.unwrap();
+        assert_eq!(read_salt(&first).unwrap(), None);
+        assert!(!dir.path().join("first.db.rekey-salt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_open_with_legacy_salt() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ratado.db");
+        let key = EncryptionKey::from_passphrase("old").unwrap();
+        // Encrypted before salts were stored: no salt file, the fixed salt
+        let hexkey = key
+            .derive(LEGACY_SALT)
+            .unwrap()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        let legacy = Builder::new_local(&path.to_string_lossy())
+            .experimental_encryption(true)
+            .with_encryption(EncryptionOpts {
+                cipher: CIPHER.to_string(),
+                hexkey,
+            })
+            .build()
+            .await
+            .unwrap();
+        let conn = legacy.connect().unwrap();
+        conn.execute("CREATE TABLE notes (text TEXT)", ()).await.unwrap();
+        checkpoint(&conn).await.unwrap();
+        drop(legacy);
+
+        assert_eq!(read_salt(&path).unwrap(), None);
+        assert!(Database::open_with_key(&path, Some(&key)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_open_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ratado.db");
+        create_plain(&path).await;
+        let key = EncryptionKey::from_passphrase("first").unwrap();
+        change_key(&path, None, Some(&key)).await.unwrap();
+
+        assert!(matches!(
+            Database::open(&path).await,
+            Err(StorageError::KeyRequired(_))
+        ));
+        let wrong = EncryptionKey::from_passphrase("wrong").unwrap();
+        assert!(matches!(
+            Database::open_with_key(&path, Some(&wrong)).await,
+            Err(StorageError::WrongKey(_))
+        ));
+        // The failed attempts leave the file usable
+        assert!(Database::open_with_key(&path, Some(&key)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_open_damaged_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ratado.db");
+        create_plain(&path).await;
+        let key = EncryptionKey::from_passphrase("first").unwrap();
+        change_key(&path, None, Some(&key)).await.unwrap();
+
+        // A truncated first page is damage, not a wrong passphrase
+        let bytes = std::fs::read(&path).unwrap();
+        let truncated = dir.path().join("truncated.db");
+        std::fs::write(&truncated, &bytes[..200]).unwrap();
+        assert!(matches!(
+            Database::open_with_key(&truncated, Some(&key)).await,
+            Err(StorageError::Corrupt(_))
+        ));
+
+        let mut other_cipher = bytes.clone();
+        other_cipher[TURSO_MAGIC.len() + 1] = CIPHER_ID + 1;
+        std::fs::write(&truncated, &other_cipher).unwrap();
+        assert!(matches!(
+            Database::open_with_key(&truncated, Some(&key)).await,
+            Err(StorageError::Corrupt(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_change_key_checks_current_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ratado.db");
+        create_plain(&path).await;
+        let key = EncryptionKey::from_passphrase("first").unwrap();
+
+        assert!(matches!(
+            change_key(&path, Some(&key), None).await,
+            Err(StorageError::Encryption(_))
+        ));
+        change_key(&path, None, Some(&key)).await.unwrap();
+        assert!(matches!(
+            change_key(&path, None, Some(&key)).await,
+            Err(StorageError::Encryption(_))
+        ));
+    }
+}
@@ -0,0 +1,844 @@
+//! Plaintext storage backend for keeping tasks in git.
+//!
+//! [`FileStore`] keeps each task and project in its own file, so changes
+//! show up as readable diffs, merge like any other text and can be reviewed
+//! in a pull request:
+//!
+//! ```text
+//! tasks/
+//! ├── .gitignore                  # ignores .ratado/
+//! ├── .ratado/state.db            # bookkeeping: webhooks, sync, app version
+//! ├── projects/inbox.toml
+//! ├── projects/0199a1b2-….toml
+//! └── tasks/0199a1b3-….md
+//! ```
+//!
+//! A task is Markdown with TOML front matter; the body is its description
+//! and notes are kept in the front matter, oldest first:
+//!
+//! ```text
+//! +++
+//! title = "Write quarterly report"
+//! status = "pending"
+//! priority = "high"
+//! project = "0199a1b2-…"
+//! tags = ["finance", "work"]
+//! due = "2026-03-31T17:00:00Z"
+//! created = "2026-03-02T09:15:00Z"
+//! updated = "2026-03-02T09:15:00Z"
+//! position = 1772442900000.0
+//!
+//! [[notes]]
+//! id = "0199a1b4-…"
+//! created = "2026-03-03T10:02:00Z"
+//! text = "Finance says the dashboard is a day behind."
+//! +++
+//!
+//! Numbers from the finance dashboard, plus a summary for the team.
+//! ```
+//!
+//! Files are named after the item's ID, so renaming a task doesn't rename
+//! its file. Tags live in the tasks that use them; a tag no task uses
+//! doesn't exist. Files are replaced atomically, so a crash never leaves
+//! half a task behind. Inside a transaction, writes are held back and
+//! applied together when it commits, so a failed bulk change writes none
+//! of its files.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{position_at, Note, Project, Task};
+use crate::storage::sqlite::{priority_to_str, status_to_str, str_to_priority, str_to_status};
+use crate::storage::{ChangeSet, Result, Store, StorageError, Tag};
+
+/// Directory inside the store holding files that don't belong in git.
+pub(crate) const STATE_DIR: &str = ".ratado";
+
+/// Directory of task files.
+const TASKS_DIR: &str = "tasks";
+
+/// Directory of project files.
+const PROJECTS_DIR: &str = "projects";
+
+/// Line that opens and closes a task file's front matter.
+const FRONT_MATTER_FENCE: &str = "+++";
+
+/// Tasks and projects as plaintext files in a directory.
+pub struct FileStore {
+    dir: PathBuf,
+    /// Size and modification time of each item file, as last seen
+    seen: Mutex<FileStamps>,
+    /// Outside edits noticed before a write, for the next
+    /// [`Store::external_changes`]
+    pending: Mutex<ChangeSet>,
+    /// Writes held back until the open transaction commits, if there is one
+    staged: Mutex<Option<StagedFiles>>,
+}
+
+/// Size and modification time of item files, by subdirectory and name.
+type FileStamps = HashMap<(&'static str, String), (u64, Option<SystemTime>)>;
+
+/// New contents of files written in a transaction, or `None` if removed.
+type StagedFiles = BTreeMap<PathBuf, Option<String>>;
+
+/// Front matter of a task file.
+#[derive(Debug, Serialize, Deserialize)]
+struct TaskHeader {
+    title: String,
+    status: String,
+    priority: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<DateTime<Utc>>,
+    created: DateTime<Utc>,
+    updated: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    completed: Option<DateTime<Utc>>,
+    /// Manual sort order; files written by hand may leave it out
+    #[serde(default)]
+    position: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    notes: Vec<NoteEntry>,
+}
+
+/// A note in a task file's front matter.
+#[derive(Debug, Serialize, Deserialize)]
+struct NoteEntry {
+    id: String,
+    created: DateTime<Utc>,
+    text: String,
+}
+
+/// Contents of a project file.
+#[derive(Debug, Serialize, Deserialize)]
+struct ProjectFile {
+    name: String,
+    color: String,
+    icon: String,
+    created: DateTime<Utc>,
+}
+
+impl FileStore {
+    /// Opens the store in `dir`, creating its layout and the Inbox project
+    /// if they are missing.
+    pub(crate) fn open(dir: &Path) -> Result<Self> {
+        let store = Self {
+            dir: dir.to_path_buf(),
+            seen: Mutex::default(),
+            pending: Mutex::default(),
+            staged: Mutex::default(),
+        };
+        for sub in [TASKS_DIR, PROJECTS_DIR, STATE_DIR] {
+            let path = dir.join(sub);
+            std::fs::create_dir_all(&path).map_err(|e| file_error(&path, e))?;
+        }
+        let gitignore = dir.join(".gitignore");
+        if !gitignore.exists() {
+            write_file(&gitignore, &format!("{}/\n", STATE_DIR))?;
+        }
+        if !store.exists(&store.project_path("inbox")?) {
+            // Same as the Inbox the SQLite schema creates
+            let inbox = Project {
+                id: "inbox".to_string(),
+                ..Project::with_style("Inbox", "#3498db", "📥")
+            };
+            store.write_project(&inbox)?;
+        }
+        *store.seen.lock().unwrap() = store.stamps();
+        Ok(store)
+    }
+
+    /// Returns the directory holding the store.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn task_path(&self, id: &str) -> Result<PathBuf> {
+        Ok(self.dir.join(TASKS_DIR).join(format!("{}.md", file_stem(id)?)))
+    }
+
+    fn project_path(&self, id: &str) -> Result<PathBuf> {
+        Ok(self.dir.join(PROJECTS_DIR).join(format!("{}.toml", file_stem(id)?)))
+    }
+
+    fn read_task(&self, path: &Path) -> Result<Task> {
+        let id = id_from_path(path)?;
+        let content = self.read_file(path)?;
+        parse_task(&id, &content).map_err(|reason| {
+            StorageError::Conversion(format!("Invalid task file {}: {}", path.display(), reason))
+        })
+    }
+
+    fn read_project(&self, path: &Path) -> Result<Project> {
+        let id = id_from_path(path)?;
+        let content = self.read_file(path)?;
+        let file: ProjectFile = toml::from_str(&content).map_err(|e| {
+            StorageError::Conversion(format!("Invalid project file {}: {}", path.display(), e))
+        })?;
+        Ok(Project {
+            id,
+            name: file.name,
+            color: file.color,
+            icon: file.icon,
+            created_at: file.created,
+        })
+    }
+
+    fn write_task(&self, task: &Task) -> Result<()> {
+        self.write_file(&self.task_path(&task.id)?, format_task(task)?)
+    }
+
+    fn write_project(&self, project: &Project) -> Result<()> {
+        let file = ProjectFile {
+            name: project.name.clone(),
+            color: project.color.clone(),
+            icon: project.icon.clone(),
+            created: project.created_at,
+        };
+        let content = toml::to_string(&file)
+            .map_err(|e| StorageError::Conversion(format!("Can't write project: {}", e)))?;
+        self.write_file(&self.project_path(&project.id)?, content)
+    }
+
+    /// Returns whether an item file exists, counting writes held back.
+    fn exists(&self, path: &Path) -> bool {
+        match self.staged.lock().unwrap().as_ref().and_then(|staged| staged.get(path)) {
+            Some(content) => content.is_some(),
+            None => path.exists(),
+        }
+    }
+
+    /// Reads an item file, as written if the write is held back.
+    fn read_file(&self, path: &Path) -> Result<String> {
+        match self.staged.lock().unwrap().as_ref().and_then(|staged| staged.get(path)) {
+            Some(Some(content)) => Ok(content.clone()),
+            Some(None) => Err(file_error(path, io::ErrorKind::NotFound.into())),
+            None => std::fs::read_to_string(path).map_err(|e| file_error(path, e)),
+        }
+    }
+
+    /// Replaces an item file, or holds the write back in a transaction.
+    fn write_file(&self, path: &Path, content: String) -> Result<()> {
+        if let Some(staged) = self.staged.lock().unwrap().as_mut() {
+            staged.insert(path.to_path_buf(), Some(content));
+            return Ok(());
+        }
+        write_file(path, &content)
+    }
+
+    /// Removes an item file, or holds the removal back in a transaction.
+    /// Returns whether it existed.
+    fn remove_file(&self, path: &Path) -> Result<bool> {
+        let existed = self.exists(path);
+        if let Some(staged) = self.staged.lock().unwrap().as_mut() {
+            staged.insert(path.to_path_buf(), None);
+            return Ok(existed);
+        }
+        remove_file(path)
+    }
+
+    /// Reads every file in a subdirectory with the given extension.
+    fn read_all<T>(&self, sub: &str, ext: &str, read: impl Fn(&Path) -> Result<T>) -> Result<Vec<T>> {
+        let dir = self.dir.join(sub);
+        let entries = std::fs::read_dir(&dir).map_err(|e| file_error(&dir, e))?;
+        let mut paths = Vec::new();
+        for entry in entries {
+            paths.push(entry.map_err(|e| file_error(&dir, e))?.path());
+        }
+        // Files written in the open transaction exist already
+        if let Some(staged) = self.staged.lock().unwrap().as_ref() {
+            paths.retain(|path| !staged.contains_key(path));
+            paths.extend(
+                staged
+                    .iter()
+                    .filter(|(path, content)| content.is_some() && path.parent() == Some(&dir))
+                    .map(|(path, _)| path.clone()),
+            );
+        }
+
+        let mut items = Vec::new();
+        for path in paths {
+            if path.extension().is_some_and(|e| e == ext) {
+                items.push(read(&path)?);
+            }
+        }
+        Ok(items)
+    }
+
+    fn all_tasks(&self) -> Result<Vec<Task>> {
+        let mut tasks = self.read_all(TASKS_DIR, "md", |path| self.read_task(path))?;
+        tasks.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.id.cmp(&a.id)));
+        Ok(tasks)
+    }
+
+    /// Runs a write, so that only edits made outside Ratado count as
+    /// external changes.
+    fn tracked<T>(&self, write: impl FnOnce() -> Result<T>) -> Result<T> {
+        // Keep what changed before the write, which would be lost otherwise
+        let before = self.take_changes();
+        self.pending.lock().unwrap().merge(before);
+        let result = write();
+        *self.seen.lock().unwrap() = self.stamps();
+        result
+    }
+
+    /// Returns the items whose files changed since they were last seen.
+    fn take_changes(&self) -> ChangeSet {
+        let current = self.stamps();
+        let previous = std::mem::replace(&mut *self.seen.lock().unwrap(), current.clone());
+        let mut changes = ChangeSet::default();
+        let changed = current
+            .iter()
+            .filter(|(file, stamp)| previous.get(file) != Some(stamp))
+            .map(|(file, _)| file);
+        let removed = previous.keys().filter(|file| !current.contains_key(file));
+        for (sub, name) in changed.chain(removed) {
+            let id = Path::new(name).file_stem().unwrap_or_default().to_string_lossy().into_owned();
+            if *sub == TASKS_DIR {
+                changes.tasks.insert(id);
+                // Tags live in the tasks that use them
+                changes.tags = true;
+            } else {
+                changes.projects.insert(id);
+            }
+        }
+        changes
+    }
+
+    /// Reads the sizes and modification times of all item files.
+    fn stamps(&self) -> FileStamps {
+        let mut stamps = FileStamps::new();
+        for (sub, ext) in [(TASKS_DIR, "md"), (PROJECTS_DIR, "toml")] {
+            let Ok(entries) = std::fs::read_dir(self.dir.join(sub)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().is_none_or(|e| e != ext) {
+                    continue;
+                }
+                let Ok(meta) = entry.metadata() else {
+                    continue;
+                };
+                let name = entry.file_name().to_string_lossy().into_owned();
+                stamps.insert((sub, name), (meta.len(), meta.modified().ok()));
+            }
+        }
+        stamps
+    }
+}
+
+#[async_trait]
+impl Store for FileStore {
+    async fn get_task(&self, id: &str) -> Result<Option<Task>> {
+        let path = self.task_path(id)?;
+        if !self.exists(&path) {
+            return Ok(None);
+        }
+        self.read_task(&path).map(Some)
+    }
+
+    async fn get_all_tasks(&self) -> Result<Vec<Task>> {
+        self.all_tasks()
+    }
+
+    async fn insert_task(&self, task: &Task) -> Result<()> {
+        if self.exists(&self.task_path(&task.id)?) {
+            return Err(StorageError::Exists(format!("Task {}", task.id)));
+        }
+        self.tracked(|| self.write_task(task))
+    }
+
+    async fn update_task(&self, task: &Task) -> Result<bool> {
+        let Some(stored) = self.get_task(&task.id).await? else {
+            return Ok(false);
+        };
+        let task = Task {
+            notes: stored.notes,
+            ..task.clone()
+        };
+        self.tracked(|| self.write_task(&task))?;
+        Ok(true)
+    }
+
+    async fn add_note(&self, task_id: &str, note: &Note) -> Result<()> {
+        let Some(mut task) = self.get_task(task_id).await? else {
+            return Err(StorageError::NotFound(format!("Task not found: {}", task_id)));
+        };
+        task.notes.push(note.clone());
+        self.tracked(|| self.write_task(&task))
+    }
+
+    async fn delete_tasks(&self, ids: &[String]) -> Result<usize> {
+        self.tracked(|| {
+            let mut deleted = 0;
+            for id in ids {
+                deleted += self.remove_file(&self.task_path(id)?)? as usize;
+            }
+            Ok(deleted)
+        })
+    }
+
+    async fn get_project(&self, id: &str) -> Result<Option<Project>> {
+        let path = self.project_path(id)?;
+        if !self.exists(&path) {
+            return Ok(None);
+        }
+        self.read_project(&path).map(Some)
+    }
+
+    async fn get_all_projects(&self) -> Result<Vec<Project>> {
+        let mut projects = self.read_all(PROJECTS_DIR, "toml", |path| self.read_project(path))?;
+        projects.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(projects)
+    }
+
+    async fn insert_project(&self, project: &Project) -> Result<()> {
+        if self.exists(&self.project_path(&project.id)?) {
+            return Err(StorageError::Exists(format!("Project {}", project.id)));
+        }
+        self.tracked(|| self.write_project(project))
+    }
+
+    async fn update_project(&self, project: &Project) -> Result<bool> {
+        if !self.exists(&self.project_path(&project.id)?) {
+            return Ok(false);
+        }
+        self.tracked(|| self.write_project(project))?;
+        Ok(true)
+    }
+
+    async fn delete_projects(&self, ids: &[String]) -> Result<usize> {
+        self.tracked(|| {
+            let mut deleted = 0;
+            for id in ids {
+                deleted += self.remove_file(&self.project_path(id)?)? as usize;
+            }
+            // Tasks stay, without a project
+            for mut task in self.all_tasks()? {
+                if task.project_id.as_ref().is_some_and(|p| ids.contains(p)) {
+                    task.project_id = None;
+                    self.write_task(&task)?;
+                }
+            }
+            Ok(deleted)
+        })
+    }
+
+    async fn get_tags_with_counts(&self) -> Result<Vec<(Tag, usize)>> {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for task in self.all_tasks()? {
+            for tag in task.tags {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+        Ok(counts
+            .into_iter()
+            .map(|(name, count)| (Tag { id: name.clone(), name }, count))
+            .collect())
+    }
+
+    /// Tags are identified by name and exist only while a task uses them,
+    /// so this stores nothing.
+    async fn insert_tag(&self, name: &str) -> Result<String> {
+        if self.all_tasks()?.iter().any(|t| t.tags.iter().any(|tag| tag == name)) {
+            return Err(StorageError::Exists(format!("Tag {}", name)));
+        }
+        Ok(name.to_string())
+    }
+
+    async fn delete_tag(&self, id: &str) -> Result<bool> {
+        self.tracked(|| {
+            let mut found = false;
+            for mut task in self.all_tasks()? {
+                let before = task.tags.len();
+                task.tags.retain(|t| t != id);
+                if task.tags.len() != before {
+                    self.write_task(&task)?;
+                    found = true;
+                }
+            }
+            Ok(found)
+        })
+    }
+
+    async fn add_tag_to_task(&self, task_id: &str, name: &str) -> Result<()> {
+        let Some(mut task) = self.get_task(task_id).await? else {
+            return Err(StorageError::NotFound(format!("Task not found: {}", task_id)));
+        };
+        if !task.tags.iter().any(|t| t == name) {
+            task.tags.push(name.to_string());
+            self.tracked(|| self.write_task(&task))?;
+        }
+        Ok(())
+    }
+
+    async fn remove_tag_from_task(&self, task_id: &str, tag_id: &str) -> Result<bool> {
+        let Some(mut task) = self.get_task(task_id).await? else {
+            return Ok(false);
+        };
+        let before = task.tags.len();
+        task.tags.retain(|t| t != tag_id);
+        if task.tags.len() == before {
+            return Ok(false);
+        }
+        self.tracked(|| self.write_task(&task))?;
+        Ok(true)
+    }
+
+    async fn external_changes(&self) -> Result<ChangeSet> {
+        let mut changes = std::mem::take(&mut *self.pending.lock().unwrap());
+        changes.merge(self.take_changes());
+        Ok(changes)
+    }
+
+    async fn begin(&self) {
+        *self.staged.lock().unwrap() = Some(StagedFiles::new());
+    }
+
+    async fn commit(&self) -> Result<()> {
+        let Some(staged) = self.staged.lock().unwrap().take() else {
+            return Ok(());
+        };
+        self.tracked(|| {
+            for (path, content) in &staged {
+                match content {
+                    Some(content) => write_file(path, content)?,
+                    None => {
+                        remove_file(path)?;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    async fn rollback(&self) {
+        *self.staged.lock().unwrap() = None;
+    }
+}
+
+/// Formats a task as Markdown with TOML front matter.
+fn format_task(task: &Task) -> Result<String> {
+    let mut tags = task.tags.clone();
+    tags.sort();
+    tags.dedup();
+    let header = TaskHeader {
+        title: task.title.clone(),
+        status: status_to_str(task.status).to_string(),
+        priority: priority_to_str(task.priority).to_string(),
+        project: task.project_id.clone(),
+        tags,
+        due: task.due_date,
+        created: task.created_at,
+        updated: task.updated_at,
+        completed: task.completed_at,
+        position: Some(task.position),
+        notes: task
+            .notes
+            .iter()
+            .map(|note| NoteEntry {
+                id: note.id.clone(),
+                created: note.created_at,
+                text: note.text.clone(),
+            })
+            .collect(),
+    };
+    let header = toml::to_string(&header)
+        .map_err(|e| StorageError::Conversion(format!("Can't write task: {}", e)))?;
+
+    let mut content = format!("{fence}\n{header}{fence}\n", fence = FRONT_MATTER_FENCE);
+    if let Some(description) = task.description.as_deref().filter(|d| !d.is_empty()) {
+        content.push('\n');
+        content.push_str(description);
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+/// Parses a task file written by [`format_task`] (or by hand).
+fn parse_task(id: &str, content: &str) -> std::result::Result<Task, String> {
+    let content = content.replace("\r\n", "\n");
+    let rest = content
+        .strip_prefix(FRONT_MATTER_FENCE)
+        .and_then(|rest| rest.strip_prefix('\n'))
+        .ok_or("missing +++ front matter")?;
+    // A multi-line note can contain a fence line of its own, so the front
+    // matter ends at the first fence before which it parses
+    let mut error = None;
+    let mut found = None;
+    for (end, fence) in rest.match_indices(&format!("\n{}", FRONT_MATTER_FENCE)) {
+        match toml::from_str::<TaskHeader>(&rest[..end]) {
+            Ok(header) => {
+                found = Some((header, &rest[end + fence.len()..]));
+                break;
+            }
+            Err(e) => {
+                error.get_or_insert(e.to_string());
+            }
+        }
+    }
+    let (header, body) = match (found, error) {
+        (Some(found), _) => found,
+        (None, Some(error)) => return Err(error),
+        (None, None) => return Err("unterminated front matter".to_string()),
+    };
+    let description = body.trim_matches('\n');
+
+    let mut tags = header.tags;
+    tags.sort();
+    tags.dedup();
+    Ok(Task {
+        id: id.to_string(),
+        title: header.title,
+        description: (!description.is_empty()).then(|| description.to_string()),
+        due_date: header.due,
+        priority: str_to_priority(&header.priority),
+        status: str_to_status(&header.status),
+        project_id: header.project,
+        tags,
+        created_at: header.created,
+        updated_at: header.updated,
+        completed_at: header.completed,
+        position: header.position.unwrap_or_else(|| position_at(header.created)),
+        notes: header
+            .notes
+            .into_iter()
+            .map(|note| Note {
+                id: note.id,
+                text: note.text,
+                created_at: note.created,
+            })
+            .collect(),
+    })
+}
+
+/// Checks that an ID is safe to use as a file name.
+fn file_stem(id: &str) -> Result<&str> {
+    if id.is_empty() || id.starts_with('.') || id.contains(['/', '\\']) {
+        return Err(StorageError::Conversion(format!("Invalid ID for a file name: {:?}", id)));
+    }
+    Ok(id)
+}
+
+fn id_from_path(path: &Path) -> Result<String> {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .ok_or_else(|| StorageError::Conversion(format!("Invalid file name: {}", path.display())))
+}
+
+/// Replaces a file atomically by writing a temporary file and renaming it.
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, content).map_err(|e| file_error(&tmp, e))?;
+    std::fs::rename(&tmp, path).map_err(|e| file_error(path, e))
+}
+
+/// Removes a file, returning whether it existed.
+fn remove_file(path: &Path) -> Result<bool> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(file_error(path, e)),
+    }
+}
+
+fn file_error(path: &Path, source: io::Error) -> StorageError {
+    StorageError::File {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, TaskStatus};
+    use crate::storage::{run_migrations, Database};
+
+    fn open_store(dir: &Path) -> FileStore {
+        FileStore::open(dir).unwrap()
+    }
+
+    #[test]
+    fn test_task_file_round_trip() {
+        let mut task = Task::new("Write report");
+        task.description = Some("First line\n\nSecond paragraph".to_string());
+        task.priority = Priority::High;
+        task.tags = vec!["work".to_string(), "finance".to_string()];
+        task.project_id = Some("inbox".to_string());
+        task.due_date = Some(Utc::now());
+        task.complete();
+        task.add_note("Numbers are in\n+++\nnot final");
+
+        let content = format_task(&task).unwrap();
+        assert!(content.starts_with("+++\ntitle = \"Write report\"\nstatus = \"completed\"\n"));
+        assert!(content.contains("tags = [\"finance\", \"work\"]"));
+        assert!(content.ends_with("+++\n\nFirst line\n\nSecond paragraph\n"));
+
+        let parsed = parse_task(&task.id, &content).unwrap();
+        task.tags.sort();
+        assert_eq!(parsed, task);
+    }
+
+    #[test]
+    fn test_hand_written_task_file() {
+        let content = "+++\r\ntitle = \"Call mom\"\r\nstatus = \"pending\"\r\npriority = \"low\"\r\n\
+                       created = \"2026-03-02T09:15:00Z\"\r\nupdated = \"2026-03-02T09:15:00Z\"\r\n+++\r\n";
+        let task = parse_task("call-mom", content).unwrap();
+        assert_eq!(task.title, "Call mom");
+        assert_eq!(task.priority, Priority::Low);
+        assert_eq!(task.status, TaskStatus::Pending);
+        assert_eq!(task.description, None);
+
+        assert!(parse_task("x", "title = \"No fences\"").is_err());
+        assert!(parse_task("x", "+++\ntitle = \"Open\"\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_layout_and_inbox_created() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = open_store(dir.path());
+
+        assert!(dir.path().join("tasks").is_dir());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(".gitignore")).unwrap(),
+            ".ratado/\n"
+        );
+        let inbox = store.get_project("inbox").await.unwrap().unwrap();
+        assert_eq!(inbox.name, "Inbox");
+    }
+
+    #[tokio::test]
+    async fn test_tasks_projects_and_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = open_store(dir.path());
+        let project = Project::new("Work");
+        store.insert_project(&project).await.unwrap();
+        let mut task = Task::new("Plan sprint");
+        task.project_id = Some(project.id.clone());
+        task.tags = vec!["planning".to_string()];
+        store.insert_task(&task).await.unwrap();
+        let other = Task::new("Other");
+        store.insert_task(&other).await.unwrap();
+
+        assert!(matches!(store.insert_task(&task).await, Err(StorageError::Exists(_))));
+        assert!(dir.path().join("tasks").join(format!("{}.md", task.id)).exists());
+
+        // Notes are added one at a time and kept through updates
+        store.add_note(&task.id, &Note::new("Agenda sent")).await.unwrap();
+        store.update_task(&task).await.unwrap();
+        let notes = store.get_task(&task.id).await.unwrap().unwrap().notes;
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].text, "Agenda sent");
+        assert!(matches!(
+            store.add_note("missing", &notes[0]).await,
+            Err(StorageError::NotFound(_))
+        ));
+
+        let all = store.get_all_tasks().await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].id, other.id); // Newest first
+
+        store.add_tag_to_task(&other.id, "planning").await.unwrap();
+        let tags = store.get_tags_with_counts().await.unwrap();
+        assert_eq!(tags, [(Tag { id: "planning".into(), name: "planning".into() }, 2)]);
+        assert!(store.delete_tag("planning").await.unwrap());
+        assert!(store.get_tags_with_counts().await.unwrap().is_empty());
+
+        assert_eq!(store.delete_projects(std::slice::from_ref(&project.id)).await.unwrap(), 1);
+        let task = store.get_task(&task.id).await.unwrap().unwrap();
+        assert_eq!(task.project_id, None);
+
+        let ids: Vec<String> = vec![task.id.clone(), "missing".to_string()];
+        assert_eq!(store.delete_tasks(&ids).await.unwrap(), 1);
+        let remaining = store.get_all_tasks().await.unwrap();
+        assert!(remaining.iter().all(|t| t.id != task.id));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unsafe_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = open_store(dir.path());
+        let mut task = Task::new("Escape");
+        task.id = "../escape".to_string();
+        assert!(matches!(store.insert_task(&task).await, Err(StorageError::Conversion(_))));
+    }
+
+    #[tokio::test]
+    async fn test_database_on_plaintext_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open_dir(dir.path()).await.unwrap();
+        run_migrations(&db).await.unwrap();
+        assert_eq!(db.plaintext_dir(), Some(dir.path()));
+
+        let task = db.add_task(Task::new("Stored as a file")).await.unwrap();
+        let mut done = task.clone();
+        done.complete();
+        db.modify_task(done).await.unwrap();
+        assert_eq!(db.get_task_counts().await.unwrap(), (0, 0, 1, 0));
+        assert!(db.backup("startup").await.unwrap().is_none());
+
+        // Edits made outside the app, e.g. by `git pull`, are noticed
+        assert!(db.external_changes().await.unwrap().is_empty());
+        let path = dir.path().join("tasks").join(format!("{}.md", task.id));
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, content.replace("Stored as a file", "Edited in git")).unwrap();
+        std::fs::remove_file(dir.path().join("projects").join("inbox.toml")).unwrap();
+        // Even when the app writes before looking
+        db.add_task(Task::new("Mine")).await.unwrap();
+        let changes = db.external_changes().await.unwrap();
+        assert_eq!(changes.tasks.into_iter().collect::<Vec<_>>(), std::slice::from_ref(&task.id));
+        assert_eq!(changes.projects.into_iter().collect::<Vec<_>>(), ["inbox"]);
+        assert!(db.external_changes().await.unwrap().is_empty());
+        assert_eq!(db.get_task(&task.id).await.unwrap().unwrap().title, "Edited in git");
+    }
+
+    #[tokio::test]
+    async fn test_transactions_hold_writes_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open_dir(dir.path()).await.unwrap();
+        run_migrations(&db).await.unwrap();
+        let kept = db.add_task(Task::new("Kept")).await.unwrap();
+        let task_file = |id: &str| dir.path().join("tasks").join(format!("{}.md", id));
+
+        let failed: Result<()> = db
+            .in_transaction(async || {
+                let added = Task::new("Added");
+                db.store.insert_task(&added).await?;
+                db.store.delete_tasks(std::slice::from_ref(&kept.id)).await?;
+                // Reads see the writes, the files don't yet
+                let titles: Vec<String> = db.get_all_tasks().await?.into_iter().map(|t| t.title).collect();
+                assert_eq!(titles, ["Added"]);
+                assert!(!task_file(&added.id).exists());
+                assert!(task_file(&kept.id).exists());
+                Err(StorageError::NotFound("missing".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+        let titles: Vec<String> = db.get_all_tasks().await.unwrap().into_iter().map(|t| t.title).collect();
+        assert_eq!(titles, ["Kept"]);
+
+        let added = Task::new("Added");
+        db.in_transaction(async || {
+            db.store.insert_task(&added).await?;
+            db.store.delete_tasks(std::slice::from_ref(&kept.id)).await
+        })
+        .await
+        .unwrap();
+        assert!(task_file(&added.id).exists());
+        assert!(!task_file(&kept.id).exists());
+        // The app's own writes aren't external changes
+        assert!(db.external_changes().await.unwrap().is_empty());
+    }
+}
@@ -61,6 +61,46 @@ const MIGRATIONS: &[Migration] = &[
                   updated_at TEXT NOT NULL DEFAULT (datetime('now'))
               )",
     },
+    Migration {
+        version: 4,
+        description: "Add data version counter for detecting external changes",
+        sql: "INSERT OR IGNORE INTO _app_meta (key, value, updated_at)
+              VALUES ('data_version', '0', datetime('now'))",
+    },
+    Migration {
+        version: 5,
+        description: "Add webhook outbox and delivery log",
+        sql: include_str!("migrations/005_webhooks.sql"),
+    },
+    Migration {
+        version: 6,
+        description: "Add field clocks, tombstones and peers for sync",
+        sql: include_str!("migrations/006_sync.sql"),
+    },
+    Migration {
+        version: 7,
+        description: "Add task position for manual ordering",
+        // Existing tasks keep their creation order: milliseconds since the epoch
+        sql: "ALTER TABLE tasks ADD COLUMN position REAL NOT NULL DEFAULT 0;
+              UPDATE tasks SET position = (julianday(created_at) - 2440587.5) * 86400000.0;",
+    },
+    Migration {
+        version: 8,
+        description: "Add task change history",
+        sql: include_str!("migrations/008_task_events.sql"),
+    },
+    Migration {
+        version: 9,
+        description: "Add task notes",
+        sql: include_str!("migrations/009_task_notes.sql"),
+    },
+    Migration {
+        version: 10,
+        description: "Remove data version counter",
+        // Changes are now passed to the running instance instead, see
+        // `crate::storage::changes`
+        sql: "DELETE FROM _app_meta WHERE key = 'data_version'",
+    },
 ];
 
 /// Runs all pending database migrations.
@@ -112,6 +152,16 @@ pub async fn run_migrations(db: &Database) -> Result<()> {
     let current_version = get_current_version(db).await?;
     info!("Current database version: {}", current_version);
 
+    // Back up an existing database before changing its schema, so a failed
+    // or unwanted upgrade can be rolled back with `ratado backup restore`.
+    let target_version = MIGRATIONS.last().map_or(0, |m| m.version);
+    let backup = if current_version > 0 && target_version > current_version {
+        db.backup(&format!("pre-migration-v{}", target_version))
+            .await?
+    } else {
+        None
+    };
+
     // Apply pending migrations
     for migration in MIGRATIONS.iter().filter(|m| m.version > current_version) {
         info!(
@@ -121,9 +171,13 @@ pub async fn run_migrations(db: &Database) -> Result<()> {
 
         // Execute the migration SQL
         db.execute_batch(migration.sql).await.map_err(|e| {
+            let hint = backup
+                .as_ref()
+                .map(|b| format!(" (restore with `ratado backup restore {}`)", b.id))
+                .unwrap_or_default();
             StorageError::Migration(format!(
-                "Failed to apply migration {}: {}",
-                migration.version, e
+                "Failed to apply migration {}: {}{}",
+                migration.version, e, hint
             ))
         })?;
 
@@ -326,12 +380,59 @@ mod tests {
         run_migrations(&db).await.unwrap();
         run_migrations(&db).await.unwrap();
 
-        // Should still have exactly 3 migrations recorded
+        // Should still have exactly 10 migrations recorded
         let result = db
             .query_scalar("SELECT COUNT(*) FROM _migrations", ())
             .await
             .unwrap();
-        assert_eq!(result, Some(Value::Integer(3)));
+        assert_eq!(result, Some(Value::Integer(10)));
+    }
+
+    #[tokio::test]
+    async fn test_backup_before_upgrading_existing_db() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("ratado.db")).await.unwrap();
+
+        // A fresh database has nothing worth backing up
+        run_migrations(&db).await.unwrap();
+        assert!(crate::storage::list_backups(db.path().unwrap()).unwrap().is_empty());
+
+        // Pretend the last migration is still pending
+        db.execute("DELETE FROM _migrations WHERE version = 10", ())
+            .await
+            .unwrap();
+        run_migrations(&db).await.unwrap();
+
+        let backups = crate::storage::list_backups(db.path().unwrap()).unwrap();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].reason, "pre-migration-v10");
+    }
+
+    #[tokio::test]
+    async fn test_existing_tasks_keep_creation_order() {
+        use crate::models::{position_at, Task};
+
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        let mut older = Task::new("Older");
+        older.created_at -= chrono::Duration::days(2);
+        let newer = Task::new("Newer");
+        db.insert_task(&older).await.unwrap();
+        db.insert_task(&newer).await.unwrap();
+
+        // Upgrade from a database without positions
+        db.execute("DELETE FROM _migrations WHERE version >= 7", ())
+            .await
+            .unwrap();
+        db.execute("ALTER TABLE tasks DROP COLUMN position", ())
+            .await
+            .unwrap();
+        run_migrations(&db).await.unwrap();
+
+        let older = db.get_task(&older.id).await.unwrap().unwrap();
+        let newer = db.get_task(&newer.id).await.unwrap().unwrap();
+        assert!(older.position < newer.position);
+        assert!((older.position - position_at(older.created_at)).abs() < 1000.0);
     }
 
     #[tokio::test]
@@ -340,7 +441,31 @@ mod tests {
         run_migrations(&db).await.unwrap();
 
         let version = get_current_version(&db).await.unwrap();
-        assert_eq!(version, 3); // We have 3 migrations
+        assert_eq!(version, 10); // We have 10 migrations
+    }
+
+    #[tokio::test]
+    async fn test_data_version_counter_removed() {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+
+        // A database from before the counter was dropped
+        db.execute("DELETE FROM _migrations WHERE version = 10", ())
+            .await
+            .unwrap();
+        db.execute(
+            "INSERT INTO _app_meta (key, value) VALUES ('data_version', '42')",
+            (),
+        )
+        .await
+        .unwrap();
+        run_migrations(&db).await.unwrap();
+
+        let counter = db
+            .query_scalar("SELECT COUNT(*) FROM _app_meta WHERE key = 'data_version'", ())
+            .await
+            .unwrap();
+        assert_eq!(counter, Some(Value::Integer(0)));
     }
 
     #[tokio::test]
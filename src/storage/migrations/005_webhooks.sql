@@ -0,0 +1,25 @@
+-- Outgoing webhooks
+
+-- Webhook deliveries waiting to be sent, one row per event and URL
+CREATE TABLE IF NOT EXISTS _webhook_outbox (
+    id INTEGER PRIMARY KEY,
+    delivery_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    event TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+-- Recent delivery attempts, shown on the debug screen
+CREATE TABLE IF NOT EXISTS _webhook_deliveries (
+    id INTEGER PRIMARY KEY,
+    delivery_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    event TEXT NOT NULL,
+    attempt INTEGER NOT NULL,
+    status INTEGER,
+    error TEXT,
+    attempted_at TEXT NOT NULL
+);
@@ -0,0 +1,23 @@
+-- Bookkeeping for `ratado sync`
+
+-- When each field of a task or project last changed. Fields without a row
+-- haven't changed since the item was created.
+CREATE TABLE IF NOT EXISTS _sync_clocks (
+    id TEXT NOT NULL,
+    field TEXT NOT NULL,
+    changed_at TEXT NOT NULL,
+    PRIMARY KEY (id, field)
+);
+
+-- Deleted tasks and projects, so deletions reach other databases
+CREATE TABLE IF NOT EXISTS _sync_tombstones (
+    id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    deleted_at TEXT NOT NULL
+);
+
+-- Databases this one has synced with
+CREATE TABLE IF NOT EXISTS _sync_peers (
+    replica_id TEXT PRIMARY KEY,
+    last_synced_at TEXT NOT NULL
+);
@@ -0,0 +1,16 @@
+-- Task change history
+
+-- One row per changed field of a task, plus one when it's created or
+-- deleted. Rows outlive the task, so the title is kept with each one.
+CREATE TABLE IF NOT EXISTS _task_events (
+    id INTEGER PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    field TEXT NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    changed_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_events_task ON _task_events(task_id);
+CREATE INDEX IF NOT EXISTS idx_task_events_changed_at ON _task_events(changed_at);
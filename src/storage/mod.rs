@@ -2,12 +2,21 @@
 //!
 //! This module handles all database operations using Turso (SQLite-compatible).
 //! Data is stored locally at `~/Library/Application Support/ratado/ratado.db` (macOS)
-//! or `~/.config/ratado/ratado.db` (Linux).
+//! or `~/.config/ratado/ratado.db` (Linux), or as plaintext files in a directory.
 //!
 //! ## Architecture
 //!
 //! - [`Database`] - Connection management and low-level query execution
+//! - [`Store`] - Where tasks, projects and tags are kept: [`SqliteStore`] or
+//!   [`FileStore`]
 //! - [`migrations`] - Schema versioning and upgrades
+//! - [`Backup`] - Database backups, taken on startup and before risky changes
+//! - [`EncryptionKey`] / [`change_key`] - Passphrase encryption of the database file
+//! - [`ChangeSet`] - The tasks and projects changed by writes or outside edits
+//! - [`OutboxEntry`] / [`Delivery`] - Queued webhook events and delivery attempts
+//! - [`FieldClocks`] / [`Tombstone`] - Change bookkeeping for `ratado sync`
+//! - [`TaskEvent`] - Per-task change history and the activity feed
+//! - [`Database::get_preference`] - View preferences remembered with the database
 //! - Task/Project/Tag repositories - CRUD operations for domain models
 //!
 //! ## Usage
@@ -25,12 +34,33 @@
 //! # }
 //! ```
 
+mod activity;
+mod backup;
+mod changes;
 mod database;
+mod encryption;
+mod files;
 mod migrations;
+mod preferences;
 mod projects;
+mod sqlite;
+mod store;
 mod tags;
+mod sync;
 mod tasks;
+mod webhooks;
 
+pub use activity::{TaskEvent, TaskField};
+pub use backup::{Backup, backup_dir, list_backups, prune_backups, restore_backup};
+pub use changes::ChangeSet;
 pub use database::{Database, Result, StorageError};
+pub use encryption::{EncryptionKey, change_key, is_encrypted};
+pub(crate) use encryption::copy_salt;
+pub use files::FileStore;
+pub(crate) use files::STATE_DIR;
 pub use migrations::{check_and_update_app_version, run_migrations};
+pub use sqlite::SqliteStore;
+pub use store::Store;
+pub use sync::{FieldClocks, ItemKind, Tombstone};
 pub use tags::Tag;
+pub use webhooks::{Delivery, OutboxEntry};
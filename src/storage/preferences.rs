@@ -0,0 +1,96 @@
+//! View preferences remembered with the database.
+//!
+//! Preferences are plain key/value strings in the `_app_meta` table, next
+//! to the app version. Writing one doesn't count as a data change, so it
+//! doesn't trigger a reload in other processes.
+
+use chrono::{DateTime, Utc};
+use turso::Value;
+
+use crate::storage::{Database, Result};
+
+/// Preference key of the time of the last weekly review.
+const LAST_REVIEW: &str = "last_review";
+
+impl Database {
+    /// Returns the stored value of the preference `key`, if it was set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn get_preference(&self, key: &str) -> Result<Option<String>> {
+        let row = self
+            .query_one("SELECT value FROM _app_meta WHERE key = ?1", [key])
+            .await?;
+        match row {
+            Some(row) => match row.get_value(0)? {
+                Value::Text(value) => Ok(Some(value)),
+                _ => Ok(None),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Stores `value` as the preference `key`, replacing any earlier value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails.
+    pub async fn set_preference(&self, key: &str, value: &str) -> Result<()> {
+        self.execute(
+            "INSERT OR REPLACE INTO _app_meta (key, value, updated_at)
+             VALUES (?1, ?2, datetime('now'))",
+            [Value::Text(key.to_string()), Value::Text(value.to_string())],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Returns when the last weekly review was finished, if ever.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn last_review(&self) -> Result<Option<DateTime<Utc>>> {
+        let value = self.get_preference(LAST_REVIEW).await?;
+        Ok(value
+            .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+            .map(|time| time.with_timezone(&Utc)))
+    }
+
+    /// Records `time` as when the last weekly review was finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails.
+    pub async fn set_last_review(&self, time: DateTime<Utc>) -> Result<()> {
+        self.set_preference(LAST_REVIEW, &time.to_rfc3339()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::{run_migrations, Database};
+
+    #[tokio::test]
+    async fn test_preferences_round_trip() {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+
+        assert_eq!(db.get_preference("group_by").await.unwrap(), None);
+        db.set_preference("group_by", "project").await.unwrap();
+        db.set_preference("group_by", "due").await.unwrap();
+        assert_eq!(db.get_preference("group_by").await.unwrap().as_deref(), Some("due"));
+    }
+
+    #[tokio::test]
+    async fn test_last_review() {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+
+        assert_eq!(db.last_review().await.unwrap(), None);
+        let time = chrono::DateTime::parse_from_rfc3339("2026-03-06T17:30:00Z").unwrap().to_utc();
+        db.set_last_review(time).await.unwrap();
+        assert_eq!(db.last_review().await.unwrap(), Some(time));
+    }
+}
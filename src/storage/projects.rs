@@ -1,12 +1,16 @@
 //! Project repository for CRUD operations.
 //!
 //! This module provides methods for creating, reading, updating, and deleting
-//! projects in the database.
+//! projects. The [`Store`](crate::storage::Store) keeps them; these methods
+//! add webhooks and sync bookkeeping on top.
 
-use turso::Value;
+use serde_json::json;
 
 use crate::models::Project;
+use crate::storage::sync::ItemKind;
 use crate::storage::{Database, Result, StorageError};
+use crate::sync::{changed_fields, project_fields};
+use crate::webhooks::WebhookEvent;
 
 impl Database {
     /// Inserts a new project into the database.
@@ -33,18 +37,9 @@ impl Database {
     /// # }
     /// ```
     pub async fn insert_project(&self, project: &Project) -> Result<()> {
-        self.execute(
-            "INSERT INTO projects (id, name, color, icon, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            [
-                Value::Text(project.id.clone()),
-                Value::Text(project.name.clone()),
-                Value::Text(project.color.clone()),
-                Value::Text(project.icon.clone()),
-                Value::Text(project.created_at.to_rfc3339()),
-            ],
-        )
-        .await?;
+        self.store.insert_project(project).await?;
+        self.queue_webhooks(WebhookEvent::ProjectCreated, json!(project))
+            .await?;
         Ok(())
     }
 
@@ -62,17 +57,7 @@ impl Database {
     ///
     /// Returns an error if the query fails or data cannot be parsed.
     pub async fn get_project(&self, id: &str) -> Result<Option<Project>> {
-        let row = self
-            .query_one(
-                "SELECT id, name, color, icon, created_at FROM projects WHERE id = ?1",
-                [id],
-            )
-            .await?;
-
-        match row {
-            Some(row) => Ok(Some(row_to_project(&row)?)),
-            None => Ok(None),
-        }
+        self.store.get_project(id).await
     }
 
     /// Retrieves all projects from the database.
@@ -85,19 +70,7 @@ impl Database {
     ///
     /// Returns an error if the query fails.
     pub async fn get_all_projects(&self) -> Result<Vec<Project>> {
-        let mut rows = self
-            .query(
-                "SELECT id, name, color, icon, created_at FROM projects ORDER BY created_at ASC",
-                (),
-            )
-            .await?;
-
-        let mut projects = Vec::new();
-        while let Some(row) = rows.next().await? {
-            projects.push(row_to_project(&row)?);
-        }
-
-        Ok(projects)
+        self.store.get_all_projects().await
     }
 
     /// Updates an existing project.
@@ -110,25 +83,20 @@ impl Database {
     ///
     /// Returns an error if the project doesn't exist or the update fails.
     pub async fn update_project(&self, project: &Project) -> Result<()> {
-        let rows_affected = self
-            .execute(
-                "UPDATE projects SET name = ?1, color = ?2, icon = ?3 WHERE id = ?4",
-                [
-                    Value::Text(project.name.clone()),
-                    Value::Text(project.color.clone()),
-                    Value::Text(project.icon.clone()),
-                    Value::Text(project.id.clone()),
-                ],
-            )
-            .await?;
-
-        if rows_affected == 0 {
+        let original = self.get_project(&project.id).await?;
+        if !self.store.update_project(project).await? {
             return Err(StorageError::NotFound(format!(
                 "Project not found: {}",
                 project.id
             )));
         }
 
+        if let Some(original) = original {
+            let changed = changed_fields(&project_fields(&original), &project_fields(project));
+            self.record_field_changes(&project.id, &changed).await?;
+        }
+        self.queue_webhooks(WebhookEvent::ProjectUpdated, json!(project))
+            .await?;
         Ok(())
     }
 
@@ -155,10 +123,15 @@ impl Database {
             ));
         }
 
-        let rows_affected = self
-            .execute("DELETE FROM projects WHERE id = ?1", [id])
+        let Some(project) = self.get_project(id).await? else {
+            return Ok(false);
+        };
+        let ids = [project.id.clone()];
+        self.record_deletions(ItemKind::Project, &ids).await?;
+        self.store.delete_projects(&ids).await?;
+        self.queue_webhooks(WebhookEvent::ProjectDeleted, json!(project))
             .await?;
-        Ok(rows_affected > 0)
+        Ok(true)
     }
 
     /// Deletes all projects except Inbox.
@@ -173,10 +146,20 @@ impl Database {
     ///
     /// Returns an error if the delete fails.
     pub async fn delete_all_projects_except_inbox(&self) -> Result<usize> {
-        let rows_affected = self
-            .execute("DELETE FROM projects WHERE id != 'inbox'", ())
-            .await?;
-        Ok(rows_affected as usize)
+        let projects: Vec<Project> = self
+            .get_all_projects()
+            .await?
+            .into_iter()
+            .filter(|p| p.id != "inbox")
+            .collect();
+        let ids: Vec<String> = projects.iter().map(|p| p.id.clone()).collect();
+        self.record_deletions(ItemKind::Project, &ids).await?;
+        let deleted = self.store.delete_projects(&ids).await?;
+        for project in projects {
+            self.queue_webhooks(WebhookEvent::ProjectDeleted, json!(project))
+                .await?;
+        }
+        Ok(deleted)
     }
 
     /// Gets the count of tasks in a project.
@@ -189,17 +172,8 @@ impl Database {
     ///
     /// The number of tasks in the project.
     pub async fn get_task_count_by_project(&self, project_id: &str) -> Result<usize> {
-        let value = self
-            .query_scalar(
-                "SELECT COUNT(*) FROM tasks WHERE project_id = ?1",
-                [project_id],
-            )
-            .await?;
-
-        match value {
-            Some(Value::Integer(count)) => Ok(count as usize),
-            _ => Ok(0),
-        }
+        let counts = self.store.count_tasks_by_project().await?;
+        Ok(counts.get(project_id).copied().unwrap_or(0))
     }
 
     /// Gets all projects with their task counts.
@@ -209,55 +183,14 @@ impl Database {
     /// A vector of (Project, task_count) tuples.
     pub async fn get_projects_with_counts(&self) -> Result<Vec<(Project, usize)>> {
         let projects = self.get_all_projects().await?;
-        let mut result = Vec::with_capacity(projects.len());
-
-        for project in projects {
-            let count = self.get_task_count_by_project(&project.id).await?;
-            result.push((project, count));
-        }
-
-        Ok(result)
-    }
-}
-
-/// Converts a database row to a Project.
-fn row_to_project(row: &turso::Row) -> Result<Project> {
-    let id = value_to_string(row.get_value(0)?)?;
-    let name = value_to_string(row.get_value(1)?)?;
-    let color = value_to_string(row.get_value(2)?)?;
-    let icon = value_to_string(row.get_value(3)?)?;
-    let created_at = value_to_datetime(row.get_value(4)?)?;
-
-    Ok(Project {
-        id,
-        name,
-        color,
-        icon,
-        created_at,
-    })
-}
-
-fn value_to_string(value: Value) -> Result<String> {
-    match value {
-        Value::Text(s) => Ok(s),
-        _ => Err(StorageError::Conversion(format!(
-            "Expected text, got {:?}",
-            value
-        ))),
-    }
-}
-
-fn value_to_datetime(value: Value) -> Result<chrono::DateTime<chrono::Utc>> {
-    use chrono::{DateTime, Utc};
-
-    match value {
-        Value::Text(s) => DateTime::parse_from_rfc3339(&s)
-            .map(|dt| dt.with_timezone(&Utc))
-            .map_err(|e| StorageError::Conversion(format!("Invalid datetime '{}': {}", s, e))),
-        _ => Err(StorageError::Conversion(format!(
-            "Expected datetime text, got {:?}",
-            value
-        ))),
+        let counts = self.store.count_tasks_by_project().await?;
+        Ok(projects
+            .into_iter()
+            .map(|project| {
+                let count = counts.get(&project.id).copied().unwrap_or(0);
+                (project, count)
+            })
+            .collect())
     }
 }
 
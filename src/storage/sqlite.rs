@@ -0,0 +1,688 @@
+//! SQLite storage backend.
+//!
+//! [`SqliteStore`] keeps tasks, projects and tags in the `tasks`,
+//! `projects`, `tags` and `task_tags` tables, and task notes in
+//! `task_notes`. Every [`Database`] also uses it
+//! for its bookkeeping tables (migrations, webhook outbox, sync clocks), even
+//! when another [`Store`] holds the data. All datetime values are stored as
+//! ISO8601 strings.
+//!
+//! [`Database`]: crate::storage::Database
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use turso::{Connection, Row, Rows, Value};
+use uuid::Uuid;
+
+use crate::models::{Filter, Note, Priority, Project, SortOrder, Task, TaskStatus};
+use crate::storage::{ChangeSet, Result, Store, StorageError, Tag};
+
+/// Tasks, projects and tags in a SQLite database.
+#[derive(Clone)]
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Wraps an open connection.
+    pub(crate) fn new(conn: Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Returns the underlying connection.
+    pub(crate) fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Executes a statement, returning the number of rows affected.
+    pub(crate) async fn execute(
+        &self,
+        sql: impl AsRef<str>,
+        params: impl turso::IntoParams,
+    ) -> Result<u64> {
+        Ok(self.conn.execute(sql, params).await?)
+    }
+
+    /// Executes a query and returns the result rows.
+    pub(crate) async fn query(
+        &self,
+        sql: impl AsRef<str>,
+        params: impl turso::IntoParams,
+    ) -> Result<Rows> {
+        Ok(self.conn.query(sql, params).await?)
+    }
+
+    /// Executes a query and returns the first row.
+    pub(crate) async fn query_one(
+        &self,
+        sql: impl AsRef<str>,
+        params: impl turso::IntoParams,
+    ) -> Result<Option<Row>> {
+        let mut rows = self.query(sql, params).await?;
+        Ok(rows.next().await?)
+    }
+
+    /// Returns the ID of the tag `name`, creating the tag if needed.
+    async fn get_or_create_tag(&self, name: &str) -> Result<String> {
+        if let Some(row) = self
+            .query_one("SELECT id FROM tags WHERE name = ?1", [name])
+            .await?
+        {
+            return value_to_string(row.get_value(0)?);
+        }
+        self.insert_tag(name).await
+    }
+
+    /// Loads tag names, by task ID, for the tasks selected by `where_clause`
+    /// (a clause of [`build_filter_clause`], or empty for every task).
+    async fn get_task_tags(
+        &self,
+        where_clause: &str,
+        params: Vec<Value>,
+    ) -> Result<HashMap<String, Vec<String>>> {
+        let mut rows = self
+            .query(
+                format!(
+                    "SELECT tt.task_id, t.name FROM task_tags tt
+                     JOIN tags t ON t.id = tt.tag_id
+                     WHERE tt.task_id IN (SELECT id FROM tasks {})
+                     ORDER BY t.name ASC",
+                    where_clause
+                ),
+                params,
+            )
+            .await?;
+
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        while let Some(row) = rows.next().await? {
+            let task_id = value_to_string(row.get_value(0)?)?;
+            let tag_name = value_to_string(row.get_value(1)?)?;
+            map.entry(task_id).or_default().push(tag_name);
+        }
+        Ok(map)
+    }
+
+    /// Loads notes, oldest first, by task ID, for the tasks selected by
+    /// `where_clause`.
+    async fn get_task_notes(
+        &self,
+        where_clause: &str,
+        params: Vec<Value>,
+    ) -> Result<HashMap<String, Vec<Note>>> {
+        let mut rows = self
+            .query(
+                format!(
+                    "SELECT task_id, id, text, created_at FROM task_notes
+                     WHERE task_id IN (SELECT id FROM tasks {})
+                     ORDER BY created_at ASC, id ASC",
+                    where_clause
+                ),
+                params,
+            )
+            .await?;
+
+        let mut map: HashMap<String, Vec<Note>> = HashMap::new();
+        while let Some(row) = rows.next().await? {
+            let task_id = value_to_string(row.get_value(0)?)?;
+            map.entry(task_id).or_default().push(Note {
+                id: value_to_string(row.get_value(1)?)?,
+                text: value_to_string(row.get_value(2)?)?,
+                created_at: value_to_datetime(row.get_value(3)?)?,
+            });
+        }
+        Ok(map)
+    }
+
+    /// Loads the tasks selected by `where_clause` with their tags and notes,
+    /// in the order of `order_clause`.
+    async fn select_tasks(
+        &self,
+        where_clause: &str,
+        params: Vec<Value>,
+        order_clause: &str,
+    ) -> Result<Vec<Task>> {
+        // Load tags and notes in one query each (fixes N+1)
+        let tag_map = self.get_task_tags(where_clause, params.clone()).await?;
+        let mut note_map = self.get_task_notes(where_clause, params.clone()).await?;
+
+        let mut rows = self
+            .query(
+                format!(
+                    "SELECT id, title, description, due_date, priority, status,
+                     project_id, created_at, updated_at, completed_at, position
+                     FROM tasks {} {}",
+                    where_clause, order_clause
+                ),
+                params,
+            )
+            .await?;
+
+        let mut tasks = Vec::new();
+        while let Some(row) = rows.next().await? {
+            let mut task = row_to_task(&row)?;
+            task.tags = tag_map.get(&task.id).cloned().unwrap_or_default();
+            task.notes = note_map.remove(&task.id).unwrap_or_default();
+            tasks.push(task);
+        }
+        Ok(tasks)
+    }
+
+    /// Runs a `SELECT key, COUNT(*) ... GROUP BY key` query.
+    async fn count_by(&self, sql: &str) -> Result<Vec<(String, usize)>> {
+        let mut rows = self.query(sql, ()).await?;
+        let mut counts = Vec::new();
+        while let Some(row) = rows.next().await? {
+            let key = value_to_string(row.get_value(0)?)?;
+            let count = match row.get_value(1)? {
+                Value::Integer(c) => c as usize,
+                _ => 0,
+            };
+            counts.push((key, count));
+        }
+        Ok(counts)
+    }
+}
+
+#[async_trait]
+impl Store for SqliteStore {
+    async fn get_task(&self, id: &str) -> Result<Option<Task>> {
+        let tasks = self
+            .select_tasks("WHERE id = ?1", vec![Value::Text(id.to_string())], "")
+            .await?;
+        Ok(tasks.into_iter().next())
+    }
+
+    async fn get_all_tasks(&self) -> Result<Vec<Task>> {
+        self.select_tasks("", Vec::new(), "ORDER BY created_at DESC, id DESC").await
+    }
+
+    async fn query_tasks(&self, filter: &Filter, sort: &SortOrder) -> Result<Vec<Task>> {
+        let (where_clause, params) = build_filter_clause(filter);
+        let mut tasks = self
+            .select_tasks(where_clause, params, build_order_clause(sort))
+            .await?;
+        // Due dates are narrowed down in SQL, but "today" is local time
+        tasks.retain(|task| filter.matches(task));
+        Ok(tasks)
+    }
+
+    async fn count_tasks_by_status(&self) -> Result<HashMap<TaskStatus, usize>> {
+        let counts = self
+            .count_by("SELECT status, COUNT(*) FROM tasks GROUP BY status")
+            .await?;
+        let mut by_status = HashMap::new();
+        for (status, count) in counts {
+            *by_status.entry(str_to_status(&status)).or_default() += count;
+        }
+        Ok(by_status)
+    }
+
+    async fn count_tasks_by_project(&self) -> Result<HashMap<String, usize>> {
+        let counts = self
+            .count_by(
+                "SELECT project_id, COUNT(*) FROM tasks
+                 WHERE project_id IS NOT NULL GROUP BY project_id",
+            )
+            .await?;
+        Ok(counts.into_iter().collect())
+    }
+
+    async fn insert_task(&self, task: &Task) -> Result<()> {
+        self.execute(
+            "INSERT INTO tasks (id, title, description, due_date, priority,
+             status, project_id, created_at, updated_at, completed_at, position)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            [
+                Value::Text(task.id.clone()),
+                Value::Text(task.title.clone()),
+                option_to_value(&task.description),
+                datetime_to_value(task.due_date),
+                Value::Text(priority_to_str(task.priority).to_string()),
+                Value::Text(status_to_str(task.status).to_string()),
+                option_to_value(&task.project_id),
+                Value::Text(task.created_at.to_rfc3339()),
+                Value::Text(task.updated_at.to_rfc3339()),
+                datetime_to_value(task.completed_at),
+                Value::Real(task.position),
+            ],
+        )
+        .await?;
+
+        for tag in &task.tags {
+            self.add_tag_to_task(&task.id, tag).await?;
+        }
+        for note in &task.notes {
+            self.add_note(&task.id, note).await?;
+        }
+        Ok(())
+    }
+
+    async fn update_task(&self, task: &Task) -> Result<bool> {
+        let rows_affected = self
+            .execute(
+                "UPDATE tasks SET
+                 title = ?1, description = ?2, due_date = ?3, priority = ?4,
+                 status = ?5, project_id = ?6, updated_at = ?7, completed_at = ?8,
+                 position = ?9
+                 WHERE id = ?10",
+                [
+                    Value::Text(task.title.clone()),
+                    option_to_value(&task.description),
+                    datetime_to_value(task.due_date),
+                    Value::Text(priority_to_str(task.priority).to_string()),
+                    Value::Text(status_to_str(task.status).to_string()),
+                    option_to_value(&task.project_id),
+                    Value::Text(task.updated_at.to_rfc3339()),
+                    datetime_to_value(task.completed_at),
+                    Value::Real(task.position),
+                    Value::Text(task.id.clone()),
+                ],
+            )
+            .await?;
+        if rows_affected == 0 {
+            return Ok(false);
+        }
+
+        // Update tags: remove all existing, add current
+        self.execute("DELETE FROM task_tags WHERE task_id = ?1", [task.id.as_str()])
+            .await?;
+        for tag in &task.tags {
+            self.add_tag_to_task(&task.id, tag).await?;
+        }
+        Ok(true)
+    }
+
+    async fn add_note(&self, task_id: &str, note: &Note) -> Result<()> {
+        let added = self
+            .execute(
+                "INSERT INTO task_notes (id, task_id, text, created_at)
+                 SELECT ?1, id, ?3, ?4 FROM tasks WHERE id = ?2",
+                [
+                    Value::Text(note.id.clone()),
+                    Value::Text(task_id.to_string()),
+                    Value::Text(note.text.clone()),
+                    Value::Text(note.created_at.to_rfc3339()),
+                ],
+            )
+            .await?;
+        if added == 0 {
+            return Err(StorageError::NotFound(format!("Task not found: {}", task_id)));
+        }
+        Ok(())
+    }
+
+    async fn delete_tasks(&self, ids: &[String]) -> Result<usize> {
+        // Tag associations and notes are deleted automatically via ON DELETE CASCADE
+        let mut deleted = 0;
+        for id in ids {
+            // Turso can report more than one change per row inside a
+            // transaction, so count the tasks that existed instead
+            if self
+                .execute("DELETE FROM tasks WHERE id = ?1", [id.as_str()])
+                .await?
+                > 0
+            {
+                deleted += 1;
+            }
+        }
+        Ok(deleted)
+    }
+
+    async fn get_project(&self, id: &str) -> Result<Option<Project>> {
+        let row = self
+            .query_one(
+                "SELECT id, name, color, icon, created_at FROM projects WHERE id = ?1",
+                [id],
+            )
+            .await?;
+
+        match row {
+            Some(row) => Ok(Some(row_to_project(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_all_projects(&self) -> Result<Vec<Project>> {
+        let mut rows = self
+            .query(
+                "SELECT id, name, color, icon, created_at FROM projects ORDER BY created_at ASC",
+                (),
+            )
+            .await?;
+
+        let mut projects = Vec::new();
+        while let Some(row) = rows.next().await? {
+            projects.push(row_to_project(&row)?);
+        }
+        Ok(projects)
+    }
+
+    async fn insert_project(&self, project: &Project) -> Result<()> {
+        self.execute(
+            "INSERT INTO projects (id, name, color, icon, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            [
+                Value::Text(project.id.clone()),
+                Value::Text(project.name.clone()),
+                Value::Text(project.color.clone()),
+                Value::Text(project.icon.clone()),
+                Value::Text(project.created_at.to_rfc3339()),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_project(&self, project: &Project) -> Result<bool> {
+        let rows_affected = self
+            .execute(
+                "UPDATE projects SET name = ?1, color = ?2, icon = ?3 WHERE id = ?4",
+                [
+                    Value::Text(project.name.clone()),
+                    Value::Text(project.color.clone()),
+                    Value::Text(project.icon.clone()),
+                    Value::Text(project.id.clone()),
+                ],
+            )
+            .await?;
+        Ok(rows_affected > 0)
+    }
+
+    async fn delete_projects(&self, ids: &[String]) -> Result<usize> {
+        // Tasks lose their project via ON DELETE SET NULL
+        let mut deleted = 0;
+        for id in ids {
+            deleted += self
+                .execute("DELETE FROM projects WHERE id = ?1", [id.as_str()])
+                .await? as usize;
+        }
+        Ok(deleted)
+    }
+
+    async fn get_tags_with_counts(&self) -> Result<Vec<(Tag, usize)>> {
+        let mut rows = self
+            .query(
+                "SELECT t.id, t.name, COUNT(tt.task_id) as count
+                 FROM tags t
+                 LEFT JOIN task_tags tt ON t.id = tt.tag_id
+                 GROUP BY t.id, t.name
+                 ORDER BY t.name ASC",
+                (),
+            )
+            .await?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows.next().await? {
+            let id = value_to_string(row.get_value(0)?)?;
+            let name = value_to_string(row.get_value(1)?)?;
+            let count = match row.get_value(2)? {
+                Value::Integer(c) => c as usize,
+                _ => 0,
+            };
+            result.push((Tag { id, name }, count));
+        }
+        Ok(result)
+    }
+
+    async fn insert_tag(&self, name: &str) -> Result<String> {
+        let id = Uuid::now_v7().to_string();
+        self.execute(
+            "INSERT INTO tags (id, name) VALUES (?1, ?2)",
+            [Value::Text(id.clone()), Value::Text(name.to_string())],
+        )
+        .await?;
+        Ok(id)
+    }
+
+    async fn delete_tag(&self, id: &str) -> Result<bool> {
+        // Task associations are deleted automatically via ON DELETE CASCADE
+        let rows_affected = self
+            .execute("DELETE FROM tags WHERE id = ?1", [id])
+            .await?;
+        Ok(rows_affected > 0)
+    }
+
+    async fn add_tag_to_task(&self, task_id: &str, name: &str) -> Result<()> {
+        let tag_id = self.get_or_create_tag(name).await?;
+
+        // Use INSERT OR IGNORE to handle duplicates gracefully
+        self.execute(
+            "INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)",
+            [Value::Text(task_id.to_string()), Value::Text(tag_id)],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_tag_from_task(&self, task_id: &str, tag_id: &str) -> Result<bool> {
+        let rows_affected = self
+            .execute(
+                "DELETE FROM task_tags WHERE task_id = ?1 AND tag_id = ?2",
+                [task_id, tag_id],
+            )
+            .await?;
+        Ok(rows_affected > 0)
+    }
+
+    async fn external_changes(&self) -> Result<ChangeSet> {
+        // The database file is held exclusively while it's open, so every
+        // write goes through this connection
+        Ok(ChangeSet::default())
+    }
+}
+
+/// Builds a WHERE clause selecting the tasks `filter` matches.
+///
+/// Due date filters only select tasks with a due date (and overdue ones,
+/// tasks that aren't done); the rest is up to [`Filter::matches`].
+fn build_filter_clause(filter: &Filter) -> (&'static str, Vec<Value>) {
+    match filter {
+        Filter::All => ("", Vec::new()),
+        Filter::Pending => ("WHERE status = 'pending'", Vec::new()),
+        Filter::InProgress => ("WHERE status = 'in_progress'", Vec::new()),
+        Filter::Completed => ("WHERE status = 'completed'", Vec::new()),
+        Filter::Archived => ("WHERE status = 'archived'", Vec::new()),
+        Filter::ByProject(project_id) => {
+            ("WHERE project_id = ?1", vec![Value::Text(project_id.clone())])
+        }
+        Filter::ByPriority(priority) => (
+            "WHERE priority = ?1",
+            vec![Value::Text(priority_to_str(*priority).to_string())],
+        ),
+        Filter::ByTag(tag) => (
+            "WHERE id IN (SELECT tt.task_id FROM task_tags tt
+             JOIN tags t ON t.id = tt.tag_id WHERE t.name = ?1)",
+            vec![Value::Text(tag.clone())],
+        ),
+        Filter::DueToday | Filter::DueThisWeek => ("WHERE due_date IS NOT NULL", Vec::new()),
+        Filter::Overdue => (
+            "WHERE due_date IS NOT NULL AND status NOT IN ('completed', 'archived')",
+            Vec::new(),
+        ),
+    }
+}
+
+/// Builds an ORDER BY clause for the given sort order, breaking ties like
+/// [`sort_by_keys`](crate::models::sort_by_keys): by manual position, then
+/// creation time, then ID.
+fn build_order_clause(sort: &SortOrder) -> &'static str {
+    match sort {
+        // Tasks without a due date come last either way
+        SortOrder::DueDateAsc => {
+            "ORDER BY due_date IS NULL, due_date ASC, position ASC, created_at ASC, id ASC"
+        }
+        SortOrder::DueDateDesc => {
+            "ORDER BY due_date IS NULL, due_date DESC, position ASC, created_at ASC, id ASC"
+        }
+        SortOrder::PriorityDesc => {
+            "ORDER BY CASE priority
+             WHEN 'urgent' THEN 3
+             WHEN 'high' THEN 2
+             WHEN 'low' THEN 0
+             ELSE 1
+             END DESC, position ASC, created_at ASC, id ASC"
+        }
+        SortOrder::PriorityAsc => {
+            "ORDER BY CASE priority
+             WHEN 'urgent' THEN 3
+             WHEN 'high' THEN 2
+             WHEN 'low' THEN 0
+             ELSE 1
+             END ASC, position ASC, created_at ASC, id ASC"
+        }
+        SortOrder::CreatedDesc => "ORDER BY created_at DESC, position ASC, id ASC",
+        SortOrder::CreatedAsc => "ORDER BY created_at ASC, position ASC, id ASC",
+        SortOrder::Alphabetical => "ORDER BY title ASC, position ASC, created_at ASC, id ASC",
+        SortOrder::Manual => "ORDER BY position ASC, created_at ASC, id ASC",
+    }
+}
+
+/// Converts a database row to a Task.
+fn row_to_task(row: &Row) -> Result<Task> {
+    let id = value_to_string(row.get_value(0)?)?;
+    let title = value_to_string(row.get_value(1)?)?;
+    let description = value_to_option_string(row.get_value(2)?)?;
+    let due_date = value_to_option_datetime(row.get_value(3)?)?;
+    let priority = str_to_priority(&value_to_string(row.get_value(4)?)?);
+    let status = str_to_status(&value_to_string(row.get_value(5)?)?);
+    let project_id = value_to_option_string(row.get_value(6)?)?;
+    let created_at = value_to_datetime(row.get_value(7)?)?;
+    let updated_at = value_to_datetime(row.get_value(8)?)?;
+    let completed_at = value_to_option_datetime(row.get_value(9)?)?;
+    let position = value_to_f64(row.get_value(10)?)?;
+
+    Ok(Task {
+        id,
+        title,
+        description,
+        due_date,
+        priority,
+        status,
+        project_id,
+        tags: Vec::new(), // Tags are loaded separately
+        created_at,
+        updated_at,
+        completed_at,
+        position,
+        notes: Vec::new(), // Notes are loaded separately
+    })
+}
+
+/// Converts a database row to a Project.
+fn row_to_project(row: &Row) -> Result<Project> {
+    let id = value_to_string(row.get_value(0)?)?;
+    let name = value_to_string(row.get_value(1)?)?;
+    let color = value_to_string(row.get_value(2)?)?;
+    let icon = value_to_string(row.get_value(3)?)?;
+    let created_at = value_to_datetime(row.get_value(4)?)?;
+
+    Ok(Project {
+        id,
+        name,
+        color,
+        icon,
+        created_at,
+    })
+}
+
+// Helper functions for type conversions
+
+fn option_to_value(opt: &Option<String>) -> Value {
+    match opt {
+        Some(s) => Value::Text(s.clone()),
+        None => Value::Null,
+    }
+}
+
+fn datetime_to_value(dt: Option<DateTime<Utc>>) -> Value {
+    dt.map(|d| Value::Text(d.to_rfc3339())).unwrap_or(Value::Null)
+}
+
+pub(crate) fn priority_to_str(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "low",
+        Priority::Medium => "medium",
+        Priority::High => "high",
+        Priority::Urgent => "urgent",
+    }
+}
+
+pub(crate) fn str_to_priority(s: &str) -> Priority {
+    match s.to_lowercase().as_str() {
+        "low" => Priority::Low,
+        "high" => Priority::High,
+        "urgent" => Priority::Urgent,
+        _ => Priority::Medium,
+    }
+}
+
+pub(crate) fn status_to_str(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Pending => "pending",
+        TaskStatus::InProgress => "in_progress",
+        TaskStatus::Completed => "completed",
+        TaskStatus::Archived => "archived",
+    }
+}
+
+pub(crate) fn str_to_status(s: &str) -> TaskStatus {
+    match s.to_lowercase().as_str() {
+        "in_progress" => TaskStatus::InProgress,
+        "completed" => TaskStatus::Completed,
+        "archived" => TaskStatus::Archived,
+        _ => TaskStatus::Pending,
+    }
+}
+
+fn value_to_string(value: Value) -> Result<String> {
+    match value {
+        Value::Text(s) => Ok(s),
+        _ => Err(StorageError::Conversion(format!(
+            "Expected text, got {:?}",
+            value
+        ))),
+    }
+}
+
+fn value_to_option_string(value: Value) -> Result<Option<String>> {
+    match value {
+        Value::Text(s) => Ok(Some(s)),
+        Value::Null => Ok(None),
+        _ => Err(StorageError::Conversion(format!(
+            "Expected text or null, got {:?}",
+            value
+        ))),
+    }
+}
+
+fn value_to_f64(value: Value) -> Result<f64> {
+    match value {
+        Value::Real(n) => Ok(n),
+        Value::Integer(n) => Ok(n as f64),
+        _ => Err(StorageError::Conversion(format!(
+            "Expected number, got {:?}",
+            value
+        ))),
+    }
+}
+
+fn value_to_datetime(value: Value) -> Result<DateTime<Utc>> {
+    match value {
+        Value::Text(s) => DateTime::parse_from_rfc3339(&s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|e| StorageError::Conversion(format!("Invalid datetime '{}': {}", s, e))),
+        _ => Err(StorageError::Conversion(format!(
+            "Expected datetime text, got {:?}",
+            value
+        ))),
+    }
+}
+
+fn value_to_option_datetime(value: Value) -> Result<Option<DateTime<Utc>>> {
+    match value {
+        Value::Null => Ok(None),
+        value => value_to_datetime(value).map(Some),
+    }
+}
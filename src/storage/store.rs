@@ -0,0 +1,133 @@
+//! The storage backend trait.
+//!
+//! A [`Store`] keeps tasks, projects and tags, and nothing else: it knows
+//! nothing about hooks, webhooks, sync clocks or backups. [`Database`]
+//! layers those on top, so the rest of the app works the same whichever
+//! store holds the data:
+//!
+//! - [`SqliteStore`] - tables in the SQLite database (the default)
+//! - [`FileStore`] - one plaintext file per task and project in a directory,
+//!   for keeping tasks in git
+//!
+//! [`Database`]: crate::storage::Database
+//! [`SqliteStore`]: crate::storage::SqliteStore
+//! [`FileStore`]: crate::storage::FileStore
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::models::{Filter, Note, Project, SortOrder, Task, TaskStatus};
+use crate::storage::{ChangeSet, Result, Tag};
+
+/// Where tasks, projects and tags are kept.
+///
+/// Writes store items as given. Deleting a project leaves its tasks without
+/// a project, and deleting a task or tag removes it from everything that
+/// refers to it.
+///
+/// Queries and counts have defaults that go through
+/// [`Store::get_all_tasks`]; stores that can answer them without loading
+/// every task override them.
+#[async_trait]
+pub trait Store: Send + Sync {
+    /// Returns the task with the given ID, with its tags.
+    async fn get_task(&self, id: &str) -> Result<Option<Task>>;
+
+    /// Returns all tasks with their tags, newest first.
+    async fn get_all_tasks(&self) -> Result<Vec<Task>>;
+
+    /// Returns the tasks matching `filter` with their tags, sorted by
+    /// `sort` as [`SortOrder::apply`] sorts them.
+    async fn query_tasks(&self, filter: &Filter, sort: &SortOrder) -> Result<Vec<Task>> {
+        let tasks = self.get_all_tasks().await?;
+        let mut matching = filter.apply(&tasks);
+        sort.apply(&mut matching);
+        Ok(matching.into_iter().cloned().collect())
+    }
+
+    /// Returns the number of tasks in each status that has any.
+    async fn count_tasks_by_status(&self) -> Result<HashMap<TaskStatus, usize>> {
+        let mut counts = HashMap::new();
+        for task in self.get_all_tasks().await? {
+            *counts.entry(task.status).or_default() += 1;
+        }
+        Ok(counts)
+    }
+
+    /// Returns the number of tasks in each project that has any.
+    async fn count_tasks_by_project(&self) -> Result<HashMap<String, usize>> {
+        let mut counts = HashMap::new();
+        for task in self.get_all_tasks().await? {
+            if let Some(project_id) = task.project_id {
+                *counts.entry(project_id).or_default() += 1;
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Adds a new task. Fails if the ID is taken.
+    async fn insert_task(&self, task: &Task) -> Result<()>;
+
+    /// Overwrites a task and its tags, keeping its stored notes. Returns
+    /// `false` if it doesn't exist.
+    async fn update_task(&self, task: &Task) -> Result<bool>;
+
+    /// Adds a note to the end of a task's notes. Notes are only ever
+    /// added, never rewritten. Fails if the task doesn't exist.
+    async fn add_note(&self, task_id: &str, note: &Note) -> Result<()>;
+
+    /// Deletes the tasks with the given IDs, returning how many existed.
+    async fn delete_tasks(&self, ids: &[String]) -> Result<usize>;
+
+    /// Returns the project with the given ID.
+    async fn get_project(&self, id: &str) -> Result<Option<Project>>;
+
+    /// Returns all projects, oldest first.
+    async fn get_all_projects(&self) -> Result<Vec<Project>>;
+
+    /// Adds a new project. Fails if the ID is taken.
+    async fn insert_project(&self, project: &Project) -> Result<()>;
+
+    /// Overwrites a project. Returns `false` if it doesn't exist.
+    async fn update_project(&self, project: &Project) -> Result<bool>;
+
+    /// Deletes the projects with the given IDs, returning how many existed.
+    async fn delete_projects(&self, ids: &[String]) -> Result<usize>;
+
+    /// Returns all tags with the number of tasks using them, by name.
+    async fn get_tags_with_counts(&self) -> Result<Vec<(Tag, usize)>>;
+
+    /// Creates a tag, returning its ID. Fails if the name is taken.
+    async fn insert_tag(&self, name: &str) -> Result<String>;
+
+    /// Deletes a tag and removes it from its tasks. Returns `false` if it
+    /// doesn't exist.
+    async fn delete_tag(&self, id: &str) -> Result<bool>;
+
+    /// Adds the tag `name` to a task, creating the tag if needed.
+    async fn add_tag_to_task(&self, task_id: &str, name: &str) -> Result<()>;
+
+    /// Removes a tag from a task. Returns `false` if the task didn't have it.
+    async fn remove_tag_from_task(&self, task_id: &str, tag_id: &str) -> Result<bool>;
+
+    /// Returns the items changed outside Ratado since the last call, e.g.
+    /// by editing the store's files by hand. Stores only Ratado can write
+    /// to return nothing.
+    async fn external_changes(&self) -> Result<ChangeSet>;
+
+    /// Holds writes back until [`Store::commit`], while reads see them.
+    /// Stores kept in the SQLite database do nothing, since [`Database`]'s
+    /// transaction covers them already.
+    ///
+    /// [`Database`]: crate::storage::Database
+    async fn begin(&self) {}
+
+    /// Applies the writes held back since [`Store::begin`].
+    async fn commit(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Drops the writes held back since [`Store::begin`].
+    async fn rollback(&self) {}
+}
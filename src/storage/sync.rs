@@ -0,0 +1,387 @@
+//! Sync bookkeeping: field clocks, tombstones and peers.
+//!
+//! [`crate::sync`] merges databases field by field, keeping whichever side
+//! changed a field last. For that, writes record when each field of a task
+//! or project changed ([`FieldClocks`]), and deletes leave a [`Tombstone`]
+//! so the deletion reaches other databases instead of the item being copied
+//! back. The tables are bookkeeping: writing to them doesn't count as an
+//! external change.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use turso::Value;
+
+use crate::models::{Project, Task};
+use crate::storage::{Database, Result, StorageError};
+
+/// When each field of a task or project last changed, by field name.
+///
+/// Fields missing from the map haven't changed since the item was created.
+pub type FieldClocks = BTreeMap<String, DateTime<Utc>>;
+
+/// The kind of item a [`Tombstone`] or clock belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    /// A task
+    Task,
+    /// A project
+    Project,
+}
+
+impl ItemKind {
+    /// Name stored in the database and shown in sync reports.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ItemKind::Task => "task",
+            ItemKind::Project => "project",
+        }
+    }
+}
+
+/// Record of a deleted task or project.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tombstone {
+    /// ID of the deleted item
+    pub id: String,
+    /// Whether it was a task or a project
+    pub kind: ItemKind,
+    /// When it was deleted
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl Database {
+    /// Records that `fields` of the item `id` changed just now.
+    pub(crate) async fn record_field_changes(&self, id: &str, fields: &[String]) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        for field in fields {
+            self.execute(
+                "INSERT OR REPLACE INTO _sync_clocks (id, field, changed_at) VALUES (?1, ?2, ?3)",
+                [
+                    Value::Text(id.to_string()),
+                    Value::Text(field.clone()),
+                    Value::Text(now.clone()),
+                ],
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Leaves tombstones for the items of `kind` with the given IDs, which
+    /// are about to be deleted, and drops their field clocks.
+    pub(crate) async fn record_deletions(&self, kind: ItemKind, ids: &[String]) -> Result<()> {
+        let deleted_at = Utc::now().to_rfc3339();
+        for id in ids {
+            self.execute("DELETE FROM _sync_clocks WHERE id = ?1", [id.as_str()])
+                .await?;
+            self.execute(
+                "INSERT OR REPLACE INTO _sync_tombstones (id, kind, deleted_at) VALUES (?1, ?2, ?3)",
+                [
+                    Value::Text(id.clone()),
+                    Value::Text(kind.as_str().to_string()),
+                    Value::Text(deleted_at.clone()),
+                ],
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the field clocks of every task and project, by item ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn field_clocks(&self) -> Result<HashMap<String, FieldClocks>> {
+        let mut rows = self
+            .query("SELECT id, field, changed_at FROM _sync_clocks", ())
+            .await?;
+
+        let mut clocks: HashMap<String, FieldClocks> = HashMap::new();
+        while let Some(row) = rows.next().await? {
+            let id = value_to_string(row.get_value(0)?)?;
+            let field = value_to_string(row.get_value(1)?)?;
+            let changed_at = value_to_datetime(row.get_value(2)?)?;
+            clocks.entry(id).or_default().insert(field, changed_at);
+        }
+        Ok(clocks)
+    }
+
+    /// Returns the tombstones of all deleted tasks and projects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn tombstones(&self) -> Result<Vec<Tombstone>> {
+        let mut rows = self
+            .query("SELECT id, kind, deleted_at FROM _sync_tombstones", ())
+            .await?;
+
+        let mut tombstones = Vec::new();
+        while let Some(row) = rows.next().await? {
+            let id = value_to_string(row.get_value(0)?)?;
+            let kind = match value_to_string(row.get_value(1)?)?.as_str() {
+                "project" => ItemKind::Project,
+                _ => ItemKind::Task,
+            };
+            tombstones.push(Tombstone {
+                id,
+                kind,
+                deleted_at: value_to_datetime(row.get_value(2)?)?,
+            });
+        }
+        Ok(tombstones)
+    }
+
+    /// Stores a task received from another database, with its field clocks,
+    /// replacing any local version. Hooks and webhooks don't run.
+    pub(crate) async fn put_synced_task(&self, task: &Task, clocks: &FieldClocks) -> Result<()> {
+        if !self.write_task(task).await? {
+            self.insert_task(task).await?;
+        }
+        self.put_clocks(&task.id, clocks).await
+    }
+
+    /// Stores a project received from another database, with its field
+    /// clocks, replacing any local version.
+    pub(crate) async fn put_synced_project(&self, project: &Project, clocks: &FieldClocks) -> Result<()> {
+        // Updated in place: deleting it would detach its tasks
+        if !self.store.update_project(project).await? {
+            self.store.insert_project(project).await?;
+        }
+        self.put_clocks(&project.id, clocks).await
+    }
+
+    /// Replaces the field clocks of an item and clears its tombstone.
+    async fn put_clocks(&self, id: &str, clocks: &FieldClocks) -> Result<()> {
+        self.execute("DELETE FROM _sync_clocks WHERE id = ?1", [id])
+            .await?;
+        for (field, changed_at) in clocks {
+            self.execute(
+                "INSERT INTO _sync_clocks (id, field, changed_at) VALUES (?1, ?2, ?3)",
+                [
+                    Value::Text(id.to_string()),
+                    Value::Text(field.clone()),
+                    Value::Text(changed_at.to_rfc3339()),
+                ],
+            )
+            .await?;
+        }
+        self.execute("DELETE FROM _sync_tombstones WHERE id = ?1", [id])
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes the item a tombstone from another database refers to, if it
+    /// exists, and keeps the tombstone. Hooks and webhooks don't run.
+    pub(crate) async fn apply_tombstone(&self, tombstone: &Tombstone) -> Result<()> {
+        let ids = [tombstone.id.clone()];
+        match tombstone.kind {
+            ItemKind::Task => {
+                self.store.delete_tasks(&ids).await?;
+                self.cleanup_orphaned_tags().await?;
+            }
+            ItemKind::Project => {
+                self.store.delete_projects(&ids).await?;
+            }
+        }
+        self.execute("DELETE FROM _sync_clocks WHERE id = ?1", [tombstone.id.as_str()])
+            .await?;
+        self.execute(
+            "INSERT OR REPLACE INTO _sync_tombstones (id, kind, deleted_at) VALUES (?1, ?2, ?3)",
+            [
+                Value::Text(tombstone.id.clone()),
+                Value::Text(tombstone.kind.as_str().to_string()),
+                Value::Text(tombstone.deleted_at.to_rfc3339()),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the ID identifying this database to the ones it syncs with,
+    /// generating it on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ID can't be read or stored.
+    pub async fn replica_id(&self) -> Result<String> {
+        if let Some(Value::Text(id)) = self
+            .query_scalar("SELECT value FROM _app_meta WHERE key = 'replica_id'", ())
+            .await?
+        {
+            return Ok(id);
+        }
+        let id = uuid::Uuid::now_v7().to_string();
+        self.execute(
+            "INSERT INTO _app_meta (key, value, updated_at) VALUES ('replica_id', ?1, datetime('now'))",
+            [id.as_str()],
+        )
+        .await?;
+        Ok(id)
+    }
+
+    /// Returns when this database last synced with the database `replica_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn last_synced_at(&self, replica_id: &str) -> Result<Option<DateTime<Utc>>> {
+        match self
+            .query_scalar(
+                "SELECT last_synced_at FROM _sync_peers WHERE replica_id = ?1",
+                [replica_id],
+            )
+            .await?
+        {
+            Some(value) => Ok(Some(value_to_datetime(value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Records that this database synced with the database `replica_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails.
+    pub async fn set_last_synced_at(&self, replica_id: &str, at: DateTime<Utc>) -> Result<()> {
+        self.execute(
+            "INSERT OR REPLACE INTO _sync_peers (replica_id, last_synced_at) VALUES (?1, ?2)",
+            [replica_id.to_string(), at.to_rfc3339()],
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+fn value_to_string(value: Value) -> Result<String> {
+    match value {
+        Value::Text(s) => Ok(s),
+        _ => Err(StorageError::Conversion(format!(
+            "Expected text, got {:?}",
+            value
+        ))),
+    }
+}
+
+fn value_to_datetime(value: Value) -> Result<DateTime<Utc>> {
+    let s = value_to_string(value)?;
+    DateTime::parse_from_rfc3339(&s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| StorageError::Conversion(format!("Invalid datetime '{}': {}", s, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::run_migrations;
+
+    async fn setup_db() -> Database {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_updates_record_changed_fields() {
+        let db = setup_db().await;
+        let mut task = Task::new("Task");
+        task.tags = vec!["work".to_string()];
+        db.insert_task(&task).await.unwrap();
+        assert!(db.field_clocks().await.unwrap().is_empty());
+
+        task.title = "Renamed".to_string();
+        task.tags = vec!["home".to_string()];
+        db.update_task(&task).await.unwrap();
+
+        let clocks = db.field_clocks().await.unwrap();
+        let fields: Vec<&str> = clocks[&task.id].keys().map(String::as_str).collect();
+        assert_eq!(fields, ["tag:home", "tag:work", "title"]);
+    }
+
+    #[tokio::test]
+    async fn test_project_updates_record_changed_fields() {
+        let db = setup_db().await;
+        let mut project = Project::new("Work");
+        db.insert_project(&project).await.unwrap();
+        project.icon = "💼".to_string();
+        db.update_project(&project).await.unwrap();
+
+        let clocks = db.field_clocks().await.unwrap();
+        let fields: Vec<&str> = clocks[&project.id].keys().map(String::as_str).collect();
+        assert_eq!(fields, ["icon"]);
+    }
+
+    #[tokio::test]
+    async fn test_moving_and_untagging_record_changes() {
+        let db = setup_db().await;
+        let project = Project::new("Work");
+        db.insert_project(&project).await.unwrap();
+        let mut task = Task::new("Task");
+        task.project_id = Some(project.id.clone());
+        task.tags = vec!["urgent".to_string()];
+        db.insert_task(&task).await.unwrap();
+
+        db.move_tasks_to_inbox(&project.id).await.unwrap();
+        let tag = db.get_tag_by_name("urgent").await.unwrap().unwrap();
+        db.delete_tag(&tag.id).await.unwrap();
+
+        let clocks = db.field_clocks().await.unwrap();
+        let fields: Vec<&str> = clocks[&task.id].keys().map(String::as_str).collect();
+        assert_eq!(fields, ["project_id", "tag:urgent"]);
+    }
+
+    #[tokio::test]
+    async fn test_deletes_leave_tombstones() {
+        let db = setup_db().await;
+        let project = Project::new("Work");
+        db.insert_project(&project).await.unwrap();
+        let mut task = Task::new("Task");
+        db.insert_task(&task).await.unwrap();
+        task.title = "Renamed".to_string();
+        db.update_task(&task).await.unwrap();
+
+        db.delete_task(&task.id).await.unwrap();
+        db.delete_project(&project.id).await.unwrap();
+
+        let mut tombstones = db.tombstones().await.unwrap();
+        tombstones.sort_by_key(|t| t.kind.as_str());
+        assert_eq!(tombstones.len(), 2);
+        assert_eq!((tombstones[0].id.as_str(), tombstones[0].kind), (project.id.as_str(), ItemKind::Project));
+        assert_eq!((tombstones[1].id.as_str(), tombstones[1].kind), (task.id.as_str(), ItemKind::Task));
+        assert!(db.field_clocks().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_synced_task_replaces_tombstone() {
+        let db = setup_db().await;
+        let task = Task::new("Task");
+        db.insert_task(&task).await.unwrap();
+        db.delete_task(&task.id).await.unwrap();
+
+        let clocks = FieldClocks::from([("title".to_string(), Utc::now())]);
+        db.put_synced_task(&task, &clocks).await.unwrap();
+
+        assert!(db.tombstones().await.unwrap().is_empty());
+        assert_eq!(db.field_clocks().await.unwrap()[&task.id], clocks);
+        assert!(db.get_task(&task.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_replica_id_is_stable() {
+        let db = setup_db().await;
+        let id = db.replica_id().await.unwrap();
+        assert_eq!(db.replica_id().await.unwrap(), id);
+        assert_ne!(setup_db().await.replica_id().await.unwrap(), id);
+    }
+
+    #[tokio::test]
+    async fn test_last_synced_at() {
+        let db = setup_db().await;
+        assert_eq!(db.last_synced_at("peer").await.unwrap(), None);
+        let now = Utc::now();
+        db.set_last_synced_at("peer", now).await.unwrap();
+        assert_eq!(db.last_synced_at("peer").await.unwrap(), Some(now));
+    }
+}
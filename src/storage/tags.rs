@@ -1,17 +1,20 @@
 //! Tag repository for CRUD operations.
 //!
 //! This module provides methods for managing tags and their associations
-//! with tasks. Tags are stored by name and linked to tasks via a junction table.
+//! with tasks. Tags are identified by name; the
+//! [`Store`](crate::storage::Store) decides how they are linked to tasks.
 
-use turso::Value;
-use uuid::Uuid;
+use std::collections::HashMap;
 
-use crate::storage::{Database, Result, StorageError};
+use chrono::Utc;
+
+use crate::models::{Filter, SortOrder, Task};
+use crate::storage::{Database, Result};
 
 /// A tag for categorizing tasks.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Tag {
-    /// Unique identifier
+    /// Unique identifier (the name itself in plaintext stores)
     pub id: String,
     /// Tag name (unique)
     pub name: String,
@@ -44,13 +47,7 @@ impl Database {
     /// # }
     /// ```
     pub async fn insert_tag(&self, name: &str) -> Result<String> {
-        let id = Uuid::now_v7().to_string();
-        self.execute(
-            "INSERT INTO tags (id, name) VALUES (?1, ?2)",
-            [Value::Text(id.clone()), Value::Text(name.to_string())],
-        )
-        .await?;
-        Ok(id)
+        self.store.insert_tag(name).await
     }
 
     /// Gets an existing tag by name, or creates it if it doesn't exist.
@@ -86,14 +83,7 @@ impl Database {
     ///
     /// The tag if found, or `None` if no tag exists with that name.
     pub async fn get_tag_by_name(&self, name: &str) -> Result<Option<Tag>> {
-        let row = self
-            .query_one("SELECT id, name FROM tags WHERE name = ?1", [name])
-            .await?;
-
-        match row {
-            Some(row) => Ok(Some(row_to_tag(&row)?)),
-            None => Ok(None),
-        }
+        Ok(self.get_all_tags().await?.into_iter().find(|t| t.name == name))
     }
 
     /// Retrieves a tag by its ID.
@@ -106,14 +96,7 @@ impl Database {
     ///
     /// The tag if found, or `None` if no tag exists with that ID.
     pub async fn get_tag(&self, id: &str) -> Result<Option<Tag>> {
-        let row = self
-            .query_one("SELECT id, name FROM tags WHERE id = ?1", [id])
-            .await?;
-
-        match row {
-            Some(row) => Ok(Some(row_to_tag(&row)?)),
-            None => Ok(None),
-        }
+        Ok(self.get_all_tags().await?.into_iter().find(|t| t.id == id))
     }
 
     /// Retrieves all tags from the database.
@@ -122,21 +105,15 @@ impl Database {
     ///
     /// A vector of all tags, ordered alphabetically by name.
     pub async fn get_all_tags(&self) -> Result<Vec<Tag>> {
-        let mut rows = self
-            .query("SELECT id, name FROM tags ORDER BY name ASC", ())
-            .await?;
-
-        let mut tags = Vec::new();
-        while let Some(row) = rows.next().await? {
-            tags.push(row_to_tag(&row)?);
-        }
-
-        Ok(tags)
+        let tags = self.get_tags_with_counts().await?;
+        Ok(tags.into_iter().map(|(tag, _)| tag).collect())
     }
 
-    /// Deletes a tag by its ID.
+    /// Deletes a tag by its ID, removing it from every task first.
     ///
-    /// This also removes the tag from all tasks (via CASCADE).
+    /// The tasks are saved with [`Database::modify_tasks`], so their
+    /// `on-modify` hooks run, webhooks are queued, `updated_at` is bumped and
+    /// the change is recorded for [`crate::sync`] and in the tasks' history.
     ///
     /// # Arguments
     ///
@@ -145,11 +122,31 @@ impl Database {
     /// # Returns
     ///
     /// `true` if a tag was deleted, `false` if no tag existed with that ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Hook`](crate::storage::StorageError::Hook) if
+    /// a hook rejects a change, in which case no task is changed and the tag
+    /// is kept, or an error if saving fails.
     pub async fn delete_tag(&self, id: &str) -> Result<bool> {
-        let rows_affected = self
-            .execute("DELETE FROM tags WHERE id = ?1", [id])
-            .await?;
-        Ok(rows_affected > 0)
+        let Some(tag) = self.get_tag(id).await? else {
+            return Ok(false);
+        };
+        let now = Utc::now();
+        let untagged: Vec<Task> = self
+            .query_tasks(&Filter::ByTag(tag.name.clone()), &SortOrder::default())
+            .await?
+            .into_iter()
+            .map(|mut task| {
+                task.tags.retain(|t| t != &tag.name);
+                task.updated_at = now;
+                task
+            })
+            .collect();
+        self.modify_tasks(untagged).await?;
+        // Usually gone already, cleaned up once no task had it
+        self.store.delete_tag(id).await?;
+        Ok(true)
     }
 
     /// Adds a tag to a task.
@@ -166,16 +163,7 @@ impl Database {
     ///
     /// Returns an error if the operation fails.
     pub async fn add_tag_to_task(&self, task_id: &str, tag_name: &str) -> Result<()> {
-        let tag_id = self.get_or_create_tag(tag_name).await?;
-
-        // Use INSERT OR IGNORE to handle duplicates gracefully
-        self.execute(
-            "INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)",
-            [Value::Text(task_id.to_string()), Value::Text(tag_id)],
-        )
-        .await?;
-
-        Ok(())
+        self.store.add_tag_to_task(task_id, tag_name).await
     }
 
     /// Removes a tag from a task.
@@ -189,13 +177,7 @@ impl Database {
     ///
     /// `true` if the tag was removed, `false` if the task didn't have that tag.
     pub async fn remove_tag_from_task(&self, task_id: &str, tag_id: &str) -> Result<bool> {
-        let rows_affected = self
-            .execute(
-                "DELETE FROM task_tags WHERE task_id = ?1 AND tag_id = ?2",
-                [task_id, tag_id],
-            )
-            .await?;
-        Ok(rows_affected > 0)
+        self.store.remove_tag_from_task(task_id, tag_id).await
     }
 
     /// Gets all tag names for a task.
@@ -208,29 +190,19 @@ impl Database {
     ///
     /// A vector of tag names associated with the task.
     pub async fn get_tags_for_task(&self, task_id: &str) -> Result<Vec<String>> {
-        let mut rows = self
-            .query(
-                "SELECT t.name FROM tags t
-                 JOIN task_tags tt ON t.id = tt.tag_id
-                 WHERE tt.task_id = ?1
-                 ORDER BY t.name ASC",
-                [task_id],
-            )
-            .await?;
-
-        let mut tags = Vec::new();
-        while let Some(row) = rows.next().await? {
-            let name = value_to_string(row.get_value(0)?)?;
-            tags.push(name);
-        }
-
+        let mut tags = self
+            .get_task(task_id)
+            .await?
+            .map(|t| t.tags)
+            .unwrap_or_default();
+        tags.sort();
         Ok(tags)
     }
 
     /// Gets all task-tag associations in a single query.
     ///
-    /// This is an optimized alternative to calling [`get_tags_for_task`] per task,
-    /// reducing N+1 queries to a single JOIN query.
+    /// Loads all tasks once instead of calling [`Database::get_tags_for_task`]
+    /// per task. Tasks without tags are left out.
     ///
     /// # Returns
     ///
@@ -239,24 +211,13 @@ impl Database {
     /// # Errors
     ///
     /// Returns an error if the query fails.
-    pub async fn get_all_task_tags(&self) -> Result<std::collections::HashMap<String, Vec<String>>> {
-        let mut rows = self
-            .query(
-                "SELECT tt.task_id, t.name FROM task_tags tt
-                 JOIN tags t ON t.id = tt.tag_id
-                 ORDER BY t.name ASC",
-                (),
-            )
-            .await?;
-
-        let mut map: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
-        while let Some(row) = rows.next().await? {
-            let task_id = value_to_string(row.get_value(0)?)?;
-            let tag_name = value_to_string(row.get_value(1)?)?;
-            map.entry(task_id).or_default().push(tag_name);
-        }
-
-        Ok(map)
+    pub async fn get_all_task_tags(&self) -> Result<HashMap<String, Vec<String>>> {
+        let tasks = self.get_all_tasks().await?;
+        Ok(tasks
+            .into_iter()
+            .filter(|t| !t.tags.is_empty())
+            .map(|t| (t.id, t.tags))
+            .collect())
     }
 
     /// Gets the count of tasks with a specific tag.
@@ -269,17 +230,11 @@ impl Database {
     ///
     /// The number of tasks with this tag.
     pub async fn get_task_count_by_tag(&self, tag_id: &str) -> Result<usize> {
-        let value = self
-            .query_scalar(
-                "SELECT COUNT(*) FROM task_tags WHERE tag_id = ?1",
-                [tag_id],
-            )
-            .await?;
-
-        match value {
-            Some(Value::Integer(count)) => Ok(count as usize),
-            _ => Ok(0),
-        }
+        let tags = self.get_tags_with_counts().await?;
+        Ok(tags
+            .into_iter()
+            .find(|(tag, _)| tag.id == tag_id)
+            .map_or(0, |(_, count)| count))
     }
 
     /// Deletes all orphaned tags (tags with no associated tasks).
@@ -295,30 +250,12 @@ impl Database {
     ///
     /// Returns an error if the delete operation fails.
     pub async fn cleanup_orphaned_tags(&self) -> Result<usize> {
-        // Find orphaned tags using LEFT JOIN (tags with no task associations)
-        let mut rows = self
-            .query(
-                "SELECT t.id FROM tags t
-                 LEFT JOIN task_tags tt ON t.id = tt.tag_id
-                 WHERE tt.task_id IS NULL",
-                (),
-            )
-            .await?;
-
-        let mut orphaned_ids = Vec::new();
-        while let Some(row) = rows.next().await? {
-            let id = value_to_string(row.get_value(0)?)?;
-            orphaned_ids.push(id);
-        }
-
-        // Delete each orphaned tag
         let mut deleted = 0;
-        for id in orphaned_ids {
-            if self.delete_tag(&id).await? {
+        for (tag, count) in self.get_tags_with_counts().await? {
+            if count == 0 && self.store.delete_tag(&tag.id).await? {
                 deleted += 1;
             }
         }
-
         Ok(deleted)
     }
 
@@ -328,46 +265,7 @@ impl Database {
     ///
     /// A vector of (Tag, task_count) tuples.
     pub async fn get_tags_with_counts(&self) -> Result<Vec<(Tag, usize)>> {
-        let mut rows = self
-            .query(
-                "SELECT t.id, t.name, COUNT(tt.task_id) as count
-                 FROM tags t
-                 LEFT JOIN task_tags tt ON t.id = tt.tag_id
-                 GROUP BY t.id, t.name
-                 ORDER BY t.name ASC",
-                (),
-            )
-            .await?;
-
-        let mut result = Vec::new();
-        while let Some(row) = rows.next().await? {
-            let id = value_to_string(row.get_value(0)?)?;
-            let name = value_to_string(row.get_value(1)?)?;
-            let count = match row.get_value(2)? {
-                Value::Integer(c) => c as usize,
-                _ => 0,
-            };
-            result.push((Tag { id, name }, count));
-        }
-
-        Ok(result)
-    }
-}
-
-/// Converts a database row to a Tag.
-fn row_to_tag(row: &turso::Row) -> Result<Tag> {
-    let id = value_to_string(row.get_value(0)?)?;
-    let name = value_to_string(row.get_value(1)?)?;
-    Ok(Tag { id, name })
-}
-
-fn value_to_string(value: Value) -> Result<String> {
-    match value {
-        Value::Text(s) => Ok(s),
-        _ => Err(StorageError::Conversion(format!(
-            "Expected text, got {:?}",
-            value
-        ))),
+        self.store.get_tags_with_counts().await
     }
 }
 
@@ -618,7 +516,7 @@ mod tests {
         assert_eq!(tags2[0], "personal");
 
         // task3 should not be in the map
-        assert!(map.get(&task3.id).is_none());
+        assert!(!map.contains_key(&task3.id));
     }
 
     #[tokio::test]
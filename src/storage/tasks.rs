@@ -1,13 +1,17 @@
 //! Task repository for CRUD operations.
 //!
 //! This module provides methods for creating, reading, updating, and deleting
-//! tasks in the database. All datetime values are stored as ISO8601 strings.
+//! tasks. The [`Store`](crate::storage::Store) keeps them; these methods add
+//! hooks, webhooks and sync bookkeeping on top.
 
-use chrono::{DateTime, Utc};
-use turso::Value;
+use serde_json::json;
 
-use crate::models::{Filter, Priority, SortOrder, Task, TaskStatus};
+use crate::hooks::HookEvent;
+use crate::models::{Filter, SortOrder, Task, TaskStatus};
+use crate::storage::sync::ItemKind;
 use crate::storage::{Database, Result, StorageError};
+use crate::sync::{changed_fields, task_fields};
+use crate::webhooks::WebhookEvent;
 
 impl Database {
     /// Inserts a new task into the database.
@@ -34,31 +38,7 @@ impl Database {
     /// # }
     /// ```
     pub async fn insert_task(&self, task: &Task) -> Result<()> {
-        self.execute(
-            "INSERT INTO tasks (id, title, description, due_date, priority,
-             status, project_id, created_at, updated_at, completed_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
-            [
-                Value::Text(task.id.clone()),
-                Value::Text(task.title.clone()),
-                option_to_value(&task.description),
-                task.due_date.map(|d| Value::Text(d.to_rfc3339())).unwrap_or(Value::Null),
-                Value::Text(priority_to_str(task.priority).to_string()),
-                Value::Text(status_to_str(task.status).to_string()),
-                option_to_value(&task.project_id),
-                Value::Text(task.created_at.to_rfc3339()),
-                Value::Text(task.updated_at.to_rfc3339()),
-                task.completed_at.map(|d| Value::Text(d.to_rfc3339())).unwrap_or(Value::Null),
-            ],
-        )
-        .await?;
-
-        // Insert tags
-        for tag in &task.tags {
-            self.add_tag_to_task(&task.id, tag).await?;
-        }
-
-        Ok(())
+        self.store.insert_task(task).await
     }
 
     /// Retrieves a task by its ID.
@@ -75,61 +55,28 @@ impl Database {
     ///
     /// Returns an error if the query fails or data cannot be parsed.
     pub async fn get_task(&self, id: &str) -> Result<Option<Task>> {
-        let row = self
-            .query_one(
-                "SELECT id, title, description, due_date, priority, status,
-                 project_id, created_at, updated_at, completed_at
-                 FROM tasks WHERE id = ?1",
-                [id],
-            )
-            .await?;
-
-        match row {
-            Some(row) => {
-                let mut task = row_to_task(&row)?;
-                task.tags = self.get_tags_for_task(&task.id).await?;
-                Ok(Some(task))
-            }
-            None => Ok(None),
-        }
+        self.store.get_task(id).await
     }
 
     /// Retrieves all tasks from the database.
     ///
     /// # Returns
     ///
-    /// A vector of all tasks, including their tags.
+    /// A vector of all tasks, including their tags, newest first.
     ///
     /// # Errors
     ///
     /// Returns an error if the query fails.
     pub async fn get_all_tasks(&self) -> Result<Vec<Task>> {
-        // Load all task-tag associations in one query (fixes N+1)
-        let tag_map = self.get_all_task_tags().await?;
-
-        let mut rows = self
-            .query(
-                "SELECT id, title, description, due_date, priority, status,
-                 project_id, created_at, updated_at, completed_at
-                 FROM tasks ORDER BY created_at DESC",
-                (),
-            )
-            .await?;
-
-        let mut tasks = Vec::new();
-        while let Some(row) = rows.next().await? {
-            let mut task = row_to_task(&row)?;
-            task.tags = tag_map.get(&task.id).cloned().unwrap_or_default();
-            tasks.push(task);
-        }
-
-        Ok(tasks)
+        self.store.get_all_tasks().await
     }
 
     /// Updates an existing task.
     ///
-    /// Also cleans up any orphaned tags (tags no longer associated with any tasks)
-    /// after updating the task's tags.
+    /// Records which fields changed, for [`crate::sync`] and the task's
+    /// history ([`Database::task_history`]). Also cleans up any
+    /// orphaned tags (tags no longer associated with any tasks) after
+    /// updating the task's tags.
     ///
     /// # Arguments
     ///
@@ -139,40 +86,167 @@ impl Database {
     ///
     /// Returns an error if the task doesn't exist or the update fails.
     pub async fn update_task(&self, task: &Task) -> Result<()> {
-        let rows_affected = self
-            .execute(
-                "UPDATE tasks SET
-                 title = ?1, description = ?2, due_date = ?3, priority = ?4,
-                 status = ?5, project_id = ?6, updated_at = ?7, completed_at = ?8
-                 WHERE id = ?9",
-                [
-                    Value::Text(task.title.clone()),
-                    option_to_value(&task.description),
-                    task.due_date.map(|d| Value::Text(d.to_rfc3339())).unwrap_or(Value::Null),
-                    Value::Text(priority_to_str(task.priority).to_string()),
-                    Value::Text(status_to_str(task.status).to_string()),
-                    option_to_value(&task.project_id),
-                    Value::Text(task.updated_at.to_rfc3339()),
-                    task.completed_at.map(|d| Value::Text(d.to_rfc3339())).unwrap_or(Value::Null),
-                    Value::Text(task.id.clone()),
-                ],
-            )
+        let original = self
+            .get_task(&task.id)
+            .await?
+            .ok_or_else(|| StorageError::NotFound(format!("Task not found: {}", task.id)))?;
+        self.write_task(task).await?;
+        self.record_field_changes(&task.id, &changed_fields(&task_fields(&original), &task_fields(task)))
             .await?;
+        self.record_task_events(Some(&original), Some(task)).await?;
+        Ok(())
+    }
 
-        if rows_affected == 0 {
-            return Err(StorageError::NotFound(format!("Task not found: {}", task.id)));
+    /// Overwrites a task and its tags and adds its new notes, then cleans
+    /// up orphaned tags.
+    ///
+    /// Returns `false` if the task doesn't exist.
+    pub(crate) async fn write_task(&self, task: &Task) -> Result<bool> {
+        let Some(stored) = self.store.get_task(&task.id).await? else {
+            return Ok(false);
+        };
+        self.overwrite_task(&stored, task).await?;
+        // Clean up any tags that are no longer associated with any tasks
+        self.cleanup_orphaned_tags().await?;
+        Ok(true)
+    }
+
+    /// Overwrites the task `stored` with `task`, and adds the notes of
+    /// `task` that `stored` doesn't have. Stored notes are never rewritten.
+    async fn overwrite_task(&self, stored: &Task, task: &Task) -> Result<()> {
+        self.store.update_task(task).await?;
+        for note in task.notes.iter().filter(|n| !stored.notes.iter().any(|s| s.id == n.id)) {
+            self.store.add_note(&task.id, note).await?;
         }
+        Ok(())
+    }
 
-        // Update tags: remove all existing, add current
-        self.execute("DELETE FROM task_tags WHERE task_id = ?1", [task.id.as_str()])
+    /// Runs the `on-add` hooks on a new task, then inserts it at the top
+    /// or bottom of the manual order, as configured.
+    ///
+    /// Use this for tasks the user creates; [`Database::insert_task`] stores
+    /// a task as is.
+    ///
+    /// # Returns
+    ///
+    /// The task as stored, including any changes made by hooks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Hook`] if a hook rejects the task, or an
+    /// error if the insert fails.
+    pub async fn add_task(&self, mut task: Task) -> Result<Task> {
+        task.position = self.new_tasks.position(task.created_at);
+        let task = self.hooks.run(HookEvent::Add, None, task).await?;
+        self.insert_task(&task).await?;
+        self.record_task_events(None, Some(&task)).await?;
+        self.queue_webhooks(WebhookEvent::TaskCreated, json!(task))
             .await?;
-        for tag in &task.tags {
-            self.add_tag_to_task(&task.id, tag).await?;
+        Ok(task)
+    }
+
+    /// Runs the `on-modify` hooks (or `on-complete`, if the change completes
+    /// the task) on a changed task, then saves it.
+    ///
+    /// Use this for changes the user makes; [`Database::update_task`] stores
+    /// a task as is.
+    ///
+    /// # Returns
+    ///
+    /// The task as stored, including any changes made by hooks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Hook`] if a hook rejects the change, or an
+    /// error if the task doesn't exist or the update fails.
+    pub async fn modify_task(&self, task: Task) -> Result<Task> {
+        if self.hooks.is_empty() && self.webhooks.is_empty() {
+            self.update_task(&task).await?;
+            return Ok(task);
         }
 
+        let original = self
+            .get_task(&task.id)
+            .await?
+            .ok_or_else(|| StorageError::NotFound(format!("Task not found: {}", task.id)))?;
+        let task = self
+            .hooks
+            .run(HookEvent::for_change(&original, &task), Some(&original), task)
+            .await?;
+        self.update_task(&task).await?;
+
+        let event = match HookEvent::for_change(&original, &task) {
+            HookEvent::Complete => WebhookEvent::TaskCompleted,
+            _ => WebhookEvent::TaskUpdated,
+        };
+        self.queue_webhooks(event, json!(task)).await?;
+        Ok(task)
+    }
+
+    /// Deletes the `selected` tasks, leaving tombstones.
+    ///
+    /// Also cleans up any orphaned tags. Returns the tasks deleted.
+    async fn delete_tasks_where(&self, selected: Selection<'_>) -> Result<Vec<Task>> {
+        self.in_transaction(async || {
+            let tasks = self.select_tasks(&selected).await?;
+            self.delete_listed_tasks(&tasks).await?;
+            Ok(tasks)
+        })
+        .await
+    }
+
+    /// Runs the `on-delete` hooks of the `selected` tasks, then
+    /// deletes them and queues `task.deleted` webhooks in one transaction.
+    ///
+    /// Like [`Database::modify_tasks`], the hooks run before the transaction
+    /// opens, so a slow hook doesn't hold the database, and one rejection
+    /// stops the delete before anything is deleted. Returns the number of
+    /// tasks deleted.
+    async fn remove_tasks_where(&self, selected: Selection<'_>) -> Result<usize> {
+        let tasks = self.select_tasks(&selected).await?;
+        for task in &tasks {
+            self.hooks.run(HookEvent::Delete, None, task.clone()).await?;
+        }
+
+        self.in_transaction(async || {
+            self.delete_listed_tasks(&tasks).await?;
+            for task in &tasks {
+                self.queue_webhooks(WebhookEvent::TaskDeleted, json!(task))
+                    .await?;
+            }
+            Ok(tasks.len())
+        })
+        .await
+    }
+
+    /// Returns the `selected` tasks that exist.
+    async fn select_tasks(&self, selected: &Selection<'_>) -> Result<Vec<Task>> {
+        match selected {
+            Selection::Ids(ids) => {
+                let mut tasks = Vec::with_capacity(ids.len());
+                for id in *ids {
+                    tasks.extend(self.store.get_task(id).await?);
+                }
+                Ok(tasks)
+            }
+            Selection::Matching(filter) => {
+                self.store.query_tasks(filter, &SortOrder::default()).await
+            }
+        }
+    }
+
+    /// Deletes `tasks`, leaving tombstones, and cleans up orphaned tags.
+    /// Must run in a transaction.
+    async fn delete_listed_tasks(&self, tasks: &[Task]) -> Result<()> {
+        let ids: Vec<String> = tasks.iter().map(|t| t.id.clone()).collect();
+        self.record_deletions(ItemKind::Task, &ids).await?;
+        self.store.delete_tasks(&ids).await?;
+
         // Clean up any tags that are no longer associated with any tasks
         self.cleanup_orphaned_tags().await?;
-
+        for task in tasks {
+            self.record_task_events(Some(task), None).await?;
+        }
         Ok(())
     }
 
@@ -192,15 +266,120 @@ impl Database {
     ///
     /// Returns an error if the delete fails.
     pub async fn delete_task(&self, id: &str) -> Result<bool> {
-        // Tags associations are deleted automatically via ON DELETE CASCADE
-        let rows_affected = self
-            .execute("DELETE FROM tasks WHERE id = ?1", [id])
-            .await?;
+        let ids = [id.to_string()];
+        Ok(!self.delete_tasks_where(Selection::Ids(&ids)).await?.is_empty())
+    }
 
-        // Clean up any tags that are no longer associated with any tasks
-        self.cleanup_orphaned_tags().await?;
+    /// Runs the `on-delete` hooks on a task, then deletes it.
+    ///
+    /// Use this for tasks the user deletes; [`Database::delete_task`]
+    /// deletes a task without running hooks or queueing webhooks.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a task was deleted, `false` if no task existed with that ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Hook`] if a hook rejects the delete, or an
+    /// error if the delete fails.
+    pub async fn remove_task(&self, id: &str) -> Result<bool> {
+        let ids = [id.to_string()];
+        Ok(self.remove_tasks_where(Selection::Ids(&ids)).await? > 0)
+    }
+
+    /// Deletes the tasks with the given IDs, all or none.
+    ///
+    /// Every task's `on-delete` hooks run first, so one rejection stops the
+    /// whole delete. Also cleans up any orphaned tags.
+    ///
+    /// # Returns
+    ///
+    /// The number of tasks deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Hook`] if a hook rejects a delete, or an
+    /// error if the delete fails.
+    pub async fn remove_tasks(&self, ids: &[String]) -> Result<usize> {
+        self.remove_tasks_where(Selection::Ids(ids)).await
+    }
+
+    /// Saves several changed tasks in one transaction, all or none.
+    ///
+    /// Like [`Database::modify_task`], each task's hooks run first and may
+    /// change it; one rejection stops the whole change before anything is
+    /// saved.
+    ///
+    /// # Returns
+    ///
+    /// The tasks as stored, in the given order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Hook`] if a hook rejects a change, or an
+    /// error if a task doesn't exist or saving fails.
+    pub async fn modify_tasks(&self, tasks: Vec<Task>) -> Result<Vec<Task>> {
+        let mut changes = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let original = self
+                .get_task(&task.id)
+                .await?
+                .ok_or_else(|| StorageError::NotFound(format!("Task not found: {}", task.id)))?;
+            let task = self
+                .hooks
+                .run(HookEvent::for_change(&original, &task), Some(&original), task)
+                .await?;
+            changes.push((original, task));
+        }
+
+        self.in_transaction(async || {
+            for (original, task) in &changes {
+                self.overwrite_task(original, task).await?;
+                self.record_field_changes(&task.id, &changed_fields(&task_fields(original), &task_fields(task)))
+                    .await?;
+                self.record_task_events(Some(original), Some(task)).await?;
+                let event = match HookEvent::for_change(original, task) {
+                    HookEvent::Complete => WebhookEvent::TaskCompleted,
+                    _ => WebhookEvent::TaskUpdated,
+                };
+                self.queue_webhooks(event, json!(task)).await?;
+            }
+            // Clean up any tags that are no longer associated with any tasks
+            self.cleanup_orphaned_tags().await?;
+            Ok(())
+        })
+        .await?;
+        Ok(changes.into_iter().map(|(_, task)| task).collect())
+    }
 
-        Ok(rows_affected > 0)
+    /// Saves new manual order positions for tasks, in one transaction.
+    ///
+    /// Only the position of each task is written. Hooks and webhooks don't
+    /// run, since reordering doesn't change the tasks themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a task doesn't exist or saving fails.
+    pub async fn set_task_positions(&self, positions: &[(String, f64)]) -> Result<()> {
+        let mut tasks = Vec::with_capacity(positions.len());
+        for (id, position) in positions {
+            let mut task = self
+                .get_task(id)
+                .await?
+                .ok_or_else(|| StorageError::NotFound(format!("Task not found: {}", id)))?;
+            task.position = *position;
+            tasks.push(task);
+        }
+
+        self.in_transaction(async || {
+            for task in &tasks {
+                self.store.update_task(task).await?;
+                self.record_field_changes(&task.id, &["position".to_string()]).await?;
+            }
+            Ok(())
+        })
+        .await
     }
 
     /// Deletes all tasks belonging to a project.
@@ -219,18 +398,28 @@ impl Database {
     ///
     /// Returns an error if the delete fails.
     pub async fn delete_tasks_by_project(&self, project_id: &str) -> Result<usize> {
-        // Tags associations are deleted automatically via ON DELETE CASCADE
-        let rows_affected = self
-            .execute("DELETE FROM tasks WHERE project_id = ?1", [project_id])
+        let deleted = self
+            .delete_tasks_where(Selection::Matching(Filter::ByProject(project_id.to_string())))
             .await?;
+        Ok(deleted.len())
+    }
 
-        // Clean up any tags that are no longer associated with any tasks
-        self.cleanup_orphaned_tags().await?;
-
-        Ok(rows_affected as usize)
+    /// Like [`Database::delete_tasks_by_project`], but runs the tasks'
+    /// `on-delete` hooks first; one rejection stops the whole delete.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Hook`] if a hook rejects a delete, or an
+    /// error if the delete fails.
+    pub async fn remove_tasks_by_project(&self, project_id: &str) -> Result<usize> {
+        self.remove_tasks_where(Selection::Matching(Filter::ByProject(project_id.to_string())))
+            .await
     }
 
-    /// Moves all tasks from a project to Inbox.
+    /// Moves all tasks from a project to Inbox, all or none.
+    ///
+    /// Like [`Database::modify_tasks`], each task's `on-modify` hooks run
+    /// first and may change it; one rejection stops the whole move.
     ///
     /// # Arguments
     ///
@@ -242,15 +431,21 @@ impl Database {
     ///
     /// # Errors
     ///
-    /// Returns an error if the update fails.
+    /// Returns [`StorageError::Hook`] if a hook rejects a move, or an error
+    /// if the update fails.
     pub async fn move_tasks_to_inbox(&self, project_id: &str) -> Result<usize> {
-        let rows_affected = self
-            .execute(
-                "UPDATE tasks SET project_id = 'inbox', updated_at = ?1 WHERE project_id = ?2",
-                [Value::Text(chrono::Utc::now().to_rfc3339()), Value::Text(project_id.to_string())],
-            )
-            .await?;
-        Ok(rows_affected as usize)
+        let now = chrono::Utc::now();
+        let moved: Vec<Task> = self
+            .query_tasks(&Filter::ByProject(project_id.to_string()), &SortOrder::default())
+            .await?
+            .into_iter()
+            .map(|mut task| {
+                task.project_id = Some("inbox".to_string());
+                task.updated_at = now;
+                task
+            })
+            .collect();
+        Ok(self.modify_tasks(moved).await?.len())
     }
 
     /// Deletes all completed tasks from the database.
@@ -265,20 +460,27 @@ impl Database {
     ///
     /// Returns an error if the delete fails.
     pub async fn delete_completed_tasks(&self) -> Result<usize> {
-        // Tags associations are deleted automatically via ON DELETE CASCADE
-        let rows_affected = self
-            .execute("DELETE FROM tasks WHERE status = 'completed'", ())
+        let deleted = self
+            .delete_tasks_where(Selection::Matching(Filter::Completed))
             .await?;
+        Ok(deleted.len())
+    }
 
-        // Clean up any tags that are no longer associated with any tasks
-        self.cleanup_orphaned_tags().await?;
-
-        Ok(rows_affected as usize)
+    /// Like [`Database::delete_completed_tasks`], but runs the tasks'
+    /// `on-delete` hooks first; one rejection stops the whole delete.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StorageError::Hook`] if a hook rejects a delete, or an
+    /// error if the delete fails.
+    pub async fn remove_completed_tasks(&self) -> Result<usize> {
+        self.remove_tasks_where(Selection::Matching(Filter::Completed))
+            .await
     }
 
     /// Resets the database by deleting all tasks.
     ///
-    /// Also cleans up all tags.
+    /// Also cleans up all tags. No hooks run and no webhooks are queued.
     ///
     /// # Returns
     ///
@@ -288,15 +490,7 @@ impl Database {
     ///
     /// Returns an error if the delete fails.
     pub async fn delete_all_tasks(&self) -> Result<usize> {
-        // Tags associations are deleted automatically via ON DELETE CASCADE
-        let rows_affected = self
-            .execute("DELETE FROM tasks", ())
-            .await?;
-
-        // Clean up all orphaned tags
-        self.cleanup_orphaned_tags().await?;
-
-        Ok(rows_affected as usize)
+        Ok(self.delete_tasks_where(Selection::Matching(Filter::All)).await?.len())
     }
 
     /// Queries tasks with filtering and sorting.
@@ -314,42 +508,7 @@ impl Database {
     ///
     /// Returns an error if the query fails.
     pub async fn query_tasks(&self, filter: &Filter, sort: &SortOrder) -> Result<Vec<Task>> {
-        // Load all task-tag associations in one query (fixes N+1)
-        let tag_map = self.get_all_task_tags().await?;
-
-        // Build the WHERE clause based on filter
-        let (where_clause, params) = build_filter_clause(filter);
-        let order_clause = build_order_clause(sort);
-
-        let sql = format!(
-            "SELECT id, title, description, due_date, priority, status,
-             project_id, created_at, updated_at, completed_at
-             FROM tasks {} {}",
-            where_clause, order_clause
-        );
-
-        let mut rows = self.query(&sql, params).await?;
-
-        let mut tasks = Vec::new();
-        while let Some(row) = rows.next().await? {
-            let mut task = row_to_task(&row)?;
-            task.tags = tag_map.get(&task.id).cloned().unwrap_or_default();
-            tasks.push(task);
-        }
-
-        // Apply in-memory filters that can't be done in SQL
-        let tasks = match filter {
-            Filter::DueToday | Filter::DueThisWeek | Filter::Overdue => {
-                tasks.into_iter().filter(|t| filter.matches(t)).collect()
-            }
-            Filter::ByTag(tag) => tasks
-                .into_iter()
-                .filter(|t| t.tags.contains(tag))
-                .collect(),
-            _ => tasks,
-        };
-
-        Ok(tasks)
+        self.store.query_tasks(filter, sort).await
     }
 
     /// Gets the count of tasks by status.
@@ -358,230 +517,31 @@ impl Database {
     ///
     /// A tuple of (pending, in_progress, completed, archived) counts.
     pub async fn get_task_counts(&self) -> Result<(usize, usize, usize, usize)> {
-        let pending = self
-            .query_scalar(
-                "SELECT COUNT(*) FROM tasks WHERE status = 'pending'",
-                (),
-            )
-            .await?
-            .map(|v| value_to_i64(&v).unwrap_or(0) as usize)
-            .unwrap_or(0);
-
-        let in_progress = self
-            .query_scalar(
-                "SELECT COUNT(*) FROM tasks WHERE status = 'in_progress'",
-                (),
-            )
-            .await?
-            .map(|v| value_to_i64(&v).unwrap_or(0) as usize)
-            .unwrap_or(0);
-
-        let completed = self
-            .query_scalar(
-                "SELECT COUNT(*) FROM tasks WHERE status = 'completed'",
-                (),
-            )
-            .await?
-            .map(|v| value_to_i64(&v).unwrap_or(0) as usize)
-            .unwrap_or(0);
-
-        let archived = self
-            .query_scalar(
-                "SELECT COUNT(*) FROM tasks WHERE status = 'archived'",
-                (),
-            )
-            .await?
-            .map(|v| value_to_i64(&v).unwrap_or(0) as usize)
-            .unwrap_or(0);
-
-        Ok((pending, in_progress, completed, archived))
-    }
-}
-
-/// Converts a database row to a Task.
-fn row_to_task(row: &turso::Row) -> Result<Task> {
-    let id = value_to_string(row.get_value(0)?)?;
-    let title = value_to_string(row.get_value(1)?)?;
-    let description = value_to_option_string(row.get_value(2)?)?;
-    let due_date = value_to_option_datetime(row.get_value(3)?)?;
-    let priority = str_to_priority(&value_to_string(row.get_value(4)?)?);
-    let status = str_to_status(&value_to_string(row.get_value(5)?)?);
-    let project_id = value_to_option_string(row.get_value(6)?)?;
-    let created_at = value_to_datetime(row.get_value(7)?)?;
-    let updated_at = value_to_datetime(row.get_value(8)?)?;
-    let completed_at = value_to_option_datetime(row.get_value(9)?)?;
-
-    Ok(Task {
-        id,
-        title,
-        description,
-        due_date,
-        priority,
-        status,
-        project_id,
-        tags: Vec::new(), // Tags are loaded separately
-        created_at,
-        updated_at,
-        completed_at,
-    })
-}
-
-/// Builds a WHERE clause for the given filter.
-fn build_filter_clause(filter: &Filter) -> (String, Vec<Value>) {
-    match filter {
-        Filter::All => (String::new(), vec![]),
-        Filter::Pending => ("WHERE status = 'pending'".to_string(), vec![]),
-        Filter::InProgress => ("WHERE status = 'in_progress'".to_string(), vec![]),
-        Filter::Completed => ("WHERE status = 'completed'".to_string(), vec![]),
-        Filter::Archived => ("WHERE status = 'archived'".to_string(), vec![]),
-        Filter::ByProject(project_id) => (
-            "WHERE project_id = ?1".to_string(),
-            vec![Value::Text(project_id.clone())],
-        ),
-        Filter::ByPriority(priority) => (
-            "WHERE priority = ?1".to_string(),
-            vec![Value::Text(priority_to_str(*priority).to_string())],
-        ),
-        // These filters need in-memory filtering after loading
-        Filter::DueToday | Filter::DueThisWeek | Filter::Overdue => {
-            ("WHERE due_date IS NOT NULL AND status NOT IN ('completed', 'archived')".to_string(), vec![])
-        }
-        Filter::ByTag(_) => (String::new(), vec![]), // Filtered in-memory after loading tags
+        let counts = self.store.count_tasks_by_status().await?;
+        let count = |status: TaskStatus| counts.get(&status).copied().unwrap_or(0);
+        Ok((
+            count(TaskStatus::Pending),
+            count(TaskStatus::InProgress),
+            count(TaskStatus::Completed),
+            count(TaskStatus::Archived),
+        ))
     }
 }
 
-/// Builds an ORDER BY clause for the given sort order.
-fn build_order_clause(sort: &SortOrder) -> &'static str {
-    match sort {
-        SortOrder::DueDateAsc => "ORDER BY due_date ASC NULLS LAST",
-        SortOrder::DueDateDesc => "ORDER BY due_date DESC NULLS FIRST",
-        SortOrder::PriorityDesc => "ORDER BY CASE priority
-            WHEN 'urgent' THEN 0
-            WHEN 'high' THEN 1
-            WHEN 'medium' THEN 2
-            WHEN 'low' THEN 3
-            END ASC",
-        SortOrder::PriorityAsc => "ORDER BY CASE priority
-            WHEN 'low' THEN 0
-            WHEN 'medium' THEN 1
-            WHEN 'high' THEN 2
-            WHEN 'urgent' THEN 3
-            END ASC",
-        SortOrder::CreatedDesc => "ORDER BY created_at DESC",
-        SortOrder::CreatedAsc => "ORDER BY created_at ASC",
-        SortOrder::Alphabetical => "ORDER BY title ASC",
-    }
-}
-
-// Helper functions for type conversions
-
-fn option_to_value(opt: &Option<String>) -> Value {
-    match opt {
-        Some(s) => Value::Text(s.clone()),
-        None => Value::Null,
-    }
-}
-
-fn priority_to_str(priority: Priority) -> &'static str {
-    match priority {
-        Priority::Low => "low",
-        Priority::Medium => "medium",
-        Priority::High => "high",
-        Priority::Urgent => "urgent",
-    }
-}
-
-fn str_to_priority(s: &str) -> Priority {
-    match s.to_lowercase().as_str() {
-        "low" => Priority::Low,
-        "high" => Priority::High,
-        "urgent" => Priority::Urgent,
-        _ => Priority::Medium,
-    }
-}
-
-fn status_to_str(status: TaskStatus) -> &'static str {
-    match status {
-        TaskStatus::Pending => "pending",
-        TaskStatus::InProgress => "in_progress",
-        TaskStatus::Completed => "completed",
-        TaskStatus::Archived => "archived",
-    }
-}
-
-fn str_to_status(s: &str) -> TaskStatus {
-    match s.to_lowercase().as_str() {
-        "in_progress" => TaskStatus::InProgress,
-        "completed" => TaskStatus::Completed,
-        "archived" => TaskStatus::Archived,
-        _ => TaskStatus::Pending,
-    }
-}
-
-fn value_to_string(value: Value) -> Result<String> {
-    match value {
-        Value::Text(s) => Ok(s),
-        _ => Err(StorageError::Conversion(format!(
-            "Expected text, got {:?}",
-            value
-        ))),
-    }
-}
-
-fn value_to_option_string(value: Value) -> Result<Option<String>> {
-    match value {
-        Value::Text(s) => Ok(Some(s)),
-        Value::Null => Ok(None),
-        _ => Err(StorageError::Conversion(format!(
-            "Expected text or null, got {:?}",
-            value
-        ))),
-    }
-}
-
-fn value_to_datetime(value: Value) -> Result<DateTime<Utc>> {
-    match value {
-        Value::Text(s) => DateTime::parse_from_rfc3339(&s)
-            .map(|dt| dt.with_timezone(&Utc))
-            .map_err(|e| StorageError::Conversion(format!("Invalid datetime '{}': {}", s, e))),
-        _ => Err(StorageError::Conversion(format!(
-            "Expected datetime text, got {:?}",
-            value
-        ))),
-    }
-}
-
-fn value_to_option_datetime(value: Value) -> Result<Option<DateTime<Utc>>> {
-    match value {
-        Value::Text(s) => {
-            let dt = DateTime::parse_from_rfc3339(&s)
-                .map(|dt| dt.with_timezone(&Utc))
-                .map_err(|e| StorageError::Conversion(format!("Invalid datetime '{}': {}", s, e)))?;
-            Ok(Some(dt))
-        }
-        Value::Null => Ok(None),
-        _ => Err(StorageError::Conversion(format!(
-            "Expected datetime text or null, got {:?}",
-            value
-        ))),
-    }
-}
-
-fn value_to_i64(value: &Value) -> Result<i64> {
-    match value {
-        Value::Integer(i) => Ok(*i),
-        _ => Err(StorageError::Conversion(format!(
-            "Expected integer, got {:?}",
-            value
-        ))),
-    }
+/// The tasks a bulk delete removes.
+enum Selection<'a> {
+    /// The tasks with these IDs
+    Ids(&'a [String]),
+    /// The tasks matching a filter
+    Matching(Filter),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::storage::run_migrations;
-    use chrono::Duration;
+    use crate::models::{NewTaskPlacement, Priority};
+    use chrono::{Duration, Utc};
 
     async fn setup_db() -> Database {
         let db = Database::open_in_memory().await.unwrap();
@@ -589,6 +549,39 @@ mod tests {
         db
     }
 
+    #[tokio::test]
+    async fn test_task_notes() {
+        let db = setup_db().await;
+        let mut task = Task::new("Investigate outage");
+        task.add_note("Started at 02:00");
+        db.insert_task(&task).await.unwrap();
+
+        task.add_note("Root cause: expired certificate\nRenewed it");
+        db.update_task(&task).await.unwrap();
+        let stored = db.get_task(&task.id).await.unwrap().unwrap();
+        assert_eq!(stored.notes, task.notes);
+        assert_eq!(db.get_all_tasks().await.unwrap()[0].notes, task.notes);
+
+        // Stored notes are only added to, never rewritten or dropped
+        let mut stale = task.clone();
+        stale.notes.truncate(1);
+        stale.notes[0].text = "Rewritten".to_string();
+        stale.title = "Outage".to_string();
+        db.update_task(&stale).await.unwrap();
+        let stored = db.get_task(&task.id).await.unwrap().unwrap();
+        assert_eq!(stored.title, "Outage");
+        assert_eq!(stored.notes, task.notes);
+        assert!(matches!(
+            db.store.add_note("missing", &task.notes[0]).await,
+            Err(StorageError::NotFound(_))
+        ));
+
+        // Notes go with the task
+        db.delete_task(&task.id).await.unwrap();
+        let count = db.query_scalar("SELECT COUNT(*) FROM task_notes", ()).await.unwrap();
+        assert_eq!(count, Some(turso::Value::Integer(0)));
+    }
+
     #[tokio::test]
     async fn test_insert_and_get_task() {
         let db = setup_db().await;
@@ -748,6 +741,97 @@ mod tests {
         assert!(retrieved.due_date.is_some());
     }
 
+    #[tokio::test]
+    async fn test_sql_queries_match_plaintext_store() {
+        let sqlite = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        let files = Database::open_dir(dir.path()).await.unwrap();
+        run_migrations(&files).await.unwrap();
+        let project = crate::models::Project::new("Work");
+        for db in [&sqlite, &files] {
+            db.insert_project(&project).await.unwrap();
+        }
+
+        let now = Utc::now();
+        let dues = [
+            None,
+            Some(now - Duration::days(2)),
+            Some(now + Duration::hours(1)),
+            Some(now + Duration::days(3)),
+            Some(now + Duration::days(30)),
+        ];
+        let priorities = [Priority::Low, Priority::Medium, Priority::High, Priority::Urgent];
+        let statuses = [
+            TaskStatus::Pending,
+            TaskStatus::InProgress,
+            TaskStatus::Completed,
+            TaskStatus::Archived,
+        ];
+        for i in 0..20 {
+            let mut task = Task::new(&format!("Task {}", (i * 7) % 20));
+            task.created_at = now - Duration::minutes(i as i64 * 13 % 17);
+            task.due_date = dues[i % dues.len()];
+            task.priority = priorities[i % priorities.len()];
+            task.status = statuses[i / 3 % statuses.len()];
+            task.position = (i * 5 % 20) as f64;
+            if i % 2 == 0 {
+                task.project_id = Some(project.id.clone());
+            }
+            if i % 3 == 0 {
+                task.tags = vec!["docs".to_string()];
+            }
+            for db in [&sqlite, &files] {
+                db.insert_task(&task).await.unwrap();
+            }
+        }
+
+        let filters = [
+            Filter::All,
+            Filter::Pending,
+            Filter::InProgress,
+            Filter::Completed,
+            Filter::Archived,
+            Filter::DueToday,
+            Filter::DueThisWeek,
+            Filter::Overdue,
+            Filter::ByProject(project.id.clone()),
+            Filter::ByTag("docs".to_string()),
+            Filter::ByPriority(Priority::High),
+        ];
+        let sorts = [
+            SortOrder::DueDateAsc,
+            SortOrder::DueDateDesc,
+            SortOrder::PriorityDesc,
+            SortOrder::PriorityAsc,
+            SortOrder::CreatedDesc,
+            SortOrder::CreatedAsc,
+            SortOrder::Alphabetical,
+            SortOrder::Manual,
+        ];
+        let ids = |tasks: Vec<Task>| tasks.into_iter().map(|t| t.id).collect::<Vec<_>>();
+        for filter in &filters {
+            for sort in &sorts {
+                assert_eq!(
+                    ids(sqlite.query_tasks(filter, sort).await.unwrap()),
+                    ids(files.query_tasks(filter, sort).await.unwrap()),
+                    "{:?} sorted by {:?}",
+                    filter,
+                    sort
+                );
+            }
+        }
+        let tagged = sqlite
+            .query_tasks(&Filter::ByTag("docs".to_string()), &SortOrder::default())
+            .await
+            .unwrap();
+        assert!(tagged.iter().all(|t| t.tags == ["docs"]));
+        assert_eq!(sqlite.get_task_counts().await.unwrap(), files.get_task_counts().await.unwrap());
+        for db in [&sqlite, &files] {
+            assert_eq!(db.get_task_count_by_project(&project.id).await.unwrap(), 10);
+            assert_eq!(db.get_task_count_by_project("inbox").await.unwrap(), 0);
+        }
+    }
+
     #[tokio::test]
     async fn test_get_task_counts() {
         let db = setup_db().await;
@@ -822,4 +906,244 @@ mod tests {
         let tasks = db.get_all_tasks().await.unwrap();
         assert!(tasks.is_empty(), "All tasks should be deleted");
     }
+
+    /// Installs hooks from `(name, shell script)` pairs.
+    #[cfg(unix)]
+    fn install_hooks(db: &mut Database, dir: &std::path::Path, hooks: &[(&str, &str)]) {
+        use std::os::unix::fs::PermissionsExt;
+        for (name, body) in hooks {
+            let path = dir.join(name);
+            std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        db.set_hooks(crate::hooks::Hooks::load(dir).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_add_and_modify_run_hooks() {
+        let mut db = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        install_hooks(
+            &mut db,
+            dir.path(),
+            &[
+                ("on-add", r#"sed 's/"title":"draft /"title":"/'"#),
+                ("on-complete", "echo 'Complete it on the website'; exit 1"),
+            ],
+        );
+
+        let task = db.add_task(Task::new("draft Write notes")).await.unwrap();
+        assert_eq!(task.title, "Write notes");
+        assert_eq!(db.get_task(&task.id).await.unwrap().unwrap().title, "Write notes");
+
+        let mut renamed = task.clone();
+        renamed.title = "Write more notes".to_string();
+        db.modify_task(renamed).await.unwrap();
+
+        let mut completed = db.get_task(&task.id).await.unwrap().unwrap();
+        completed.complete();
+        let err = db.modify_task(completed).await.unwrap_err();
+        assert!(matches!(err, StorageError::Hook(_)));
+        assert_eq!(err.to_string(), "on-complete: Complete it on the website");
+        let stored = db.get_task(&task.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, TaskStatus::Pending);
+        assert_eq!(stored.title, "Write more notes");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_delete_hooks_reject_before_deleting() {
+        let mut db = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        install_hooks(
+            &mut db,
+            dir.path(),
+            &[("on-delete", "grep -q keep && { echo 'Task is pinned'; exit 1; }; exit 0")],
+        );
+
+        let pinned = Task::new("keep me");
+        let other = Task::new("Other");
+        db.insert_task(&pinned).await.unwrap();
+        db.insert_task(&other).await.unwrap();
+
+        assert!(db.remove_task(&other.id).await.unwrap());
+        assert!(db.remove_task(&pinned.id).await.is_err());
+
+        // A bulk delete stops before deleting anything
+        let another = Task::new("Another");
+        db.insert_task(&another).await.unwrap();
+        let ids = vec![another.id.clone(), pinned.id.clone()];
+        assert!(db.remove_tasks(&ids).await.is_err());
+        assert_eq!(db.get_all_tasks().await.unwrap().len(), 2);
+
+        // Deletes that aren't the user's, like a reset, don't run hooks
+        assert_eq!(db.delete_all_tasks().await.unwrap(), 2);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_delete_hooks_run_before_the_transaction() {
+        let mut db = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        install_hooks(&mut db, dir.path(), &[("on-delete", "sleep 1")]);
+        let task = Task::new("Slow to delete");
+        db.insert_task(&task).await.unwrap();
+
+        // The database stays free while the hook runs
+        let (removed, waited) = tokio::join!(db.remove_task(&task.id), async {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            let start = std::time::Instant::now();
+            db.outside_transaction(async || Ok(())).await.unwrap();
+            start.elapsed()
+        });
+        assert!(removed.unwrap());
+        assert!(waited < std::time::Duration::from_millis(500), "waited {:?}", waited);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_delete_tag_modifies_tasks() {
+        let mut db = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        install_hooks(
+            &mut db,
+            dir.path(),
+            &[("on-modify", "tail -n 1 | grep -q keep && { echo 'Task is pinned'; exit 1; }; exit 0")],
+        );
+        db.set_webhooks(vec![crate::webhooks::Webhook {
+            url: "http://localhost:9/hook".to_string(),
+            secret: None,
+            events: Vec::new(),
+        }]);
+        let mut task = Task::new("Write notes");
+        task.tags = vec!["docs".to_string(), "work".to_string()];
+        task.updated_at -= Duration::days(1);
+        let mut pinned = Task::new("keep me");
+        pinned.tags = vec!["archive".to_string()];
+        db.insert_task(&task).await.unwrap();
+        db.insert_task(&pinned).await.unwrap();
+
+        let docs = db.get_tag_by_name("docs").await.unwrap().unwrap();
+        assert!(db.delete_tag(&docs.id).await.unwrap());
+        let stored = db.get_task(&task.id).await.unwrap().unwrap();
+        assert_eq!(stored.tags, vec!["work"]);
+        assert!(stored.updated_at > task.updated_at);
+        assert!(db.get_tag_by_name("docs").await.unwrap().is_none());
+        let history = db.task_history(&task.id).await.unwrap();
+        assert!(history.iter().any(|e| e.old_value.as_deref() == Some("docs")));
+        let outbox = db.webhook_outbox().await.unwrap();
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].event, "task.updated");
+
+        // A rejected change keeps the tag on every task
+        let archive = db.get_tag_by_name("archive").await.unwrap().unwrap();
+        assert!(matches!(db.delete_tag(&archive.id).await, Err(StorageError::Hook(_))));
+        assert_eq!(db.get_task(&pinned.id).await.unwrap().unwrap().tags, vec!["archive"]);
+        assert!(db.get_tag_by_name("archive").await.unwrap().is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_move_to_inbox_keeps_hook_changes() {
+        let mut db = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        install_hooks(
+            &mut db,
+            dir.path(),
+            &[("on-modify", "tail -n 1 | sed 's/\"title\":\"[^\"]*\"/\"title\":\"Moved\"/'")],
+        );
+        let project = crate::models::Project::new("Work");
+        db.insert_project(&project).await.unwrap();
+        let mut task = Task::new("Plan");
+        task.project_id = Some(project.id.clone());
+        db.insert_task(&task).await.unwrap();
+
+        assert_eq!(db.move_tasks_to_inbox(&project.id).await.unwrap(), 1);
+        let moved = db.get_task(&task.id).await.unwrap().unwrap();
+        assert_eq!(moved.project_id.as_deref(), Some("inbox"));
+        assert_eq!(moved.title, "Moved");
+    }
+
+    #[tokio::test]
+    async fn test_modify_tasks_saves_all_or_none() {
+        let mut db = setup_db().await;
+        let dir = tempfile::tempdir().unwrap();
+        install_hooks(
+            &mut db,
+            dir.path(),
+            &[("on-modify", "tail -n 1 | grep -q keep && { echo 'Task is pinned'; exit 1; }; exit 0")],
+        );
+
+        let tasks: Vec<Task> = ["One", "Two", "keep me"].into_iter().map(Task::new).collect();
+        for task in &tasks {
+            db.insert_task(task).await.unwrap();
+        }
+        let raise = |task: &Task| Task { priority: Priority::High, ..task.clone() };
+
+        let saved = db.modify_tasks(tasks[..2].iter().map(raise).collect()).await.unwrap();
+        assert_eq!(saved.len(), 2);
+        for task in &tasks[..2] {
+            assert_eq!(db.get_task(&task.id).await.unwrap().unwrap().priority, Priority::High);
+        }
+
+        // One rejection leaves every task unchanged
+        let lowered: Vec<Task> = tasks
+            .iter()
+            .map(|t| Task { priority: Priority::Low, ..t.clone() })
+            .collect();
+        assert!(matches!(db.modify_tasks(lowered).await, Err(StorageError::Hook(_))));
+        for task in &tasks {
+            assert_ne!(db.get_task(&task.id).await.unwrap().unwrap().priority, Priority::Low);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remove_tasks() {
+        let db = setup_db().await;
+        let tasks: Vec<Task> = ["One", "Two", "Three"].into_iter().map(Task::new).collect();
+        for task in &tasks {
+            db.insert_task(task).await.unwrap();
+        }
+
+        let ids = vec![tasks[0].id.clone(), tasks[2].id.clone()];
+        assert_eq!(db.remove_tasks(&ids).await.unwrap(), 2);
+        assert!(db.get_task(&tasks[0].id).await.unwrap().is_none());
+        assert!(db.get_task(&tasks[1].id).await.unwrap().is_some());
+        assert!(db.get_task(&tasks[2].id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_new_task_placement() {
+        let mut db = setup_db().await;
+        let first = db.add_task(Task::new("First")).await.unwrap();
+        let second = db.add_task(Task::new("Second")).await.unwrap();
+        assert!(first.position < second.position);
+
+        db.set_new_task_placement(NewTaskPlacement::Top);
+        let third = db.add_task(Task::new("Third")).await.unwrap();
+        assert!(third.position < first.position);
+        let saved = db.get_task(&third.id).await.unwrap().unwrap();
+        assert_eq!(saved.position, third.position);
+    }
+
+    #[tokio::test]
+    async fn test_set_task_positions() {
+        let db = setup_db().await;
+        let one = Task::new("One");
+        let two = Task::new("Two");
+        db.insert_task(&one).await.unwrap();
+        db.insert_task(&two).await.unwrap();
+
+        let positions = vec![(one.id.clone(), 5.0), (two.id.clone(), 2.5)];
+        db.set_task_positions(&positions).await.unwrap();
+        let sorted = db.query_tasks(&Filter::All, &SortOrder::Manual).await.unwrap();
+        let titles: Vec<&str> = sorted.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Two", "One"]);
+
+        // An unknown task leaves every position as it was
+        let positions = vec![(one.id.clone(), 1.0), ("missing".to_string(), 0.0)];
+        assert!(db.set_task_positions(&positions).await.is_err());
+        assert_eq!(db.get_task(&one.id).await.unwrap().unwrap().position, 5.0);
+    }
 }
@@ -0,0 +1,394 @@
+//! Webhook outbox and delivery log.
+//!
+//! Changes queue one outbox row per subscribed
+//! [`Webhook`](crate::webhooks::Webhook), so events
+//! survive restarts and outages until [`crate::webhooks::deliver_due`] sends
+//! them. Both tables are bookkeeping: writing to them doesn't count as an
+//! external change.
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value as Json};
+use turso::Value;
+
+use crate::storage::{Database, Result, StorageError};
+use crate::webhooks::WebhookEvent;
+
+/// Delivery attempts kept in the log; older ones are deleted.
+const DELIVERY_LOG_SIZE: i64 = 200;
+
+/// An event waiting to be delivered to one webhook URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxEntry {
+    /// Row ID, in the order events were queued
+    pub id: i64,
+    /// Unique ID sent as `X-Ratado-Delivery`, the same on every retry
+    pub delivery_id: String,
+    /// Destination URL
+    pub url: String,
+    /// Event name, e.g. `task.created`
+    pub event: String,
+    /// JSON request body
+    pub payload: String,
+    /// Failed attempts so far
+    pub attempts: u32,
+    /// Earliest time of the next attempt
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+/// The outcome of one delivery attempt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delivery {
+    /// The `X-Ratado-Delivery` ID of the event
+    pub delivery_id: String,
+    /// Destination URL
+    pub url: String,
+    /// Event name, e.g. `task.created`
+    pub event: String,
+    /// Attempt number, starting at 1
+    pub attempt: u32,
+    /// HTTP status of the response, if one was received
+    pub status: Option<u16>,
+    /// Why the request failed, if no response was received
+    pub error: Option<String>,
+    /// When the attempt was made
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl Delivery {
+    /// Returns whether the endpoint accepted the event (a 2xx response).
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none() && self.status.is_some_and(|s| (200..300).contains(&s))
+    }
+
+    /// Short description of the outcome, e.g. "HTTP 200" or the error.
+    pub fn outcome(&self) -> String {
+        match (&self.error, self.status) {
+            (Some(error), _) => error.clone(),
+            (None, Some(status)) => format!("HTTP {}", status),
+            (None, None) => "No response".to_string(),
+        }
+    }
+}
+
+impl Database {
+    /// Queues `event` for every webhook subscribed to it.
+    ///
+    /// `item` is the task or project the event is about.
+    pub(crate) async fn queue_webhooks(&self, event: WebhookEvent, item: Json) -> Result<()> {
+        let now = Utc::now();
+        for webhook in self.webhooks.iter().filter(|w| w.wants(event)) {
+            let delivery_id = uuid::Uuid::new_v4().to_string();
+            let payload = json!({
+                "id": delivery_id,
+                "event": event.name(),
+                "created_at": now,
+                event.subject(): item,
+            });
+            self.execute(
+                "INSERT INTO _webhook_outbox
+                 (delivery_id, url, event, payload, attempts, next_attempt_at, created_at)
+                 VALUES (?1, ?2, ?3, ?4, 0, ?5, ?5)",
+                [
+                    Value::Text(delivery_id),
+                    Value::Text(webhook.url.clone()),
+                    Value::Text(event.name().to_string()),
+                    Value::Text(payload.to_string()),
+                    Value::Text(now.to_rfc3339()),
+                ],
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Returns all queued events, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn webhook_outbox(&self) -> Result<Vec<OutboxEntry>> {
+        self.outside_transaction(async || {
+            let mut rows = self
+                .query(
+                    "SELECT id, delivery_id, url, event, payload, attempts, next_attempt_at
+                     FROM _webhook_outbox ORDER BY id ASC",
+                    (),
+                )
+                .await?;
+
+            let mut entries = Vec::new();
+            while let Some(row) = rows.next().await? {
+                entries.push(OutboxEntry {
+                    id: value_to_i64(row.get_value(0)?)?,
+                    delivery_id: value_to_string(row.get_value(1)?)?,
+                    url: value_to_string(row.get_value(2)?)?,
+                    event: value_to_string(row.get_value(3)?)?,
+                    payload: value_to_string(row.get_value(4)?)?,
+                    attempts: value_to_i64(row.get_value(5)?)? as u32,
+                    next_attempt_at: value_to_datetime(row.get_value(6)?)?,
+                });
+            }
+            Ok(entries)
+        })
+        .await
+    }
+
+    /// Records a failed attempt and when to try again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update fails.
+    pub async fn reschedule_webhook(&self, id: i64, attempts: u32, next_attempt_at: DateTime<Utc>) -> Result<()> {
+        self.outside_transaction(async || {
+            self.execute(
+                "UPDATE _webhook_outbox SET attempts = ?1, next_attempt_at = ?2 WHERE id = ?3",
+                [
+                    Value::Integer(attempts as i64),
+                    Value::Text(next_attempt_at.to_rfc3339()),
+                    Value::Integer(id),
+                ],
+            )
+            .await
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Removes a delivered or abandoned event from the outbox.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the delete fails.
+    pub async fn remove_from_webhook_outbox(&self, id: i64) -> Result<()> {
+        self.outside_transaction(async || {
+            self.execute("DELETE FROM _webhook_outbox WHERE id = ?1", [Value::Integer(id)])
+                .await
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Adds a delivery attempt to the log, keeping only the most recent ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the insert fails.
+    pub async fn log_webhook_delivery(&self, delivery: &Delivery) -> Result<()> {
+        self.outside_transaction(async || {
+            self.execute(
+                "INSERT INTO _webhook_deliveries
+                 (delivery_id, url, event, attempt, status, error, attempted_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                [
+                    Value::Text(delivery.delivery_id.clone()),
+                    Value::Text(delivery.url.clone()),
+                    Value::Text(delivery.event.clone()),
+                    Value::Integer(delivery.attempt as i64),
+                    delivery.status.map_or(Value::Null, |s| Value::Integer(s as i64)),
+                    delivery.error.clone().map_or(Value::Null, Value::Text),
+                    Value::Text(delivery.attempted_at.to_rfc3339()),
+                ],
+            )
+            .await?;
+            if let Some(Value::Integer(newest)) = self
+                .query_scalar("SELECT MAX(id) FROM _webhook_deliveries", ())
+                .await?
+            {
+                self.execute(
+                    "DELETE FROM _webhook_deliveries WHERE id <= ?1",
+                    [Value::Integer(newest - DELIVERY_LOG_SIZE)],
+                )
+                .await?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Returns up to `limit` delivery attempts, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails.
+    pub async fn recent_webhook_deliveries(&self, limit: usize) -> Result<Vec<Delivery>> {
+        let mut rows = self
+            .query(
+                "SELECT delivery_id, url, event, attempt, status, error, attempted_at
+                 FROM _webhook_deliveries ORDER BY id DESC LIMIT ?1",
+                [Value::Integer(limit as i64)],
+            )
+            .await?;
+
+        let mut deliveries = Vec::new();
+        while let Some(row) = rows.next().await? {
+            deliveries.push(Delivery {
+                delivery_id: value_to_string(row.get_value(0)?)?,
+                url: value_to_string(row.get_value(1)?)?,
+                event: value_to_string(row.get_value(2)?)?,
+                attempt: value_to_i64(row.get_value(3)?)? as u32,
+                status: match row.get_value(4)? {
+                    Value::Null => None,
+                    value => Some(value_to_i64(value)? as u16),
+                },
+                error: match row.get_value(5)? {
+                    Value::Null => None,
+                    value => Some(value_to_string(value)?),
+                },
+                attempted_at: value_to_datetime(row.get_value(6)?)?,
+            });
+        }
+        Ok(deliveries)
+    }
+}
+
+fn value_to_string(value: Value) -> Result<String> {
+    match value {
+        Value::Text(s) => Ok(s),
+        _ => Err(StorageError::Conversion(format!(
+            "Expected text, got {:?}",
+            value
+        ))),
+    }
+}
+
+fn value_to_i64(value: Value) -> Result<i64> {
+    match value {
+        Value::Integer(i) => Ok(i),
+        _ => Err(StorageError::Conversion(format!(
+            "Expected integer, got {:?}",
+            value
+        ))),
+    }
+}
+
+fn value_to_datetime(value: Value) -> Result<DateTime<Utc>> {
+    let s = value_to_string(value)?;
+    DateTime::parse_from_rfc3339(&s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| StorageError::Conversion(format!("Invalid datetime '{}': {}", s, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Project, Task};
+    use crate::storage::run_migrations;
+    use crate::webhooks::Webhook;
+
+    async fn setup_db(events: Vec<WebhookEvent>) -> Database {
+        let mut db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db.set_webhooks(vec![Webhook {
+            url: "http://localhost:9/hook".to_string(),
+            secret: None,
+            events,
+        }]);
+        db
+    }
+
+    fn queued_events(outbox: &[OutboxEntry]) -> Vec<&str> {
+        outbox.iter().map(|e| e.event.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_changes_are_queued() {
+        let db = setup_db(Vec::new()).await;
+        let project = Project::new("Work");
+        db.insert_project(&project).await.unwrap();
+        let mut task = Task::new("Write docs");
+        task.project_id = Some(project.id.clone());
+        let task = db.add_task(task).await.unwrap();
+        let mut renamed = task.clone();
+        renamed.title = "Write more docs".to_string();
+        db.modify_task(renamed).await.unwrap();
+        db.move_tasks_to_inbox(&project.id).await.unwrap();
+        db.delete_project(&project.id).await.unwrap();
+        db.remove_task(&task.id).await.unwrap();
+
+        let outbox = db.webhook_outbox().await.unwrap();
+        assert_eq!(
+            queued_events(&outbox),
+            [
+                "project.created",
+                "task.created",
+                "task.updated",
+                "task.updated",
+                "project.deleted",
+                "task.deleted",
+            ]
+        );
+        let moved: Json = serde_json::from_str(&outbox[3].payload).unwrap();
+        assert_eq!(moved["task"]["project_id"], "inbox");
+        let deleted: Json = serde_json::from_str(&outbox[5].payload).unwrap();
+        assert_eq!(deleted["event"], "task.deleted");
+        assert_eq!(deleted["task"]["title"], "Write more docs");
+        assert_eq!(deleted["id"], outbox[5].delivery_id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_only_subscribed_events_are_queued() {
+        let db = setup_db(vec![WebhookEvent::TaskCompleted]).await;
+        let task = db.add_task(Task::new("Task")).await.unwrap();
+        let mut done = task.clone();
+        done.complete();
+        db.modify_task(done).await.unwrap();
+        db.remove_completed_tasks().await.unwrap();
+
+        let outbox = db.webhook_outbox().await.unwrap();
+        assert_eq!(queued_events(&outbox), ["task.completed"]);
+    }
+
+    #[tokio::test]
+    async fn test_outbox_reads_wait_for_transactions() {
+        let db = setup_db(Vec::new()).await;
+        let worker = db.clone();
+        let mut reading = None;
+        let result: Result<()> = db
+            .in_transaction(async || {
+                db.queue_webhooks(WebhookEvent::TaskCreated, json!({})).await?;
+                let read = tokio::spawn(async move { worker.webhook_outbox().await });
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                assert!(!read.is_finished());
+                reading = Some(read);
+                Err(StorageError::NotFound("missing".to_string()))
+            })
+            .await;
+        assert!(result.is_err());
+
+        // The worker never saw the event that was rolled back
+        let outbox = reading.unwrap().await.unwrap().unwrap();
+        assert!(outbox.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_nothing_queued_without_webhooks() {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db.add_task(Task::new("Task")).await.unwrap();
+
+        assert!(db.webhook_outbox().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_log_is_trimmed() {
+        let db = setup_db(Vec::new()).await;
+        for attempt in 1..=DELIVERY_LOG_SIZE as u32 + 5 {
+            db.log_webhook_delivery(&Delivery {
+                delivery_id: "d".to_string(),
+                url: "http://localhost:9/hook".to_string(),
+                event: "task.created".to_string(),
+                attempt,
+                status: Some(200),
+                error: None,
+                attempted_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        }
+
+        let log = db.recent_webhook_deliveries(1000).await.unwrap();
+        assert_eq!(log.len(), DELIVERY_LOG_SIZE as usize);
+        assert_eq!(log[0].attempt, DELIVERY_LOG_SIZE as u32 + 5);
+        assert!(log[0].succeeded());
+    }
+}
@@ -0,0 +1,942 @@
+//! Syncing with other Ratado databases.
+//!
+//! `ratado sync <other.db>` merges this database and another one, so both
+//! end up with the same tasks and projects. `ratado sync <folder>` does the
+//! same through a folder shared between machines (Syncthing, Dropbox, a
+//! dotfiles repo): each machine publishes a copy of its database there as
+//! `<replica-id>.db` and merges the copies the others published, without
+//! ever writing to them, so the folder never has two writers per file.
+//!
+//! ## Merging
+//!
+//! Tasks and projects are matched by their UUIDv7 IDs. An item in both
+//! databases is merged field by field: whichever side changed a field last
+//! wins (see [`FieldClocks`]), so a title edited on the laptop and a due
+//! date edited on the desktop are both kept. Tags merge one by one the same
+//! way: a tag added on one side stays unless the other side removed it
+//! later. Fields nobody changed since the item was created fall back to the
+//! task's `updated_at`.
+//!
+//! A deleted item leaves a [`Tombstone`], and is deleted on the other side
+//! too, unless it was changed there after the deletion; then it is restored.
+//!
+//! Fields both sides changed since they last synced, and restored items,
+//! are reported as [`Conflict`]s. Sync doesn't run hooks or webhooks.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use log::info;
+use serde_json::{json, Value as Json};
+use thiserror::Error;
+
+use crate::models::{Project, Task};
+use crate::storage::{
+    copy_salt, run_migrations, Database, FieldClocks, ItemKind, StorageError, Tombstone, STATE_DIR,
+};
+
+/// Errors that stop a sync.
+#[derive(Error, Debug)]
+pub enum SyncError {
+    /// Reading or writing one of the databases failed
+    #[error("{0}")]
+    Storage(#[from] StorageError),
+
+    /// The database to sync with doesn't exist
+    #[error("No database at {}", .0.display())]
+    NotFound(PathBuf),
+
+    /// A file in the sync folder couldn't be read or copied
+    #[error("Failed to {action} {}: {source}", path.display())]
+    Io {
+        /// What was being done, e.g. `read`
+        action: &'static str,
+        /// The file or folder involved
+        path: PathBuf,
+        /// Underlying I/O error
+        source: io::Error,
+    },
+}
+
+/// Result type for sync operations.
+pub type Result<T> = std::result::Result<T, SyncError>;
+
+/// Which databases a sync writes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Both end up with the merged data
+    Both,
+    /// Only this database is changed; the other is read
+    Pull,
+}
+
+/// What a sync changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncReport {
+    /// Number of databases merged with
+    pub peers: usize,
+    /// Tasks and projects added, changed or deleted in this database
+    pub received: usize,
+    /// Tasks and projects added, changed or deleted in the other database
+    pub sent: usize,
+    /// Fields changed on both sides, and restored items
+    pub conflicts: Vec<Conflict>,
+    /// Where this database was published, when syncing through a folder
+    pub published: Option<PathBuf>,
+}
+
+impl SyncReport {
+    /// Adds the counts and conflicts of another sync to this one.
+    fn absorb(&mut self, other: SyncReport) {
+        self.peers += other.peers;
+        self.received += other.received;
+        self.sent += other.sent;
+        self.conflicts.extend(other.conflicts);
+    }
+}
+
+/// A change on one side that lost to, or overrode, a change on the other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    /// Whether a task or a project
+    pub kind: ItemKind,
+    /// ID of the item
+    pub id: String,
+    /// Task title or project name, after merging
+    pub label: String,
+    /// How the conflict was resolved
+    pub resolution: Resolution,
+}
+
+/// How a [`Conflict`] was resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution {
+    /// Both sides changed a field; the later change was kept
+    Field {
+        /// Field name, e.g. `title` or `tag:work`
+        field: String,
+        /// The value kept
+        kept: String,
+        /// The value discarded
+        discarded: String,
+    },
+    /// One side deleted the item, the other changed it afterwards
+    Restored,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} \"{}\": ", self.kind.as_str(), self.label)?;
+        match &self.resolution {
+            Resolution::Field {
+                field,
+                kept,
+                discarded,
+            } => write!(f, "{} \"{}\" kept over \"{}\"", field, kept, discarded),
+            Resolution::Restored => write!(f, "restored, it was changed after being deleted"),
+        }
+    }
+}
+
+/// Syncs `db` with the database file or sync folder at `target`.
+///
+/// A file is merged both ways. A folder is pulled from, then `db` is
+/// published into it (see the [module docs](self)).
+///
+/// # Errors
+///
+/// Returns an error if a database can't be opened, read or written, or the
+/// folder can't be read. Changes made before the failure are kept.
+pub async fn sync_path(db: &Database, target: &Path) -> Result<SyncReport> {
+    let plaintext = target.join(STATE_DIR).is_dir();
+    if target.is_dir() && !plaintext {
+        return sync_folder(db, target).await;
+    }
+    if !target.exists() {
+        return Err(SyncError::NotFound(target.to_path_buf()));
+    }
+    // A copy of an encrypted database shares its key
+    let other = if plaintext {
+        Database::open_dir(target).await?
+    } else {
+        Database::open_with_key(target, db.encryption_key()).await?
+    };
+    run_migrations(&other).await?;
+    sync_databases(db, &other, Direction::Both).await
+}
+
+/// Pulls every other database copy in `dir`, then publishes `db` there.
+async fn sync_folder(db: &Database, dir: &Path) -> Result<SyncReport> {
+    let own_name = format!("{}.db", db.replica_id().await?);
+    let mut peers: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|source| SyncError::Io {
+            action: "read",
+            path: dir.to_path_buf(),
+            source,
+        })?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "db")
+                && path.file_name().is_some_and(|name| *name != *own_name)
+        })
+        .collect();
+    peers.sort();
+
+    let mut report = SyncReport::default();
+    for path in peers {
+        info!("Pulling changes from {:?}", path);
+        // Work on a private copy: the folder's files belong to other machines
+        let copy = std::env::temp_dir().join(format!(
+            "ratado-sync-{}-{}",
+            std::process::id(),
+            path.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        std::fs::copy(&path, &copy).map_err(|source| SyncError::Io {
+            action: "copy",
+            path: path.clone(),
+            source,
+        })?;
+        copy_salt(&path, &copy)?;
+        let result = pull_from_copy(db, &copy).await;
+        for suffix in ["", "-wal", "-shm", "-salt"] {
+            let _ = std::fs::remove_file(format!("{}{}", copy.display(), suffix));
+        }
+        report.absorb(result?);
+    }
+
+    let published = dir.join(own_name);
+    db.snapshot_to(&published).await?;
+    report.published = Some(published);
+    Ok(report)
+}
+
+/// Merges a private copy of another machine's database into `db`.
+async fn pull_from_copy(db: &Database, copy: &Path) -> Result<SyncReport> {
+    let mut peer = Database::open_with_key(copy, db.encryption_key()).await?;
+    peer.set_backup_retention(0);
+    run_migrations(&peer).await?;
+    sync_databases(db, &peer, Direction::Pull).await
+}
+
+/// Merges `other` into `db`, and `db` into `other` when syncing both ways.
+///
+/// # Errors
+///
+/// Returns an error if either database can't be read or written.
+pub async fn sync_databases(
+    db: &Database,
+    other: &Database,
+    direction: Direction,
+) -> Result<SyncReport> {
+    let own_id = db.replica_id().await?;
+    let other_id = other.replica_id().await?;
+    let last_synced = db.last_synced_at(&other_id).await?;
+    let now = Utc::now();
+
+    let ours = Snapshot::load(db).await?;
+    let theirs = Snapshot::load(other).await?;
+
+    let mut conflicts = Vec::new();
+    let (local_projects, remote_projects) = plan(
+        ours.side(&ours.projects),
+        theirs.side(&theirs.projects),
+        last_synced,
+        &mut conflicts,
+    );
+    let (local_tasks, remote_tasks) = plan(
+        ours.side(&ours.tasks),
+        theirs.side(&theirs.tasks),
+        last_synced,
+        &mut conflicts,
+    );
+
+    apply(db, &local_projects, &local_tasks).await?;
+    db.set_last_synced_at(&other_id, now).await?;
+    let mut report = SyncReport {
+        peers: 1,
+        received: local_projects.changed + local_tasks.changed,
+        conflicts,
+        ..SyncReport::default()
+    };
+    if direction == Direction::Both {
+        apply(other, &remote_projects, &remote_tasks).await?;
+        other.set_last_synced_at(&own_id, now).await?;
+        report.sent = remote_projects.changed + remote_tasks.changed;
+    }
+    Ok(report)
+}
+
+/// Everything a merge needs from one database.
+struct Snapshot {
+    tasks: HashMap<String, Task>,
+    projects: HashMap<String, Project>,
+    clocks: HashMap<String, FieldClocks>,
+    tombstones: HashMap<String, Tombstone>,
+}
+
+impl Snapshot {
+    async fn load(db: &Database) -> Result<Self> {
+        Ok(Self {
+            tasks: by_id(db.get_all_tasks().await?, |t| &t.id),
+            projects: by_id(db.get_all_projects().await?, |p| &p.id),
+            clocks: db.field_clocks().await?,
+            tombstones: by_id(db.tombstones().await?, |t| &t.id),
+        })
+    }
+
+    fn side<'a, T>(&'a self, items: &'a HashMap<String, T>) -> Side<'a, T> {
+        Side {
+            items,
+            clocks: &self.clocks,
+            tombstones: &self.tombstones,
+        }
+    }
+}
+
+fn by_id<T>(items: Vec<T>, id: impl Fn(&T) -> &String) -> HashMap<String, T> {
+    items.into_iter().map(|item| (id(&item).clone(), item)).collect()
+}
+
+/// One database's items of one kind, with their bookkeeping.
+struct Side<'a, T> {
+    items: &'a HashMap<String, T>,
+    clocks: &'a HashMap<String, FieldClocks>,
+    tombstones: &'a HashMap<String, Tombstone>,
+}
+
+impl<T: Item> Side<'_, T> {
+    fn clocks(&self, id: &str) -> &FieldClocks {
+        static EMPTY: FieldClocks = FieldClocks::new();
+        self.clocks.get(id).unwrap_or(&EMPTY)
+    }
+
+    fn tombstone(&self, id: &str) -> Option<&Tombstone> {
+        self.tombstones.get(id).filter(|t| t.kind == T::KIND)
+    }
+}
+
+/// Changes to make to one database.
+struct Changes<T> {
+    /// Items to insert or overwrite, with their clocks
+    upserts: Vec<(T, FieldClocks)>,
+    /// Tombstones to apply, deleting the item if present
+    deletes: Vec<Tombstone>,
+    /// Items whose data changes, as opposed to just their clocks
+    changed: usize,
+}
+
+impl<T> Default for Changes<T> {
+    fn default() -> Self {
+        Self {
+            upserts: Vec::new(),
+            deletes: Vec::new(),
+            changed: 0,
+        }
+    }
+}
+
+/// Works out the changes that bring both sides to the merged state.
+fn plan<T: Item>(
+    ours: Side<'_, T>,
+    theirs: Side<'_, T>,
+    last_synced: Option<DateTime<Utc>>,
+    conflicts: &mut Vec<Conflict>,
+) -> (Changes<T>, Changes<T>) {
+    let mut local = Changes::default();
+    let mut remote = Changes::default();
+
+    let ids: BTreeSet<&String> = ours.items.keys().chain(theirs.items.keys()).collect();
+    for id in ids {
+        match (ours.items.get(id), theirs.items.get(id)) {
+            (Some(a), Some(b)) => {
+                let (a_clocks, b_clocks) = (ours.clocks(id), theirs.clocks(id));
+                let (merged, clocks) = merge(a, a_clocks, b, b_clocks, last_synced, conflicts);
+                for (changes, item, item_clocks) in
+                    [(&mut local, a, a_clocks), (&mut remote, b, b_clocks)]
+                {
+                    let changed = merged.fields() != item.fields();
+                    if changed || clocks != *item_clocks {
+                        changes.changed += changed as usize;
+                        changes.upserts.push((merged.clone(), clocks.clone()));
+                    }
+                }
+            }
+            (Some(a), None) => one_sided(a, ours.clocks(id), theirs.tombstone(id), &mut local, &mut remote, conflicts),
+            (None, Some(b)) => one_sided(b, theirs.clocks(id), ours.tombstone(id), &mut remote, &mut local, conflicts),
+            (None, None) => unreachable!("IDs come from either side"),
+        }
+    }
+
+    // Pass on deletions of items neither side has any more
+    for (from, to, changes) in [(&ours, &theirs, &mut remote), (&theirs, &ours, &mut local)] {
+        for tombstone in from.tombstones.values().filter(|t| t.kind == T::KIND) {
+            if !to.items.contains_key(&tombstone.id) && !to.tombstones.contains_key(&tombstone.id) {
+                changes.deletes.push(tombstone.clone());
+            }
+        }
+    }
+
+    (local, remote)
+}
+
+/// Handles an item that only one side (`own`) has: the other side either
+/// deleted it or hasn't seen it yet.
+fn one_sided<T: Item>(
+    item: &T,
+    clocks: &FieldClocks,
+    tombstone: Option<&Tombstone>,
+    own: &mut Changes<T>,
+    other: &mut Changes<T>,
+    conflicts: &mut Vec<Conflict>,
+) {
+    match tombstone {
+        Some(tombstone) if last_change(item, clocks) <= tombstone.deleted_at => {
+            own.deletes.push(tombstone.clone());
+            own.changed += 1;
+        }
+        Some(_) => {
+            conflicts.push(Conflict {
+                kind: T::KIND,
+                id: item.id().to_string(),
+                label: item.label().to_string(),
+                resolution: Resolution::Restored,
+            });
+            other.upserts.push((item.clone(), clocks.clone()));
+            other.changed += 1;
+        }
+        None => {
+            other.upserts.push((item.clone(), clocks.clone()));
+            other.changed += 1;
+        }
+    }
+}
+
+/// Merges two versions of an item field by field, the later change winning.
+///
+/// Returns the merged item and clocks, and adds a [`Conflict`] for each
+/// field both sides changed since `last_synced`.
+fn merge<T: Item>(
+    a: &T,
+    a_clocks: &FieldClocks,
+    b: &T,
+    b_clocks: &FieldClocks,
+    last_synced: Option<DateTime<Utc>>,
+    conflicts: &mut Vec<Conflict>,
+) -> (T, FieldClocks) {
+    let a_fields = a.fields();
+    let b_fields = b.fields();
+    let mut merged = a.clone();
+    let mut resolutions = Vec::new();
+
+    let names: BTreeSet<&String> = a_fields.keys().chain(b_fields.keys()).collect();
+    for name in names {
+        let a_value = a_fields.get(name).unwrap_or(&Json::Null);
+        let b_value = b_fields.get(name).unwrap_or(&Json::Null);
+        if a_value == b_value {
+            continue;
+        }
+
+        // Ties (e.g. fields unchanged since creation) go to the item changed
+        // last, then to the larger value, so both sides pick the same one
+        let a_changed = a_clocks.get(name).copied();
+        let b_changed = b_clocks.get(name).copied();
+        let b_wins = (b_changed.unwrap_or(b.created_at()), b.updated_at(), b_value.to_string())
+            > (a_changed.unwrap_or(a.created_at()), a.updated_at(), a_value.to_string());
+        if b_wins {
+            merged.copy_field(name, b);
+        }
+
+        if changed_since(a_changed, last_synced) && changed_since(b_changed, last_synced) {
+            let (kept, discarded) = if b_wins { (b_value, a_value) } else { (a_value, b_value) };
+            resolutions.push(Resolution::Field {
+                field: name.clone(),
+                kept: describe(name, kept),
+                discarded: describe(name, discarded),
+            });
+        }
+    }
+    merged.set_updated_at(a.updated_at().max(b.updated_at()));
+
+    conflicts.extend(resolutions.into_iter().map(|resolution| Conflict {
+        kind: T::KIND,
+        id: merged.id().to_string(),
+        label: merged.label().to_string(),
+        resolution,
+    }));
+
+    let mut clocks = a_clocks.clone();
+    for (field, changed_at) in b_clocks {
+        let entry = clocks.entry(field.clone()).or_insert(*changed_at);
+        *entry = (*entry).max(*changed_at);
+    }
+    (merged, clocks)
+}
+
+/// Whether a field changed after the last sync (ever, if never synced).
+fn changed_since(changed_at: Option<DateTime<Utc>>, last_synced: Option<DateTime<Utc>>) -> bool {
+    changed_at.is_some_and(|changed| last_synced.is_none_or(|last| changed > last))
+}
+
+/// When an item was last changed, as far as its clocks know.
+fn last_change<T: Item>(item: &T, clocks: &FieldClocks) -> DateTime<Utc> {
+    clocks
+        .values()
+        .copied()
+        .fold(item.updated_at(), DateTime::max)
+}
+
+/// Describes a field value for a conflict report.
+fn describe(field: &str, value: &Json) -> String {
+    if field.starts_with("tag:") {
+        return if value.is_null() { "removed" } else { "added" }.to_string();
+    }
+    match value {
+        Json::Null => "none".to_string(),
+        Json::String(s) => s.clone(),
+        Json::Array(values) => values.first().map_or_else(String::new, |v| describe(field, v)),
+        other => other.to_string(),
+    }
+}
+
+/// Writes planned changes to one database.
+///
+/// Projects are added before the tasks that may reference them and deleted
+/// after, and tasks whose project is gone move out of it, as they would if
+/// the project had been deleted here.
+async fn apply(db: &Database, projects: &Changes<Project>, tasks: &Changes<Task>) -> Result<()> {
+    for (project, clocks) in &projects.upserts {
+        db.put_synced_project(project, clocks).await?;
+    }
+
+    let deleted: HashSet<&str> = projects.deletes.iter().map(|t| t.id.as_str()).collect();
+    let project_ids: HashSet<String> = db
+        .get_all_projects()
+        .await?
+        .into_iter()
+        .map(|p| p.id)
+        .filter(|id| !deleted.contains(id.as_str()))
+        .collect();
+    for (task, clocks) in &tasks.upserts {
+        let mut task = task.clone();
+        if task.project_id.as_ref().is_some_and(|id| !project_ids.contains(id)) {
+            task.project_id = None;
+        }
+        db.put_synced_task(&task, clocks).await?;
+    }
+
+    for tombstone in tasks.deletes.iter().chain(&projects.deletes) {
+        db.apply_tombstone(tombstone).await?;
+    }
+    Ok(())
+}
+
+/// A task or project, as far as merging is concerned.
+trait Item: Clone {
+    /// Kind recorded in tombstones and conflicts
+    const KIND: ItemKind;
+
+    fn id(&self) -> &str;
+
+    /// Task title or project name
+    fn label(&self) -> &str;
+
+    fn created_at(&self) -> DateTime<Utc>;
+
+    fn updated_at(&self) -> DateTime<Utc>;
+
+    fn set_updated_at(&mut self, at: DateTime<Utc>);
+
+    /// Merged fields by name, as compared during a merge
+    fn fields(&self) -> BTreeMap<String, Json>;
+
+    /// Sets the field `name` to its value in `from`.
+    fn copy_field(&mut self, name: &str, from: &Self);
+}
+
+impl Item for Task {
+    const KIND: ItemKind = ItemKind::Task;
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn label(&self) -> &str {
+        &self.title
+    }
+
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    fn set_updated_at(&mut self, at: DateTime<Utc>) {
+        self.updated_at = at;
+    }
+
+    fn fields(&self) -> BTreeMap<String, Json> {
+        task_fields(self)
+    }
+
+    fn copy_field(&mut self, name: &str, from: &Self) {
+        match name {
+            "title" => self.title = from.title.clone(),
+            "description" => self.description = from.description.clone(),
+            "due_date" => self.due_date = from.due_date,
+            "priority" => self.priority = from.priority,
+            "status" => {
+                self.status = from.status;
+                self.completed_at = from.completed_at;
+            }
+            "project_id" => self.project_id = from.project_id.clone(),
+            "position" => self.position = from.position,
+            _ => {
+                if let Some(tag) = name.strip_prefix("tag:") {
+                    self.tags.retain(|t| t != tag);
+                    if from.tags.iter().any(|t| t == tag) {
+                        self.tags.push(tag.to_string());
+                    }
+                } else if let Some(id) = name.strip_prefix("note:") {
+                    self.notes.retain(|n| n.id != id);
+                    if let Some(note) = from.notes.iter().find(|n| n.id == id) {
+                        self.notes.push(note.clone());
+                        self.notes.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Item for Project {
+    const KIND: ItemKind = ItemKind::Project;
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn label(&self) -> &str {
+        &self.name
+    }
+
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    // Projects don't track changes; their field clocks do
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn set_updated_at(&mut self, _at: DateTime<Utc>) {}
+
+    fn fields(&self) -> BTreeMap<String, Json> {
+        project_fields(self)
+    }
+
+    fn copy_field(&mut self, name: &str, from: &Self) {
+        match name {
+            "name" => self.name = from.name.clone(),
+            "color" => self.color = from.color.clone(),
+            "icon" => self.icon = from.icon.clone(),
+            _ => {}
+        }
+    }
+}
+
+/// The fields of a task that merge independently, by name.
+///
+/// Status and completion time change together, so they are one field.
+/// Each tag is its own `tag:<name>` field and each note its own
+/// `note:<id>` field, so notes added on both sides are all kept.
+pub(crate) fn task_fields(task: &Task) -> BTreeMap<String, Json> {
+    let mut fields = BTreeMap::from([
+        ("title".to_string(), json!(task.title)),
+        ("description".to_string(), json!(task.description)),
+        ("due_date".to_string(), json!(task.due_date)),
+        ("priority".to_string(), json!(task.priority)),
+        ("status".to_string(), json!([task.status, task.completed_at])),
+        ("project_id".to_string(), json!(task.project_id)),
+        ("position".to_string(), json!(task.position)),
+    ]);
+    for tag in &task.tags {
+        fields.insert(format!("tag:{}", tag), Json::Bool(true));
+    }
+    for note in &task.notes {
+        fields.insert(format!("note:{}", note.id), json!(note));
+    }
+    fields
+}
+
+/// The fields of a project that merge independently, by name.
+pub(crate) fn project_fields(project: &Project) -> BTreeMap<String, Json> {
+    BTreeMap::from([
+        ("name".to_string(), json!(project.name)),
+        ("color".to_string(), json!(project.color)),
+        ("icon".to_string(), json!(project.icon)),
+    ])
+}
+
+/// Names of the fields that differ between two sets of fields.
+pub(crate) fn changed_fields(
+    before: &BTreeMap<String, Json>,
+    after: &BTreeMap<String, Json>,
+) -> Vec<String> {
+    before
+        .keys()
+        .chain(after.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|name| before.get(*name) != after.get(*name))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+
+    async fn setup_db() -> Database {
+        let db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db
+    }
+
+    async fn titles(db: &Database) -> Vec<String> {
+        let mut titles: Vec<String> = db
+            .get_all_tasks()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.title)
+            .collect();
+        titles.sort();
+        titles
+    }
+
+    /// Creates a task in `a` and syncs it to `b`.
+    async fn shared_task(a: &Database, b: &Database, title: &str) -> Task {
+        let task = Task::new(title);
+        a.insert_task(&task).await.unwrap();
+        sync_databases(a, b, Direction::Both).await.unwrap();
+        task
+    }
+
+    #[tokio::test]
+    async fn test_new_items_are_copied_both_ways() {
+        let (a, b) = (setup_db().await, setup_db().await);
+        let project = Project::new("Work");
+        a.insert_project(&project).await.unwrap();
+        let mut task = Task::new("From A");
+        task.project_id = Some(project.id.clone());
+        a.insert_task(&task).await.unwrap();
+        b.insert_task(&Task::new("From B")).await.unwrap();
+
+        let report = sync_databases(&a, &b, Direction::Both).await.unwrap();
+        assert_eq!((report.received, report.sent), (1, 2));
+        assert!(report.conflicts.is_empty());
+
+        assert_eq!(titles(&a).await, ["From A", "From B"]);
+        assert_eq!(titles(&b).await, ["From A", "From B"]);
+        let copied = b.get_task(&task.id).await.unwrap().unwrap();
+        assert_eq!(copied.project_id, Some(project.id.clone()));
+        assert!(b.get_project(&project.id).await.unwrap().is_some());
+
+        // Nothing left to do the second time
+        let report = sync_databases(&a, &b, Direction::Both).await.unwrap();
+        assert_eq!((report.received, report.sent), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_edits_to_different_fields_are_both_kept() {
+        let (a, b) = (setup_db().await, setup_db().await);
+        let task = shared_task(&a, &b, "Report").await;
+
+        let mut on_a = task.clone();
+        on_a.title = "Quarterly report".to_string();
+        on_a.tags = vec!["work".to_string()];
+        a.update_task(&on_a).await.unwrap();
+        let mut on_b = task.clone();
+        on_b.priority = Priority::Urgent;
+        on_b.tags = vec!["finance".to_string()];
+        b.update_task(&on_b).await.unwrap();
+
+        let report = sync_databases(&a, &b, Direction::Both).await.unwrap();
+        assert!(report.conflicts.is_empty());
+        for db in [&a, &b] {
+            let mut merged = db.get_task(&task.id).await.unwrap().unwrap();
+            merged.tags.sort();
+            assert_eq!(merged.title, "Quarterly report");
+            assert_eq!(merged.priority, Priority::Urgent);
+            assert_eq!(merged.tags, ["finance", "work"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notes_from_both_sides_are_kept() {
+        let (a, b) = (setup_db().await, setup_db().await);
+        let task = shared_task(&a, &b, "Report").await;
+
+        let mut on_a = task.clone();
+        on_a.add_note("Asked finance for numbers");
+        a.update_task(&on_a).await.unwrap();
+        let mut on_b = task.clone();
+        on_b.add_note("Draft outline done");
+        b.update_task(&on_b).await.unwrap();
+
+        let report = sync_databases(&a, &b, Direction::Both).await.unwrap();
+        assert!(report.conflicts.is_empty());
+        for db in [&a, &b] {
+            let merged = db.get_task(&task.id).await.unwrap().unwrap();
+            let texts: Vec<&str> = merged.notes.iter().map(|n| n.text.as_str()).collect();
+            assert_eq!(texts, ["Asked finance for numbers", "Draft outline done"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_later_edit_wins_and_is_reported() {
+        let (a, b) = (setup_db().await, setup_db().await);
+        let task = shared_task(&a, &b, "Buy milk").await;
+
+        let mut on_a = task.clone();
+        on_a.title = "Buy oat milk".to_string();
+        a.update_task(&on_a).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let mut on_b = task.clone();
+        on_b.title = "Buy soy milk".to_string();
+        b.update_task(&on_b).await.unwrap();
+
+        let report = sync_databases(&a, &b, Direction::Both).await.unwrap();
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(
+            report.conflicts[0].to_string(),
+            "task \"Buy soy milk\": title \"Buy soy milk\" kept over \"Buy oat milk\""
+        );
+        assert_eq!(titles(&a).await, ["Buy soy milk"]);
+        assert_eq!(titles(&b).await, ["Buy soy milk"]);
+    }
+
+    #[tokio::test]
+    async fn test_removed_tag_stays_removed() {
+        let (a, b) = (setup_db().await, setup_db().await);
+        let mut task = Task::new("Tagged");
+        task.tags = vec!["old".to_string()];
+        a.insert_task(&task).await.unwrap();
+        sync_databases(&a, &b, Direction::Both).await.unwrap();
+
+        task.tags.clear();
+        a.update_task(&task).await.unwrap();
+        sync_databases(&a, &b, Direction::Both).await.unwrap();
+
+        assert!(b.get_task(&task.id).await.unwrap().unwrap().tags.is_empty());
+        assert!(b.get_tag_by_name("old").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_deletions_propagate() {
+        let (a, b) = (setup_db().await, setup_db().await);
+        let task = shared_task(&a, &b, "Obsolete").await;
+        let project = Project::new("Old");
+        b.insert_project(&project).await.unwrap();
+        sync_databases(&a, &b, Direction::Both).await.unwrap();
+
+        a.delete_task(&task.id).await.unwrap();
+        b.delete_project(&project.id).await.unwrap();
+        let report = sync_databases(&a, &b, Direction::Both).await.unwrap();
+        assert_eq!((report.received, report.sent), (1, 1));
+
+        assert!(b.get_task(&task.id).await.unwrap().is_none());
+        assert!(a.get_project(&project.id).await.unwrap().is_none());
+        assert_eq!(b.tombstones().await.unwrap().len(), 2);
+
+        // A third database learns about deletions of items it never had
+        let c = setup_db().await;
+        sync_databases(&c, &b, Direction::Both).await.unwrap();
+        assert_eq!(c.tombstones().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_edit_after_delete_restores() {
+        let (a, b) = (setup_db().await, setup_db().await);
+        let task = shared_task(&a, &b, "Keep me").await;
+
+        a.delete_task(&task.id).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let mut edited = task.clone();
+        edited.title = "Keep me, really".to_string();
+        edited.updated_at = Utc::now();
+        b.update_task(&edited).await.unwrap();
+
+        let report = sync_databases(&a, &b, Direction::Both).await.unwrap();
+        assert_eq!(report.conflicts[0].resolution, Resolution::Restored);
+        assert_eq!(titles(&a).await, ["Keep me, really"]);
+        assert!(a.tombstones().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pull_leaves_other_database_alone() {
+        let (a, b) = (setup_db().await, setup_db().await);
+        a.insert_task(&Task::new("Mine")).await.unwrap();
+        b.insert_task(&Task::new("Theirs")).await.unwrap();
+
+        let report = sync_databases(&a, &b, Direction::Pull).await.unwrap();
+        assert_eq!((report.received, report.sent), (1, 0));
+        assert_eq!(titles(&a).await, ["Mine", "Theirs"]);
+        assert_eq!(titles(&b).await, ["Theirs"]);
+    }
+
+    #[tokio::test]
+    async fn test_sync_through_folder() {
+        let folder = tempfile::tempdir().unwrap();
+        let laptop_dir = tempfile::tempdir().unwrap();
+        let desktop_dir = tempfile::tempdir().unwrap();
+        let laptop = Database::open(&laptop_dir.path().join("ratado.db")).await.unwrap();
+        run_migrations(&laptop).await.unwrap();
+        let desktop = Database::open(&desktop_dir.path().join("ratado.db")).await.unwrap();
+        run_migrations(&desktop).await.unwrap();
+
+        laptop.insert_task(&Task::new("On the laptop")).await.unwrap();
+        let report = sync_path(&laptop, folder.path()).await.unwrap();
+        assert_eq!(report.peers, 0);
+        let published = report.published.unwrap();
+        assert_eq!(
+            published.file_name().unwrap().to_string_lossy(),
+            format!("{}.db", laptop.replica_id().await.unwrap())
+        );
+
+        desktop.insert_task(&Task::new("On the desktop")).await.unwrap();
+        let report = sync_path(&desktop, folder.path()).await.unwrap();
+        assert_eq!((report.peers, report.received, report.sent), (1, 1, 0));
+        assert_eq!(titles(&desktop).await, ["On the desktop", "On the laptop"]);
+
+        let report = sync_path(&laptop, folder.path()).await.unwrap();
+        assert_eq!(report.received, 1);
+        assert_eq!(titles(&laptop).await, ["On the desktop", "On the laptop"]);
+    }
+
+    #[tokio::test]
+    async fn test_missing_database() {
+        let db = setup_db().await;
+        let result = sync_path(&db, Path::new("/nonexistent/other.db")).await;
+        assert!(matches!(result, Err(SyncError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_changed_fields() {
+        let task = Task::new("Task");
+        let mut changed = task.clone();
+        changed.complete();
+        changed.tags = vec!["done".to_string()];
+        assert_eq!(
+            changed_fields(&task_fields(&task), &task_fields(&changed)),
+            ["status", "tag:done"]
+        );
+    }
+}
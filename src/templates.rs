@@ -0,0 +1,424 @@
+//! Task templates.
+//!
+//! TOML files in the `templates` directory next to `config.toml`
+//! (`~/.config/ratado/templates/` on Linux) describe tasks that are created
+//! together, like a release checklist. Each file is one template, invoked
+//! by its file stem: `release.toml` is used with `tpl:release v1.4` in quick
+//! capture, or picked from the template dialog.
+//!
+//! ```toml
+//! name = "Release checklist"
+//! title = "Release {input}"
+//! project = "Ratado"
+//! tags = ["release"]
+//! priority = "high"
+//! due = "+3d"
+//! checklist = ["Bump version", "Update CHANGELOG", "Tag the release"]
+//!
+//! [[tasks]]
+//! title = "Announce {input}"
+//! due = "+4d"
+//! ```
+//!
+//! In titles and descriptions, `{input}` is replaced with the text typed
+//! after the template name and `{date}` with today's date. Checklist items
+//! are appended to the description as `- [ ]` lines. Related tasks under
+//! `[[tasks]]` go to the same project, add the template's tags to their
+//! own, and use its priority unless they set one.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use directories::ProjectDirs;
+use log::debug;
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+
+use crate::models::{Priority, Task};
+
+/// Name of the templates directory inside the config directory.
+const TEMPLATES_DIR: &str = "templates";
+
+/// Prefix that invokes a template from quick capture.
+pub const TEMPLATE_PREFIX: &str = "tpl:";
+
+/// Errors from loading templates.
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    /// The templates directory or a template file couldn't be read
+    #[error("Failed to read templates: {0}")]
+    Io(#[from] io::Error),
+
+    /// A template file isn't valid
+    #[error("Invalid template {path:?}: {message}")]
+    Invalid {
+        /// Path of the template file
+        path: PathBuf,
+        /// What is wrong with it
+        message: String,
+    },
+}
+
+/// A task created by a template, besides the main one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TemplateTask {
+    /// Title pattern
+    pub title: String,
+    /// Description pattern
+    pub description: Option<String>,
+    /// Tags, added to the template's
+    pub tags: Vec<String>,
+    /// Priority, defaulting to the template's
+    #[serde(deserialize_with = "deserialize_priority")]
+    pub priority: Option<Priority>,
+    /// Due date offset, like `+3d`
+    pub due: Option<String>,
+    /// Checklist items appended to the description
+    pub checklist: Vec<String>,
+}
+
+/// A reusable recipe for one or more tasks.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Template {
+    /// File stem, used to invoke the template
+    #[serde(skip)]
+    pub key: String,
+    /// Display name, defaulting to the key
+    pub name: Option<String>,
+    /// Title pattern of the main task
+    pub title: String,
+    /// Description pattern of the main task
+    pub description: Option<String>,
+    /// Tags for every task
+    pub tags: Vec<String>,
+    /// Priority for every task that doesn't set its own
+    #[serde(deserialize_with = "deserialize_priority")]
+    pub priority: Option<Priority>,
+    /// Name of the project the tasks go to
+    pub project: Option<String>,
+    /// Due date offset of the main task, like `+3d`
+    pub due: Option<String>,
+    /// Checklist items appended to the main task's description
+    pub checklist: Vec<String>,
+    /// Related tasks created along with the main one
+    pub tasks: Vec<TemplateTask>,
+}
+
+impl Template {
+    /// Parses a template from TOML. `key` is the name it's invoked by.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the TOML is invalid, a title is missing, or a
+    /// due offset can't be parsed.
+    pub fn parse(key: &str, content: &str) -> Result<Self, String> {
+        let mut template: Template = toml::from_str(content).map_err(|e| e.to_string())?;
+        template.key = key.to_string();
+
+        if template.title.trim().is_empty() {
+            return Err("title is required".to_string());
+        }
+        if template.tasks.iter().any(|t| t.title.trim().is_empty()) {
+            return Err("every [[tasks]] entry needs a title".to_string());
+        }
+        let offsets = template.due.iter().chain(template.tasks.iter().filter_map(|t| t.due.as_ref()));
+        for due in offsets {
+            if parse_offset(due).is_none() {
+                return Err(format!("invalid due offset '{}' (use e.g. +3d or +1w)", due));
+            }
+        }
+        Ok(template)
+    }
+
+    /// Returns the name shown in the template picker.
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.key)
+    }
+
+    /// Returns how many tasks the template creates.
+    pub fn task_count(&self) -> usize {
+        1 + self.tasks.len()
+    }
+
+    /// Builds the template's tasks, main task first.
+    ///
+    /// `input` fills `{input}` placeholders, and due offsets count from
+    /// `now`. The tasks have no project; the caller resolves
+    /// [`Template::project`].
+    pub fn instantiate(&self, input: &str, now: DateTime<Utc>) -> Vec<Task> {
+        let date = now.with_timezone(&Local).date_naive();
+        let fill = |pattern: &str| {
+            pattern
+                .replace("{input}", input.trim())
+                .replace("{date}", &date.format("%Y-%m-%d").to_string())
+                .trim()
+                .to_string()
+        };
+        let build = |title: &str,
+                     description: Option<&str>,
+                     checklist: &[String],
+                     tags: Vec<String>,
+                     priority: Option<Priority>,
+                     due: Option<&str>| {
+            let mut task = Task::new(&fill(title));
+            task.description = describe(description.map(&fill), checklist);
+            task.tags = tags;
+            task.priority = priority.unwrap_or_default();
+            task.due_date = due.and_then(parse_offset).map(|days| end_of_day(date + Duration::days(days)));
+            task
+        };
+
+        let mut tasks = vec![build(
+            &self.title,
+            self.description.as_deref(),
+            &self.checklist,
+            self.tags.clone(),
+            self.priority,
+            self.due.as_deref(),
+        )];
+        for related in &self.tasks {
+            let mut tags = self.tags.clone();
+            for tag in &related.tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+            tasks.push(build(
+                &related.title,
+                related.description.as_deref(),
+                &related.checklist,
+                tags,
+                related.priority.or(self.priority),
+                related.due.as_deref(),
+            ));
+        }
+        tasks
+    }
+}
+
+/// The templates in the templates directory, sorted by key.
+#[derive(Debug, Clone, Default)]
+pub struct Templates {
+    templates: Vec<Template>,
+}
+
+impl Templates {
+    /// Returns the default templates directory.
+    pub fn default_dir() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "ratado").map(|dirs| dirs.config_dir().join(TEMPLATES_DIR))
+    }
+
+    /// Loads the `*.toml` templates in `dir`. A missing directory has no
+    /// templates.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a template can't be read, or a
+    /// template is invalid.
+    pub fn load(dir: &Path) -> Result<Self, TemplateError> {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        let mut templates = Vec::new();
+        for path in paths {
+            let key = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string();
+            let content = std::fs::read_to_string(&path)?;
+            let template = Template::parse(&key, &content)
+                .map_err(|message| TemplateError::Invalid { path: path.clone(), message })?;
+            debug!("Loaded template {:?} from {:?}", key, path);
+            templates.push(template);
+        }
+        Ok(Self { templates })
+    }
+
+    /// Creates a set from already parsed templates.
+    pub fn from_templates(mut templates: Vec<Template>) -> Self {
+        templates.sort_by(|a, b| a.key.cmp(&b.key));
+        Self { templates }
+    }
+
+    /// Returns all templates.
+    pub fn all(&self) -> &[Template] {
+        &self.templates
+    }
+
+    /// Returns whether there are no templates.
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Finds a template by key, ignoring case.
+    pub fn get(&self, key: &str) -> Option<&Template> {
+        self.templates.iter().find(|t| t.key.eq_ignore_ascii_case(key))
+    }
+}
+
+/// Splits quick capture text like `tpl:release v1.4` into the template key
+/// and the rest of the input. Returns `None` if the text doesn't start with
+/// [`TEMPLATE_PREFIX`].
+pub fn parse_invocation(text: &str) -> Option<(&str, &str)> {
+    let rest = text.trim_start().strip_prefix(TEMPLATE_PREFIX)?;
+    match rest.split_once(char::is_whitespace) {
+        Some((key, input)) => Some((key, input.trim())),
+        None => Some((rest.trim_end(), "")),
+    }
+}
+
+/// Joins a description with checklist items as `- [ ]` lines.
+fn describe(description: Option<String>, checklist: &[String]) -> Option<String> {
+    let items: Vec<String> = checklist.iter().map(|item| format!("- [ ] {}", item)).collect();
+    let parts: Vec<String> = description
+        .filter(|d| !d.is_empty())
+        .into_iter()
+        .chain((!items.is_empty()).then(|| items.join("\n")))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("\n\n"))
+}
+
+/// Parses a due offset into days: `today`, `tomorrow`, `+3d` or `+2w`.
+fn parse_offset(offset: &str) -> Option<i64> {
+    let offset = offset.trim().to_lowercase();
+    match offset.as_str() {
+        "today" => return Some(0),
+        "tomorrow" => return Some(1),
+        _ => {}
+    }
+    let rest = offset.strip_prefix('+')?;
+    if let Some(days) = rest.strip_suffix('d') {
+        return days.parse().ok();
+    }
+    if let Some(weeks) = rest.strip_suffix('w') {
+        return weeks.parse::<i64>().ok().map(|w| w * 7);
+    }
+    None
+}
+
+/// Returns the end of `date` (23:59:59 local time), as due dates use.
+fn end_of_day(date: NaiveDate) -> DateTime<Utc> {
+    let local_eod = date.and_hms_opt(23, 59, 59).unwrap();
+    Local
+        .from_local_datetime(&local_eod)
+        .single()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&local_eod))
+}
+
+/// Reads a priority name in any case, like `high` or `Urgent`.
+fn deserialize_priority<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Priority>, D::Error> {
+    let Some(name) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    match name.to_lowercase().as_str() {
+        "low" => Ok(Some(Priority::Low)),
+        "medium" => Ok(Some(Priority::Medium)),
+        "high" => Ok(Some(Priority::High)),
+        "urgent" => Ok(Some(Priority::Urgent)),
+        _ => Err(serde::de::Error::custom(format!(
+            "unknown priority '{}' (use low, medium, high or urgent)",
+            name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELEASE: &str = r#"
+name = "Release checklist"
+title = "Release {input}"
+project = "Ratado"
+tags = ["release"]
+priority = "high"
+due = "+3d"
+checklist = ["Bump version", "Tag the release"]
+
+[[tasks]]
+title = "Announce {input}"
+tags = ["comms"]
+due = "+1w"
+
+[[tasks]]
+title = "Retro for {input}"
+priority = "low"
+"#;
+
+    #[test]
+    fn test_instantiate_fills_patterns() {
+        let template = Template::parse("release", RELEASE).unwrap();
+        assert_eq!(template.display_name(), "Release checklist");
+        assert_eq!(template.task_count(), 3);
+
+        let now = Utc::now();
+        let today = now.with_timezone(&Local).date_naive();
+        let tasks = template.instantiate(" v1.4 ", now);
+        assert_eq!(tasks.len(), 3);
+
+        let main = &tasks[0];
+        assert_eq!(main.title, "Release v1.4");
+        assert_eq!(main.priority, Priority::High);
+        assert_eq!(main.tags, vec!["release"]);
+        assert_eq!(main.description.as_deref(), Some("- [ ] Bump version\n- [ ] Tag the release"));
+        let due = main.due_date.unwrap().with_timezone(&Local).date_naive();
+        assert_eq!(due, today + Duration::days(3));
+
+        let announce = &tasks[1];
+        assert_eq!(announce.title, "Announce v1.4");
+        assert_eq!(announce.tags, vec!["release", "comms"]);
+        assert_eq!(announce.priority, Priority::High);
+        let due = announce.due_date.unwrap().with_timezone(&Local).date_naive();
+        assert_eq!(due, today + Duration::days(7));
+
+        let retro = &tasks[2];
+        assert_eq!(retro.priority, Priority::Low);
+        assert!(retro.due_date.is_none());
+        assert!(retro.description.is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_templates() {
+        assert!(Template::parse("x", "description = \"no title\"").is_err());
+        assert!(Template::parse("x", "title = \"a\"\ndue = \"soon\"").is_err());
+        assert!(Template::parse("x", "title = \"a\"\npriority = \"huge\"").is_err());
+        assert!(Template::parse("x", "title = \"a\"\ncolour = \"red\"").is_err());
+        assert!(Template::parse("x", "title = \"a\"\n[[tasks]]\ndue = \"+1d\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_invocation() {
+        assert_eq!(parse_invocation("tpl:release v1.4"), Some(("release", "v1.4")));
+        assert_eq!(parse_invocation("tpl:standup"), Some(("standup", "")));
+        assert_eq!(parse_invocation("Write tpl:docs"), None);
+    }
+
+    #[test]
+    fn test_load_reads_toml_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("release.toml"), RELEASE).unwrap();
+        std::fs::write(dir.path().join("onboard.toml"), "title = \"Onboard {input}\"").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a template").unwrap();
+
+        let templates = Templates::load(dir.path()).unwrap();
+        let keys: Vec<&str> = templates.all().iter().map(|t| t.key.as_str()).collect();
+        assert_eq!(keys, vec!["onboard", "release"]);
+        assert_eq!(templates.get("Release").unwrap().task_count(), 3);
+
+        let missing = Templates::load(&dir.path().join("missing")).unwrap();
+        assert!(missing.is_empty());
+
+        std::fs::write(dir.path().join("broken.toml"), "title = ").unwrap();
+        assert!(matches!(Templates::load(dir.path()), Err(TemplateError::Invalid { .. })));
+    }
+}
@@ -0,0 +1,163 @@
+//! Activity view: recent changes to all tasks, by day.
+//!
+//! Lists the [`TaskEvent`]s recorded by the database, newest first, so
+//! what was done today or yesterday can be looked up at a glance.
+
+use chrono::{Duration, Local, NaiveDate};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::storage::{TaskEvent, TaskField};
+use super::theme::{self, icons};
+
+/// Renders the activity view.
+pub fn render_activity(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title(Span::styled(
+            " Activity ",
+            Style::default()
+                .fg(theme::PRIMARY_LIGHT)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme::PRIMARY_LIGHT))
+        .style(Style::default().bg(theme::BG_ELEVATED));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(3),    // Feed
+            Constraint::Length(1), // Help line
+        ])
+        .split(inner);
+
+    if app.activity.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                "No changes recorded yet.",
+                Style::default().fg(theme::TEXT_MUTED),
+            )),
+            chunks[0],
+        );
+    } else {
+        render_feed(frame, app, chunks[0]);
+    }
+    render_help_line(frame, chunks[1]);
+}
+
+/// Renders the events under a heading per day, scrolled to keep the
+/// selected one in view.
+fn render_feed(frame: &mut Frame, app: &App, area: Rect) {
+    let today = Local::now().date_naive();
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    let mut day: Option<NaiveDate> = None;
+
+    for (index, event) in app.activity.iter().enumerate() {
+        let changed_at = event.changed_at.with_timezone(&Local);
+        if day != Some(changed_at.date_naive()) {
+            day = Some(changed_at.date_naive());
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(
+                day_heading(changed_at.date_naive(), today),
+                Style::default().fg(theme::ACCENT).add_modifier(Modifier::BOLD),
+            )));
+        }
+
+        let selected = index == app.selected_activity_index;
+        if selected {
+            selected_line = lines.len();
+        }
+        let (icon, color) = event_marker(event);
+        let mut line = Line::from(vec![
+            Span::styled(
+                format!("  {}  ", changed_at.format("%H:%M")),
+                Style::default().fg(theme::TEXT_MUTED),
+            ),
+            Span::styled(format!("{} ", icon), Style::default().fg(color)),
+            Span::styled(event.title.clone(), Style::default().fg(theme::TEXT_PRIMARY)),
+            Span::styled(
+                format!("  {}  {}", icons::DOT, event.summary()),
+                Style::default().fg(theme::TEXT_SECONDARY),
+            ),
+        ]);
+        if selected {
+            line = line.style(Style::default().bg(theme::BG_SELECTION));
+        }
+        lines.push(line);
+    }
+
+    let height = area.height as usize;
+    let offset = (selected_line + 1).saturating_sub(height);
+    frame.render_widget(Paragraph::new(lines).scroll((offset as u16, 0)), area);
+}
+
+/// Returns "Today", "Yesterday" or the date.
+fn day_heading(date: NaiveDate, today: NaiveDate) -> String {
+    if date == today {
+        "Today".to_string()
+    } else if date == today - Duration::days(1) {
+        "Yesterday".to_string()
+    } else {
+        date.format("%A %-d %B").to_string()
+    }
+}
+
+/// Returns the icon and color marking an event, shared with the task
+/// detail history.
+pub(super) fn event_marker(event: &TaskEvent) -> (&'static str, Color) {
+    match event.field {
+        TaskField::Created => (icons::SPARKLE, theme::ACCENT),
+        TaskField::Deleted => (icons::CROSS, theme::ERROR),
+        TaskField::Status if event.is_completion() => (icons::CHECK, theme::SUCCESS),
+        TaskField::Status => (icons::CHECKBOX_PROGRESS, theme::STATUS_IN_PROGRESS),
+        TaskField::Project => (icons::PROJECT_PREFIX, theme::PROJECT),
+        TaskField::Tag => (icons::TAG_PREFIX, theme::TAG),
+        TaskField::Note => (icons::DIAMOND, theme::ACCENT),
+        TaskField::Title | TaskField::Priority | TaskField::DueDate => (icons::BULLET, theme::TEXT_SECONDARY),
+    }
+}
+
+/// Renders the key hints.
+fn render_help_line(frame: &mut Frame, area: Rect) {
+    let key = Style::default().fg(theme::PRIMARY_LIGHT).add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(theme::TEXT_MUTED);
+    let hints = [
+        ("[j/k]", " Move  "),
+        ("[Enter]", " Go to task  "),
+        ("[r]", " Refresh  "),
+        ("[Esc]", " Back"),
+    ];
+    let spans: Vec<Span> = hints
+        .iter()
+        .flat_map(|(k, label)| [Span::styled(*k, key), Span::styled(*label, text)])
+        .collect();
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_heading() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        assert_eq!(day_heading(today, today), "Today");
+        assert_eq!(day_heading(today - Duration::days(1), today), "Yesterday");
+        assert_eq!(day_heading(today - Duration::days(2), today), "Monday 2 March");
+    }
+}
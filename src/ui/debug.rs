@@ -1,22 +1,39 @@
 //! Debug log viewer.
 //!
-//! Displays application logs using tui-logger.
+//! Displays application logs using tui-logger. When webhooks are configured,
+//! recent delivery attempts are listed below the logs.
 
+use chrono::Local;
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     symbols::border,
-    text::Span,
-    widgets::{Block, Borders},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
 use tui_logger::TuiLoggerWidget;
 
 use crate::app::App;
+use crate::storage::Delivery;
 use super::theme;
 
+/// Height of the webhook delivery pane, including its border.
+const DELIVERY_PANE_HEIGHT: u16 = 12;
+
 /// Renders the debug log viewer.
 pub fn render_debug_logs(frame: &mut Frame, app: &App, area: Rect) {
+    let log_area = if app.db.webhooks().is_empty() {
+        area
+    } else {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(DELIVERY_PANE_HEIGHT)])
+            .split(area);
+        render_webhook_deliveries(frame, &app.webhook_deliveries, chunks[1]);
+        chunks[0]
+    };
+
     let block = Block::default()
         .title(Span::styled(
             " Debug Logs (F12 to close) ",
@@ -42,5 +59,63 @@ pub fn render_debug_logs(frame: &mut Frame, app: &App, area: Rect) {
     // The log_state field is for potential future use with TuiLoggerSmartWidget
     let _ = &app.log_state; // Acknowledge the field exists
 
-    frame.render_widget(logger, area);
+    frame.render_widget(logger, log_area);
+}
+
+/// Renders recent webhook delivery attempts, newest first.
+fn render_webhook_deliveries(frame: &mut Frame, deliveries: &[Delivery], area: Rect) {
+    let block = Block::default()
+        .title(Span::styled(
+            " Webhook Deliveries ",
+            Style::default()
+                .fg(theme::INFO)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme::BORDER))
+        .style(Style::default().bg(theme::BG_ELEVATED));
+
+    let lines: Vec<Line> = if deliveries.is_empty() {
+        vec![Line::from(Span::styled(
+            "No deliveries yet",
+            Style::default().fg(theme::TEXT_MUTED),
+        ))]
+    } else {
+        deliveries.iter().map(delivery_line).collect()
+    };
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Formats one delivery attempt as `time event #attempt outcome url`.
+fn delivery_line(delivery: &Delivery) -> Line<'static> {
+    let outcome_color = if delivery.succeeded() {
+        theme::SUCCESS
+    } else {
+        theme::ERROR
+    };
+    Line::from(vec![
+        Span::styled(
+            delivery
+                .attempted_at
+                .with_timezone(&Local)
+                .format("%H:%M:%S ")
+                .to_string(),
+            Style::default().fg(theme::TEXT_MUTED),
+        ),
+        Span::styled(
+            format!("{:<16}", delivery.event),
+            Style::default().fg(theme::TEXT_PRIMARY),
+        ),
+        Span::styled(
+            format!("#{:<3}", delivery.attempt),
+            Style::default().fg(theme::TEXT_SECONDARY),
+        ),
+        Span::styled(
+            format!("{} ", delivery.outcome()),
+            Style::default().fg(outcome_color),
+        ),
+        Span::styled(delivery.url.clone(), Style::default().fg(theme::TEXT_MUTED)),
+    ])
 }
@@ -4,6 +4,7 @@
 //! Includes calendar picker integration for date selection, tag input
 //! with autocomplete, and a multi-line description with clickable links.
 
+use chrono::{DateTime, Utc};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
@@ -23,6 +24,7 @@ use crate::ui::description_textarea::{DescriptionTextArea, TextAreaAction};
 use crate::ui::input::TextInput;
 use crate::ui::tag_input::TagInput;
 use crate::ui::theme;
+use crate::utils::parse_due_date;
 
 /// The currently focused field in the dialog.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -89,6 +91,12 @@ pub struct AddTaskDialog {
     date_picker: Option<DatePicker>,
     /// Status message to show (e.g., "Link opened")
     pub status_message: Option<String>,
+    /// When the task being edited was last updated, as loaded into the dialog
+    pub base_updated_at: Option<DateTime<Utc>>,
+    /// Warning shown when the task being edited changed in another process
+    pub conflict: Option<String>,
+    /// Whether the user has seen the conflict warning and chose to save anyway
+    overwrite_confirmed: bool,
 }
 
 impl AddTaskDialog {
@@ -107,6 +115,9 @@ impl AddTaskDialog {
             dialog_title: "Add Task".to_string(),
             date_picker: None,
             status_message: None,
+            base_updated_at: None,
+            conflict: None,
+            overwrite_confirmed: false,
         }
     }
 
@@ -130,6 +141,9 @@ impl AddTaskDialog {
             dialog_title: "Edit Task".to_string(),
             date_picker: None,
             status_message: None,
+            base_updated_at: Some(task.updated_at),
+            conflict: None,
+            overwrite_confirmed: false,
         }
     }
 
@@ -144,6 +158,33 @@ impl AddTaskDialog {
         self.editing_task_id.is_some()
     }
 
+    /// Records that the task being edited was changed or deleted by another
+    /// process while the dialog was open.
+    pub fn mark_conflict(&mut self, message: impl Into<String>) {
+        self.conflict = Some(message.into());
+        self.overwrite_confirmed = false;
+    }
+
+    /// Returns whether the dialog may be saved.
+    ///
+    /// Without a conflict this is always true. With one, the first submit
+    /// only asks the user to confirm, so external changes are never
+    /// overwritten silently.
+    pub fn confirm_save(&mut self) -> bool {
+        match &self.conflict {
+            Some(_) if self.overwrite_confirmed => true,
+            Some(message) => {
+                self.conflict = Some(format!(
+                    "{} - save again to overwrite, Esc to discard",
+                    message
+                ));
+                self.overwrite_confirmed = true;
+                false
+            }
+            None => true,
+        }
+    }
+
     /// Handles a key event and returns the resulting action.
     pub fn handle_key(&mut self, key: KeyEvent) -> DialogAction {
         // If date picker is active, route input to it
@@ -446,8 +487,12 @@ impl AddTaskDialog {
         // Render tags field
         self.render_tags_field(frame, chunks[4], self.focused_field == AddTaskField::Tags);
 
-        // Render status message if any
-        if let Some(ref msg) = self.status_message {
+        // Render conflict warning or status message if any
+        if let Some(ref conflict) = self.conflict {
+            let warning = Paragraph::new(conflict.as_str())
+                .style(Style::default().fg(theme::WARNING));
+            frame.render_widget(warning, chunks[5]);
+        } else if let Some(ref msg) = self.status_message {
             let status = Paragraph::new(msg.as_str())
                 .style(Style::default().fg(theme::SUCCESS));
             frame.render_widget(status, chunks[5]);
@@ -566,137 +611,6 @@ fn priority_color(priority: Priority) -> ratatui::style::Color {
     }
 }
 
-/// Parses a due date string into a DateTime.
-///
-/// Supports formats:
-/// - Natural language via `human-date-parser` (e.g., "next friday", "in 3 days", "december 25th")
-/// - "YYYY-MM-DD" or "YYYY/MM/DD"
-/// - "MM/DD" or "DD/MM" (assumes current year)
-/// - "today", "tomorrow", "yesterday"
-/// - "+1d", "+3d", "+1w", "+2w" (relative days/weeks)
-/// - "mon", "tue", "wed", "thu", "fri", "sat", "sun" (next occurrence)
-/// - "next week", "next month"
-pub fn parse_due_date(input: &str) -> Option<chrono::DateTime<chrono::Utc>> {
-    use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone, Utc, Weekday};
-
-    let input = input.to_lowercase().trim().to_string();
-
-    if input.is_empty() {
-        return None;
-    }
-
-    // Use local timezone for "today" reference
-    let today = Local::now().date_naive();
-
-    // Helper to create UTC datetime at end of day in local timezone
-    let to_datetime = |date: NaiveDate| {
-        // Create end of day (23:59:59) in local timezone, then convert to UTC
-        let local_eod = date.and_hms_opt(23, 59, 59).unwrap();
-        Local.from_local_datetime(&local_eod)
-            .single()
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or_else(|| Utc.from_utc_datetime(&local_eod))
-    };
-
-    // Try human-date-parser first for natural language dates
-    if let Ok(result) = human_date_parser::from_human_time(&input) {
-        match result {
-            human_date_parser::ParseResult::DateTime(dt) => {
-                return Some(dt.with_timezone(&Utc));
-            }
-            human_date_parser::ParseResult::Date(date) => {
-                return Some(to_datetime(date));
-            }
-            human_date_parser::ParseResult::Time(_) => {
-                // Time-only result, fall through to existing parsing
-            }
-        }
-    }
-
-    // Keywords
-    match input.as_str() {
-        "today" | "tod" => return Some(to_datetime(today)),
-        "tomorrow" | "tom" => return Some(to_datetime(today + Duration::days(1))),
-        "yesterday" => return Some(to_datetime(today - Duration::days(1))),
-        "next week" => return Some(to_datetime(today + Duration::days(7))),
-        "next month" => return Some(to_datetime(today + Duration::days(30))),
-        _ => {}
-    }
-
-    // Relative days: +1d, +3d, +1w, +2w, etc.
-    if let Some(rest) = input.strip_prefix('+') {
-        if let Some(days_str) = rest.strip_suffix('d')
-            && let Ok(days) = days_str.parse::<i64>() {
-                return Some(to_datetime(today + Duration::days(days)));
-            }
-        if let Some(weeks_str) = rest.strip_suffix('w')
-            && let Ok(weeks) = weeks_str.parse::<i64>() {
-                return Some(to_datetime(today + Duration::weeks(weeks)));
-            }
-    }
-
-    // Weekday names: mon, tue, wed, thu, fri, sat, sun
-    let weekday = match input.as_str() {
-        "mon" | "monday" => Some(Weekday::Mon),
-        "tue" | "tuesday" => Some(Weekday::Tue),
-        "wed" | "wednesday" => Some(Weekday::Wed),
-        "thu" | "thursday" => Some(Weekday::Thu),
-        "fri" | "friday" => Some(Weekday::Fri),
-        "sat" | "saturday" => Some(Weekday::Sat),
-        "sun" | "sunday" => Some(Weekday::Sun),
-        _ => None,
-    };
-
-    if let Some(target_weekday) = weekday {
-        let current_weekday = today.weekday();
-        let days_ahead = (target_weekday.num_days_from_monday() as i64
-            - current_weekday.num_days_from_monday() as i64
-            + 7) % 7;
-        // If it's the same day, go to next week
-        let days_ahead = if days_ahead == 0 { 7 } else { days_ahead };
-        return Some(to_datetime(today + Duration::days(days_ahead)));
-    }
-
-    // Try parsing as YYYY-MM-DD
-    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
-        return Some(to_datetime(date));
-    }
-
-    // Try parsing as YYYY/MM/DD
-    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y/%m/%d") {
-        return Some(to_datetime(date));
-    }
-
-    // Try parsing as MM/DD (assume current year)
-    if let Ok(date) = NaiveDate::parse_from_str(&format!("{}/{}", today.year(), input), "%Y/%m/%d") {
-        // If date is in the past, use next year
-        let date = if date < today {
-            NaiveDate::from_ymd_opt(today.year() + 1, date.month(), date.day()).unwrap_or(date)
-        } else {
-            date
-        };
-        return Some(to_datetime(date));
-    }
-
-    // Try parsing as DD/MM (assume current year) - European format
-    if input.contains('/') {
-        let parts: Vec<&str> = input.split('/').collect();
-        if parts.len() == 2
-            && let (Ok(day), Ok(month)) = (parts[0].parse::<u32>(), parts[1].parse::<u32>())
-                && day <= 31 && month <= 12
-                    && let Some(date) = NaiveDate::from_ymd_opt(today.year(), month, day) {
-                        let date = if date < today {
-                            NaiveDate::from_ymd_opt(today.year() + 1, month, day).unwrap_or(date)
-                        } else {
-                            date
-                        };
-                        return Some(to_datetime(date));
-                    }
-    }
-
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -739,25 +653,6 @@ mod tests {
         assert_eq!(task.priority, Priority::Urgent);
     }
 
-    #[test]
-    fn test_parse_due_date_today() {
-        let date = parse_due_date("today");
-        assert!(date.is_some());
-    }
-
-    #[test]
-    fn test_parse_due_date_iso() {
-        let date = parse_due_date("2025-12-31");
-        assert!(date.is_some());
-        let d = date.unwrap();
-        assert_eq!(d.format("%Y-%m-%d").to_string(), "2025-12-31");
-    }
-
-    #[test]
-    fn test_parse_due_date_empty() {
-        assert!(parse_due_date("").is_none());
-    }
-
     #[test]
     fn test_field_navigation() {
         let field = AddTaskField::Title;
@@ -781,4 +676,17 @@ mod tests {
         dialog.handle_key(key);
         assert_eq!(dialog.focused_field, AddTaskField::Description);
     }
+
+    #[test]
+    fn test_conflict_requires_second_save() {
+        let task = Task::new("Shared");
+        let mut dialog = AddTaskDialog::from_task(&task);
+        assert_eq!(dialog.base_updated_at, Some(task.updated_at));
+        assert!(dialog.confirm_save());
+
+        dialog.mark_conflict("Task was changed externally");
+        assert!(!dialog.confirm_save());
+        assert!(dialog.conflict.as_deref().unwrap().contains("save again"));
+        assert!(dialog.confirm_save());
+    }
 }
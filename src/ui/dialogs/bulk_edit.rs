@@ -0,0 +1,358 @@
+//! Bulk edit dialog.
+//!
+//! Asks for the new value of one field (project, priority, status, due date
+//! or a tag) to set on every marked task.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use super::{centered_rect, dialog_block, hint_style, selected_style, DialogAction};
+use crate::models::{BulkEdit, Priority, Project, TaskStatus};
+use crate::ui::input::TextInput;
+use crate::ui::theme;
+use crate::utils::parse_due_date;
+
+/// Priorities offered, in menu order.
+const PRIORITIES: [(Priority, &str); 4] = [
+    (Priority::Low, "Low"),
+    (Priority::Medium, "Medium"),
+    (Priority::High, "High"),
+    (Priority::Urgent, "Urgent"),
+];
+
+/// Statuses offered, in menu order.
+const STATUSES: [(TaskStatus, &str); 4] = [
+    (TaskStatus::Pending, "Pending"),
+    (TaskStatus::InProgress, "In Progress"),
+    (TaskStatus::Completed, "Completed"),
+    (TaskStatus::Archived, "Archived"),
+];
+
+/// The field a bulk edit changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkField {
+    /// Move to a project
+    Project,
+    /// Set the priority
+    Priority,
+    /// Set the status
+    Status,
+    /// Set or clear the due date
+    DueDate,
+    /// Add or remove a tag
+    Tags,
+}
+
+impl BulkField {
+    /// Returns the dialog title for this field.
+    fn title(self) -> &'static str {
+        match self {
+            BulkField::Project => "Move to Project",
+            BulkField::Priority => "Set Priority",
+            BulkField::Status => "Set Status",
+            BulkField::DueDate => "Set Due Date",
+            BulkField::Tags => "Edit Tags",
+        }
+    }
+}
+
+/// Dialog for choosing the change to make to the marked tasks.
+#[derive(Debug, Clone)]
+pub struct BulkEditDialog {
+    /// The field being changed
+    pub field: BulkField,
+    /// Number of marked tasks
+    pub count: usize,
+    /// Projects to move to, for [`BulkField::Project`]
+    pub projects: Vec<Project>,
+    /// Selected option, for the fields picked from a list
+    pub selected_index: usize,
+    /// Typed value, for the due date and tag fields
+    pub input: TextInput,
+    /// Whether the tag is removed rather than added
+    pub remove_tag: bool,
+    /// Why the typed value was refused
+    pub error: Option<String>,
+}
+
+impl BulkEditDialog {
+    /// Creates a dialog changing `field` on `count` tasks.
+    pub fn new(field: BulkField, count: usize, projects: Vec<Project>) -> Self {
+        let placeholder = match field {
+            BulkField::DueDate => "tomorrow, fri, +3d, 2026-12-01 (empty clears)",
+            _ => "Tag name",
+        };
+        Self {
+            field,
+            count,
+            projects,
+            selected_index: 0,
+            input: TextInput::new().with_placeholder(placeholder),
+            remove_tag: false,
+            error: None,
+        }
+    }
+
+    /// Returns the labels of the options, for the fields picked from a list.
+    fn options(&self) -> Vec<&str> {
+        match self.field {
+            BulkField::Project => self.projects.iter().map(|p| p.name.as_str()).collect(),
+            BulkField::Priority => PRIORITIES.iter().map(|(_, label)| *label).collect(),
+            BulkField::Status => STATUSES.iter().map(|(_, label)| *label).collect(),
+            BulkField::DueDate | BulkField::Tags => Vec::new(),
+        }
+    }
+
+    /// Returns whether the value is typed rather than picked from a list.
+    fn is_text(&self) -> bool {
+        matches!(self.field, BulkField::DueDate | BulkField::Tags)
+    }
+
+    /// Returns the chosen change, or `None` if the typed value is invalid.
+    pub fn to_edit(&self) -> Option<BulkEdit> {
+        let value = self.input.value().trim();
+        match self.field {
+            BulkField::Project => self
+                .projects
+                .get(self.selected_index)
+                .map(|p| BulkEdit::MoveToProject(p.id.clone())),
+            BulkField::Priority => PRIORITIES
+                .get(self.selected_index)
+                .map(|(p, _)| BulkEdit::SetPriority(*p)),
+            BulkField::Status => STATUSES
+                .get(self.selected_index)
+                .map(|(s, _)| BulkEdit::SetStatus(*s)),
+            BulkField::DueDate if value.is_empty() => Some(BulkEdit::SetDueDate(None)),
+            BulkField::DueDate => parse_due_date(value).map(|d| BulkEdit::SetDueDate(Some(d))),
+            BulkField::Tags => {
+                let tag = value.trim_start_matches('#').to_lowercase();
+                if tag.is_empty() || tag.contains(char::is_whitespace) {
+                    None
+                } else if self.remove_tag {
+                    Some(BulkEdit::RemoveTag(tag))
+                } else {
+                    Some(BulkEdit::AddTag(tag))
+                }
+            }
+        }
+    }
+
+    /// Returns the question for the confirmation dialog, naming the change
+    /// and how many tasks it affects.
+    pub fn summary(&self) -> String {
+        let tasks = task_count(self.count);
+        let option = self.options().get(self.selected_index).copied().unwrap_or_default();
+        match self.to_edit() {
+            Some(BulkEdit::MoveToProject(_)) => format!("Move {} to {}?", tasks, option),
+            Some(BulkEdit::SetPriority(_)) => format!("Set priority of {} to {}?", tasks, option),
+            Some(BulkEdit::SetStatus(_)) => format!("Set status of {} to {}?", tasks, option),
+            Some(BulkEdit::SetDueDate(Some(date))) => format!(
+                "Set due date of {} to {}?",
+                tasks,
+                date.with_timezone(&chrono::Local).format("%Y-%m-%d")
+            ),
+            Some(BulkEdit::SetDueDate(None)) => format!("Clear the due date of {}?", tasks),
+            Some(BulkEdit::AddTag(tag)) => format!("Add #{} to {}?", tag, tasks),
+            Some(BulkEdit::RemoveTag(tag)) => format!("Remove #{} from {}?", tag, tasks),
+            None => String::new(),
+        }
+    }
+
+    /// Handles a key event and returns the resulting action.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogAction {
+        match key.code {
+            KeyCode::Esc => DialogAction::Cancel,
+
+            KeyCode::Enter => {
+                if self.to_edit().is_some() {
+                    DialogAction::Submit
+                } else {
+                    self.error = Some(match self.field {
+                        BulkField::DueDate => "Unrecognized date".to_string(),
+                        _ => "Enter a single tag".to_string(),
+                    });
+                    DialogAction::None
+                }
+            }
+
+            // Add or remove the tag
+            KeyCode::Tab if self.field == BulkField::Tags => {
+                self.remove_tag = !self.remove_tag;
+                DialogAction::None
+            }
+
+            _ if self.is_text() => {
+                match key.code {
+                    KeyCode::Char(c) => self.input.insert(c),
+                    KeyCode::Backspace => self.input.delete_backward(),
+                    KeyCode::Delete => self.input.delete_forward(),
+                    KeyCode::Left => self.input.move_left(),
+                    KeyCode::Right => self.input.move_right(),
+                    _ => {}
+                }
+                self.error = None;
+                DialogAction::None
+            }
+
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                DialogAction::None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let count = self.options().len();
+                if count > 0 {
+                    self.selected_index = (self.selected_index + 1).min(count - 1);
+                }
+                DialogAction::None
+            }
+
+            _ => DialogAction::None,
+        }
+    }
+
+    /// Renders the dialog to the frame.
+    pub fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let options = self.options();
+        let body_height = if self.is_text() { 4 } else { options.len().min(12) as u16 };
+        let dialog_height = body_height + 5; // borders, spacer and help text
+        let dialog_width = 52.min(area.width.saturating_sub(4));
+        let dialog_area = centered_rect(dialog_width, dialog_height, area);
+
+        // Render dimmed background
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new("").style(Style::default().bg(theme::BG_DARK)),
+            area,
+        );
+
+        let title = format!("{} · {}", self.field.title(), task_count(self.count));
+        let block = dialog_block(&title, false);
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let [body_area, _, help_area] = Layout::vertical([
+            Constraint::Length(body_height),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let help = if self.is_text() {
+            let [input_area, note_area] =
+                Layout::vertical([Constraint::Length(3), Constraint::Length(1)]).areas(body_area);
+            let label = match (self.field, self.remove_tag) {
+                (BulkField::Tags, false) => "Add tag",
+                (BulkField::Tags, true) => "Remove tag",
+                _ => "Due date",
+            };
+            self.input
+                .render_to_buffer(input_area, frame.buffer_mut(), true, Some(label));
+            if let Some(error) = &self.error {
+                frame.render_widget(
+                    Paragraph::new(Span::styled(error.clone(), Style::default().fg(theme::ERROR))),
+                    note_area,
+                );
+            }
+            if self.field == BulkField::Tags {
+                "Tab:add/remove  Enter:apply  Esc:cancel"
+            } else {
+                "Enter:apply  Esc:cancel"
+            }
+        } else {
+            let lines: Vec<Line> = options
+                .iter()
+                .enumerate()
+                .map(|(i, label)| {
+                    let is_selected = i == self.selected_index;
+                    let style = if is_selected {
+                        selected_style()
+                    } else {
+                        Style::default().fg(theme::TEXT_PRIMARY)
+                    };
+                    let prefix = if is_selected { "▶ " } else { "  " };
+                    Line::from(vec![Span::styled(prefix, style), Span::styled(label.to_string(), style)])
+                })
+                .collect();
+            frame.render_widget(Paragraph::new(lines), body_area);
+            "↑↓:select  Enter:apply  Esc:cancel"
+        };
+
+        frame.render_widget(Paragraph::new(Span::styled(help, hint_style())), help_area);
+    }
+}
+
+/// Formats a task count, like "1 task" or "3 tasks".
+pub fn task_count(count: usize) -> String {
+    if count == 1 {
+        "1 task".to_string()
+    } else {
+        format!("{} tasks", count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(dialog: &mut BulkEditDialog, text: &str) {
+        for c in text.chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_pick_from_list() {
+        let mut dialog = BulkEditDialog::new(BulkField::Priority, 3, Vec::new());
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Down));
+        assert_eq!(dialog.to_edit(), Some(BulkEdit::SetPriority(Priority::High)));
+        assert_eq!(dialog.summary(), "Set priority of 3 tasks to High?");
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::Submit);
+
+        let projects = vec![Project::new("Work"), Project::new("Home")];
+        let home = projects[1].id.clone();
+        let mut dialog = BulkEditDialog::new(BulkField::Project, 1, projects);
+        dialog.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(dialog.to_edit(), Some(BulkEdit::MoveToProject(home)));
+        assert_eq!(dialog.summary(), "Move 1 task to Home?");
+    }
+
+    #[test]
+    fn test_tags() {
+        let mut dialog = BulkEditDialog::new(BulkField::Tags, 2, Vec::new());
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::None);
+        assert!(dialog.error.is_some());
+
+        type_text(&mut dialog, "#Review");
+        assert_eq!(dialog.to_edit(), Some(BulkEdit::AddTag("review".to_string())));
+        dialog.handle_key(key(KeyCode::Tab));
+        assert_eq!(dialog.to_edit(), Some(BulkEdit::RemoveTag("review".to_string())));
+        assert_eq!(dialog.summary(), "Remove #review from 2 tasks?");
+    }
+
+    #[test]
+    fn test_due_date() {
+        let mut dialog = BulkEditDialog::new(BulkField::DueDate, 2, Vec::new());
+        assert_eq!(dialog.to_edit(), Some(BulkEdit::SetDueDate(None)));
+
+        type_text(&mut dialog, "not a date at all");
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::None);
+        assert_eq!(dialog.error.as_deref(), Some("Unrecognized date"));
+
+        dialog.input.set_value("tomorrow");
+        assert!(matches!(dialog.to_edit(), Some(BulkEdit::SetDueDate(Some(_)))));
+    }
+}
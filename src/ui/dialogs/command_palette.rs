@@ -0,0 +1,621 @@
+//! Command palette dialog.
+//!
+//! A spotlight-style overlay listing every action by name, fuzzy filtered as
+//! you type. Typing `verb: argument` runs a parameterized action instead:
+//!
+//! | Input | Action |
+//! |-------|--------|
+//! | `set filter: overdue` | Switch the task list filter (`#tag` filters by tag) |
+//! | `move to project: Platform` | Move the selected task to a project |
+//! | `set due: next fri` | Set the selected task's due date (`none` clears it) |
+//! | `set priority: high` | Set the selected task's priority |
+//! | `go to task: <title>` | Jump to a task |
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::{render_spotlight, DialogAction};
+use crate::models::{BulkEdit, Filter, Priority, Project, Task};
+use crate::storage::Tag;
+use crate::ui::input::TextInput;
+use crate::ui::theme;
+use crate::utils::{format_relative_date, parse_due_date};
+
+/// Most suggestions shown at once.
+const MAX_VISIBLE: usize = 8;
+
+/// An action the palette lists, named for humans.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteAction {
+    /// Human name, e.g. "Toggle task completion"
+    pub name: String,
+    /// Current keybindings, e.g. "Space", or empty if it has none
+    pub keys: String,
+}
+
+/// What the user picked in the palette.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteChoice {
+    /// The action at this index of the listed actions
+    Action(usize),
+    /// Switch the task list filter
+    SetFilter(Filter),
+    /// Change the selected task
+    Edit(BulkEdit),
+    /// Jump to the task with this ID
+    GoToTask(String),
+}
+
+/// Actions that take an argument after a colon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Parameter {
+    Filter,
+    Project,
+    Due,
+    Priority,
+    Task,
+}
+
+impl Parameter {
+    /// Every parameter with its verb and an example argument.
+    const ALL: &'static [(Parameter, &'static str, &'static str)] = &[
+        (Parameter::Filter, "Set filter", "overdue, due today, #tag"),
+        (Parameter::Project, "Move to project", "project name"),
+        (Parameter::Due, "Set due", "next fri, +3d, none"),
+        (Parameter::Priority, "Set priority", "low, medium, high, urgent"),
+        (Parameter::Task, "Go to task", "task title"),
+    ];
+
+    fn verb(self) -> &'static str {
+        Self::ALL.iter().find(|(p, _, _)| *p == self).map(|(_, v, _)| *v).unwrap_or("")
+    }
+}
+
+/// A row in the suggestion list.
+#[derive(Debug, Clone, PartialEq)]
+struct Suggestion {
+    label: String,
+    detail: String,
+    target: Target,
+}
+
+/// What selecting a suggestion does.
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    /// Submit the dialog with this choice
+    Choice(PaletteChoice),
+    /// Fill in `verb: ` so the user can type the argument
+    Complete(Parameter),
+}
+
+/// Scores how well `query` fuzzy matches `text`, or `None` if it doesn't.
+///
+/// Every character of the query must appear in `text` in order, ignoring
+/// case. Runs of consecutive characters and matches at the start of words
+/// score higher, so "tc" prefers "Toggle completion" over "Edit tags".
+///
+/// # Examples
+///
+/// ```
+/// use ratado::ui::dialogs::fuzzy_score;
+///
+/// assert!(fuzzy_score("qc", "Quick capture").is_some());
+/// assert!(fuzzy_score("cq", "Quick capture").is_none());
+/// assert!(fuzzy_score("quick", "Quick capture") > fuzzy_score("qc", "Quick capture"));
+/// ```
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for q in query {
+        let position = (next..text.len()).find(|&i| text[i] == q)?;
+        score += 1;
+        if position == 0 || !text[position - 1].is_alphanumeric() {
+            score += 8;
+        }
+        match previous {
+            Some(p) if p + 1 == position => score += 5,
+            Some(p) => score -= (position - p - 1).min(5) as i64,
+            None => score -= position.min(5) as i64,
+        }
+        previous = Some(position);
+        next = position + 1;
+    }
+    Some(score)
+}
+
+/// Spotlight-style dialog for running any action by name.
+#[derive(Debug, Clone)]
+pub struct CommandPaletteDialog {
+    /// Actions to list, in display order
+    actions: Vec<PaletteAction>,
+    /// Projects for `move to project:`
+    projects: Vec<Project>,
+    /// Tag names for `set filter: #tag`
+    tags: Vec<String>,
+    /// Task IDs and titles for `go to task:`
+    tasks: Vec<(String, String)>,
+    /// The typed query
+    pub input: TextInput,
+    /// Suggestions matching the query, best first
+    suggestions: Vec<Suggestion>,
+    /// Index of the highlighted suggestion
+    pub selected_index: usize,
+}
+
+impl CommandPaletteDialog {
+    /// Creates a palette listing `actions`.
+    ///
+    /// `projects`, `tags` and `tasks` supply the arguments of the
+    /// parameterized actions.
+    pub fn new(actions: Vec<PaletteAction>, projects: &[Project], tags: &[Tag], tasks: &[Task]) -> Self {
+        let mut dialog = Self {
+            actions,
+            projects: projects.to_vec(),
+            tags: tags.iter().map(|t| t.name.clone()).collect(),
+            tasks: tasks.iter().map(|t| (t.id.clone(), t.title.clone())).collect(),
+            input: TextInput::new(),
+            suggestions: Vec::new(),
+            selected_index: 0,
+        };
+        dialog.refresh();
+        dialog
+    }
+
+    /// Returns what the highlighted suggestion does, if it runs something.
+    pub fn selected_choice(&self) -> Option<&PaletteChoice> {
+        match &self.suggestions.get(self.selected_index)?.target {
+            Target::Choice(choice) => Some(choice),
+            Target::Complete(_) => None,
+        }
+    }
+
+    /// Returns the listed actions.
+    pub fn actions(&self) -> &[PaletteAction] {
+        &self.actions
+    }
+
+    /// Returns the labels of the current suggestions, best first.
+    pub fn suggestion_labels(&self) -> Vec<&str> {
+        self.suggestions.iter().map(|s| s.label.as_str()).collect()
+    }
+
+    /// Handles a key event and returns the resulting action.
+    ///
+    /// Enter runs the highlighted suggestion, or fills in `verb: ` when it's
+    /// a parameterized action; Tab only fills in.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => DialogAction::Cancel,
+            KeyCode::Enter => match self.suggestions.get(self.selected_index).map(|s| &s.target) {
+                Some(Target::Choice(_)) => DialogAction::Submit,
+                Some(Target::Complete(parameter)) => {
+                    self.complete(*parameter);
+                    DialogAction::None
+                }
+                None => DialogAction::None,
+            },
+            KeyCode::Tab => {
+                if let Some(Target::Complete(parameter)) =
+                    self.suggestions.get(self.selected_index).map(|s| &s.target)
+                {
+                    self.complete(*parameter);
+                }
+                DialogAction::None
+            }
+
+            KeyCode::Up => {
+                self.select_previous();
+                DialogAction::None
+            }
+            KeyCode::Char('p') if ctrl => {
+                self.select_previous();
+                DialogAction::None
+            }
+            KeyCode::Down => {
+                self.select_next();
+                DialogAction::None
+            }
+            KeyCode::Char('n') if ctrl => {
+                self.select_next();
+                DialogAction::None
+            }
+
+            KeyCode::Char('w') if ctrl => {
+                self.input.delete_word_backward();
+                self.refresh();
+                DialogAction::None
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.input.insert(c);
+                self.refresh();
+                DialogAction::None
+            }
+            KeyCode::Backspace => {
+                self.input.delete_backward();
+                self.refresh();
+                DialogAction::None
+            }
+            KeyCode::Delete => {
+                self.input.delete_forward();
+                self.refresh();
+                DialogAction::None
+            }
+            KeyCode::Left => {
+                self.input.move_left();
+                DialogAction::None
+            }
+            KeyCode::Right => {
+                self.input.move_right();
+                DialogAction::None
+            }
+            KeyCode::Home => {
+                self.input.move_home();
+                DialogAction::None
+            }
+            KeyCode::End => {
+                self.input.move_end();
+                DialogAction::None
+            }
+
+            _ => DialogAction::None,
+        }
+    }
+
+    fn select_previous(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    fn select_next(&mut self) {
+        if !self.suggestions.is_empty() {
+            self.selected_index = (self.selected_index + 1).min(self.suggestions.len() - 1);
+        }
+    }
+
+    /// Replaces the input with `verb: ` for `parameter`.
+    fn complete(&mut self, parameter: Parameter) {
+        self.input.set_value(format!("{}: ", parameter.verb().to_lowercase()));
+        self.refresh();
+    }
+
+    /// Recomputes the suggestions for the current input.
+    fn refresh(&mut self) {
+        let query = self.input.value().trim_start().to_string();
+        self.suggestions = match self.split_parameter(&query) {
+            Some((parameter, argument)) => self.argument_suggestions(parameter, argument),
+            None => self.action_suggestions(&query),
+        };
+        self.selected_index = 0;
+    }
+
+    /// Splits `verb: argument` input, matching the verb fuzzily so that
+    /// `due: fri` works as well as `set due: fri`.
+    fn split_parameter<'q>(&self, query: &'q str) -> Option<(Parameter, &'q str)> {
+        let (verb, argument) = query.split_once(':')?;
+        let verb = verb.trim();
+        if verb.is_empty() {
+            return None;
+        }
+        Parameter::ALL
+            .iter()
+            .filter_map(|(parameter, name, _)| fuzzy_score(verb, name).map(|score| (score, *parameter)))
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, parameter)| (parameter, argument.trim()))
+    }
+
+    /// Actions and parameter verbs matching `query`, best first.
+    fn action_suggestions(&self, query: &str) -> Vec<Suggestion> {
+        let actions = self.actions.iter().enumerate().map(|(i, action)| Suggestion {
+            label: action.name.clone(),
+            detail: action.keys.clone(),
+            target: Target::Choice(PaletteChoice::Action(i)),
+        });
+        let parameters = Parameter::ALL.iter().map(|(parameter, verb, example)| Suggestion {
+            label: format!("{}: …", verb),
+            detail: (*example).to_string(),
+            target: Target::Complete(*parameter),
+        });
+        rank(query, actions.chain(parameters))
+    }
+
+    /// Arguments for `parameter` matching `argument`, best first.
+    fn argument_suggestions(&self, parameter: Parameter, argument: &str) -> Vec<Suggestion> {
+        let verb = parameter.verb();
+        let choice = |label: String, choice: PaletteChoice| Suggestion {
+            label,
+            detail: verb.to_string(),
+            target: Target::Choice(choice),
+        };
+
+        match parameter {
+            Parameter::Filter => {
+                let filters = FILTERS
+                    .iter()
+                    .map(|(filter, name)| choice((*name).to_string(), PaletteChoice::SetFilter(filter.clone())));
+                let tags = self
+                    .tags
+                    .iter()
+                    .map(|tag| choice(format!("#{}", tag), PaletteChoice::SetFilter(Filter::ByTag(tag.clone()))));
+                rank(argument, filters.chain(tags))
+            }
+            Parameter::Project => rank(
+                argument,
+                self.projects.iter().map(|project| {
+                    choice(
+                        project.name.clone(),
+                        PaletteChoice::Edit(BulkEdit::MoveToProject(project.id.clone())),
+                    )
+                }),
+            ),
+            Parameter::Priority => rank(
+                argument,
+                PRIORITIES
+                    .iter()
+                    .map(|(priority, name)| choice((*name).to_string(), PaletteChoice::Edit(BulkEdit::SetPriority(*priority)))),
+            ),
+            Parameter::Task => rank(
+                argument,
+                self.tasks
+                    .iter()
+                    .map(|(id, title)| choice(title.clone(), PaletteChoice::GoToTask(id.clone()))),
+            ),
+            Parameter::Due => {
+                if matches!(argument.to_lowercase().as_str(), "none" | "clear") {
+                    vec![choice("Clear due date".to_string(), PaletteChoice::Edit(BulkEdit::SetDueDate(None)))]
+                } else {
+                    parse_due_date(argument)
+                        .map(|due| {
+                            choice(
+                                format!("Due {}", format_relative_date(due)),
+                                PaletteChoice::Edit(BulkEdit::SetDueDate(Some(due))),
+                            )
+                        })
+                        .into_iter()
+                        .collect()
+                }
+            }
+        }
+    }
+
+    /// Renders the dialog to the frame.
+    pub fn render(&self, frame: &mut Frame) {
+        let rows = self.suggestions.len().clamp(1, MAX_VISIBLE) as u16;
+        // Input line, separator and the suggestion rows, plus borders
+        let (_, inner) = render_spotlight(frame, "Commands", rows + 4);
+        if inner.height < 3 || inner.width < 10 {
+            return;
+        }
+
+        let [input_area, separator_area, list_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
+        .areas(inner);
+
+        self.render_input_line(frame, input_area);
+        frame.render_widget(
+            Paragraph::new("─".repeat(separator_area.width as usize))
+                .style(Style::default().fg(theme::BORDER_MUTED)),
+            separator_area,
+        );
+        self.render_suggestions(frame, list_area);
+    }
+
+    /// Renders the prompt, query and cursor.
+    fn render_input_line(&self, frame: &mut Frame, area: Rect) {
+        let prompt = Span::styled("> ", Style::default().fg(theme::ACCENT));
+        let value = self.input.value();
+        let line = if value.is_empty() {
+            Line::from(vec![
+                prompt,
+                Span::styled(
+                    "Type a command, or e.g. set due: next fri",
+                    Style::default().fg(theme::TEXT_MUTED),
+                ),
+            ])
+        } else {
+            Line::from(vec![prompt, Span::styled(value.to_string(), Style::default().fg(theme::TEXT_PRIMARY))])
+        };
+        frame.render_widget(Paragraph::new(line), area);
+
+        let cursor_x = area.x + 2 + self.input.cursor() as u16; // 2 for "> "
+        if cursor_x < area.x + area.width {
+            let cursor_char = value.chars().nth(self.input.cursor()).unwrap_or(' ');
+            frame.buffer_mut()[(cursor_x, area.y)]
+                .set_char(cursor_char)
+                .set_style(Style::default().bg(theme::ACCENT).fg(ratatui::style::Color::Black));
+        }
+    }
+
+    /// Renders the suggestions, scrolled to keep the highlighted one visible.
+    fn render_suggestions(&self, frame: &mut Frame, area: Rect) {
+        if self.suggestions.is_empty() {
+            let message = match self.split_parameter(self.input.value().trim_start()) {
+                Some((Parameter::Due, "")) => "Type a date: tomorrow, fri, +3d, 2026-03-01, none",
+                Some((Parameter::Due, _)) => "Can't read that date",
+                _ => "No matching commands",
+            };
+            frame.render_widget(
+                Paragraph::new(Span::styled(message, Style::default().fg(theme::TEXT_MUTED))),
+                area,
+            );
+            return;
+        }
+
+        let visible = area.height as usize;
+        let offset = (self.selected_index + 1).saturating_sub(visible);
+        let lines: Vec<Line> = self
+            .suggestions
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(i, suggestion)| {
+                let selected = i == self.selected_index;
+                let label_style = if selected {
+                    Style::default()
+                        .bg(theme::PRIMARY)
+                        .fg(theme::TEXT_PRIMARY)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme::TEXT_PRIMARY)
+                };
+                let detail_style = if selected {
+                    Style::default().bg(theme::PRIMARY).fg(theme::TEXT_SECONDARY)
+                } else {
+                    Style::default().fg(theme::TEXT_MUTED)
+                };
+
+                // Right-align the keybindings or hint
+                let width = area.width as usize;
+                let label = format!(" {}", suggestion.label);
+                let detail = format!("{} ", suggestion.detail);
+                let padding = width.saturating_sub(label.chars().count() + detail.chars().count());
+                Line::from(vec![
+                    Span::styled(label, label_style),
+                    Span::styled(" ".repeat(padding), label_style),
+                    Span::styled(detail, detail_style),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+}
+
+/// Filters offered by `set filter:`.
+const FILTERS: &[(Filter, &str)] = &[
+    (Filter::All, "All"),
+    (Filter::Pending, "Pending"),
+    (Filter::InProgress, "In Progress"),
+    (Filter::Completed, "Completed"),
+    (Filter::Archived, "Archived"),
+    (Filter::DueToday, "Due Today"),
+    (Filter::DueThisWeek, "Due This Week"),
+    (Filter::Overdue, "Overdue"),
+    (Filter::ByPriority(Priority::Urgent), "Urgent"),
+    (Filter::ByPriority(Priority::High), "High"),
+    (Filter::ByPriority(Priority::Medium), "Medium"),
+    (Filter::ByPriority(Priority::Low), "Low"),
+];
+
+/// Priorities offered by `set priority:`.
+const PRIORITIES: &[(Priority, &str)] = &[
+    (Priority::Urgent, "Urgent"),
+    (Priority::High, "High"),
+    (Priority::Medium, "Medium"),
+    (Priority::Low, "Low"),
+];
+
+/// Keeps the suggestions matching `query`, best first. Ties keep their
+/// original order.
+fn rank(query: &str, suggestions: impl Iterator<Item = Suggestion>) -> Vec<Suggestion> {
+    let mut scored: Vec<(i64, Suggestion)> = suggestions
+        .filter_map(|s| fuzzy_score(query, &s.label).map(|score| (score, s)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, s)| s).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(dialog: &mut CommandPaletteDialog, text: &str) {
+        for c in text.chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    fn dialog() -> CommandPaletteDialog {
+        let actions = ["Quick capture", "Toggle task completion", "Edit tags"]
+            .into_iter()
+            .map(|name| PaletteAction { name: name.to_string(), keys: String::new() })
+            .collect();
+        let projects = vec![Project::new("Platform"), Project::new("Home")];
+        let tags = vec![Tag { id: "1".to_string(), name: "review".to_string() }];
+        let tasks = vec![Task::new("Write quarterly report")];
+        CommandPaletteDialog::new(actions, &projects, &tags, &tasks)
+    }
+
+    #[test]
+    fn test_fuzzy_filtering() {
+        let mut dialog = dialog();
+        assert_eq!(dialog.suggestion_labels().len(), 3 + Parameter::ALL.len());
+
+        type_text(&mut dialog, "tc");
+        assert_eq!(dialog.suggestion_labels()[0], "Toggle task completion");
+        assert_eq!(dialog.selected_choice(), Some(&PaletteChoice::Action(1)));
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::Submit);
+
+        type_text(&mut dialog, "zzz");
+        assert!(dialog.suggestion_labels().is_empty());
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::None);
+    }
+
+    #[test]
+    fn test_enter_on_parameter_completes_verb() {
+        let mut dialog = dialog();
+        type_text(&mut dialog, "move");
+        assert_eq!(dialog.suggestion_labels()[0], "Move to project: …");
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::None);
+        assert_eq!(dialog.input.value(), "move to project: ");
+
+        type_text(&mut dialog, "plat");
+        assert_eq!(dialog.suggestion_labels(), vec!["Platform"]);
+        assert!(matches!(
+            dialog.selected_choice(),
+            Some(PaletteChoice::Edit(BulkEdit::MoveToProject(_)))
+        ));
+    }
+
+    #[test]
+    fn test_parameterized_input() {
+        let mut dialog = dialog();
+        type_text(&mut dialog, "set filter: overdue");
+        assert_eq!(dialog.selected_choice(), Some(&PaletteChoice::SetFilter(Filter::Overdue)));
+
+        dialog.input.clear();
+        type_text(&mut dialog, "filter: #rev");
+        assert_eq!(
+            dialog.selected_choice(),
+            Some(&PaletteChoice::SetFilter(Filter::ByTag("review".to_string())))
+        );
+
+        dialog.input.clear();
+        type_text(&mut dialog, "set due: tomorrow");
+        assert!(matches!(
+            dialog.selected_choice(),
+            Some(PaletteChoice::Edit(BulkEdit::SetDueDate(Some(_))))
+        ));
+
+        dialog.input.clear();
+        type_text(&mut dialog, "due: none");
+        assert_eq!(dialog.selected_choice(), Some(&PaletteChoice::Edit(BulkEdit::SetDueDate(None))));
+
+        dialog.input.clear();
+        type_text(&mut dialog, "go to task: quarterly");
+        assert!(matches!(dialog.selected_choice(), Some(PaletteChoice::GoToTask(_))));
+
+        dialog.input.clear();
+        type_text(&mut dialog, "priority: hi");
+        assert_eq!(
+            dialog.selected_choice(),
+            Some(&PaletteChoice::Edit(BulkEdit::SetPriority(Priority::High)))
+        );
+    }
+}
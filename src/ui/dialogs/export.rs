@@ -0,0 +1,493 @@
+//! Export report dialog.
+//!
+//! A popup dialog for choosing the format, grouping, columns and output file
+//! of a report of the current task list view.
+
+use std::path::PathBuf;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use super::{centered_rect, dialog_block, field_block, hint_style, selected_style, DialogAction};
+use crate::interop::report::{ReportColumn, ReportFormat, ReportGroup, ReportOptions};
+use crate::ui::input::TextInput;
+use crate::ui::theme;
+
+/// Which section of the dialog is focused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportSection {
+    #[default]
+    Format,
+    Group,
+    Columns,
+    Path,
+}
+
+impl ExportSection {
+    /// Returns the section after this one, wrapping around.
+    fn next(self) -> Self {
+        match self {
+            ExportSection::Format => ExportSection::Group,
+            ExportSection::Group => ExportSection::Columns,
+            ExportSection::Columns => ExportSection::Path,
+            ExportSection::Path => ExportSection::Format,
+        }
+    }
+
+    /// Returns the section before this one, wrapping around.
+    fn prev(self) -> Self {
+        match self {
+            ExportSection::Format => ExportSection::Path,
+            ExportSection::Group => ExportSection::Format,
+            ExportSection::Columns => ExportSection::Group,
+            ExportSection::Path => ExportSection::Columns,
+        }
+    }
+}
+
+/// Dialog for exporting the visible tasks as a Markdown or CSV report.
+#[derive(Debug, Clone)]
+pub struct ExportDialog {
+    /// Currently focused section
+    pub section: ExportSection,
+    /// Selected format index
+    pub format_index: usize,
+    /// Selected grouping index
+    pub group_index: usize,
+    /// Whether each of [`ReportColumn::ALL`] is included
+    pub columns: Vec<bool>,
+    /// Highlighted column index
+    pub column_index: usize,
+    /// Output file path
+    pub path: TextInput,
+    /// Whether the path was typed rather than generated
+    path_edited: bool,
+    /// Base name of the generated path, without the extension
+    default_stem: String,
+    /// Number of tasks that will be exported
+    pub task_count: usize,
+}
+
+impl ExportDialog {
+    /// All available formats.
+    const FORMATS: &'static [(ReportFormat, &'static str, &'static str)] = &[
+        (ReportFormat::Markdown, "Markdown", "Checklist for PRs and wikis"),
+        (ReportFormat::Csv, "CSV", "For spreadsheets"),
+    ];
+
+    /// All available groupings.
+    const GROUPS: &'static [(ReportGroup, &'static str)] = &[
+        (ReportGroup::None, "No grouping"),
+        (ReportGroup::Project, "Project"),
+        (ReportGroup::Tag, "Tag"),
+        (ReportGroup::Status, "Status"),
+        (ReportGroup::DueWeek, "Due week"),
+    ];
+
+    /// Creates a new dialog for exporting `task_count` tasks.
+    ///
+    /// The path defaults to a timestamped file in the current directory.
+    pub fn new(task_count: usize) -> Self {
+        let default_stem = format!("ratado-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        let mut dialog = Self {
+            section: ExportSection::Format,
+            format_index: 0,
+            group_index: 0,
+            columns: ReportColumn::ALL
+                .iter()
+                .map(|c| ReportColumn::DEFAULT.contains(c))
+                .collect(),
+            column_index: 0,
+            path: TextInput::new().with_placeholder("File to write..."),
+            path_edited: false,
+            default_stem,
+            task_count,
+        };
+        dialog.update_default_path();
+        dialog
+    }
+
+    /// Returns the selected format.
+    pub fn selected_format(&self) -> ReportFormat {
+        Self::FORMATS[self.format_index].0
+    }
+
+    /// Returns the selected grouping.
+    pub fn selected_group(&self) -> ReportGroup {
+        Self::GROUPS[self.group_index].0
+    }
+
+    /// Returns the checked columns, in their usual order.
+    pub fn selected_columns(&self) -> Vec<ReportColumn> {
+        ReportColumn::ALL
+            .iter()
+            .zip(&self.columns)
+            .filter(|(_, checked)| **checked)
+            .map(|(column, _)| *column)
+            .collect()
+    }
+
+    /// Returns the report options chosen in the dialog.
+    pub fn options(&self) -> ReportOptions {
+        ReportOptions {
+            columns: self.selected_columns(),
+            group_by: self.selected_group(),
+        }
+    }
+
+    /// Returns the output path, relative to the current directory unless absolute.
+    pub fn output_path(&self) -> PathBuf {
+        PathBuf::from(self.path.value().trim())
+    }
+
+    /// Keeps the generated file name's extension in step with the format.
+    fn update_default_path(&mut self) {
+        if !self.path_edited {
+            self.path.set_value(format!(
+                "{}.{}",
+                self.default_stem,
+                self.selected_format().extension()
+            ));
+        }
+    }
+
+    /// Handles a key event and returns the resulting action.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogAction {
+        match key.code {
+            // Cancel
+            KeyCode::Esc => return DialogAction::Cancel,
+
+            // Export, once there is something to write and somewhere to write it
+            KeyCode::Enter => {
+                if self.selected_columns().is_empty() || self.path.value().trim().is_empty() {
+                    return DialogAction::None;
+                }
+                return DialogAction::Submit;
+            }
+
+            // Move between sections
+            KeyCode::Tab => {
+                self.section = self.section.next();
+                return DialogAction::None;
+            }
+            KeyCode::BackTab => {
+                self.section = self.section.prev();
+                return DialogAction::None;
+            }
+
+            _ => {}
+        }
+
+        if self.section == ExportSection::Path {
+            self.handle_path_input(key);
+            return DialogAction::None;
+        }
+
+        match key.code {
+            KeyCode::Char('q') => return DialogAction::Cancel,
+
+            KeyCode::Left | KeyCode::Char('h') => self.section = self.section.prev(),
+            KeyCode::Right | KeyCode::Char('l') => self.section = self.section.next(),
+
+            // Navigate within section
+            KeyCode::Up | KeyCode::Char('k') => match self.section {
+                ExportSection::Format => {
+                    self.format_index = self.format_index.saturating_sub(1);
+                    self.update_default_path();
+                }
+                ExportSection::Group => {
+                    self.group_index = self.group_index.saturating_sub(1);
+                }
+                ExportSection::Columns => {
+                    self.column_index = self.column_index.saturating_sub(1);
+                }
+                ExportSection::Path => {}
+            },
+            KeyCode::Down | KeyCode::Char('j') => match self.section {
+                ExportSection::Format => {
+                    self.format_index = (self.format_index + 1).min(Self::FORMATS.len() - 1);
+                    self.update_default_path();
+                }
+                ExportSection::Group => {
+                    self.group_index = (self.group_index + 1).min(Self::GROUPS.len() - 1);
+                }
+                ExportSection::Columns => {
+                    self.column_index = (self.column_index + 1).min(self.columns.len() - 1);
+                }
+                ExportSection::Path => {}
+            },
+
+            // Toggle the highlighted column
+            KeyCode::Char(' ') if self.section == ExportSection::Columns => {
+                self.columns[self.column_index] = !self.columns[self.column_index];
+            }
+
+            _ => {}
+        }
+        DialogAction::None
+    }
+
+    /// Handles input for the path field.
+    fn handle_path_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) => self.path.insert(c),
+            KeyCode::Backspace => self.path.delete_backward(),
+            KeyCode::Delete => self.path.delete_forward(),
+            KeyCode::Left => self.path.move_left(),
+            KeyCode::Right => self.path.move_right(),
+            KeyCode::Home => self.path.move_home(),
+            KeyCode::End => self.path.move_end(),
+            _ => return,
+        }
+        self.path_edited = true;
+    }
+
+    /// Renders the dialog to the frame.
+    pub fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        // Dialog dimensions
+        let dialog_width = 76.min(area.width.saturating_sub(4));
+        let dialog_height = 20.min(area.height.saturating_sub(2));
+        let dialog_area = centered_rect(dialog_width, dialog_height, area);
+
+        // Render dimmed background
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new("").style(Style::default().bg(theme::BG_DARK)),
+            area,
+        );
+
+        // Render dialog box with themed styling
+        let title = format!("Export {} Tasks", self.task_count);
+        let block = dialog_block(&title, false);
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let [columns_area, path_area, help_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+        let columns = Layout::horizontal([
+            Constraint::Percentage(38),
+            Constraint::Percentage(30),
+            Constraint::Percentage(32),
+        ])
+        .split(columns_area);
+
+        let formats: Vec<(&str, Option<&str>)> = Self::FORMATS
+            .iter()
+            .map(|(_, name, desc)| (*name, Some(*desc)))
+            .collect();
+        self.render_list(
+            frame,
+            columns[0],
+            "Format",
+            &formats,
+            self.format_index,
+            self.section == ExportSection::Format,
+        );
+
+        let groups: Vec<(&str, Option<&str>)> =
+            Self::GROUPS.iter().map(|(_, name)| (*name, None)).collect();
+        self.render_list(
+            frame,
+            columns[1],
+            "Group by",
+            &groups,
+            self.group_index,
+            self.section == ExportSection::Group,
+        );
+
+        self.render_columns(frame, columns[2]);
+
+        self.path.render_to_buffer(
+            path_area,
+            frame.buffer_mut(),
+            self.section == ExportSection::Path,
+            Some("Save to"),
+        );
+
+        let hint = match self.section {
+            ExportSection::Columns => "Tab:next  ↑↓:select  Space:toggle  Enter:export  Esc:cancel",
+            ExportSection::Path => "Tab:next  Enter:export  Esc:cancel",
+            _ => "Tab:next  ↑↓:select  Enter:export  Esc:cancel",
+        };
+        frame.render_widget(Paragraph::new(Span::styled(hint, hint_style())), help_area);
+    }
+
+    /// Renders the column checklist.
+    fn render_columns(&self, frame: &mut Frame, area: Rect) {
+        let is_focused = self.section == ExportSection::Columns;
+        let block = field_block("Columns", is_focused);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let lines: Vec<Line> = ReportColumn::ALL
+            .iter()
+            .zip(&self.columns)
+            .enumerate()
+            .map(|(i, (column, checked))| {
+                let style = if i == self.column_index && is_focused {
+                    selected_style()
+                } else {
+                    Style::default().fg(theme::TEXT_PRIMARY)
+                };
+                let checkbox = if *checked { "[x] " } else { "[ ] " };
+                Line::from(vec![
+                    Span::styled(checkbox, style),
+                    Span::styled(column.name(), style),
+                ])
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_list(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        title: &str,
+        items: &[(&str, Option<&str>)],
+        selected: usize,
+        is_focused: bool,
+    ) {
+        let block = field_block(title, is_focused);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines: Vec<Line> = Vec::new();
+        for (i, (name, desc)) in items.iter().enumerate() {
+            let is_selected = i == selected;
+            let style = if is_selected && is_focused {
+                selected_style()
+            } else if is_selected {
+                Style::default()
+                    .fg(theme::ACCENT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::TEXT_PRIMARY)
+            };
+
+            let prefix = if is_selected { "▶ " } else { "  " };
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(*name, style),
+            ]));
+
+            // Show description for selected item
+            if is_selected && let Some(desc) = desc {
+                lines.push(Line::from(Span::styled(
+                    format!("    {}", desc),
+                    hint_style(),
+                )));
+            }
+        }
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_defaults() {
+        let dialog = ExportDialog::new(3);
+        assert_eq!(dialog.selected_format(), ReportFormat::Markdown);
+        assert_eq!(dialog.selected_group(), ReportGroup::None);
+    }
+
+    #[test]
+    fn test_select_format_and_group() {
+        let mut dialog = ExportDialog::new(3);
+        dialog.handle_key(key(KeyCode::Down));
+        assert_eq!(dialog.selected_format(), ReportFormat::Csv);
+
+        dialog.handle_key(key(KeyCode::Tab));
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Down));
+        assert_eq!(dialog.selected_group(), ReportGroup::Tag);
+        // Format is unchanged while the group section is focused
+        assert_eq!(dialog.selected_format(), ReportFormat::Csv);
+    }
+
+    #[test]
+    fn test_navigation_clamps() {
+        let mut dialog = ExportDialog::new(0);
+        dialog.handle_key(key(KeyCode::Up));
+        assert_eq!(dialog.format_index, 0);
+        for _ in 0..5 {
+            dialog.handle_key(key(KeyCode::Down));
+        }
+        assert_eq!(dialog.format_index, 1);
+    }
+
+    #[test]
+    fn test_toggle_columns() {
+        let mut dialog = ExportDialog::new(3);
+        assert_eq!(dialog.selected_columns(), ReportColumn::DEFAULT);
+
+        dialog.section = ExportSection::Columns;
+        // Id is first and unchecked by default; Title is second and checked
+        dialog.handle_key(key(KeyCode::Char(' ')));
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Char(' ')));
+        let columns = dialog.selected_columns();
+        assert_eq!(columns[0], ReportColumn::Id);
+        assert!(!columns.contains(&ReportColumn::Title));
+        assert_eq!(dialog.options().columns, columns);
+    }
+
+    #[test]
+    fn test_path_follows_format_until_edited() {
+        let mut dialog = ExportDialog::new(3);
+        assert!(dialog.path.value().ends_with(".md"));
+        dialog.handle_key(key(KeyCode::Down));
+        assert!(dialog.path.value().ends_with(".csv"));
+
+        dialog.section = ExportSection::Path;
+        dialog.path.clear();
+        for c in "out/q.md".chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(dialog.output_path(), PathBuf::from("out/q.md"));
+
+        dialog.section = ExportSection::Format;
+        dialog.handle_key(key(KeyCode::Up));
+        assert_eq!(dialog.path.value(), "out/q.md");
+    }
+
+    #[test]
+    fn test_submit_needs_columns_and_path() {
+        let mut dialog = ExportDialog::new(1);
+        dialog.columns.iter_mut().for_each(|c| *c = false);
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::None);
+
+        let mut dialog = ExportDialog::new(1);
+        dialog.path.clear();
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::None);
+    }
+
+    #[test]
+    fn test_submit_and_cancel() {
+        let mut dialog = ExportDialog::new(1);
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::Submit);
+        assert_eq!(dialog.handle_key(key(KeyCode::Esc)), DialogAction::Cancel);
+    }
+}
@@ -1,6 +1,8 @@
 //! Filter and sort selection dialog.
 //!
-//! A popup dialog for selecting filter and sort options with keyboard navigation.
+//! A popup dialog for selecting filter, sort and grouping options with
+//! keyboard navigation. Besides the main sort, a "Then by" list holds
+//! secondary sort keys, added from the sort options with `a`.
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
@@ -12,7 +14,7 @@ use ratatui::{
 };
 
 use super::{centered_rect, dialog_block, field_block, hint_style, selected_style, DialogAction};
-use crate::models::{Filter, SortOrder, Task};
+use crate::models::{Filter, GroupBy, SortKey, SortOrder, Task};
 use crate::ui::theme;
 
 /// Which section of the dialog is focused.
@@ -21,9 +23,28 @@ pub enum FilterSortSection {
     #[default]
     Filter,
     Sort,
+    ThenBy,
+    Group,
 }
 
-/// Dialog for selecting filter and sort options.
+impl FilterSortSection {
+    /// Returns the section Tab moves to.
+    fn next(self) -> Self {
+        match self {
+            FilterSortSection::Filter => FilterSortSection::Sort,
+            FilterSortSection::Sort => FilterSortSection::ThenBy,
+            FilterSortSection::ThenBy => FilterSortSection::Group,
+            FilterSortSection::Group => FilterSortSection::Filter,
+        }
+    }
+
+    /// Returns the section Shift+Tab moves to.
+    fn previous(self) -> Self {
+        self.next().next().next()
+    }
+}
+
+/// Dialog for selecting filter, sort and grouping options.
 #[derive(Debug, Clone)]
 pub struct FilterSortDialog {
     /// Currently focused section
@@ -32,6 +53,12 @@ pub struct FilterSortDialog {
     pub filter_index: usize,
     /// Selected sort index
     pub sort_index: usize,
+    /// Secondary sort keys, in order
+    pub then_by: Vec<SortKey>,
+    /// Selected secondary key index
+    pub then_by_index: usize,
+    /// Selected grouping index
+    pub group_index: usize,
     /// Task counts for each filter option
     pub filter_counts: Vec<usize>,
 }
@@ -57,10 +84,27 @@ impl FilterSortDialog {
         (SortOrder::PriorityDesc, "Priority", "Highest priority first"),
         (SortOrder::CreatedDesc, "Created", "Newest first"),
         (SortOrder::Alphabetical, "Alphabetical", "A-Z by title"),
+        (SortOrder::Manual, "Manual", "Your own order (J/K)"),
+    ];
+
+    /// All available groupings.
+    const GROUPS: &'static [(GroupBy, &'static str, &'static str)] = &[
+        (GroupBy::None, "None", "One flat list"),
+        (GroupBy::Project, "Project", "A section per project"),
+        (GroupBy::Priority, "Priority", "Urgent first"),
+        (GroupBy::Status, "Status", "In progress first"),
+        (GroupBy::Due, "Due Date", "Overdue, today, tomorrow..."),
+        (GroupBy::Tag, "Tag", "By first tag"),
     ];
 
-    /// Creates a new dialog with current filter/sort pre-selected.
-    pub fn new(current_filter: &Filter, current_sort: &SortOrder, tasks: &[Task]) -> Self {
+    /// Creates a new dialog with current filter/sort/grouping pre-selected.
+    pub fn new(
+        current_filter: &Filter,
+        current_sort: &SortOrder,
+        then_by: &[SortKey],
+        current_group: GroupBy,
+        tasks: &[Task],
+    ) -> Self {
         let filter_index = Self::FILTERS
             .iter()
             .position(|(f, _, _)| std::mem::discriminant(f) == std::mem::discriminant(current_filter))
@@ -71,6 +115,11 @@ impl FilterSortDialog {
             .position(|(s, _, _)| s == current_sort)
             .unwrap_or(0);
 
+        let group_index = Self::GROUPS
+            .iter()
+            .position(|(g, _, _)| *g == current_group)
+            .unwrap_or(0);
+
         // Calculate counts for each filter
         let filter_counts = Self::FILTERS
             .iter()
@@ -81,6 +130,9 @@ impl FilterSortDialog {
             section: FilterSortSection::Filter,
             filter_index,
             sort_index,
+            then_by: then_by.to_vec(),
+            then_by_index: 0,
+            group_index,
             filter_counts,
         }
     }
@@ -100,6 +152,39 @@ impl FilterSortDialog {
         Self::SORTS[self.sort_index].0
     }
 
+    /// Returns the secondary sort keys, leaving out any on the same field
+    /// as the main sort.
+    pub fn selected_then_by(&self) -> Vec<SortKey> {
+        let main = self.selected_sort().key().field;
+        self.then_by.iter().copied().filter(|k| k.field != main).collect()
+    }
+
+    /// Adds the highlighted sort option as the last secondary key, or
+    /// updates its direction if its field is already there.
+    fn add_then_by(&mut self) {
+        let key = self.selected_sort().key();
+        match self.then_by.iter_mut().find(|k| k.field == key.field) {
+            Some(existing) => existing.descending = key.descending,
+            None => self.then_by.push(key),
+        }
+    }
+
+    /// Returns the currently selected grouping.
+    pub fn selected_group(&self) -> GroupBy {
+        Self::GROUPS[self.group_index].0
+    }
+
+    /// Returns the selected index of the focused section and its number
+    /// of options.
+    fn focused_index(&mut self) -> (&mut usize, usize) {
+        match self.section {
+            FilterSortSection::Filter => (&mut self.filter_index, Self::FILTERS.len()),
+            FilterSortSection::Sort => (&mut self.sort_index, Self::SORTS.len()),
+            FilterSortSection::ThenBy => (&mut self.then_by_index, self.then_by.len().max(1)),
+            FilterSortSection::Group => (&mut self.group_index, Self::GROUPS.len()),
+        }
+    }
+
     /// Handles a key event and returns the resulting action.
     pub fn handle_key(&mut self, key: KeyEvent) -> DialogAction {
         match key.code {
@@ -109,52 +194,68 @@ impl FilterSortDialog {
             // Confirm selection
             KeyCode::Enter => DialogAction::Submit,
 
-            // Switch between filter and sort sections
-            KeyCode::Tab | KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l') => {
-                self.section = match self.section {
-                    FilterSortSection::Filter => FilterSortSection::Sort,
-                    FilterSortSection::Sort => FilterSortSection::Filter,
+            // Build the secondary keys
+            KeyCode::Char('a') | KeyCode::Char('+') if self.section == FilterSortSection::Sort => {
+                self.add_then_by();
+                DialogAction::None
+            }
+            KeyCode::Char('K') | KeyCode::Char('J')
+                if self.section == FilterSortSection::ThenBy && !self.then_by.is_empty() =>
+            {
+                let from = self.then_by_index;
+                let to = if key.code == KeyCode::Char('K') {
+                    from.saturating_sub(1)
+                } else {
+                    (from + 1).min(self.then_by.len() - 1)
                 };
+                self.then_by.swap(from, to);
+                self.then_by_index = to;
+                DialogAction::None
+            }
+            KeyCode::Char('r') | KeyCode::Char(' ') if self.section == FilterSortSection::ThenBy => {
+                if let Some(key) = self.then_by.get_mut(self.then_by_index) {
+                    key.descending = !key.descending;
+                }
+                DialogAction::None
+            }
+            KeyCode::Char('d') | KeyCode::Delete | KeyCode::Backspace
+                if self.section == FilterSortSection::ThenBy && !self.then_by.is_empty() =>
+            {
+                self.then_by.remove(self.then_by_index);
+                self.then_by_index = self.then_by_index.min(self.then_by.len().saturating_sub(1));
+                DialogAction::None
+            }
+
+            // Switch between sections
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => {
+                self.section = self.section.next();
+                DialogAction::None
+            }
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+                self.section = self.section.previous();
                 DialogAction::None
             }
 
             // Navigate within section
             KeyCode::Up | KeyCode::Char('k') => {
-                match self.section {
-                    FilterSortSection::Filter => {
-                        self.filter_index = self.filter_index.saturating_sub(1);
-                    }
-                    FilterSortSection::Sort => {
-                        self.sort_index = self.sort_index.saturating_sub(1);
-                    }
-                }
+                let (index, _) = self.focused_index();
+                *index = index.saturating_sub(1);
                 DialogAction::None
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                match self.section {
-                    FilterSortSection::Filter => {
-                        self.filter_index = (self.filter_index + 1).min(Self::FILTERS.len() - 1);
-                    }
-                    FilterSortSection::Sort => {
-                        self.sort_index = (self.sort_index + 1).min(Self::SORTS.len() - 1);
-                    }
-                }
+                let (index, len) = self.focused_index();
+                *index = (*index + 1).min(len - 1);
                 DialogAction::None
             }
 
             // Jump to top/bottom
             KeyCode::Home | KeyCode::Char('g') => {
-                match self.section {
-                    FilterSortSection::Filter => self.filter_index = 0,
-                    FilterSortSection::Sort => self.sort_index = 0,
-                }
+                *self.focused_index().0 = 0;
                 DialogAction::None
             }
             KeyCode::End | KeyCode::Char('G') => {
-                match self.section {
-                    FilterSortSection::Filter => self.filter_index = Self::FILTERS.len() - 1,
-                    FilterSortSection::Sort => self.sort_index = Self::SORTS.len() - 1,
-                }
+                let (index, len) = self.focused_index();
+                *index = len - 1;
                 DialogAction::None
             }
 
@@ -168,7 +269,8 @@ impl FilterSortDialog {
 
         // Dialog dimensions
         let dialog_width = 50.min(area.width.saturating_sub(4));
-        let dialog_height = 18.min(area.height.saturating_sub(4));
+        let then_by_height = self.then_by.len().max(1) as u16 + 3;
+        let dialog_height = (21 + then_by_height).min(area.height.saturating_sub(4));
         let dialog_area = centered_rect(dialog_width, dialog_height, area);
 
         // Render dimmed background
@@ -179,7 +281,7 @@ impl FilterSortDialog {
         );
 
         // Render dialog box with themed styling
-        let block = dialog_block("Filter & Sort", false);
+        let block = dialog_block("Filter, Sort & Group", false);
         let inner = block.inner(dialog_area);
         frame.render_widget(block, dialog_area);
 
@@ -193,8 +295,52 @@ impl FilterSortDialog {
         // Render filter column
         self.render_filter_column(frame, columns[0]);
 
-        // Render sort column
-        self.render_sort_column(frame, columns[1]);
+        // Sort, secondary keys and grouping share the right column
+        let sort_height = Self::SORTS.len() as u16 + 3;
+        let rows = Layout::vertical([
+            Constraint::Length(sort_height),
+            Constraint::Length(then_by_height),
+            Constraint::Min(0),
+        ])
+        .split(columns[1]);
+        let sorts: Vec<_> = Self::SORTS.iter().map(|(_, name, desc)| (*name, *desc)).collect();
+        self.render_choice_column(frame, rows[0], FilterSortSection::Sort, &sorts, self.sort_index);
+        self.render_then_by(frame, rows[1]);
+        let groups: Vec<_> = Self::GROUPS.iter().map(|(_, name, desc)| (*name, *desc)).collect();
+        self.render_choice_column(frame, rows[2], FilterSortSection::Group, &groups, self.group_index);
+    }
+
+    /// Renders the secondary sort keys, numbered from 2 after the main sort.
+    fn render_then_by(&self, frame: &mut Frame, area: Rect) {
+        let is_focused = self.section == FilterSortSection::ThenBy;
+        let block = field_block("Then by", is_focused);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines: Vec<Line> = Vec::new();
+        if self.then_by.is_empty() {
+            lines.push(Line::from(Span::styled("  a in Sort adds a key", hint_style())));
+        }
+        for (i, key) in self.then_by.iter().enumerate() {
+            let is_selected = i == self.then_by_index && is_focused;
+            let style = if is_selected {
+                selected_style()
+            } else {
+                Style::default().fg(theme::TEXT_PRIMARY)
+            };
+            let prefix = if is_selected { "▶ " } else { "  " };
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(format!("{}. {}", i + 2, key.label()), style),
+            ]));
+        }
+        lines.push(Line::from(Span::styled(
+            if is_focused { "J/K:move  r:reverse  d:remove" } else { "" },
+            hint_style(),
+        )));
+
+        let paragraph = Paragraph::new(lines);
+        frame.render_widget(paragraph, inner);
     }
 
     fn render_filter_column(&self, frame: &mut Frame, area: Rect) {
@@ -243,15 +389,25 @@ impl FilterSortDialog {
         frame.render_widget(paragraph, inner);
     }
 
-    fn render_sort_column(&self, frame: &mut Frame, area: Rect) {
-        let is_focused = self.section == FilterSortSection::Sort;
-        let block = field_block("Sort", is_focused);
+    /// Renders the sort or grouping options, with the key hints below
+    /// the grouping.
+    fn render_choice_column(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        section: FilterSortSection,
+        options: &[(&'static str, &'static str)],
+        selected: usize,
+    ) {
+        let is_focused = self.section == section;
+        let title = if section == FilterSortSection::Sort { "Sort" } else { "Group" };
+        let block = field_block(title, is_focused);
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
         let mut lines: Vec<Line> = Vec::new();
-        for (i, (_, name, desc)) in Self::SORTS.iter().enumerate() {
-            let is_selected = i == self.sort_index;
+        for (i, (name, desc)) in options.iter().enumerate() {
+            let is_selected = i == selected;
             let style = if is_selected && is_focused {
                 selected_style()
             } else if is_selected {
@@ -278,11 +434,13 @@ impl FilterSortDialog {
         }
 
         // Add help text at bottom
-        lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "Tab:switch  ↑↓:select  Enter:apply",
-            hint_style(),
-        )));
+        if section == FilterSortSection::Group {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Tab:switch  ↑↓:select  Enter:apply",
+                hint_style(),
+            )));
+        }
 
         let paragraph = Paragraph::new(lines);
         frame.render_widget(paragraph, inner);
@@ -291,7 +449,7 @@ impl FilterSortDialog {
 
 impl Default for FilterSortDialog {
     fn default() -> Self {
-        Self::new(&Filter::All, &SortOrder::DueDateAsc, &[])
+        Self::new(&Filter::All, &SortOrder::DueDateAsc, &[], GroupBy::None, &[])
     }
 }
 
@@ -306,9 +464,10 @@ mod tests {
 
     #[test]
     fn test_new_dialog() {
-        let dialog = FilterSortDialog::new(&Filter::Pending, &SortOrder::PriorityDesc, &[]);
+        let dialog = FilterSortDialog::new(&Filter::Pending, &SortOrder::PriorityDesc, &[], GroupBy::Due, &[]);
         assert_eq!(dialog.filter_index, 1); // Pending is index 1
         assert_eq!(dialog.sort_index, 1); // PriorityDesc is index 1
+        assert_eq!(dialog.selected_group(), GroupBy::Due);
     }
 
     #[test]
@@ -321,7 +480,7 @@ mod tests {
 
     #[test]
     fn test_navigation_up() {
-        let mut dialog = FilterSortDialog::new(&Filter::Completed, &SortOrder::DueDateAsc, &[]);
+        let mut dialog = FilterSortDialog::new(&Filter::Completed, &SortOrder::DueDateAsc, &[], GroupBy::None, &[]);
         assert_eq!(dialog.filter_index, 2);
         dialog.handle_key(key(KeyCode::Up));
         assert_eq!(dialog.filter_index, 1);
@@ -334,7 +493,24 @@ mod tests {
         dialog.handle_key(key(KeyCode::Tab));
         assert_eq!(dialog.section, FilterSortSection::Sort);
         dialog.handle_key(key(KeyCode::Tab));
+        assert_eq!(dialog.section, FilterSortSection::ThenBy);
+        dialog.handle_key(key(KeyCode::Tab));
+        assert_eq!(dialog.section, FilterSortSection::Group);
+        dialog.handle_key(key(KeyCode::Tab));
         assert_eq!(dialog.section, FilterSortSection::Filter);
+        dialog.handle_key(key(KeyCode::BackTab));
+        assert_eq!(dialog.section, FilterSortSection::Group);
+    }
+
+    #[test]
+    fn test_group_selection() {
+        let mut dialog = FilterSortDialog::default();
+        dialog.handle_key(key(KeyCode::Left));
+        dialog.handle_key(key(KeyCode::End));
+        assert_eq!(dialog.selected_group(), GroupBy::Tag);
+        dialog.handle_key(key(KeyCode::Up));
+        assert_eq!(dialog.selected_group(), GroupBy::Due);
+        assert_eq!(dialog.selected_filter(), Filter::All);
     }
 
     #[test]
@@ -348,4 +524,37 @@ mod tests {
         let mut dialog = FilterSortDialog::default();
         assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::Submit);
     }
+
+    #[test]
+    fn test_then_by_keys() {
+        let mut dialog = FilterSortDialog {
+            section: FilterSortSection::Sort,
+            ..Default::default()
+        };
+        // Priority, then title
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Char('a')));
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Char('a')));
+        assert_eq!(dialog.then_by, vec![SortOrder::PriorityDesc.key(), SortOrder::Alphabetical.key()]);
+
+        // Move the title up and reverse it
+        dialog.handle_key(key(KeyCode::Tab));
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Char('K')));
+        dialog.handle_key(key(KeyCode::Char('r')));
+        assert_eq!(dialog.then_by[0].field, crate::models::SortField::Title);
+        assert!(dialog.then_by[0].descending);
+        assert_eq!(dialog.then_by_index, 0);
+
+        // Keys on the main sort's field are dropped
+        dialog.sort_index = 1;
+        assert_eq!(dialog.selected_then_by().len(), 1);
+
+        dialog.handle_key(key(KeyCode::Char('d')));
+        dialog.handle_key(key(KeyCode::Char('d')));
+        assert!(dialog.then_by.is_empty());
+        dialog.handle_key(key(KeyCode::Char('d')));
+    }
 }
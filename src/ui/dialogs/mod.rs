@@ -6,11 +6,17 @@
 //! ## Dialog Types
 //!
 //! - [`AddTaskDialog`] - Create or edit a task
+//! - [`BulkEditDialog`] - Change one field of the marked tasks
+//! - [`CommandPaletteDialog`] - Run any action by name
 //! - [`ConfirmDialog`] - Yes/No confirmation prompts
 //! - [`DeleteProjectDialog`] - Project deletion with task handling options
+//! - [`ExportDialog`] - Markdown/CSV report export of the current view
 //! - [`FilterSortDialog`] - Filter and sort selection
 //! - [`MoveToProjectDialog`] - Move task to different project
+//! - [`NoteDialog`] - Add a timestamped note to a task
 //! - [`ProjectDialog`] - Create or edit a project
+//! - [`SwitchProfileDialog`] - Open another profile's database
+//! - [`TemplateDialog`] - Create tasks from a template
 //!
 //! ## Usage
 //!
@@ -19,22 +25,36 @@
 //! indicating what happened.
 
 mod add_task;
+mod bulk_edit;
+mod command_palette;
 mod confirm;
 mod delete_project;
+mod export;
 mod filter_sort;
 mod move_to_project;
+mod note;
 mod project;
 mod quick_capture;
 mod settings;
+mod switch_profile;
+mod template;
 
 pub use add_task::AddTaskDialog;
+pub use bulk_edit::{task_count, BulkEditDialog, BulkField};
+pub use command_palette::{fuzzy_score, CommandPaletteDialog, PaletteAction, PaletteChoice};
 pub use confirm::ConfirmDialog;
 pub use delete_project::{DeleteProjectChoice, DeleteProjectDialog};
+pub use export::ExportDialog;
 pub use filter_sort::FilterSortDialog;
 pub use move_to_project::MoveToProjectDialog;
+pub use note::NoteDialog;
 pub use project::ProjectDialog;
-pub use quick_capture::{QuickCaptureAction, QuickCaptureDialog};
+pub use quick_capture::{
+    match_project, parse_capture_input, ParsedCapture, QuickCaptureAction, QuickCaptureDialog,
+};
 pub use settings::{SettingsDialog, SettingsOption};
+pub use switch_profile::SwitchProfileDialog;
+pub use template::{TemplateDialog, TemplateEntry};
 
 use ratatui::{
     buffer::Buffer,
@@ -42,7 +62,7 @@ use ratatui::{
     style::{Modifier, Style},
     symbols::border,
     text::Span,
-    widgets::{Block, Borders, Clear, Widget},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
     Frame,
 };
 
@@ -68,16 +88,28 @@ pub enum Dialog {
     Confirm(ConfirmDialog),
     /// Delete project dialog with task handling options
     DeleteProject(DeleteProjectDialog),
+    /// Report export format and grouping selection
+    Export(ExportDialog),
     /// Filter and sort selection dialog
     FilterSort(FilterSortDialog),
     /// Move task to project dialog
     MoveToProject(MoveToProjectDialog),
+    /// Add a note to a task
+    Note(NoteDialog),
     /// Add or edit project dialog
     Project(ProjectDialog),
     /// Settings dialog for app configuration
     Settings(SettingsDialog),
     /// Quick Capture spotlight dialog (boxed due to large size with suggestion fields)
     QuickCapture(Box<QuickCaptureDialog>),
+    /// Profile switcher
+    SwitchProfile(SwitchProfileDialog),
+    /// Template picker
+    Template(TemplateDialog),
+    /// Bulk edit of the marked tasks
+    BulkEdit(BulkEditDialog),
+    /// Command palette (boxed due to the lists it searches)
+    CommandPalette(Box<CommandPaletteDialog>),
 }
 
 impl Dialog {
@@ -87,11 +119,17 @@ impl Dialog {
             Dialog::AddTask(dialog) => dialog.render(frame),
             Dialog::Confirm(dialog) => dialog.render(frame),
             Dialog::DeleteProject(dialog) => dialog.render(frame),
+            Dialog::Export(dialog) => dialog.render(frame),
             Dialog::FilterSort(dialog) => dialog.render(frame),
             Dialog::MoveToProject(dialog) => dialog.render(frame),
+            Dialog::Note(dialog) => dialog.render(frame),
             Dialog::Project(dialog) => dialog.render(frame),
             Dialog::Settings(dialog) => dialog.render(frame),
             Dialog::QuickCapture(dialog) => dialog.render(frame),
+            Dialog::SwitchProfile(dialog) => dialog.render(frame),
+            Dialog::Template(dialog) => dialog.render(frame),
+            Dialog::BulkEdit(dialog) => dialog.render(frame),
+            Dialog::CommandPalette(dialog) => dialog.render(frame),
         }
     }
 }
@@ -109,6 +147,50 @@ pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     area
 }
 
+/// Dims the screen and draws a spotlight overlay, the rounded box in the
+/// upper fifth of the screen used by Quick Capture and the command palette.
+///
+/// Returns the overlay area and the area inside its border.
+pub fn render_spotlight(frame: &mut Frame, title: &str, height: u16) -> (Rect, Rect) {
+    let area = frame.area();
+
+    // Dim background
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new("").style(Style::default().bg(theme::BG_DARK)),
+        area,
+    );
+
+    let width = 60.min((area.width * 60 / 100).max(45)).max(45);
+
+    // Position in upper 1/5th of screen
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = area.height / 5;
+    let dialog_area = Rect::new(
+        area.x + x,
+        area.y + y,
+        width.min(area.width.saturating_sub(x)),
+        height.min(area.height.saturating_sub(y)),
+    );
+
+    let title_span = Span::styled(
+        format!(" {} {} ", theme::icons::SPARKLE, title),
+        Style::default()
+            .fg(theme::ACCENT)
+            .add_modifier(Modifier::BOLD),
+    );
+    let block = Block::default()
+        .title(title_span)
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme::PRIMARY_LIGHT))
+        .style(Style::default().bg(theme::BG_ELEVATED));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+    (dialog_area, inner)
+}
+
 /// Renders a dialog background (dims the main content).
 pub fn render_dialog_background(area: Rect, buf: &mut Buffer) {
     // Clear the area first
@@ -0,0 +1,138 @@
+//! Add note dialog.
+//!
+//! A popup with a multi-line text area for adding a timestamped note to a
+//! task. Enter starts a new line; Ctrl+S or Ctrl+Enter saves.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Style,
+    text::Span,
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use super::{centered_rect, dialog_block, hint_style, DialogAction};
+use crate::ui::description_textarea::{DescriptionTextArea, TextAreaAction};
+use crate::ui::theme;
+
+/// Dialog for adding a note to a task.
+#[derive(Debug, Clone)]
+pub struct NoteDialog {
+    /// ID of the task the note is for
+    pub task_id: String,
+    /// Title of the task, shown in the dialog title
+    pub task_title: String,
+    /// Note text
+    pub text: DescriptionTextArea,
+}
+
+impl NoteDialog {
+    /// Creates a new dialog for adding a note to the given task.
+    pub fn new(task_id: &str, task_title: &str) -> Self {
+        Self {
+            task_id: task_id.to_string(),
+            task_title: task_title.to_string(),
+            text: DescriptionTextArea::new(),
+        }
+    }
+
+    /// Returns the note text with surrounding whitespace removed.
+    pub fn note_text(&self) -> String {
+        self.text.text().trim().to_string()
+    }
+
+    /// Handles a key event and returns the resulting action.
+    ///
+    /// Saving an empty note cancels instead.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => DialogAction::Cancel,
+            KeyCode::Char('s') | KeyCode::Enter if ctrl => {
+                if self.note_text().is_empty() {
+                    DialogAction::Cancel
+                } else {
+                    DialogAction::Submit
+                }
+            }
+            _ => {
+                // Ctrl+O opens the link under the cursor
+                if let TextAreaAction::OpenLink(url) = self.text.handle_key(key) {
+                    let _ = open::that(&url);
+                }
+                DialogAction::None
+            }
+        }
+    }
+
+    /// Renders the dialog to the frame.
+    pub fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        // Dialog dimensions
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = 12.min(area.height.saturating_sub(4));
+        let dialog_area = centered_rect(dialog_width, dialog_height, area);
+
+        // Render dimmed background
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new("").style(Style::default().bg(theme::BG_DARK)),
+            area,
+        );
+
+        let title = format!("Note on \"{}\"", self.task_title);
+        let block = dialog_block(&title, false);
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let [text_area, help_area] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(inner);
+        self.text.render(text_area, frame.buffer_mut(), true, Some("Note"));
+
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                "Enter:new line  Ctrl+S:save  Esc:cancel",
+                hint_style(),
+            )),
+            help_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn test_multiline_note() {
+        let mut dialog = NoteDialog::new("task-1", "Write docs");
+        for c in "First".chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::None);
+        for c in "Second ".chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+
+        assert_eq!(dialog.handle_key(ctrl('s')), DialogAction::Submit);
+        assert_eq!(dialog.note_text(), "First\nSecond");
+    }
+
+    #[test]
+    fn test_empty_note_cancels() {
+        let mut dialog = NoteDialog::new("task-1", "Write docs");
+        dialog.handle_key(key(KeyCode::Char(' ')));
+        assert_eq!(dialog.handle_key(ctrl('s')), DialogAction::Cancel);
+        assert_eq!(dialog.handle_key(key(KeyCode::Esc)), DialogAction::Cancel);
+    }
+}
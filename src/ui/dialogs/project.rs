@@ -202,15 +202,13 @@ impl ProjectDialog {
             KeyCode::Right | KeyCode::Char('l') => {
                 self.selected_color = (self.selected_color + 1) % PROJECT_COLORS.len();
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.selected_color >= ITEMS_PER_ROW {
-                    self.selected_color -= ITEMS_PER_ROW;
-                }
+            KeyCode::Up | KeyCode::Char('k') if self.selected_color >= ITEMS_PER_ROW => {
+                self.selected_color -= ITEMS_PER_ROW;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.selected_color + ITEMS_PER_ROW < PROJECT_COLORS.len() {
-                    self.selected_color += ITEMS_PER_ROW;
-                }
+            KeyCode::Down | KeyCode::Char('j')
+                if self.selected_color + ITEMS_PER_ROW < PROJECT_COLORS.len() =>
+            {
+                self.selected_color += ITEMS_PER_ROW;
             }
             KeyCode::Char(c) if c.is_ascii_digit() => {
                 let idx = c.to_digit(10).unwrap() as usize;
@@ -236,15 +234,13 @@ impl ProjectDialog {
             KeyCode::Right | KeyCode::Char('l') => {
                 self.selected_icon = (self.selected_icon + 1) % PROJECT_ICONS.len();
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.selected_icon >= ITEMS_PER_ROW {
-                    self.selected_icon -= ITEMS_PER_ROW;
-                }
+            KeyCode::Up | KeyCode::Char('k') if self.selected_icon >= ITEMS_PER_ROW => {
+                self.selected_icon -= ITEMS_PER_ROW;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.selected_icon + ITEMS_PER_ROW < PROJECT_ICONS.len() {
-                    self.selected_icon += ITEMS_PER_ROW;
-                }
+            KeyCode::Down | KeyCode::Char('j')
+                if self.selected_icon + ITEMS_PER_ROW < PROJECT_ICONS.len() =>
+            {
+                self.selected_icon += ITEMS_PER_ROW;
             }
             KeyCode::Char(c) if c.is_ascii_digit() => {
                 let idx = c.to_digit(10).unwrap() as usize;
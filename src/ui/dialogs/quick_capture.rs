@@ -25,12 +25,12 @@ use ratatui::{
     Frame,
 };
 
-use super::add_task::parse_due_date;
-use super::AddTaskDialog;
+use super::{render_spotlight, AddTaskDialog};
 use crate::models::{Priority, Project, Task};
 use crate::storage::Tag;
 use crate::ui::input::TextInput;
 use crate::ui::theme;
+use crate::utils::parse_due_date;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Suggestion types
@@ -135,6 +135,31 @@ pub fn parse_capture_input(input: &str) -> ParsedCapture {
     result
 }
 
+/// Finds the project an `@name` token refers to.
+///
+/// Matching priority: exact (case-insensitive) → prefix → substring.
+pub fn match_project<'a>(projects: &'a [Project], name: &str) -> Option<&'a Project> {
+    let lower = name.to_lowercase();
+
+    // Exact match (case-insensitive)
+    if let Some(p) = projects.iter().find(|p| p.name.to_lowercase() == lower) {
+        return Some(p);
+    }
+
+    // Prefix match
+    if let Some(p) = projects
+        .iter()
+        .find(|p| p.name.to_lowercase().starts_with(&lower))
+    {
+        return Some(p);
+    }
+
+    // Substring match
+    projects
+        .iter()
+        .find(|p| p.name.to_lowercase().contains(&lower))
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Dialog
 // ─────────────────────────────────────────────────────────────────────────────
@@ -227,6 +252,12 @@ impl QuickCaptureDialog {
         self.explicit_project.as_ref()
     }
 
+    /// Replaces the input text and parses it, as if it had been typed.
+    pub fn set_input(&mut self, text: &str) {
+        self.input.set_value(text);
+        self.reparse();
+    }
+
     /// Handles a key event and returns the resulting action.
     ///
     /// When suggestions are visible, Tab accepts the selected suggestion,
@@ -539,38 +570,9 @@ impl QuickCaptureDialog {
 
     /// Fuzzy matches a project name against available projects.
     ///
-    /// Matching priority: exact (case-insensitive) → prefix → substring.
+    /// See [`match_project`].
     fn fuzzy_match_project(&self, name: &str) -> Option<Project> {
-        let lower = name.to_lowercase();
-
-        // Exact match (case-insensitive)
-        if let Some(p) = self
-            .projects
-            .iter()
-            .find(|p| p.name.to_lowercase() == lower)
-        {
-            return Some(p.clone());
-        }
-
-        // Prefix match
-        if let Some(p) = self
-            .projects
-            .iter()
-            .find(|p| p.name.to_lowercase().starts_with(&lower))
-        {
-            return Some(p.clone());
-        }
-
-        // Substring match
-        if let Some(p) = self
-            .projects
-            .iter()
-            .find(|p| p.name.to_lowercase().contains(&lower))
-        {
-            return Some(p.clone());
-        }
-
-        None
+        match_project(&self.projects, name).cloned()
     }
 
     /// Accepts the currently selected suggestion.
@@ -717,45 +719,7 @@ impl QuickCaptureDialog {
 
     /// Renders the Quick Capture spotlight overlay.
     pub fn render(&self, frame: &mut Frame) {
-        let area = frame.area();
-
-        // Dim background
-        frame.render_widget(Clear, area);
-        frame.render_widget(
-            Paragraph::new("").style(Style::default().bg(theme::BG_DARK)),
-            area,
-        );
-
-        // Calculate overlay dimensions
-        let dialog_width = 60.min((area.width * 60 / 100).max(45)).max(45);
-        let dialog_height: u16 = 6;
-
-        // Position in upper 1/5th of screen
-        let x = (area.width.saturating_sub(dialog_width)) / 2;
-        let y = area.height / 5;
-        let dialog_area = Rect::new(
-            area.x + x,
-            area.y + y,
-            dialog_width.min(area.width.saturating_sub(x)),
-            dialog_height.min(area.height.saturating_sub(y)),
-        );
-
-        // Dialog block
-        let title_span = Span::styled(
-            format!(" {} Quick Capture ", theme::icons::SPARKLE),
-            Style::default()
-                .fg(theme::ACCENT)
-                .add_modifier(Modifier::BOLD),
-        );
-        let block = Block::default()
-            .title(title_span)
-            .borders(Borders::ALL)
-            .border_set(ratatui::symbols::border::ROUNDED)
-            .border_style(Style::default().fg(theme::PRIMARY_LIGHT))
-            .style(Style::default().bg(theme::BG_ELEVATED));
-
-        let inner = block.inner(dialog_area);
-        frame.render_widget(block, dialog_area);
+        let (dialog_area, inner) = render_spotlight(frame, "Quick Capture", 6);
 
         if inner.height < 4 || inner.width < 10 {
             return;
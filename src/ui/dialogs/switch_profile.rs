@@ -0,0 +1,157 @@
+//! Profile switcher dialog.
+//!
+//! A popup dialog for choosing a profile from the `[profiles]` config to
+//! open in place of the current database.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use super::{centered_rect, dialog_block, hint_style, selected_style, DialogAction};
+use crate::ui::theme;
+
+/// Dialog for selecting a profile to switch to.
+#[derive(Debug, Clone)]
+pub struct SwitchProfileDialog {
+    /// Names of the configured profiles
+    pub profiles: Vec<String>,
+    /// Currently selected profile index
+    pub selected_index: usize,
+    /// Name of the open profile, if any
+    pub current: Option<String>,
+}
+
+impl SwitchProfileDialog {
+    /// Creates a new dialog listing `profiles`, with the open one selected.
+    pub fn new(profiles: Vec<String>, current: Option<String>) -> Self {
+        let selected_index = current
+            .as_ref()
+            .and_then(|name| profiles.iter().position(|p| p == name))
+            .unwrap_or(0);
+
+        Self {
+            profiles,
+            selected_index,
+            current,
+        }
+    }
+
+    /// Returns the name of the selected profile.
+    pub fn selected_profile(&self) -> Option<&str> {
+        self.profiles.get(self.selected_index).map(String::as_str)
+    }
+
+    /// Handles a key event and returns the resulting action.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogAction {
+        match key.code {
+            // Cancel
+            KeyCode::Esc | KeyCode::Char('q') => DialogAction::Cancel,
+
+            // Confirm selection
+            KeyCode::Enter => DialogAction::Submit,
+
+            // Navigate up
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                DialogAction::None
+            }
+
+            // Navigate down
+            KeyCode::Down | KeyCode::Char('j') => {
+                if !self.profiles.is_empty() {
+                    self.selected_index = (self.selected_index + 1).min(self.profiles.len() - 1);
+                }
+                DialogAction::None
+            }
+
+            _ => DialogAction::None,
+        }
+    }
+
+    /// Renders the dialog to the frame.
+    pub fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let content_height = self.profiles.len().min(15) as u16;
+        let dialog_height = content_height + 5; // +5 for borders, title, and help text
+        let dialog_width = 40.min(area.width.saturating_sub(4));
+        let dialog_area = centered_rect(dialog_width, dialog_height, area);
+
+        // Render dimmed background
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new("").style(Style::default().bg(theme::BG_DARK)),
+            area,
+        );
+
+        let block = dialog_block("Switch Profile", false);
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let mut lines: Vec<Line> = Vec::new();
+        for (i, name) in self.profiles.iter().enumerate() {
+            let is_selected = i == self.selected_index;
+            let style = if is_selected {
+                selected_style()
+            } else {
+                Style::default().fg(theme::TEXT_PRIMARY)
+            };
+            let prefix = if is_selected { "▶ " } else { "  " };
+
+            let mut spans = vec![Span::styled(prefix, style), Span::styled(name, style)];
+            if self.current.as_deref() == Some(name) {
+                spans.push(Span::styled("  (open)", hint_style()));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "↑↓:select  Enter:open  Esc:cancel",
+            hint_style(),
+        )));
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn profiles() -> Vec<String> {
+        vec!["personal".to_string(), "work".to_string()]
+    }
+
+    #[test]
+    fn test_open_profile_is_preselected() {
+        let dialog = SwitchProfileDialog::new(profiles(), Some("work".to_string()));
+        assert_eq!(dialog.selected_profile(), Some("work"));
+
+        let dialog = SwitchProfileDialog::new(profiles(), None);
+        assert_eq!(dialog.selected_profile(), Some("personal"));
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut dialog = SwitchProfileDialog::new(profiles(), None);
+        assert_eq!(dialog.handle_key(key(KeyCode::Down)), DialogAction::None);
+        assert_eq!(dialog.selected_profile(), Some("work"));
+        dialog.handle_key(key(KeyCode::Down));
+        assert_eq!(dialog.selected_profile(), Some("work"));
+        dialog.handle_key(key(KeyCode::Char('k')));
+        assert_eq!(dialog.selected_profile(), Some("personal"));
+
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::Submit);
+        assert_eq!(dialog.handle_key(key(KeyCode::Esc)), DialogAction::Cancel);
+    }
+}
@@ -0,0 +1,215 @@
+//! Template picker dialog.
+//!
+//! A popup dialog listing the task templates, with an input line for the
+//! text that fills their `{input}` placeholders.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use super::{centered_rect, dialog_block, hint_style, selected_style, DialogAction};
+use crate::templates::Template;
+use crate::ui::input::TextInput;
+use crate::ui::theme;
+
+/// A template as listed in the picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateEntry {
+    /// Key the template is invoked by
+    pub key: String,
+    /// Display name
+    pub name: String,
+    /// Number of tasks it creates
+    pub task_count: usize,
+}
+
+impl From<&Template> for TemplateEntry {
+    fn from(template: &Template) -> Self {
+        Self {
+            key: template.key.clone(),
+            name: template.display_name().to_string(),
+            task_count: template.task_count(),
+        }
+    }
+}
+
+/// Dialog for picking a template to create tasks from.
+#[derive(Debug, Clone)]
+pub struct TemplateDialog {
+    /// Available templates
+    pub templates: Vec<TemplateEntry>,
+    /// Currently selected template index
+    pub selected_index: usize,
+    /// Text for the `{input}` placeholders
+    pub input: TextInput,
+}
+
+impl TemplateDialog {
+    /// Creates a new dialog listing `templates`.
+    pub fn new(templates: &[Template]) -> Self {
+        Self {
+            templates: templates.iter().map(TemplateEntry::from).collect(),
+            selected_index: 0,
+            input: TextInput::new().with_placeholder("Fills {input}, e.g. v1.4"),
+        }
+    }
+
+    /// Returns the key of the selected template.
+    pub fn selected_key(&self) -> Option<&str> {
+        self.templates.get(self.selected_index).map(|t| t.key.as_str())
+    }
+
+    /// Handles a key event and returns the resulting action.
+    ///
+    /// Letters go to the input line, so only the arrow keys move the
+    /// selection.
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogAction {
+        match key.code {
+            KeyCode::Esc => DialogAction::Cancel,
+            KeyCode::Enter => DialogAction::Submit,
+
+            KeyCode::Up => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                DialogAction::None
+            }
+            KeyCode::Down => {
+                if !self.templates.is_empty() {
+                    self.selected_index = (self.selected_index + 1).min(self.templates.len() - 1);
+                }
+                DialogAction::None
+            }
+
+            KeyCode::Char(c) => {
+                self.input.insert(c);
+                DialogAction::None
+            }
+            KeyCode::Backspace => {
+                self.input.delete_backward();
+                DialogAction::None
+            }
+            KeyCode::Delete => {
+                self.input.delete_forward();
+                DialogAction::None
+            }
+            KeyCode::Left => {
+                self.input.move_left();
+                DialogAction::None
+            }
+            KeyCode::Right => {
+                self.input.move_right();
+                DialogAction::None
+            }
+
+            _ => DialogAction::None,
+        }
+    }
+
+    /// Renders the dialog to the frame.
+    pub fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        let list_height = self.templates.len().min(12) as u16;
+        let dialog_height = list_height + 8; // borders, input, spacer and help text
+        let dialog_width = 56.min(area.width.saturating_sub(4));
+        let dialog_area = centered_rect(dialog_width, dialog_height, area);
+
+        // Render dimmed background
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new("").style(Style::default().bg(theme::BG_DARK)),
+            area,
+        );
+
+        let block = dialog_block("New from Template", false);
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let [list_area, input_area, help_area] = Layout::vertical([
+            Constraint::Length(list_height + 1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let mut lines: Vec<Line> = Vec::new();
+        for (i, entry) in self.templates.iter().enumerate() {
+            let is_selected = i == self.selected_index;
+            let style = if is_selected {
+                selected_style()
+            } else {
+                Style::default().fg(theme::TEXT_PRIMARY)
+            };
+            let prefix = if is_selected { "▶ " } else { "  " };
+            let count = if entry.task_count == 1 {
+                "  1 task".to_string()
+            } else {
+                format!("  {} tasks", entry.task_count)
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(entry.name.clone(), style),
+                Span::styled(format!("  tpl:{}", entry.key), hint_style()),
+                Span::styled(count, hint_style()),
+            ]));
+        }
+        frame.render_widget(Paragraph::new(lines), list_area);
+
+        self.input
+            .render_to_buffer(input_area, frame.buffer_mut(), true, Some("Input"));
+
+        frame.render_widget(
+            Paragraph::new(Span::styled("↑↓:select  Enter:create  Esc:cancel", hint_style())),
+            help_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn templates() -> Vec<Template> {
+        vec![
+            Template::parse("onboard", "name = \"Onboard new hire\"\ntitle = \"Onboard {input}\"").unwrap(),
+            Template::parse("release", "title = \"Release {input}\"\n[[tasks]]\ntitle = \"Announce\"").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_entries() {
+        let dialog = TemplateDialog::new(&templates());
+        assert_eq!(dialog.templates[0].name, "Onboard new hire");
+        assert_eq!(dialog.templates[1].name, "release");
+        assert_eq!(dialog.templates[1].task_count, 2);
+    }
+
+    #[test]
+    fn test_typing_and_navigation() {
+        let mut dialog = TemplateDialog::new(&templates());
+        assert_eq!(dialog.handle_key(key(KeyCode::Down)), DialogAction::None);
+        assert_eq!(dialog.selected_key(), Some("release"));
+        dialog.handle_key(key(KeyCode::Down));
+        assert_eq!(dialog.selected_key(), Some("release"));
+
+        // Letters are typed, not used for navigation
+        for c in "jk".chars() {
+            dialog.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(dialog.input.value(), "jk");
+        assert_eq!(dialog.selected_key(), Some("release"));
+
+        assert_eq!(dialog.handle_key(key(KeyCode::Enter)), DialogAction::Submit);
+        assert_eq!(dialog.handle_key(key(KeyCode::Esc)), DialogAction::Cancel);
+    }
+}
@@ -2,10 +2,13 @@
 //!
 //! Displays a distinctive branded header with progress visualization
 //! and key statistics. Designed to make an immediate visual impression.
+//! When a profile is open, its name is shown after the brand, and its
+//! accent color (if configured) replaces the brand color. When a weekly
+//! review is due, the header says so.
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
@@ -20,6 +23,7 @@ use super::theme::{self, icons};
 /// - Distinctive ASCII brand mark
 /// - Visual progress bar showing completion
 /// - Color-coded status indicators
+/// - The open profile, in its accent color
 pub fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     // Calculate stats
     let total = app.total_task_count();
@@ -40,14 +44,15 @@ pub fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     // ═══════════════════════════════════════════════════════════════════════
     // BRAND SECTION - Distinctive logo
     // ═══════════════════════════════════════════════════════════════════════
+    let accent = app.profile.theme.accent.as_deref().and_then(parse_hex_color);
     let brand = Line::from(vec![
-        Span::styled(" ◆", Style::default().fg(theme::PRIMARY)),
-        Span::styled("═══", Style::default().fg(theme::PRIMARY_DARK)),
+        Span::styled(" ◆", Style::default().fg(accent.unwrap_or(theme::PRIMARY))),
+        Span::styled("═══", Style::default().fg(accent.unwrap_or(theme::PRIMARY_DARK))),
         Span::styled(" RATADO ", Style::default()
-            .fg(theme::PRIMARY_LIGHT)
+            .fg(accent.unwrap_or(theme::PRIMARY_LIGHT))
             .add_modifier(Modifier::BOLD)),
-        Span::styled("═══", Style::default().fg(theme::PRIMARY_DARK)),
-        Span::styled("◆ ", Style::default().fg(theme::PRIMARY)),
+        Span::styled("═══", Style::default().fg(accent.unwrap_or(theme::PRIMARY_DARK))),
+        Span::styled("◆ ", Style::default().fg(accent.unwrap_or(theme::PRIMARY))),
     ]);
 
     let brand_widget = Paragraph::new(brand).block(
@@ -62,6 +67,17 @@ pub fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     // ═══════════════════════════════════════════════════════════════════════
     let mut stats_spans = Vec::new();
 
+    // Open profile
+    if let Some(name) = &app.profile_name {
+        stats_spans.push(Span::styled(
+            format!(" {} ", name),
+            Style::default()
+                .fg(theme::BG_DARK)
+                .bg(accent.unwrap_or(theme::PRIMARY_LIGHT))
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     // Progress bar
     let progress = if total > 0 {
         completed as f32 / total as f32
@@ -124,6 +140,14 @@ pub fn render_header(frame: &mut Frame, app: &App, area: Rect) {
         Style::default().fg(theme::TEXT_MUTED),
     ));
 
+    // Weekly review nudge
+    if app.review_due() {
+        stats_spans.push(Span::styled(
+            format!("  {} review due (R)", icons::STAR),
+            Style::default().fg(theme::ACCENT).add_modifier(Modifier::BOLD),
+        ));
+    }
+
     let stats_widget = Paragraph::new(Line::from(stats_spans)).block(
         Block::default()
             .borders(Borders::BOTTOM)
@@ -132,6 +156,20 @@ pub fn render_header(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(stats_widget, chunks[1]);
 }
 
+/// Parses a hex color string (e.g., "#f97316") to a Color.
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+
+    let r = u8::from_str_radix(hex.get(0..2)?, 16).ok()?;
+    let g = u8::from_str_radix(hex.get(2..4)?, 16).ok()?;
+    let b = u8::from_str_radix(hex.get(4..6)?, 16).ok()?;
+
+    Some(Color::Rgb(r, g, b))
+}
+
 /// Renders a visual progress bar using Unicode blocks.
 fn render_progress_bar(progress: f32, width: usize) -> Vec<Span<'static>> {
     let filled = (progress * width as f32) as usize;
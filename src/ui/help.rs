@@ -50,11 +50,23 @@ pub fn render_help(frame: &mut Frame, _app: &App, area: Rect) {
         Line::from(""),
         keybinding_line("a", "Quick capture task"),
         keybinding_line("A", "Add task (full form)"),
+        keybinding_line("n", "New tasks from template"),
         keybinding_line("e / Enter", "Edit selected task"),
         keybinding_line("d", "Delete selected task"),
         keybinding_line("Space", "Toggle task completion"),
         keybinding_line("p", "Cycle priority"),
         keybinding_line("t", "Edit tags"),
+        keybinding_line("N", "Add note (also in task detail)"),
+        keybinding_line("J / K", "Move task down / up (manual sort)"),
+        Line::from(""),
+        section_header("SELECTION (press v in the task list)"),
+        Line::from(""),
+        keybinding_line("Space", "Mark / unmark task"),
+        keybinding_line("a", "Mark / unmark all visible"),
+        keybinding_line("T / W / o / 1-4", "Mark due today / this week / overdue / by priority"),
+        keybinding_line("c / d", "Complete / delete marked"),
+        keybinding_line("m / t / p / D / s", "Set project / tag / priority / due / status"),
+        keybinding_line("Esc / v", "Leave selection mode"),
         Line::from(""),
         section_header("PROJECTS (when sidebar focused)"),
         Line::from(""),
@@ -63,24 +75,32 @@ pub fn render_help(frame: &mut Frame, _app: &App, area: Rect) {
         keybinding_line("d", "Delete selected project"),
         keybinding_line("Tab", "Switch between Projects/Tags"),
         Line::from(""),
-        section_header("FILTERS & SORT"),
+        section_header("FILTERS, SORT & GROUPS"),
         Line::from(""),
-        keybinding_line("f", "Open filter/sort dialog"),
+        keybinding_line("f", "Open filter/sort/group dialog"),
         keybinding_line("T", "Filter: Due today"),
         keybinding_line("W", "Filter: Due this week"),
         keybinding_line("1-4", "Filter by priority (1=Low, 4=Urgent)"),
+        keybinding_line("z / Z", "Collapse/expand group / all groups"),
         Line::from(""),
         section_header("VIEWS"),
         Line::from(""),
         keybinding_line("/", "Search tasks"),
         keybinding_line("c", "Weekly calendar"),
-        keybinding_line("v", "Task detail view"),
+        keybinding_line("s", "Statistics (w: days/weeks)"),
+        keybinding_line("R", "Weekly review"),
+        keybinding_line("H", "Activity feed"),
+        keybinding_line("U", "Standup report"),
+        keybinding_line("i", "Task detail view"),
         Line::from(""),
         section_header("GENERAL"),
         Line::from(""),
         keybinding_line("?", "Show this help"),
+        keybinding_line("Ctrl+p / :", "Command palette"),
         keybinding_line("F12", "Toggle debug logs"),
         keybinding_line("S", "Settings"),
+        keybinding_line("E", "Export view as Markdown/CSV"),
+        keybinding_line("P", "Switch profile"),
         keybinding_line("r", "Refresh data"),
         keybinding_line("q", "Quit"),
         Line::from(""),
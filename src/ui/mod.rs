@@ -20,8 +20,13 @@
 //! - [`search`] - Search view for finding tasks
 //! - [`task_detail`] - Task detail view
 //! - [`calendar`] - Weekly calendar view
+//! - [`stats`] - Statistics and productivity dashboard
+//! - [`review`] - Guided weekly review
+//! - [`activity`] - Feed of recent changes to tasks
+//! - [`standup`] - Standup report
 //! - [`theme`] - Color palette and styling system
 
+mod activity;
 pub mod calendar;
 pub mod date_picker;
 mod debug;
@@ -32,9 +37,12 @@ mod header;
 mod help;
 pub mod input;
 mod layout;
+pub mod review;
 pub mod search;
 mod sidebar;
 pub mod splash;
+mod standup;
+mod stats;
 mod status_bar;
 pub mod tag_input;
 mod task_detail;
@@ -92,6 +100,10 @@ pub fn draw(frame: &mut Frame, app: &App) {
         ),
         View::TaskDetail => task_detail::render_task_detail(frame, app, frame.area()),
         View::Calendar => calendar::render_calendar(frame, app, frame.area()),
+        View::Stats => stats::render_stats(frame, app, frame.area()),
+        View::Review => review::render_review(frame, app, frame.area()),
+        View::Activity => activity::render_activity(frame, app, frame.area()),
+        View::Standup => standup::render_standup(frame, app, frame.area()),
     }
 
     // Render any active dialog on top
@@ -0,0 +1,255 @@
+//! Guided weekly review view.
+//!
+//! Steps through overdue tasks, tasks needing triage and stale tasks one at
+//! a time, with one-key actions for each, then shows the coming week. See
+//! [`ReviewStep`] for what each step includes.
+
+use chrono::{Duration, Local};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use crate::models::{Priority, ReviewStep, Task};
+use crate::utils::{format_due_date, format_relative_date};
+use super::theme::{self, icons};
+
+/// Progress through the weekly review.
+#[derive(Debug, Clone, Default)]
+pub struct ReviewState {
+    /// The step being shown
+    pub step: ReviewStep,
+    /// IDs of the step's tasks, as they were when the step started
+    pub queue: Vec<String>,
+    /// Index in `queue` of the task being asked about
+    pub position: usize,
+    /// Tasks changed so far in this review
+    pub handled: usize,
+}
+
+/// Renders the weekly review view.
+pub fn render_review(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title(Span::styled(
+            " Weekly Review ",
+            Style::default()
+                .fg(theme::PRIMARY_LIGHT)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme::PRIMARY_LIGHT))
+        .style(Style::default().bg(theme::BG_ELEVATED));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Layout: steps, description, current task or coming week, help
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // Steps
+            Constraint::Length(2), // Description and progress
+            Constraint::Min(5),    // Task card or next week
+            Constraint::Length(1), // Help line
+        ])
+        .split(inner);
+
+    let state = &app.review;
+    render_steps(frame, state.step, chunks[0]);
+
+    let mut description = vec![Span::styled(
+        state.step.description(),
+        Style::default().fg(theme::TEXT_SECONDARY),
+    )];
+    if state.step != ReviewStep::NextWeek {
+        description.push(Span::styled(
+            format!("  {} {} of {}", icons::DOT, state.position + 1, state.queue.len()),
+            Style::default().fg(theme::TEXT_MUTED),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(description)), chunks[1]);
+
+    match (state.step, app.review_task()) {
+        (ReviewStep::NextWeek, _) => render_next_week(frame, app, chunks[2]),
+        (_, Some(task)) => render_task_card(frame, app, task, chunks[2]),
+        (_, None) => {}
+    }
+
+    render_help_line(frame, state.step, chunks[3]);
+}
+
+/// Renders the steps, marking those already done.
+fn render_steps(frame: &mut Frame, current: ReviewStep, area: Rect) {
+    let mut spans = Vec::new();
+    let mut done = true;
+    for step in ReviewStep::ALL {
+        let (icon, style) = if *step == current {
+            done = false;
+            (
+                icons::CIRCLE,
+                Style::default().fg(theme::PRIMARY_LIGHT).add_modifier(Modifier::BOLD),
+            )
+        } else if done {
+            (icons::CHECK, Style::default().fg(theme::SUCCESS))
+        } else {
+            (icons::CHECKBOX_EMPTY, Style::default().fg(theme::TEXT_MUTED))
+        };
+        spans.push(Span::styled(format!("{} {}", icon, step.title()), style));
+        spans.push(Span::styled(
+            format!("  {}  ", icons::CHEVRON_RIGHT),
+            Style::default().fg(theme::BORDER),
+        ));
+    }
+    spans.pop();
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Renders the task being reviewed with its details.
+fn render_task_card(frame: &mut Frame, app: &App, task: &Task, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme::BORDER))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let label = Style::default().fg(theme::TEXT_MUTED);
+    let value = Style::default().fg(theme::TEXT_PRIMARY);
+    let project = task
+        .project_id
+        .as_ref()
+        .and_then(|id| app.projects.iter().find(|p| &p.id == id))
+        .map(|p| format!("{}{}", icons::PROJECT_PREFIX, p.name));
+    let due_style = if task.is_overdue() {
+        Style::default().fg(theme::DUE_OVERDUE)
+    } else {
+        value
+    };
+    let priority_color = match task.priority {
+        Priority::Urgent => theme::PRIORITY_URGENT,
+        Priority::High => theme::PRIORITY_HIGH,
+        Priority::Medium => theme::PRIORITY_NORMAL,
+        Priority::Low => theme::PRIORITY_LOW,
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            task.title.clone(),
+            Style::default().fg(theme::TEXT_PRIMARY).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Due       ", label),
+            Span::styled(format_due_date(task.due_date), due_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Project   ", label),
+            match project {
+                Some(name) => Span::styled(name, Style::default().fg(theme::PROJECT)),
+                None => Span::styled("None", label),
+            },
+        ]),
+        Line::from(vec![
+            Span::styled("Priority  ", label),
+            Span::styled(format!("{:?}", task.priority), Style::default().fg(priority_color)),
+        ]),
+    ];
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task.tags.iter().map(|t| format!("{}{}", icons::TAG_PREFIX, t)).collect();
+        lines.push(Line::from(vec![
+            Span::styled("Tags      ", label),
+            Span::styled(tags.join(" "), Style::default().fg(theme::TAG)),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("Updated   ", label),
+        Span::styled(format_relative_date(task.updated_at), value),
+        Span::styled(format!("  (created {})", format_relative_date(task.created_at)), label),
+    ]));
+    if let Some(description) = task.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        lines.push(Line::from(""));
+        lines.extend(
+            description
+                .lines()
+                .map(|l| Line::from(Span::styled(l.to_string(), Style::default().fg(theme::TEXT_SECONDARY)))),
+        );
+    }
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+/// Renders the tasks due in the next 7 days, by day.
+fn render_next_week(frame: &mut Frame, app: &App, area: Rect) {
+    let upcoming = ReviewStep::NextWeek.tasks(&app.tasks, chrono::Utc::now(), app.review_config.stale_days);
+    if upcoming.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                "Nothing is due in the next 7 days.",
+                Style::default().fg(theme::TEXT_MUTED),
+            )),
+            area,
+        );
+        return;
+    }
+
+    let today = Local::now().date_naive();
+    let mut lines = Vec::new();
+    for offset in 0..8 {
+        let date = today + Duration::days(offset);
+        let due: Vec<&&Task> = upcoming
+            .iter()
+            .filter(|t| t.due_date.is_some_and(|d| d.with_timezone(&Local).date_naive() == date))
+            .collect();
+        if due.is_empty() {
+            continue;
+        }
+        let heading = match offset {
+            0 => "Today".to_string(),
+            1 => "Tomorrow".to_string(),
+            _ => date.format("%A %-d %b").to_string(),
+        };
+        lines.push(Line::from(Span::styled(
+            heading,
+            Style::default().fg(theme::ACCENT).add_modifier(Modifier::BOLD),
+        )));
+        for task in due {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", icons::BULLET), Style::default().fg(theme::TEXT_MUTED)),
+                Span::styled(task.title.clone(), Style::default().fg(theme::TEXT_PRIMARY)),
+            ]));
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Renders the key hints for the current step.
+fn render_help_line(frame: &mut Frame, step: ReviewStep, area: Rect) {
+    let key = Style::default().fg(theme::PRIMARY_LIGHT).add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(theme::TEXT_MUTED);
+    let hints: &[(&str, &str)] = if step == ReviewStep::NextWeek {
+        &[("[Enter]", " Finish review  "), ("[Esc]", " Leave")]
+    } else {
+        &[
+            ("[t/T/w]", " Reschedule  "),
+            ("[m]", " Project  "),
+            ("[c]", " Complete  "),
+            ("[d]", " Drop  "),
+            ("[s]", " Skip  "),
+            ("[Tab]", " Next  "),
+            ("[Esc]", " Leave"),
+        ]
+    };
+    let spans: Vec<Span> = hints
+        .iter()
+        .flat_map(|(k, label)| [Span::styled(*k, key), Span::styled(*label, text)])
+        .collect();
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
@@ -24,13 +24,15 @@ pub struct SearchResult {
     pub title_match: Option<(usize, usize)>,
     /// Snippet from description with match
     pub desc_snippet: Option<String>,
+    /// Snippet from the first matching note
+    pub note_snippet: Option<String>,
     /// Matching tag name (if search matched a tag)
     pub tag_match: Option<String>,
 }
 
 /// Performs search on tasks and returns matching results.
 ///
-/// Searches in task title, description, and tags (case-insensitive).
+/// Searches in task title, description, notes and tags (case-insensitive).
 /// Tag searches can use the `#tag` syntax or just the tag name.
 pub fn search_tasks(query: &str, tasks: &[Task]) -> Vec<SearchResult> {
     let query_lower = query.to_lowercase();
@@ -53,17 +55,14 @@ pub fn search_tasks(query: &str, tasks: &[Task]) -> Vec<SearchResult> {
                 (start, start + query_lower.len())
             });
 
-            let desc_snippet = task.description.as_ref().and_then(|desc| {
-                let desc_lower = desc.to_lowercase();
-                desc_lower.find(&query_lower).map(|pos| {
-                    // Extract snippet around match
-                    let start = pos.saturating_sub(20);
-                    let end = (pos + query_lower.len() + 30).min(desc.len());
-                    let prefix = if start > 0 { "..." } else { "" };
-                    let suffix = if end < desc.len() { "..." } else { "" };
-                    format!("{}{}{}", prefix, &desc[start..end], suffix)
-                })
-            });
+            let desc_snippet = task
+                .description
+                .as_ref()
+                .and_then(|desc| match_snippet(desc, &query_lower));
+            let note_snippet = task
+                .notes
+                .iter()
+                .find_map(|note| match_snippet(&note.text, &query_lower));
 
             // Search in tags - match if any tag contains the query
             let search_term = tag_query.unwrap_or(&query_lower);
@@ -71,11 +70,12 @@ pub fn search_tasks(query: &str, tasks: &[Task]) -> Vec<SearchResult> {
                 tag.to_lowercase().contains(search_term)
             }).cloned();
 
-            if title_match.is_some() || desc_snippet.is_some() || tag_match.is_some() {
+            if title_match.is_some() || desc_snippet.is_some() || note_snippet.is_some() || tag_match.is_some() {
                 Some(SearchResult {
                     task: task.clone(),
                     title_match,
                     desc_snippet,
+                    note_snippet,
                     tag_match,
                 })
             } else {
@@ -85,6 +85,19 @@ pub fn search_tasks(query: &str, tasks: &[Task]) -> Vec<SearchResult> {
         .collect()
 }
 
+/// Extracts the text around the first match of `query_lower`, if any.
+fn match_snippet(text: &str, query_lower: &str) -> Option<String> {
+    let text_lower = text.to_lowercase();
+    text_lower.find(query_lower).map(|pos| {
+        // Extract snippet around match
+        let start = pos.saturating_sub(20);
+        let end = (pos + query_lower.len() + 30).min(text.len());
+        let prefix = if start > 0 { "..." } else { "" };
+        let suffix = if end < text.len() { "..." } else { "" };
+        format!("{}{}{}", prefix, &text[start..end], suffix)
+    })
+}
+
 /// Renders the search view.
 pub fn render_search(
     frame: &mut Frame,
@@ -230,11 +243,14 @@ fn render_search_results(
         let task_line = render_task_result(&result.task, result.title_match, is_selected, inner.width);
         lines.push(task_line);
 
-        // Render match info (description snippet or tag match) if present
+        // Render match info (description or note snippet, or tag match) if present
         if lines.len() < visible_height {
             if let Some(ref snippet) = result.desc_snippet {
                 let desc_line = render_description_snippet(snippet, query, is_selected);
                 lines.push(desc_line);
+            } else if let Some(ref snippet) = result.note_snippet {
+                let note_line = render_description_snippet(snippet, query, is_selected);
+                lines.push(note_line);
             } else if let Some(ref tag) = result.tag_match {
                 let tag_line = render_tag_match(tag, query, is_selected);
                 lines.push(tag_line);
@@ -479,6 +495,17 @@ mod tests {
         assert!(results[0].desc_snippet.is_some());
     }
 
+    #[test]
+    fn test_search_by_note() {
+        let mut tasks = sample_tasks();
+        tasks[2].add_note("Reproduced with an empty query");
+        let results = search_tasks("empty query", &tasks);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].task.title, "Fix bug in search");
+        assert_eq!(results[0].note_snippet.as_deref(), Some("Reproduced with an empty query"));
+        assert!(results[0].desc_snippet.is_none());
+    }
+
     #[test]
     fn test_search_by_tag() {
         let tasks = sample_tasks();
@@ -0,0 +1,74 @@
+//! Standup view: the report `ratado standup` prints, ready to copy.
+//!
+//! Shows the report as it will be pasted, in plain text or Markdown. See
+//! [`Standup`](crate::interop::standup::Standup) for what each section
+//! includes.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use super::theme;
+
+/// Renders the standup view.
+pub fn render_standup(frame: &mut Frame, app: &App, area: Rect) {
+    let title = if app.standup_markdown {
+        " Standup (Markdown) "
+    } else {
+        " Standup "
+    };
+    let block = Block::default()
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(theme::PRIMARY_LIGHT)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme::PRIMARY_LIGHT))
+        .style(Style::default().bg(theme::BG_ELEVATED));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(3),    // Report
+            Constraint::Length(1), // Help line
+        ])
+        .split(inner);
+
+    let lines: Vec<Line> = app
+        .standup_report()
+        .lines()
+        .map(|line| Line::from(Span::styled(line.to_string(), Style::default().fg(theme::TEXT_PRIMARY))))
+        .collect();
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+    render_help_line(frame, chunks[1]);
+}
+
+/// Renders the key hints.
+fn render_help_line(frame: &mut Frame, area: Rect) {
+    let key = Style::default().fg(theme::PRIMARY_LIGHT).add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(theme::TEXT_MUTED);
+    let hints = [
+        ("[y]", " Copy  "),
+        ("[m]", " Text/Markdown  "),
+        ("[r]", " Refresh  "),
+        ("[Esc]", " Back"),
+    ];
+    let spans: Vec<Span> = hints
+        .iter()
+        .flat_map(|(k, label)| [Span::styled(*k, key), Span::styled(*label, text)])
+        .collect();
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
@@ -0,0 +1,398 @@
+//! Statistics and productivity dashboard.
+//!
+//! Shows completed tasks per day or week, created vs completed over time,
+//! the overdue count, completion by project and tag, and a heatmap of the
+//! last twelve weeks. Everything is computed from the loaded tasks with
+//! [`Stats::compute`] each time the view is drawn.
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols::{self, border},
+    text::{Line, Span},
+    widgets::{Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Dataset, GraphType, Paragraph, Sparkline},
+    Frame,
+};
+
+use crate::app::App;
+use crate::models::{Completion, DayStats, Stats};
+use super::theme::{self, icons};
+
+/// Days shown in the daily bar chart.
+const BAR_DAYS: usize = 14;
+
+/// Days shown in the daily trend chart.
+const TREND_DAYS: usize = 30;
+
+/// Heatmap shades, from no completions to the busiest days.
+const HEAT: [Color; 5] = [
+    theme::BG_SELECTION,
+    Color::Rgb(14, 68, 41),
+    Color::Rgb(0, 109, 50),
+    Color::Rgb(38, 166, 65),
+    Color::Rgb(57, 211, 83),
+];
+
+/// Renders the stats view.
+pub fn render_stats(frame: &mut Frame, app: &App, area: Rect) {
+    let stats = Stats::compute(&app.tasks, &app.projects, Local::now(), Stats::DAYS);
+    let period = if app.stats_weekly { "Weekly" } else { "Daily" };
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" Statistics {} {} ", icons::DOT, period),
+            Style::default()
+                .fg(theme::PRIMARY_LIGHT)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme::PRIMARY_LIGHT))
+        .style(Style::default().bg(theme::BG_ELEVATED));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Layout: summary, charts, overdue and heatmap, completion, help
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2),  // Summary
+            Constraint::Min(8),     // Completed bars and trend
+            Constraint::Length(10), // Overdue and heatmap
+            Constraint::Length(8),  // Completion by project and tag
+            Constraint::Length(1),  // Help line
+        ])
+        .split(inner);
+
+    render_summary(frame, &stats, chunks[0]);
+
+    let periods = if app.stats_weekly {
+        stats.weekly()
+    } else {
+        stats.days.clone()
+    };
+    let charts = Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).split(chunks[1]);
+    let bars = if app.stats_weekly { periods.len() } else { BAR_DAYS };
+    render_completed_bars(frame, last(&periods, bars), app.stats_weekly, charts[0]);
+    let trend = if app.stats_weekly { periods.len() } else { TREND_DAYS };
+    render_trend(frame, last(&periods, trend), app.stats_weekly, charts[1]);
+
+    let middle = Layout::horizontal([Constraint::Min(20), Constraint::Length(34)]).split(chunks[2]);
+    render_overdue(frame, &stats.days, middle[0]);
+    render_heatmap(frame, &stats.days, middle[1]);
+
+    let completion = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).split(chunks[3]);
+    render_completion(frame, "By project", &stats.by_project, completion[0]);
+    render_completion(frame, "By tag", &stats.by_tag, completion[1]);
+
+    render_help_line(frame, chunks[4]);
+}
+
+/// Returns the last `count` entries.
+fn last(days: &[DayStats], count: usize) -> &[DayStats] {
+    &days[days.len().saturating_sub(count)..]
+}
+
+/// Returns a bordered panel with a muted title.
+fn panel(title: &str) -> Block<'_> {
+    Block::default()
+        .title(Span::styled(format!(" {} ", title), Style::default().fg(theme::TEXT_SECONDARY)))
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme::BORDER_MUTED))
+}
+
+/// Renders the headline numbers.
+fn render_summary(frame: &mut Frame, stats: &Stats, area: Rect) {
+    let value = Style::default().fg(theme::TEXT_PRIMARY).add_modifier(Modifier::BOLD);
+    let label = Style::default().fg(theme::TEXT_MUTED);
+    let this_week = stats.weekly().last().map(|w| w.completed).unwrap_or(0);
+    let overdue = stats.days.last().map(|d| d.overdue).unwrap_or(0);
+    let lead_time = stats.lead_time.map(format_duration).unwrap_or_else(|| "-".to_string());
+
+    let line = Line::from(vec![
+        Span::styled(format!("{}", stats.completed()), value.fg(theme::SUCCESS)),
+        Span::styled(format!(" completed in {} weeks  ", Stats::DAYS / 7), label),
+        Span::styled(format!("{}", stats.created()), value),
+        Span::styled(" created  ", label),
+        Span::styled(format!("{}", this_week), value),
+        Span::styled(" done this week  ", label),
+        Span::styled(lead_time, value),
+        Span::styled(" average lead time  ", label),
+        Span::styled(
+            format!("{}", overdue),
+            value.fg(if overdue > 0 { theme::DUE_OVERDUE } else { theme::TEXT_PRIMARY }),
+        ),
+        Span::styled(" overdue", label),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// Formats a lead time as minutes, hours or days and hours.
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::hours(1) {
+        format!("{}m", duration.num_minutes())
+    } else if duration < Duration::days(1) {
+        format!("{}h", duration.num_hours())
+    } else {
+        format!("{}d {}h", duration.num_days(), duration.num_hours() % 24)
+    }
+}
+
+/// Returns the x-axis label of a day or week.
+fn period_label(date: NaiveDate, weekly: bool) -> String {
+    if weekly {
+        format!("{}/{}", date.day(), date.month())
+    } else {
+        date.format("%d").to_string()
+    }
+}
+
+/// Renders a bar per day or week with the number of completed tasks.
+fn render_completed_bars(frame: &mut Frame, periods: &[DayStats], weekly: bool, area: Rect) {
+    let block = panel(if weekly { "Completed per week" } else { "Completed per day" });
+    let inner = block.inner(area);
+    let count = periods.len().max(1) as u16;
+    let bar_width = (inner.width.saturating_sub(count) / count).max(1);
+
+    let bars: Vec<Bar> = periods
+        .iter()
+        .map(|p| {
+            Bar::default()
+                .value(p.completed as u64)
+                .label(Line::from(period_label(p.date, weekly)))
+                .style(Style::default().fg(theme::SUCCESS))
+                .value_style(Style::default().fg(theme::BG_DARK).bg(theme::SUCCESS))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(block)
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .label_style(Style::default().fg(theme::TEXT_MUTED));
+    frame.render_widget(chart, area);
+}
+
+/// Renders created and completed counts as two lines.
+fn render_trend(frame: &mut Frame, periods: &[DayStats], weekly: bool, area: Rect) {
+    let points = |value: fn(&DayStats) -> usize| -> Vec<(f64, f64)> {
+        periods
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i as f64, value(p) as f64))
+            .collect()
+    };
+    let created = points(|p| p.created);
+    let completed = points(|p| p.completed);
+    let max = periods
+        .iter()
+        .map(|p| p.created.max(p.completed))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let datasets = vec![
+        Dataset::default()
+            .name("Created")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme::INFO))
+            .data(&created),
+        Dataset::default()
+            .name("Completed")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme::SUCCESS))
+            .data(&completed),
+    ];
+
+    let axis_style = Style::default().fg(theme::TEXT_MUTED);
+    let x_labels: Vec<Line> = [periods.first(), periods.last()]
+        .into_iter()
+        .flatten()
+        .map(|p| Line::from(period_label(p.date, weekly)))
+        .collect();
+    let chart = Chart::new(datasets)
+        .block(panel("Created vs completed"))
+        .x_axis(
+            Axis::default()
+                .style(axis_style)
+                .bounds([0.0, periods.len().saturating_sub(1).max(1) as f64])
+                .labels(x_labels),
+        )
+        .y_axis(
+            Axis::default()
+                .style(axis_style)
+                .bounds([0.0, max as f64])
+                .labels(vec![Line::from("0"), Line::from(max.to_string())]),
+        );
+    frame.render_widget(chart, area);
+}
+
+/// Renders the number of overdue tasks at the end of each day.
+fn render_overdue(frame: &mut Frame, days: &[DayStats], area: Rect) {
+    let block = panel("Overdue over time");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(inner);
+    // One column per day, most recent on the right
+    let shown = last(days, rows[0].width as usize);
+    let values: Vec<u64> = shown.iter().map(|d| d.overdue as u64).collect();
+    frame.render_widget(
+        Sparkline::default()
+            .data(&values)
+            .style(Style::default().fg(theme::DUE_OVERDUE)),
+        rows[0],
+    );
+
+    let peak = values.iter().max().copied().unwrap_or(0);
+    let caption = format!(
+        "{} now {} peak {} over {} days",
+        values.last().copied().unwrap_or(0),
+        icons::DOT,
+        peak,
+        shown.len()
+    );
+    frame.render_widget(
+        Paragraph::new(Span::styled(caption, Style::default().fg(theme::TEXT_MUTED))),
+        rows[1],
+    );
+}
+
+/// Renders a GitHub-style grid of completions, one column per week.
+fn render_heatmap(frame: &mut Frame, days: &[DayStats], area: Rect) {
+    let block = panel("Completions");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(first) = days.first() else {
+        return;
+    };
+    let start = first.date - Duration::days(first.date.weekday().num_days_from_monday() as i64);
+    let weeks = (days.len() + first.date.weekday().num_days_from_monday() as usize).div_ceil(7);
+    let max = days.iter().map(|d| d.completed).max().unwrap_or(0);
+    let day_at = |date: NaiveDate| {
+        let index = (date - first.date).num_days();
+        usize::try_from(index).ok().and_then(|i| days.get(i))
+    };
+
+    let label_style = Style::default().fg(theme::TEXT_MUTED);
+    let mut lines = Vec::with_capacity(8);
+
+    // Month names above the first week starting in them
+    let mut months = String::from("    ");
+    for week in 0..weeks {
+        let monday = start + Duration::days(week as i64 * 7);
+        let column = 4 + week * 2;
+        if monday.day() <= 7 && months.chars().count() <= column {
+            while months.chars().count() < column {
+                months.push(' ');
+            }
+            months.push_str(&monday.format("%b").to_string());
+        }
+    }
+    lines.push(Line::from(Span::styled(months, label_style)));
+
+    for (weekday, name) in ["Mon", "", "Wed", "", "Fri", "", "Sun"].iter().enumerate() {
+        let mut spans = vec![Span::styled(format!("{:<4}", name), label_style)];
+        for week in 0..weeks {
+            let date = start + Duration::days((week * 7 + weekday) as i64);
+            let cell = match day_at(date) {
+                Some(day) => Span::styled("■ ", Style::default().fg(HEAT[heat_level(day.completed, max)])),
+                None => Span::raw("  "),
+            };
+            spans.push(cell);
+        }
+        lines.push(Line::from(spans));
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Returns the heatmap shade for `completed` out of the busiest day's `max`.
+fn heat_level(completed: usize, max: usize) -> usize {
+    if completed == 0 || max == 0 {
+        0
+    } else {
+        (completed * 4).div_ceil(max).clamp(1, 4)
+    }
+}
+
+/// Renders a completion bar per project or tag.
+fn render_completion(frame: &mut Frame, title: &str, rows: &[Completion], area: Rect) {
+    let block = panel(title);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if rows.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled("No tasks", Style::default().fg(theme::TEXT_MUTED))),
+            inner,
+        );
+        return;
+    }
+
+    let label_width = 16;
+    let bar_width = (inner.width as usize).saturating_sub(label_width + 12).max(4);
+    let lines: Vec<Line> = rows
+        .iter()
+        .take(inner.height as usize)
+        .map(|row| {
+            let label: String = row.label.chars().take(label_width - 1).collect();
+            let filled = (row.done * bar_width).checked_div(row.total).unwrap_or(0);
+            let color = if row.label.starts_with('#') { theme::TAG } else { theme::PROJECT };
+            Line::from(vec![
+                Span::styled(format!("{:<width$}", label, width = label_width), Style::default().fg(color)),
+                Span::styled("█".repeat(filled), Style::default().fg(theme::SUCCESS)),
+                Span::styled("░".repeat(bar_width - filled), Style::default().fg(theme::BORDER_MUTED)),
+                Span::styled(
+                    format!(" {}/{}", row.done, row.total),
+                    Style::default().fg(theme::TEXT_SECONDARY),
+                ),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Renders the key hints at the bottom of the view.
+fn render_help_line(frame: &mut Frame, area: Rect) {
+    let key = Style::default().fg(theme::PRIMARY_LIGHT).add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(theme::TEXT_MUTED);
+    let help = Line::from(vec![
+        Span::styled("[w]", key),
+        Span::styled(" Daily/weekly  ", text),
+        Span::styled("[r]", key),
+        Span::styled(" Refresh  ", text),
+        Span::styled("[Esc]", key),
+        Span::styled(" Back", text),
+    ]);
+    frame.render_widget(Paragraph::new(help), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heat_level() {
+        assert_eq!(heat_level(0, 5), 0);
+        assert_eq!(heat_level(1, 8), 1);
+        assert_eq!(heat_level(4, 8), 2);
+        assert_eq!(heat_level(8, 8), 4);
+        assert_eq!(heat_level(3, 0), 0);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::minutes(42)), "42m");
+        assert_eq!(format_duration(Duration::hours(5)), "5h");
+        assert_eq!(format_duration(Duration::hours(50)), "2d 2h");
+    }
+}
@@ -21,6 +21,7 @@ pub fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         InputMode::Normal => render_normal_mode_hints(app),
         InputMode::Editing => render_editing_mode_hints(),
         InputMode::Search => render_search_mode_hints(),
+        InputMode::Select => render_select_mode_hints(app),
     };
 
     let status_bar = Paragraph::new(content);
@@ -157,3 +158,35 @@ fn render_search_mode_hints() -> Line<'static> {
     spans.extend(key_hint("Esc", "Cancel"));
     Line::from(spans)
 }
+
+/// Renders hints for selection mode, with the number of marked tasks.
+fn render_select_mode_hints(app: &App) -> Line<'static> {
+    let mut spans = vec![
+        Span::styled(
+            format!(" {} Select: {} marked ", icons::BULLET, app.marked_tasks().len()),
+            Style::default()
+                .fg(theme::ACCENT)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{} ", icons::LINE_VERTICAL),
+            Style::default().fg(theme::BORDER),
+        ),
+    ];
+    if let Some(ref msg) = app.status_message {
+        spans.push(Span::styled(
+            format!("{} ", msg),
+            Style::default().fg(theme::SUCCESS),
+        ));
+        return Line::from(spans);
+    }
+    spans.extend(key_hint("Space", "Mark"));
+    spans.extend(key_hint("a", "All"));
+    spans.extend(key_hint("c", "Complete"));
+    spans.extend(key_hint("d", "Delete"));
+    spans.extend(key_hint("m", "Move"));
+    spans.extend(key_hint("t", "Tag"));
+    spans.extend(key_hint("p/D/s", "Priority/Due/Status"));
+    spans.extend(key_hint("Esc", "Done"));
+    Line::from(spans)
+}
@@ -188,30 +188,22 @@ impl TagInput {
             }
 
             // Up/Down navigate suggestions
-            KeyCode::Up => {
-                if !self.suggestions.is_empty() {
-                    self.selected_suggestion = Some(
-                        self.selected_suggestion
-                            .map(|i| if i == 0 { self.suggestions.len() - 1 } else { i - 1 })
-                            .unwrap_or(0),
-                    );
-                    true
-                } else {
-                    false
-                }
+            KeyCode::Up if !self.suggestions.is_empty() => {
+                self.selected_suggestion = Some(
+                    self.selected_suggestion
+                        .map(|i| if i == 0 { self.suggestions.len() - 1 } else { i - 1 })
+                        .unwrap_or(0),
+                );
+                true
             }
 
-            KeyCode::Down => {
-                if !self.suggestions.is_empty() {
-                    self.selected_suggestion = Some(
-                        self.selected_suggestion
-                            .map(|i| (i + 1) % self.suggestions.len())
-                            .unwrap_or(0),
-                    );
-                    true
-                } else {
-                    false
-                }
+            KeyCode::Down if !self.suggestions.is_empty() => {
+                self.selected_suggestion = Some(
+                    self.selected_suggestion
+                        .map(|i| (i + 1) % self.suggestions.len())
+                        .unwrap_or(0),
+                );
+                true
             }
 
             // Regular character input
@@ -365,8 +357,7 @@ impl TagInput {
         frame.render_widget(block, suggestion_area);
 
         // Render suggestions
-        let mut y = inner.y;
-        for (i, suggestion) in self.suggestions.iter().enumerate() {
+        for (y, (i, suggestion)) in (inner.y..).zip(self.suggestions.iter().enumerate()) {
             let style = if Some(i) == self.selected_suggestion {
                 Style::default()
                     .fg(Color::Black)
@@ -380,7 +371,6 @@ impl TagInput {
             let paragraph = Paragraph::new(text).style(style);
             let line_area = Rect::new(inner.x, y, inner.width, 1);
             frame.render_widget(paragraph, line_area);
-            y += 1;
         }
     }
 }
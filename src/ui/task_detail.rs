@@ -1,7 +1,8 @@
 //! Task detail view widget.
 //!
-//! Displays a full view of a single task with all its fields and
-//! supports quick actions like toggling status and cycling priority.
+//! Displays a full view of a single task with all its fields, its notes
+//! and its change history, and supports quick actions like toggling status,
+//! cycling priority and adding notes.
 
 use chrono::{DateTime, Local, Utc};
 use ratatui::{
@@ -15,9 +16,14 @@ use ratatui::{
 
 use crate::app::App;
 use crate::models::{Priority, Task, TaskStatus};
+use crate::storage::TaskEvent;
 use crate::utils::format_relative_date;
+use super::activity::event_marker;
 use super::theme;
 
+/// Most history entries shown below the description.
+const HISTORY_ROWS: usize = 6;
+
 /// Renders the task detail view.
 ///
 /// Shows a full-screen view of the selected task with all its fields
@@ -59,6 +65,9 @@ pub fn render_task_detail(frame: &mut Frame, app: &App, area: Rect) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    // The history takes as many rows as it has entries, up to a limit
+    let history_height = app.task_history.len().clamp(1, HISTORY_ROWS) as u16 + 2;
+
     // Layout for the content
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -70,7 +79,9 @@ pub fn render_task_detail(frame: &mut Frame, app: &App, area: Rect) {
             Constraint::Length(3), // Due date & Project row
             Constraint::Length(2), // Tags
             Constraint::Length(1), // Spacer
-            Constraint::Min(5),    // Description
+            Constraint::Min(4),    // Description
+            Constraint::Min(5),    // Notes
+            Constraint::Length(history_height), // History
             Constraint::Length(1), // Spacer
             Constraint::Length(2), // Timestamps
             Constraint::Length(1), // Spacer
@@ -93,11 +104,17 @@ pub fn render_task_detail(frame: &mut Frame, app: &App, area: Rect) {
     // Description
     render_description(frame, task, chunks[6]);
 
+    // Notes
+    render_notes(frame, task, chunks[7]);
+
+    // History
+    render_history(frame, &app.task_history, chunks[8]);
+
     // Timestamps
-    render_timestamps(frame, task, chunks[8]);
+    render_timestamps(frame, task, chunks[10]);
 
     // Help line
-    render_help_line(frame, chunks[10]);
+    render_help_line(frame, chunks[12]);
 }
 
 /// Renders the task title.
@@ -266,6 +283,84 @@ fn render_description(frame: &mut Frame, task: &Task, area: Rect) {
     frame.render_widget(description, area);
 }
 
+/// Renders the task's notes, oldest first.
+///
+/// When they don't all fit, the oldest lines are left out so the latest
+/// note stays visible.
+fn render_notes(frame: &mut Frame, task: &Task, area: Rect) {
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" Notes ({}) ", task.notes.len()),
+            Style::default().fg(theme::TEXT_SECONDARY),
+        ))
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme::BORDER));
+
+    let mut lines: Vec<Line> = Vec::new();
+    for note in &task.notes {
+        let local = note.created_at.with_timezone(&Local);
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", theme::icons::DIAMOND), Style::default().fg(theme::ACCENT)),
+            Span::styled(
+                format!("{} {}", format_relative_date(note.created_at), local.format("%H:%M")),
+                Style::default().fg(theme::TEXT_MUTED),
+            ),
+        ]));
+        lines.extend(note.text.lines().map(|line| {
+            Line::from(Span::styled(format!("  {}", line), Style::default().fg(theme::TEXT_PRIMARY)))
+        }));
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No notes; press N to add one",
+            Style::default().fg(theme::TEXT_MUTED),
+        )));
+    }
+
+    let visible = block.inner(area).height as usize;
+    let skip = lines.len().saturating_sub(visible);
+    let notes = Paragraph::new(lines.split_off(skip)).block(block);
+    frame.render_widget(notes, area);
+}
+
+/// Renders the task's recorded changes, newest first.
+fn render_history(frame: &mut Frame, history: &[TaskEvent], area: Rect) {
+    let block = Block::default()
+        .title(Span::styled(
+            " History ",
+            Style::default().fg(theme::TEXT_SECONDARY),
+        ))
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme::BORDER));
+
+    let lines: Vec<Line> = if history.is_empty() {
+        vec![Line::from(Span::styled(
+            "No changes recorded",
+            Style::default().fg(theme::TEXT_MUTED),
+        ))]
+    } else {
+        history
+            .iter()
+            .take(HISTORY_ROWS)
+            .map(|event| {
+                let (icon, color) = event_marker(event);
+                Line::from(vec![
+                    Span::styled(
+                        format!("{}  ", format_timestamp(event.changed_at)),
+                        Style::default().fg(theme::TEXT_MUTED),
+                    ),
+                    Span::styled(format!("{} ", icon), Style::default().fg(color)),
+                    Span::styled(event.summary(), Style::default().fg(theme::TEXT_PRIMARY)),
+                ])
+            })
+            .collect()
+    };
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 /// Renders created/updated timestamps.
 fn render_timestamps(frame: &mut Frame, task: &Task, area: Rect) {
     let created = format_timestamp(task.created_at);
@@ -298,6 +393,8 @@ fn render_help_line(frame: &mut Frame, area: Rect) {
         Span::styled(" Priority  ", Style::default().fg(theme::TEXT_MUTED)),
         Span::styled("[e]", Style::default().fg(theme::PRIMARY_LIGHT).add_modifier(Modifier::BOLD)),
         Span::styled(" Edit  ", Style::default().fg(theme::TEXT_MUTED)),
+        Span::styled("[N]", Style::default().fg(theme::PRIMARY_LIGHT).add_modifier(Modifier::BOLD)),
+        Span::styled(" Note  ", Style::default().fg(theme::TEXT_MUTED)),
         Span::styled("[d]", Style::default().fg(theme::PRIMARY_LIGHT).add_modifier(Modifier::BOLD)),
         Span::styled(" Delete  ", Style::default().fg(theme::TEXT_MUTED)),
         Span::styled("[Esc]", Style::default().fg(theme::PRIMARY_LIGHT).add_modifier(Modifier::BOLD)),
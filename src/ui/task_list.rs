@@ -11,8 +11,8 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::{App, FocusPanel};
-use crate::models::{Filter, Priority, Project, Task, TaskStatus};
+use crate::app::{App, FocusPanel, InputMode, ListRow};
+use crate::models::{Filter, GroupBy, Priority, Project, Task, TaskStatus};
 use crate::utils::format_relative_date;
 use super::theme::{self, icons};
 
@@ -31,7 +31,7 @@ pub fn render_task_list(frame: &mut Frame, app: &App, area: Rect) {
 
     let filter_style = Style::default().fg(theme::TEXT_MUTED);
 
-    let title = Line::from(vec![
+    let mut title_spans = vec![
         Span::styled(" Tasks ", title_style),
         Span::styled(
             format!("{} {} ", icons::DOT, app.filter_name()),
@@ -41,37 +41,70 @@ pub fn render_task_list(frame: &mut Frame, app: &App, area: Rect) {
             format!("{} {} ", icons::DOT, app.sort_name()),
             filter_style,
         ),
-    ]);
+    ];
+    if !app.then_by.is_empty() {
+        let keys: Vec<String> = app.then_by.iter().map(|k| k.label()).collect();
+        title_spans.push(Span::styled(
+            format!("{} then {} ", icons::DOT, keys.join(", ")),
+            filter_style,
+        ));
+    }
+    if app.group_by != GroupBy::None {
+        title_spans.push(Span::styled(
+            format!("{} by {} ", icons::DOT, app.group_by),
+            filter_style,
+        ));
+    }
+    if app.input_mode == InputMode::Select {
+        title_spans.push(Span::styled(
+            format!("{} {} marked ", icons::DOT, app.marked_tasks().len()),
+            Style::default().fg(theme::ACCENT),
+        ));
+    }
+    let title = Line::from(title_spans);
 
     let block = Block::default()
         .title(title)
         .borders(Borders::NONE);
 
-    let tasks = app.visible_tasks();
+    let rows = app.list_rows();
 
     // Handle empty state with context-aware artwork
-    if tasks.is_empty() {
+    if rows.is_empty() {
         render_empty_state(frame, block, area, app);
         return;
     }
 
-    // Determine if we should show project names (only in "All Tasks" view)
-    let show_projects = app.selected_project_index == 0;
+    // Determine if we should show project names (only in "All Tasks" view,
+    // and not under project headers)
+    let show_projects = app.selected_project_index == 0 && app.group_by != GroupBy::Project;
 
-    // Build task list items
-    let items: Vec<ListItem> = tasks
+    // Build list items; tasks and the rows they're drawn on
+    let mut tasks: Vec<&Task> = Vec::new();
+    let mut task_rows: Vec<usize> = Vec::new();
+    let mut selected_row = None;
+    let items: Vec<ListItem> = rows
         .iter()
         .enumerate()
-        .map(|(i, task)| {
-            let selected = Some(i) == app.selected_task_index;
-            let project_name = if show_projects {
-                task.project_id
-                    .as_ref()
-                    .and_then(|pid| app.projects.iter().find(|p| &p.id == pid))
-            } else {
-                None
-            };
-            render_task_row(task, selected, is_focused, area.width, project_name)
+        .map(|(row, list_row)| match list_row {
+            ListRow::Header { label, count, collapsed } => render_group_header(label, *count, *collapsed),
+            ListRow::Task(i, task) => {
+                let selected = Some(*i) == app.selected_task_index;
+                if selected {
+                    selected_row = Some(row);
+                }
+                tasks.push(task);
+                task_rows.push(row);
+                let project_name = if show_projects {
+                    task.project_id
+                        .as_ref()
+                        .and_then(|pid| app.projects.iter().find(|p| &p.id == pid))
+                } else {
+                    None
+                };
+                let marked = app.marked.contains(&task.id);
+                render_task_row(task, selected, marked, is_focused, area.width, project_name)
+            }
         })
         .collect();
 
@@ -87,7 +120,7 @@ pub fn render_task_list(frame: &mut Frame, app: &App, area: Rect) {
 
     // Render with state for selection highlighting
     let mut state = ListState::default();
-    state.select(app.selected_task_index);
+    state.select(selected_row);
 
     frame.render_stateful_widget(list, area, &mut state);
 
@@ -106,6 +139,7 @@ pub fn render_task_list(frame: &mut Frame, app: &App, area: Rect) {
             content_area: list_content_area,
             scroll_offset: state.offset(),
             task_ids: tasks.iter().map(|t| t.id.clone()).collect(),
+            task_rows,
         });
     });
 }
@@ -119,6 +153,9 @@ pub struct ListRenderInfo {
     pub scroll_offset: usize,
     /// Task IDs in visible order
     pub task_ids: Vec<String>,
+    /// List row of each task, which differs from its index when group
+    /// headers are shown
+    pub task_rows: Vec<usize>,
 }
 
 impl ListRenderInfo {
@@ -126,10 +163,11 @@ impl ListRenderInfo {
     ///
     /// Returns `None` if the task is not currently visible on screen.
     pub fn task_row_rect(&self, task_index: usize) -> Option<Rect> {
-        if task_index < self.scroll_offset {
+        let row = *self.task_rows.get(task_index)?;
+        if row < self.scroll_offset {
             return None;
         }
-        let row_in_view = task_index - self.scroll_offset;
+        let row_in_view = row - self.scroll_offset;
         if row_in_view as u16 >= self.content_area.height {
             return None;
         }
@@ -276,10 +314,27 @@ fn render_empty_state(frame: &mut Frame, block: Block, area: Rect, app: &App) {
     frame.render_widget(empty, area);
 }
 
+/// Renders the header of a task group, with a chevron showing whether
+/// it's collapsed and the number of tasks in it.
+fn render_group_header(label: &str, count: usize, collapsed: bool) -> ListItem<'static> {
+    let chevron = if collapsed { icons::CHEVRON_RIGHT } else { icons::CHEVRON_DOWN };
+    ListItem::new(Line::from(vec![
+        Span::styled(format!("{} ", chevron), Style::default().fg(theme::TEXT_MUTED)),
+        Span::styled(
+            label.to_string(),
+            Style::default()
+                .fg(theme::TEXT_SECONDARY)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!(" {}", count), Style::default().fg(theme::TEXT_MUTED)),
+    ]))
+}
+
 /// Renders a single task row with modern styling.
 fn render_task_row(
     task: &Task,
     selected: bool,
+    marked: bool,
     focused: bool,
     width: u16,
     project: Option<&Project>,
@@ -388,8 +443,15 @@ fn render_task_row(
     };
 
     // Build the line with spans
+    // Marked tasks (selection mode) get a bullet in the leading column
+    let mark = if marked {
+        Span::styled(icons::BULLET, Style::default().fg(theme::ACCENT).add_modifier(Modifier::BOLD))
+    } else {
+        Span::raw(" ")
+    };
     let mut spans = vec![
-        Span::styled(format!(" {} ", status_icon), status_style),
+        mark,
+        Span::styled(format!("{} ", status_icon), status_style),
         Span::styled(format!("{} ", priority_icon), priority_style),
         Span::styled(format!("{:<width$}", title, width = title_width), title_style),
     ];
@@ -448,9 +510,9 @@ mod tests {
         task.priority = Priority::High;
 
         // Just verify it doesn't panic with various widths
-        let _item = render_task_row(&task, false, false, 80, None);
-        let _item = render_task_row(&task, true, true, 40, None);
-        let _item = render_task_row(&task, false, false, 20, None);
+        let _item = render_task_row(&task, false, false, false, 80, None);
+        let _item = render_task_row(&task, true, true, true, 40, None);
+        let _item = render_task_row(&task, false, false, false, 20, None);
     }
 
     #[test]
@@ -459,7 +521,7 @@ mod tests {
         task.due_date = Some(Utc::now() - Duration::days(1));
 
         // Should be rendered without panic
-        let _item = render_task_row(&task, false, false, 80, None);
+        let _item = render_task_row(&task, false, false, false, 80, None);
     }
 
     #[test]
@@ -468,7 +530,7 @@ mod tests {
         task.complete();
 
         // Should be rendered without panic
-        let _item = render_task_row(&task, false, false, 80, None);
+        let _item = render_task_row(&task, false, false, false, 80, None);
     }
 
     #[test]
@@ -479,6 +541,20 @@ mod tests {
         let project = Project::new("Work");
 
         // Should render with project name
-        let _item = render_task_row(&task, false, false, 80, Some(&project));
+        let _item = render_task_row(&task, false, false, false, 80, Some(&project));
+    }
+
+    #[test]
+    fn test_task_row_rect_skips_headers() {
+        let info = ListRenderInfo {
+            content_area: Rect::new(0, 1, 40, 10),
+            scroll_offset: 1,
+            task_ids: vec!["a".to_string(), "b".to_string()],
+            // Headers on rows 0 and 2
+            task_rows: vec![1, 3],
+        };
+        assert_eq!(info.find_task_rect("a").map(|r| r.y), Some(1));
+        assert_eq!(info.find_task_rect("b").map(|r| r.y), Some(3));
+        assert_eq!(info.task_row_rect(2), None);
     }
 }
@@ -0,0 +1,44 @@
+//! Copying text to the clipboard through the terminal.
+//!
+//! The OSC 52 escape sequence asks the terminal itself to set the system
+//! clipboard, so copying works over SSH and without a clipboard tool
+//! installed. Most terminals support it, some only once enabled; inside
+//! tmux the sequence is passed through to the outer terminal.
+
+use std::io::{self, Write};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// Returns the OSC 52 sequence that copies `text` to the clipboard.
+///
+/// # Examples
+///
+/// ```
+/// use ratado::utils::osc52;
+///
+/// assert_eq!(osc52("hi"), "\x1b]52;c;aGk=\x07");
+/// ```
+pub fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+/// Copies `text` to the clipboard by writing the OSC 52 sequence to `out`,
+/// which must be the terminal.
+///
+/// Nothing visible is written. Whether the clipboard changes is up to the
+/// terminal, which doesn't report back.
+///
+/// # Errors
+///
+/// Returns an error if writing to `out` fails.
+pub fn copy_to_clipboard(text: &str, out: &mut (impl Write + ?Sized)) -> io::Result<()> {
+    let sequence = osc52(text);
+    if std::env::var_os("TMUX").is_some() {
+        // tmux passes on sequences wrapped in DCS, with escapes doubled
+        write!(out, "\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))?;
+    } else {
+        out.write_all(sequence.as_bytes())?;
+    }
+    out.flush()
+}
@@ -4,10 +4,7 @@
 //! and for performing date comparisons. All dates are handled in UTC internally
 //! but displayed in the local timezone.
 
-use chrono::{DateTime, Duration, Local, Utc};
-
-#[cfg(test)]
-use chrono::TimeZone;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc, Weekday};
 
 /// Formats a date relative to today.
 ///
@@ -198,6 +195,170 @@ pub fn days_until(date: DateTime<Utc>) -> i64 {
     (local_date - today).num_days()
 }
 
+/// Returns the last workday before `date`.
+///
+/// Days whose weekday isn't in `workdays` are skipped, so on a Monday with
+/// the usual Monday to Friday week this is the Friday before. With no
+/// workdays at all, it's the day before.
+///
+/// # Examples
+///
+/// ```
+/// use ratado::utils::previous_workday;
+/// use chrono::{NaiveDate, Weekday};
+///
+/// let week = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+/// let monday = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+/// assert_eq!(previous_workday(monday, &week), NaiveDate::from_ymd_opt(2026, 3, 6).unwrap());
+/// ```
+pub fn previous_workday(date: NaiveDate, workdays: &[Weekday]) -> NaiveDate {
+    (1..=7)
+        .map(|days| date - Duration::days(days))
+        .find(|day| workdays.contains(&day.weekday()))
+        .unwrap_or(date - Duration::days(1))
+}
+
+/// Parses a due date string into a DateTime.
+///
+/// Supports formats:
+/// - Natural language via `human-date-parser` (e.g., "next friday", "in 3 days", "december 25th")
+/// - "YYYY-MM-DD" or "YYYY/MM/DD"
+/// - "MM/DD" or "DD/MM" (assumes current year)
+/// - "today", "tomorrow", "yesterday"
+/// - "+1d", "+3d", "+1w", "+2w" (relative days/weeks)
+/// - "mon", "tue", "wed", "thu", "fri", "sat", "sun" (next occurrence)
+/// - "next week", "next month"
+///
+/// Dates without a time are due at the end of the day, local time.
+///
+/// # Examples
+///
+/// ```
+/// use ratado::utils::parse_due_date;
+///
+/// assert!(parse_due_date("2025-12-31").is_some());
+/// assert!(parse_due_date("tomorrow").is_some());
+/// assert!(parse_due_date("").is_none());
+/// ```
+pub fn parse_due_date(input: &str) -> Option<DateTime<Utc>> {
+    let input = input.to_lowercase().trim().to_string();
+
+    if input.is_empty() {
+        return None;
+    }
+
+    // Use local timezone for "today" reference
+    let today = Local::now().date_naive();
+
+    // Helper to create UTC datetime at end of day in local timezone
+    let to_datetime = |date: NaiveDate| {
+        // Create end of day (23:59:59) in local timezone, then convert to UTC
+        let local_eod = date.and_hms_opt(23, 59, 59).unwrap();
+        Local.from_local_datetime(&local_eod)
+            .single()
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|| Utc.from_utc_datetime(&local_eod))
+    };
+
+    // Try human-date-parser first for natural language dates
+    if let Ok(result) = human_date_parser::from_human_time(&input) {
+        match result {
+            human_date_parser::ParseResult::DateTime(dt) => {
+                return Some(dt.with_timezone(&Utc));
+            }
+            human_date_parser::ParseResult::Date(date) => {
+                return Some(to_datetime(date));
+            }
+            human_date_parser::ParseResult::Time(_) => {
+                // Time-only result, fall through to existing parsing
+            }
+        }
+    }
+
+    // Keywords
+    match input.as_str() {
+        "today" | "tod" => return Some(to_datetime(today)),
+        "tomorrow" | "tom" => return Some(to_datetime(today + Duration::days(1))),
+        "yesterday" => return Some(to_datetime(today - Duration::days(1))),
+        "next week" => return Some(to_datetime(today + Duration::days(7))),
+        "next month" => return Some(to_datetime(today + Duration::days(30))),
+        _ => {}
+    }
+
+    // Relative days: +1d, +3d, +1w, +2w, etc.
+    if let Some(rest) = input.strip_prefix('+') {
+        if let Some(days_str) = rest.strip_suffix('d')
+            && let Ok(days) = days_str.parse::<i64>() {
+                return Some(to_datetime(today + Duration::days(days)));
+            }
+        if let Some(weeks_str) = rest.strip_suffix('w')
+            && let Ok(weeks) = weeks_str.parse::<i64>() {
+                return Some(to_datetime(today + Duration::weeks(weeks)));
+            }
+    }
+
+    // Weekday names: mon, tue, wed, thu, fri, sat, sun
+    let weekday = match input.as_str() {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    };
+
+    if let Some(target_weekday) = weekday {
+        let current_weekday = today.weekday();
+        let days_ahead = (target_weekday.num_days_from_monday() as i64
+            - current_weekday.num_days_from_monday() as i64
+            + 7) % 7;
+        // If it's the same day, go to next week
+        let days_ahead = if days_ahead == 0 { 7 } else { days_ahead };
+        return Some(to_datetime(today + Duration::days(days_ahead)));
+    }
+
+    // Try parsing as YYYY-MM-DD
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Some(to_datetime(date));
+    }
+
+    // Try parsing as YYYY/MM/DD
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y/%m/%d") {
+        return Some(to_datetime(date));
+    }
+
+    // Try parsing as MM/DD (assume current year)
+    if let Ok(date) = NaiveDate::parse_from_str(&format!("{}/{}", today.year(), input), "%Y/%m/%d") {
+        // If date is in the past, use next year
+        let date = if date < today {
+            NaiveDate::from_ymd_opt(today.year() + 1, date.month(), date.day()).unwrap_or(date)
+        } else {
+            date
+        };
+        return Some(to_datetime(date));
+    }
+
+    // Try parsing as DD/MM (assume current year) - European format
+    if input.contains('/') {
+        let parts: Vec<&str> = input.split('/').collect();
+        if parts.len() == 2
+            && let (Ok(day), Ok(month)) = (parts[0].parse::<u32>(), parts[1].parse::<u32>())
+                && day <= 31 && month <= 12
+                    && let Some(date) = NaiveDate::from_ymd_opt(today.year(), month, day) {
+                        let date = if date < today {
+                            NaiveDate::from_ymd_opt(today.year() + 1, month, day).unwrap_or(date)
+                        } else {
+                            date
+                        };
+                        return Some(to_datetime(date));
+                    }
+    }
+
+    None
+}
+
 /// Returns the current UTC time.
 ///
 /// This is a wrapper around `Utc::now()` that can be useful for testing
@@ -290,7 +451,7 @@ mod tests {
         let future = Utc::now() + Duration::days(5);
         let days = days_until(future);
         // Allow for timezone edge cases
-        assert!(days >= 4 && days <= 6);
+        assert!((4..=6).contains(&days));
     }
 
     #[test]
@@ -298,7 +459,22 @@ mod tests {
         let past = Utc::now() - Duration::days(3);
         let days = days_until(past);
         // Allow for timezone edge cases
-        assert!(days >= -4 && days <= -2);
+        assert!((-4..=-2).contains(&days));
+    }
+
+    #[test]
+    fn test_previous_workday() {
+        let week = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+
+        // Wednesday 11 March 2026 follows Tuesday; Sunday and Monday follow Friday
+        assert_eq!(previous_workday(day(11), &week), day(10));
+        assert_eq!(previous_workday(day(8), &week), day(6));
+        assert_eq!(previous_workday(day(9), &week), day(6));
+        // A Sunday to Thursday week skips Friday and Saturday
+        let week = [Weekday::Sun, Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu];
+        assert_eq!(previous_workday(day(8), &week), day(5));
+        assert_eq!(previous_workday(day(8), &[]), day(7));
     }
 
     #[test]
@@ -307,4 +483,23 @@ mod tests {
         let days = days_until(today);
         assert_eq!(days, 0);
     }
+
+    #[test]
+    fn test_parse_due_date_today() {
+        let date = parse_due_date("today");
+        assert!(date.is_some());
+    }
+
+    #[test]
+    fn test_parse_due_date_iso() {
+        let date = parse_due_date("2025-12-31");
+        assert!(date.is_some());
+        let d = date.unwrap();
+        assert_eq!(d.format("%Y-%m-%d").to_string(), "2025-12-31");
+    }
+
+    #[test]
+    fn test_parse_due_date_empty() {
+        assert!(parse_due_date("").is_none());
+    }
 }
@@ -1,12 +1,14 @@
 //! Utility functions for Ratado.
 //!
 //! This module provides helper functions used throughout the application,
-//! including date/time formatting and ID generation.
+//! including date/time formatting, ID generation and copying to the
+//! clipboard.
 //!
 //! ## Submodules
 //!
-//! - `datetime` - Date/time formatting and comparison utilities
+//! - `datetime` - Date/time parsing, formatting and comparison utilities
 //! - `ids` - UUID generation
+//! - `clipboard` - Copying text through the terminal (OSC 52)
 //!
 //! ## Examples
 //!
@@ -23,10 +25,13 @@
 //! assert_eq!(id.len(), 36);
 //! ```
 
+mod clipboard;
 mod datetime;
 mod ids;
 
+pub use clipboard::{copy_to_clipboard, osc52};
 pub use datetime::{
     days_until, format_due_date, format_relative_date, is_same_day, is_this_week, is_today, now,
+    parse_due_date, previous_workday,
 };
 pub use ids::generate_id;
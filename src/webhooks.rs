@@ -0,0 +1,479 @@
+//! Outgoing webhooks.
+//!
+//! URLs configured under `[[webhooks]]` in `config.toml` receive a JSON
+//! `POST` whenever a task or project changes:
+//!
+//! ```toml
+//! [[webhooks]]
+//! url = "https://example.com/ratado"
+//! secret = "s3cret"                             # optional, signs each request
+//! events = ["task.created", "task.completed"]   # optional, defaults to all
+//! ```
+//!
+//! Events are written to an outbox table in the database in the same step
+//! as the change, so nothing is lost while offline or if Ratado quits before
+//! sending. A [`spawn`]ed background task delivers them in order, touching
+//! the outbox only between transactions, so it never sends an event whose
+//! change is then rolled back. Failures are retried with exponential
+//! [`backoff`], and an event is dropped after [`MAX_ATTEMPTS`]. Every attempt is kept in a delivery log shown on the
+//! debug screen (F12).
+//!
+//! Each request carries these headers:
+//!
+//! | Header               | Value                                              |
+//! |----------------------|----------------------------------------------------|
+//! | `X-Ratado-Event`     | The event name, e.g. `task.completed`              |
+//! | `X-Ratado-Delivery`  | Unique ID of the delivery, the same on every retry |
+//! | `X-Ratado-Signature` | `sha256=` + hex HMAC-SHA256 of the body, if a secret is set |
+//!
+//! The body is `{"id", "event", "created_at", "task" | "project"}`, where the
+//! task or project has the same JSON shape as in the REST API. For deletions
+//! it is the item as it was before being deleted.
+
+use std::collections::HashSet;
+use std::error::Error as _;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use log::{debug, info, warn};
+use reqwest::header::{CONTENT_TYPE, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::task::JoinHandle;
+
+use crate::storage::{Database, Delivery, OutboxEntry, Result};
+
+/// Attempts before an event is dropped. With [`backoff`] this spans about ten hours.
+pub const MAX_ATTEMPTS: u32 = 12;
+
+/// How often the background task looks for events to deliver.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long to wait for a webhook endpoint to respond.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A task or project change that webhooks can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    /// A task was created or imported
+    #[serde(rename = "task.created")]
+    TaskCreated,
+    /// A task was changed, other than completing it
+    #[serde(rename = "task.updated")]
+    TaskUpdated,
+    /// A task was marked completed
+    #[serde(rename = "task.completed")]
+    TaskCompleted,
+    /// A task was deleted
+    #[serde(rename = "task.deleted")]
+    TaskDeleted,
+    /// A project was created
+    #[serde(rename = "project.created")]
+    ProjectCreated,
+    /// A project was renamed or restyled
+    #[serde(rename = "project.updated")]
+    ProjectUpdated,
+    /// A project was deleted
+    #[serde(rename = "project.deleted")]
+    ProjectDeleted,
+}
+
+impl WebhookEvent {
+    /// The event name used in payloads and the `X-Ratado-Event` header.
+    pub fn name(self) -> &'static str {
+        match self {
+            WebhookEvent::TaskCreated => "task.created",
+            WebhookEvent::TaskUpdated => "task.updated",
+            WebhookEvent::TaskCompleted => "task.completed",
+            WebhookEvent::TaskDeleted => "task.deleted",
+            WebhookEvent::ProjectCreated => "project.created",
+            WebhookEvent::ProjectUpdated => "project.updated",
+            WebhookEvent::ProjectDeleted => "project.deleted",
+        }
+    }
+
+    /// The payload field holding the changed item: `task` or `project`.
+    pub fn subject(self) -> &'static str {
+        match self {
+            WebhookEvent::TaskCreated
+            | WebhookEvent::TaskUpdated
+            | WebhookEvent::TaskCompleted
+            | WebhookEvent::TaskDeleted => "task",
+            WebhookEvent::ProjectCreated
+            | WebhookEvent::ProjectUpdated
+            | WebhookEvent::ProjectDeleted => "project",
+        }
+    }
+}
+
+/// A configured webhook endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Webhook {
+    /// URL that receives the `POST` requests
+    pub url: String,
+    /// Key for the `X-Ratado-Signature` HMAC, if requests should be signed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// Events to send; empty means all of them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<WebhookEvent>,
+}
+
+impl Webhook {
+    /// Returns whether this webhook subscribes to `event`.
+    pub fn wants(&self, event: WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// Returns the `X-Ratado-Signature` value for `body`: `sha256=` followed by
+/// the hex HMAC-SHA256 of the body keyed with `secret`.
+pub fn signature(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let hex: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("sha256={}", hex)
+}
+
+/// Delay before retrying after the `attempts`th failed attempt: 30 seconds,
+/// doubling each time, capped at six hours.
+pub fn backoff(attempts: u32) -> chrono::Duration {
+    let seconds = 30i64 << attempts.saturating_sub(1).min(16);
+    chrono::Duration::seconds(seconds.min(6 * 60 * 60))
+}
+
+/// Builds the HTTP client used for deliveries.
+pub fn client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("HTTP client configuration is valid")
+}
+
+/// Starts delivering queued events in the background, every [`POLL_INTERVAL`].
+///
+/// Returns `None` if `db` has no webhooks configured. Events left over when
+/// the task is aborted stay queued for the next run.
+pub fn spawn(db: Database) -> Option<JoinHandle<()>> {
+    if db.webhooks().is_empty() {
+        return None;
+    }
+    info!("Delivering webhooks to {} URL(s)", db.webhooks().len());
+    Some(tokio::spawn(async move {
+        let client = client();
+        loop {
+            if let Err(e) = deliver_due(&db, &client, Utc::now()).await {
+                warn!("Webhook delivery failed: {}", e);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }))
+}
+
+/// Sends every queued event that is due at `now`, oldest first.
+///
+/// After a failed attempt, later events for the same URL wait, so each
+/// endpoint receives events in the order they happened. Events for URLs no
+/// longer in the configuration are dropped.
+///
+/// # Returns
+///
+/// The number of events delivered.
+///
+/// # Errors
+///
+/// Returns an error if reading or updating the outbox fails. Failed HTTP
+/// requests are not errors; they are logged and retried later.
+pub async fn deliver_due(db: &Database, client: &reqwest::Client, now: DateTime<Utc>) -> Result<usize> {
+    let mut delivered = 0;
+    let mut waiting: HashSet<String> = HashSet::new();
+
+    for entry in db.webhook_outbox().await? {
+        if waiting.contains(&entry.url) {
+            continue;
+        }
+        let Some(webhook) = db.webhooks().iter().find(|w| w.url == entry.url) else {
+            info!("Dropping {} for unconfigured webhook {}", entry.event, entry.url);
+            db.remove_from_webhook_outbox(entry.id).await?;
+            continue;
+        };
+        if entry.next_attempt_at > now {
+            waiting.insert(entry.url);
+            continue;
+        }
+
+        let delivery = send(client, webhook, &entry, now).await;
+        db.log_webhook_delivery(&delivery).await?;
+        if delivery.succeeded() {
+            debug!("Delivered {} to {}", entry.event, entry.url);
+            db.remove_from_webhook_outbox(entry.id).await?;
+            delivered += 1;
+        } else if delivery.attempt >= MAX_ATTEMPTS {
+            warn!(
+                "Giving up on {} to {} after {} attempts: {}",
+                entry.event,
+                entry.url,
+                delivery.attempt,
+                delivery.outcome()
+            );
+            db.remove_from_webhook_outbox(entry.id).await?;
+        } else {
+            let retry_at = now + backoff(delivery.attempt);
+            warn!(
+                "Delivering {} to {} failed ({}), retrying at {}",
+                entry.event,
+                entry.url,
+                delivery.outcome(),
+                retry_at.format("%H:%M:%S")
+            );
+            db.reschedule_webhook(entry.id, delivery.attempt, retry_at)
+                .await?;
+            waiting.insert(entry.url);
+        }
+    }
+
+    Ok(delivered)
+}
+
+/// Makes one delivery attempt.
+async fn send(client: &reqwest::Client, webhook: &Webhook, entry: &OutboxEntry, now: DateTime<Utc>) -> Delivery {
+    let mut request = client
+        .post(&entry.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(USER_AGENT, concat!("ratado/", env!("CARGO_PKG_VERSION")))
+        .header("X-Ratado-Event", &entry.event)
+        .header("X-Ratado-Delivery", &entry.delivery_id)
+        .body(entry.payload.clone());
+    if let Some(secret) = &webhook.secret {
+        request = request.header("X-Ratado-Signature", signature(secret, entry.payload.as_bytes()));
+    }
+
+    let (status, error) = match request.send().await {
+        Ok(response) => (Some(response.status().as_u16()), None),
+        Err(e) => (e.status().map(|s| s.as_u16()), Some(error_chain(e))),
+    };
+    Delivery {
+        delivery_id: entry.delivery_id.clone(),
+        url: entry.url.clone(),
+        event: entry.event.clone(),
+        attempt: entry.attempts + 1,
+        status,
+        error,
+        attempted_at: now,
+    }
+}
+
+/// Formats an error with its causes, e.g. "error sending request: ... Connection refused".
+fn error_chain(error: reqwest::Error) -> String {
+    // The URL is already in the delivery log
+    let error = error.without_url();
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message = format!("{}: {}", message, cause);
+        source = cause.source();
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::models::Task;
+    use crate::storage::run_migrations;
+
+    /// A request received by the stand-in server.
+    #[derive(Debug, Clone)]
+    struct Received {
+        headers: HeaderMap,
+        body: String,
+    }
+
+    /// Starts a local HTTP server that records requests and answers with the
+    /// given statuses in turn (200 once they run out). Returns its URL.
+    async fn stand_in(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<Received>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let statuses = Arc::new(Mutex::new(statuses.into_iter()));
+        let log = received.clone();
+        let app = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: String| {
+                let log = log.clone();
+                let statuses = statuses.clone();
+                async move {
+                    log.lock().unwrap().push(Received { headers, body });
+                    let status = statuses.lock().unwrap().next().unwrap_or(200);
+                    StatusCode::from_u16(status).unwrap()
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, received)
+    }
+
+    async fn setup_db(webhooks: Vec<Webhook>) -> Database {
+        let mut db = Database::open_in_memory().await.unwrap();
+        run_migrations(&db).await.unwrap();
+        db.set_webhooks(webhooks);
+        db
+    }
+
+    fn webhook(url: &str) -> Webhook {
+        Webhook {
+            url: url.to_string(),
+            secret: None,
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_signature() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            signature("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        assert_eq!(backoff(1), chrono::Duration::seconds(30));
+        assert_eq!(backoff(2), chrono::Duration::seconds(60));
+        assert_eq!(backoff(5), chrono::Duration::seconds(480));
+        assert_eq!(backoff(MAX_ATTEMPTS), chrono::Duration::hours(6));
+        assert_eq!(backoff(u32::MAX), chrono::Duration::hours(6));
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let hook = Webhook {
+            events: vec![WebhookEvent::TaskCompleted],
+            ..webhook("http://localhost/")
+        };
+        assert!(hook.wants(WebhookEvent::TaskCompleted));
+        assert!(!hook.wants(WebhookEvent::TaskCreated));
+        assert!(webhook("http://localhost/").wants(WebhookEvent::ProjectDeleted));
+    }
+
+    #[tokio::test]
+    async fn test_delivers_signed_payloads_in_order() {
+        let (url, received) = stand_in(vec![]).await;
+        let db = setup_db(vec![Webhook {
+            secret: Some("s3cret".to_string()),
+            ..webhook(&url)
+        }])
+        .await;
+
+        let task = db.add_task(Task::new("Ship it")).await.unwrap();
+        let mut done = task.clone();
+        done.complete();
+        db.modify_task(done).await.unwrap();
+
+        let delivered = deliver_due(&db, &client(), Utc::now()).await.unwrap();
+        assert_eq!(delivered, 2);
+        assert!(db.webhook_outbox().await.unwrap().is_empty());
+
+        let received = received.lock().unwrap().clone();
+        let events: Vec<&str> = received
+            .iter()
+            .map(|r| r.headers["x-ratado-event"].to_str().unwrap())
+            .collect();
+        assert_eq!(events, ["task.created", "task.completed"]);
+
+        let first = &received[0];
+        assert_eq!(
+            first.headers["x-ratado-signature"].to_str().unwrap(),
+            signature("s3cret", first.body.as_bytes())
+        );
+        let payload: Value = serde_json::from_str(&first.body).unwrap();
+        assert_eq!(payload["event"], "task.created");
+        assert_eq!(payload["task"]["id"], task.id.as_str());
+        assert_eq!(payload["task"]["title"], "Ship it");
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_retried_later() {
+        let (url, received) = stand_in(vec![500]).await;
+        let db = setup_db(vec![webhook(&url)]).await;
+        db.add_task(Task::new("First")).await.unwrap();
+        db.add_task(Task::new("Second")).await.unwrap();
+
+        // The first event fails, and the second waits behind it
+        let now = Utc::now();
+        assert_eq!(deliver_due(&db, &client(), now).await.unwrap(), 0);
+        let outbox = db.webhook_outbox().await.unwrap();
+        assert_eq!(outbox.len(), 2);
+        assert_eq!(outbox[0].attempts, 1);
+        assert_eq!(outbox[0].next_attempt_at, now + backoff(1));
+        assert_eq!(received.lock().unwrap().len(), 1);
+
+        // Nothing is due until the backoff has passed
+        assert_eq!(deliver_due(&db, &client(), now).await.unwrap(), 0);
+        assert_eq!(received.lock().unwrap().len(), 1);
+
+        let later = now + backoff(1);
+        assert_eq!(deliver_due(&db, &client(), later).await.unwrap(), 2);
+        assert!(db.webhook_outbox().await.unwrap().is_empty());
+
+        // The retry reuses the delivery ID
+        let received = received.lock().unwrap().clone();
+        assert_eq!(received.len(), 3);
+        assert_eq!(
+            received[0].headers["x-ratado-delivery"],
+            received[1].headers["x-ratado-delivery"]
+        );
+
+        let log = db.recent_webhook_deliveries(10).await.unwrap();
+        let outcomes: Vec<(u32, Option<u16>)> = log.iter().map(|d| (d.attempt, d.status)).collect();
+        assert_eq!(outcomes, [(1, Some(200)), (2, Some(200)), (1, Some(500))]);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_keeps_events() {
+        // Nothing listens on this port once the listener is dropped
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+
+        let db = setup_db(vec![webhook(&url)]).await;
+        db.add_task(Task::new("Offline")).await.unwrap();
+        assert_eq!(deliver_due(&db, &client(), Utc::now()).await.unwrap(), 0);
+
+        assert_eq!(db.webhook_outbox().await.unwrap().len(), 1);
+        let log = db.recent_webhook_deliveries(10).await.unwrap();
+        assert_eq!(log[0].status, None);
+        assert!(log[0].error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let (url, received) = stand_in(vec![503; MAX_ATTEMPTS as usize]).await;
+        let db = setup_db(vec![webhook(&url)]).await;
+        db.add_task(Task::new("Doomed")).await.unwrap();
+
+        let mut now = Utc::now();
+        for attempt in 1..=MAX_ATTEMPTS {
+            deliver_due(&db, &client(), now).await.unwrap();
+            now += backoff(attempt);
+        }
+
+        assert!(db.webhook_outbox().await.unwrap().is_empty());
+        assert_eq!(received.lock().unwrap().len(), MAX_ATTEMPTS as usize);
+    }
+}
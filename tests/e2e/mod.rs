@@ -98,8 +98,9 @@ impl RatadoTest {
     /// Animations and splash screen are disabled via RATADO_NO_ANIMATIONS env var.
     pub fn wait_for_startup(&mut self) -> &mut Self {
         self.session.set_expect_timeout(Some(EXTENDED_TIMEOUT));
-        // Wait for main view to be ready (Tasks header or empty state)
-        let _ = self.session.expect(Regex("Tasks|liftoff"));
+        // Wait for main view to be ready (the Tasks header is always shown).
+        // Matching the empty state too would consume it before tests can expect it.
+        let _ = self.session.expect(Regex("Tasks"));
         self.session.set_expect_timeout(Some(DEFAULT_TIMEOUT));
         self
     }
@@ -242,6 +243,73 @@ impl RatadoTest {
         self
     }
 
+    /// Runs `ratado rpc` against this instance and returns its stdout.
+    pub fn rpc(&self, method: &str, params: &[&str]) -> String {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_ratado"))
+            .arg("-d")
+            .arg(&self.db_path)
+            .arg("rpc")
+            .arg(method)
+            .args(params)
+            .output()
+            .expect("Failed to run ratado rpc");
+        assert!(
+            output.status.success(),
+            "ratado rpc {} failed: {}",
+            method,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    /// Runs a `ratado` command against this instance's database and
+    /// returns its stdout.
+    pub fn command(&self, args: &[&str]) -> String {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_ratado"))
+            .arg("-d")
+            .arg(&self.db_path)
+            .args(args)
+            .output()
+            .expect("Failed to run ratado");
+        assert!(
+            output.status.success(),
+            "ratado {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    /// Runs a `ratado` command against this instance's database, expecting
+    /// it to fail, and returns its stderr.
+    pub fn failing_command(&self, args: &[&str]) -> String {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_ratado"))
+            .arg("-d")
+            .arg(&self.db_path)
+            .args(args)
+            .output()
+            .expect("Failed to run ratado");
+        assert!(!output.status.success(), "ratado {} succeeded", args.join(" "));
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    }
+
+    /// Starts a long-running `ratado` command, such as `serve`, against
+    /// this instance's database.
+    pub fn spawn_command(&self, args: &[&str]) -> std::process::Child {
+        std::process::Command::new(env!("CARGO_BIN_EXE_ratado"))
+            .arg("-d")
+            .arg(&self.db_path)
+            .args(args)
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .expect("Failed to run ratado")
+    }
+
+    /// Returns the path of this instance's database.
+    pub fn db_path(&self) -> &std::path::Path {
+        &self.db_path
+    }
+
     /// Checks if the database file was created on disk.
     pub fn database_exists(&self) -> bool {
         self.db_path.exists()
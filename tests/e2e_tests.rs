@@ -509,6 +509,139 @@ fn test_debug_view_toggle() {
     db.assert_task_exists("Debug test");
 }
 
+#[cfg(unix)]
+#[test]
+fn test_control_socket_drives_running_app() {
+    let mut app = RatadoTest::spawn();
+    app.wait_for_startup();
+
+    let output = app.rpc("add_task", &["text=From the socket !1"]);
+    assert!(output.contains("\"title\": \"From the socket\""));
+
+    // UI: the task appears without any key press
+    app.expect_text("From the socket");
+
+    let state = app.rpc("switch_view", &["view=help"]);
+    assert!(state.contains("\"view\": \"help\""));
+    app.rpc("switch_view", &["view=main"]);
+
+    // DB: the task was persisted by the running instance
+    let db = app.quit();
+    db.assert_task_exists("From the socket");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_commands_run_in_running_app() {
+    let mut app = RatadoTest::spawn();
+    app.wait_for_startup();
+
+    // The running instance holds the database, so it runs the command
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("todo.txt");
+    std::fs::write(&file, "(A) Imported while open +Errands\n").unwrap();
+    let output = app.command(&["import", "-f", "todotxt", file.to_str().unwrap()]);
+    assert!(output.contains("Imported 1 tasks"));
+
+    // UI: the imported task appears without any key press
+    app.expect_text("Imported while open");
+
+    let export = app.command(&["export", "-f", "todotxt"]);
+    assert!(export.contains("Imported while open +Errands"));
+
+    let db = app.quit();
+    db.assert_task_exists("Imported while open");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_second_app_points_at_running_app() {
+    let mut app = RatadoTest::spawn();
+    app.wait_for_startup();
+
+    // A second TUI can't open the held database and says where it is open
+    let error = app.failing_command(&[]);
+    assert!(error.contains("Ratado is already open on"), "{}", error);
+
+    // The running instance is unaffected
+    app.add_task("Kept by the first window");
+    app.expect_text("Kept by the first window");
+    let db = app.quit();
+    db.assert_task_exists("Kept by the first window");
+}
+
+#[test]
+fn test_serve_beside_running_app() {
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    let mut app = RatadoTest::spawn();
+    app.wait_for_startup();
+
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let bind = format!("127.0.0.1:{}", port);
+    let mut server = app.spawn_command(&["serve", "--bind", &bind, "--token", "s3cret"]);
+
+    // The server passes the request to the running instance
+    let body = r#"{"title": "Posted while open"}"#;
+    let request = format!(
+        "POST /tasks HTTP/1.1\r\nHost: {}\r\nAuthorization: Bearer s3cret\r\n\
+         Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        bind,
+        body.len(),
+        body
+    );
+    let mut stream = (0..50)
+        .find_map(|_| {
+            TcpStream::connect(&bind)
+                .inspect_err(|_| std::thread::sleep(Duration::from_millis(100)))
+                .ok()
+        })
+        .expect("server didn't start");
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    server.kill().unwrap();
+    server.wait().unwrap();
+    assert!(response.starts_with("HTTP/1.1 201"), "{}", response);
+
+    // UI: the created task appears without any key press
+    app.expect_text("Posted while open");
+
+    let db = app.quit();
+    db.assert_task_exists("Posted while open");
+}
+
+#[test]
+fn test_mcp_beside_running_app() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut app = RatadoTest::spawn();
+    app.wait_for_startup();
+
+    // The server passes the tool call to the running instance
+    let mut server = std::process::Command::new(env!("CARGO_BIN_EXE_ratado"))
+        .arg("-d")
+        .arg(app.db_path())
+        .arg("mcp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to run ratado");
+    let call = r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"create_task","arguments":{"text":"Asked while open"}}}"#;
+    writeln!(server.stdin.take().unwrap(), "{}", call).unwrap();
+    let output = server.wait_with_output().unwrap();
+    let response = String::from_utf8_lossy(&output.stdout);
+    assert!(response.contains("\"isError\":false"), "{}", response);
+
+    // UI: the created task appears without any key press
+    app.expect_text("Asked while open");
+
+    let db = app.quit();
+    db.assert_task_exists("Asked while open");
+}
+
 // ============================================================================
 // Filter Tests
 // ============================================================================